@@ -0,0 +1,32 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(2)
+var<storage> heat_map: array<f32>;
+@group(2) @binding(3)
+var<uniform> width: u32;
+@group(2) @binding(4)
+var<uniform> height: u32;
+
+// Keep in sync with `heatmap_color` in src/heatmap_overlay.rs.
+const COLD: vec3<f32> = vec3<f32>(0.1, 0.3, 1.0);
+const WARM: vec3<f32> = vec3<f32>(1.0, 0.9, 0.1);
+const HOT: vec3<f32> = vec3<f32>(1.0, 0.1, 0.05);
+const MIN_ALPHA: f32 = 0.25;
+const MAX_ALPHA: f32 = 0.7;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let x: u32 = min(u32(in.uv.x * f32(width)), width - 1u);
+    let y: u32 = min(u32(in.uv.y * f32(height)), height - 1u);
+    let t = clamp(heat_map[y * width + x], 0.0, 1.0);
+    if t <= 0.0 {
+        return vec4<f32>(0.0);
+    }
+    var color: vec3<f32>;
+    if t < 0.5 {
+        color = mix(COLD, WARM, t * 2.0);
+    } else {
+        color = mix(WARM, HOT, (t - 0.5) * 2.0);
+    }
+    return vec4<f32>(color, mix(MIN_ALPHA, MAX_ALPHA, t));
+}
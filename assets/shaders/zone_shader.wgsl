@@ -16,10 +16,11 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let build_bit = floor(value / 2.0) - floor(value / 4.0) * 2.0;
     let defend_bit = floor(value / 4.0) - floor(value / 8.0) * 2.0;
     let corridor_bit = floor(value / 8.0) - floor(value / 16.0) * 2.0;
-    let layer_count = gather_bit + build_bit + defend_bit + corridor_bit;
+    let reclaim_bit = floor(value / 16.0) - floor(value / 32.0) * 2.0;
+    let layer_count = gather_bit + build_bit + defend_bit + corridor_bit + reclaim_bit;
     let color_sum = vec3<f32>(
-        gather_bit + build_bit + corridor_bit,
-        corridor_bit,
+        gather_bit + build_bit + corridor_bit + reclaim_bit,
+        corridor_bit + reclaim_bit * 0.5,
         build_bit + defend_bit,
     );
     let color = color_sum / max(layer_count, 1.0);
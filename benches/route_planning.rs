@@ -0,0 +1,126 @@
+//! Logistics Leg picking cost with hundreds of stockpiles.
+//!
+//! One Hauler picks a buffer leg among Source and Sink Stockpiles
+//! scattered over a corridor-painted grid, so every pick scores every
+//! `(source, sink)` pair by route cost. `warm` reuses one
+//! [`RouteCostCache`] across picks, the steady state between Corridor
+//! edits. `cold` starts each pick with an empty cache, so it measures
+//! the hierarchical portal search. `flat` runs the uncached
+//! 8-neighbour planner and only runs at the smallest size.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use top_down_2d_rts_prototype_nano_swarm::{
+    ZONE_BLOCK_SIZE,
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        HAULER_CARRY_CAPACITY, HaulerContext, RouteCostCache, StockpileCandidate, SwarmId,
+        hauler_route_cost, pick_logistics_leg_with_cost,
+    },
+    resources::{ResourceKind, StockpileRole},
+};
+
+const STOCKPILE_COUNTS: [usize; 3] = [100, 200, 400];
+const GRID_CELLS: i32 = 96;
+/// Stockpiles spread over this many cells on each axis around the
+/// origin.
+const SPREAD_CELLS: i32 = 64;
+
+fn cell_center(cell: IVec2) -> Vec2 {
+    (cell.as_vec2() + 0.5) * ZONE_BLOCK_SIZE
+}
+
+fn corridor_grid() -> IntentGrid {
+    let mut grid = IntentGrid::new(GRID_CELLS, GRID_CELLS);
+    for offset in -SPREAD_CELLS / 2..SPREAD_CELLS / 2 {
+        for line in [-16, 0, 16] {
+            grid.paint(IVec2::new(offset, line), IntentKind::Corridor);
+            grid.paint(IVec2::new(line, offset), IntentKind::Corridor);
+        }
+    }
+    grid
+}
+
+/// `count` stockpiles, alternating Source and Sink, on a fixed
+/// pseudo-random scatter so every run scores the same pairs.
+fn stockpiles(count: usize) -> Vec<StockpileCandidate> {
+    (0..count)
+        .map(|index| {
+            let hash = (index as u32).wrapping_mul(2_654_435_761);
+            let cell = IVec2::new(
+                (hash % SPREAD_CELLS as u32) as i32 - SPREAD_CELLS / 2,
+                ((hash >> 16) % SPREAD_CELLS as u32) as i32 - SPREAD_CELLS / 2,
+            );
+            let source = index % 2 == 0;
+            StockpileCandidate {
+                entity: Entity::from_bits(index as u64 + 1),
+                pos: cell_center(cell),
+                kind: ResourceKind::Minerals,
+                role: if source {
+                    StockpileRole::Source
+                } else {
+                    StockpileRole::Sink
+                },
+                amount: if source { 100 } else { 0 },
+                free_space: if source { 0 } else { 100 },
+                owner: Some(SwarmId::PLAYER),
+            }
+        })
+        .collect()
+}
+
+fn hauler() -> HaulerContext {
+    HaulerContext {
+        pos: cell_center(IVec2::new(3, -5)),
+        swarm: SwarmId::PLAYER,
+        kind: ResourceKind::Minerals,
+        carry_capacity: HAULER_CARRY_CAPACITY,
+    }
+}
+
+fn route_planning(c: &mut Criterion) {
+    let grid = corridor_grid();
+    let mut group = c.benchmark_group("logistics_leg_picking");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+    group.warm_up_time(Duration::from_secs(2));
+
+    for count in STOCKPILE_COUNTS {
+        let candidates = stockpiles(count);
+        group.throughput(Throughput::Elements(count as u64));
+
+        let cache = RouteCostCache::default();
+        group.bench_function(BenchmarkId::new("warm", count), |b| {
+            b.iter(|| {
+                pick_logistics_leg_with_cost(hauler(), &candidates, &[], |from, to| {
+                    cache.cost(from, to, &grid, SwarmId::PLAYER)
+                })
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("cold", count), |b| {
+            b.iter(|| {
+                let cache = RouteCostCache::default();
+                pick_logistics_leg_with_cost(hauler(), &candidates, &[], |from, to| {
+                    cache.cost(from, to, &grid, SwarmId::PLAYER)
+                })
+            })
+        });
+
+        if count == STOCKPILE_COUNTS[0] {
+            group.bench_function(BenchmarkId::new("flat", count), |b| {
+                b.iter(|| {
+                    pick_logistics_leg_with_cost(hauler(), &candidates, &[], |from, to| {
+                        hauler_route_cost(from, to, &grid, SwarmId::PLAYER)
+                    })
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, route_planning);
+criterion_main!(benches);
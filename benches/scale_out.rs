@@ -0,0 +1,327 @@
+//! Scale-out benchmarks for a fixed population of two swarms.
+//!
+//! Two swarms each run gather, haul, build, maintenance, charge, and Defend
+//! work, and meet on a shared Defend front where their Defenders fight.
+//! Production is left out so the population stays fixed at the measured
+//! size. Before each Criterion group a single-threaded run prints a
+//! per-stage cost profile; it is not a breakdown of the multi-threaded
+//! Criterion frame. Record both in `docs/performance/scale-out.md`.
+
+use std::time::{Duration, Instant};
+
+use bevy::{ecs::schedule::ExecutorKind, prelude::*, time::TimeUpdateStrategy};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use top_down_2d_rts_prototype_nano_swarm::{
+    ZONE_BLOCK_SIZE,
+    game_settings::GameSettings,
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        ChargePlugin, Charger, CombatPlugin, Commitment, DefendPlugin, GatherPlugin, HaulPlugin,
+        Health, MaintenancePlugin, Nanobot, NanobotBundle, NanobotPlugin, NanobotSimulationSet,
+        NanobotType, OwnerSwarm, PlannedStructurePlugin, RegionalAllocationPlugin,
+        RegionalAllocationSet, RepairPlugin, Swarm, SwarmId, SwarmMember, idle_spread_system,
+        separation_system, velocity_system,
+    },
+    resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile, StockpileRole},
+};
+
+const BOT_COUNTS: [usize; 3] = [5_000, 20_000, 50_000];
+const WARMUP_FRAMES: usize = 60;
+const BREAKDOWN_FRAMES: u32 = 120;
+const OPPONENT: SwarmId = SwarmId(11);
+
+/// Base half-width in cells. Each base spans eight columns: four of
+/// Gather paint and four of Build paint.
+const BASE_HALF_CELLS: i32 = 4;
+/// Base centre column for the player; the opponent mirrors it.
+const BASE_COLUMN: i32 = 10;
+/// Unowned Defend cells both swarms contest.
+const FRONT_HALF_CELLS: i32 = 2;
+
+const PROJECTION: usize = 0;
+const LEASES: usize = 1;
+const ACQUISITION: usize = 2;
+const SPREAD: usize = 3;
+const COMBAT: usize = 4;
+const STAGE_COUNT: usize = 5;
+const STAGE_NAMES: [&str; STAGE_COUNT] =
+    ["projection", "leases", "acquisition", "spread", "combat"];
+
+fn cell_center(cell: IVec2) -> Vec2 {
+    (cell.as_vec2() + 0.5) * ZONE_BLOCK_SIZE
+}
+
+fn fixed_population_app(bots: usize) -> App {
+    let mut app = App::new();
+    app.add_plugins(bevy::time::TimePlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_micros(
+            16_667,
+        )))
+        .insert_resource(Time::<Fixed>::from_hz(60.0))
+        .insert_resource(IntentGrid::new(1000, 1000))
+        .insert_resource(GameSettings {
+            width: 512_000.0,
+            height: 512_000.0,
+            bot_speed: 5.0,
+            debug_draw_circles: false,
+        })
+        .init_resource::<ResourceLedger>()
+        .add_plugins(NanobotPlugin::default())
+        .add_plugins(GatherPlugin)
+        .add_plugins(HaulPlugin)
+        .add_plugins(PlannedStructurePlugin)
+        .add_plugins(MaintenancePlugin)
+        .add_plugins(RepairPlugin)
+        .add_plugins(DefendPlugin)
+        .add_plugins(ChargePlugin)
+        .add_plugins(CombatPlugin)
+        .add_plugins(RegionalAllocationPlugin);
+
+    {
+        let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+        for y in -BASE_HALF_CELLS..BASE_HALF_CELLS {
+            for x in -FRONT_HALF_CELLS..FRONT_HALF_CELLS {
+                grid.add(IVec2::new(x, y), IntentKind::Defend);
+            }
+        }
+    }
+    spawn_swarm_economy(&mut app, SwarmId::PLAYER, -BASE_COLUMN, bots / 2);
+    spawn_swarm_economy(&mut app, OPPONENT, BASE_COLUMN, bots - bots / 2);
+    app
+}
+
+/// One swarm's base: Gather paint over deposits and Source Stockpiles on
+/// the far side, Build paint with Sink Stockpiles and Chargers facing the
+/// front, and `bots` nanobots split between Workers, Haulers, and
+/// Defenders.
+fn spawn_swarm_economy(app: &mut App, id: SwarmId, base_column: i32, bots: usize) {
+    let toward_front = -base_column.signum();
+    let gather_columns = (1..=BASE_HALF_CELLS).map(|i| base_column - toward_front * i);
+    let build_columns = (0..BASE_HALF_CELLS).map(|i| base_column + toward_front * i);
+    let rows = -BASE_HALF_CELLS..BASE_HALF_CELLS;
+
+    let world = app.world_mut();
+    let swarm = world
+        .spawn((
+            Swarm {},
+            id,
+            Transform::from_translation(cell_center(IVec2::new(base_column, 0)).extend(0.0)),
+        ))
+        .id();
+
+    let mut seeded = 0;
+    {
+        let mut grid = world.resource_mut::<IntentGrid>();
+        for x in gather_columns.clone() {
+            for y in rows.clone() {
+                grid.add_owned(IVec2::new(x, y), IntentKind::Gather, Some(id));
+            }
+        }
+        for x in build_columns.clone() {
+            for y in rows.clone() {
+                grid.add_owned(IVec2::new(x, y), IntentKind::Build, Some(id));
+            }
+        }
+    }
+
+    let deposits = (bots / 250).max(4);
+    let gather_cells = gather_columns
+        .flat_map(|x| rows.clone().map(move |y| IVec2::new(x, y)))
+        .collect::<Vec<_>>();
+    for i in 0..deposits {
+        let cell = gather_cells[i % gather_cells.len()];
+        let offset = Vec2::new(((i / gather_cells.len()) % 4) as f32 * 96.0 - 144.0, 0.0);
+        world.spawn((
+            ResourceDeposit {
+                kind: ResourceKind::Minerals,
+                amount: 1_000_000,
+                capacity: 1_000_000,
+                radius: 32.0,
+            },
+            Transform::from_translation((cell_center(cell) + offset).extend(0.0)),
+        ));
+    }
+    for cell in gather_cells.iter().step_by(2) {
+        world.spawn((
+            Stockpile {
+                kind: ResourceKind::Minerals,
+                amount: 100,
+                capacity: 400,
+                radius: 32.0,
+            },
+            StockpileRole::Source,
+            OwnerSwarm(swarm),
+            Transform::from_translation((cell_center(*cell) + Vec2::new(0.0, 160.0)).extend(0.0)),
+        ));
+        seeded += 100;
+    }
+
+    let build_cells = build_columns
+        .flat_map(|x| rows.clone().map(move |y| IVec2::new(x, y)))
+        .collect::<Vec<_>>();
+    let terminals = (bots / 1_000).max(2);
+    for (i, cell) in build_cells.iter().cycle().take(terminals * 2).enumerate() {
+        let pos = cell_center(*cell) + Vec2::new(((i / build_cells.len()) as f32) * 128.0, 0.0);
+        if i % 2 == 0 {
+            world.spawn((
+                Stockpile {
+                    kind: ResourceKind::Minerals,
+                    amount: 200,
+                    capacity: 1_000,
+                    radius: 32.0,
+                },
+                StockpileRole::Sink,
+                OwnerSwarm(swarm),
+                Transform::from_translation(pos.extend(0.0)),
+            ));
+            seeded += 200;
+        } else {
+            let mut charger = Charger::new(*cell);
+            charger.amount = 50;
+            world.spawn((
+                charger,
+                OwnerSwarm(swarm),
+                Transform::from_translation(pos.extend(0.0)),
+            ));
+            seeded += 50;
+        }
+    }
+    world
+        .resource_mut::<ResourceLedger>()
+        .add_for(id, ResourceKind::Minerals, seeded);
+
+    let origin = cell_center(IVec2::new(base_column, -BASE_HALF_CELLS));
+    for i in 0..bots {
+        let kind = match i % 10 {
+            0..5 => NanobotType::Worker,
+            5..7 => NanobotType::Hauler,
+            _ => NanobotType::Defender,
+        };
+        let pos = origin + Vec2::new((i % 100) as f32 * 40.0 - 2_000.0, (i / 100) as f32 * 40.0);
+        world.spawn((
+            NanobotBundle {
+                nanobot_type: kind,
+                swarm_member: SwarmMember::new(id),
+                health: Health::full(u32::MAX / 2),
+                ..Default::default()
+            },
+            Commitment::Idle,
+            Transform::from_translation(pos.extend(0.0)),
+        ));
+    }
+}
+
+fn warmed_app(bots: usize) -> App {
+    let mut app = fixed_population_app(bots);
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+    let population = app
+        .world_mut()
+        .query_filtered::<Entity, With<Nanobot>>()
+        .iter(app.world())
+        .count();
+    assert_eq!(population, bots, "benchmark warmup must preserve load");
+    app
+}
+
+/// Accumulated wall time between each stage's start and end probes.
+#[derive(Resource, Default)]
+struct StageTimings {
+    started: [Option<Instant>; STAGE_COUNT],
+    total: [Duration; STAGE_COUNT],
+}
+
+fn stage_start<const STAGE: usize>(mut timings: ResMut<StageTimings>) {
+    timings.started[STAGE] = Some(Instant::now());
+}
+
+fn stage_end<const STAGE: usize>(mut timings: ResMut<StageTimings>) {
+    if let Some(started) = timings.started[STAGE].take() {
+        timings.total[STAGE] += started.elapsed();
+    }
+}
+
+/// Bracket each stage with probe systems. The fixed schedule runs
+/// single-threaded here so a bracket never overlaps unrelated systems
+/// running on other threads.
+fn add_stage_probes(app: &mut App) {
+    app.init_resource::<StageTimings>()
+        .edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        })
+        .add_systems(
+            FixedUpdate,
+            (
+                stage_start::<PROJECTION>.before(RegionalAllocationSet::Project),
+                stage_end::<PROJECTION>
+                    .after(RegionalAllocationSet::Project)
+                    .before(RegionalAllocationSet::Invalidate),
+                stage_start::<LEASES>
+                    .after(RegionalAllocationSet::Project)
+                    .before(RegionalAllocationSet::Invalidate),
+                stage_end::<LEASES>
+                    .after(RegionalAllocationSet::Invalidate)
+                    .before(RegionalAllocationSet::Acquire),
+                stage_start::<ACQUISITION>
+                    .after(RegionalAllocationSet::Invalidate)
+                    .before(RegionalAllocationSet::Acquire),
+                stage_end::<ACQUISITION>.after(RegionalAllocationSet::Acquire),
+                stage_start::<SPREAD>
+                    .after(separation_system)
+                    .before(idle_spread_system),
+                stage_end::<SPREAD>
+                    .after(idle_spread_system)
+                    .before(velocity_system),
+                stage_start::<COMBAT>.before(NanobotSimulationSet::Combat),
+                stage_end::<COMBAT>.after(NanobotSimulationSet::Combat),
+            ),
+        );
+}
+
+/// Print mean single-threaded milliseconds per frame for the whole frame
+/// and each stage.
+fn print_stage_breakdown(bots: usize) {
+    let mut app = fixed_population_app(bots);
+    add_stage_probes(&mut app);
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+    app.insert_resource(StageTimings::default());
+    let started = Instant::now();
+    for _ in 0..BREAKDOWN_FRAMES {
+        app.update();
+    }
+    let frame = started.elapsed() / BREAKDOWN_FRAMES;
+    let timings = app.world().resource::<StageTimings>();
+    println!(
+        "fixed_population {bots} bots: single-threaded frame {:.3} ms",
+        ms(frame)
+    );
+    for (name, total) in STAGE_NAMES.iter().zip(timings.total) {
+        println!("  {name:<12} {:.3} ms/frame", ms(total / BREAKDOWN_FRAMES));
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+fn scale_out(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scale_out_fixed_population");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+    group.warm_up_time(Duration::from_secs(2));
+
+    for bots in BOT_COUNTS {
+        print_stage_breakdown(bots);
+        let mut app = warmed_app(bots);
+        group.throughput(Throughput::Elements(bots as u64));
+        group.bench_function(BenchmarkId::new("frame", bots), |b| b.iter(|| app.update()));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, scale_out);
+criterion_main!(benches);
@@ -45,7 +45,7 @@ fn clear_scene(world: &mut World) {
 fn focus_camera(world: &mut World) {
     let midpoint = CELLS
         .iter()
-        .map(|cell| top_down_2d_rts_prototype_nano_swarm::ai::get_world_from_zone(*cell))
+        .map(|cell| top_down_2d_rts_prototype_nano_swarm::nanobot::get_world_from_zone(*cell))
         .sum::<Vec2>()
         / CELLS.len() as f32;
     for (mut transform, mut projection, mut zoom) in world
@@ -63,7 +63,7 @@ fn focus_camera(world: &mut World) {
 
 fn spawn_placement_examples(world: &mut World) {
     for (index, cell) in CELLS.into_iter().enumerate() {
-        let center = top_down_2d_rts_prototype_nano_swarm::ai::get_world_from_zone(cell);
+        let center = top_down_2d_rts_prototype_nano_swarm::nanobot::get_world_from_zone(cell);
         world.spawn((
             Sprite::from_color(
                 Color::srgba(0.15, 0.38, 0.95, 0.28),
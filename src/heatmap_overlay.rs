@@ -0,0 +1,482 @@
+//! Shader-driven heatmap overlay for the simulation's hidden fields.
+//!
+//! One [`HeatmapLayer`] at a time is sampled from the player's view of
+//! a simulation resource into [`HeatmapField`], then normalised and
+//! uploaded to a full-map [`HeatmapMaterial`] the same way the zone
+//! material mirrors the intent grid:
+//!
+//! ```text
+//!   CellDensity          -> Crowding     (per cell)
+//!   DefendPressure       -> Threat       (per cell, above baseline)
+//!   ActionableProjection -> Pressure(c)  (per region, category c)
+//!   RegionalServiceAges  -> Starvation   (per region, oldest type)
+//! ```
+//!
+//! Sampling runs every [`HEATMAP_REFRESH_TICKS`] fixed ticks, or at
+//! once when the layer changes. [`HEATMAP_LAYER_KEY`] and the heatmap
+//! panel cycle the layer; the panel also draws the legend from
+//! [`heatmap_color`], which mirrors the shader's ramp.
+
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::*,
+    reflect::TypePath,
+    render::{render_resource::AsBindGroup, storage::ShaderStorageBuffer},
+    shader::ShaderRef,
+    sprite_render::{AlphaMode2d, Material2d},
+};
+
+use crate::nanobot::{
+    ALLOCATION_REGION_CELLS, ActionableProjection, AllocationRegion, CategoryWeights, CellDensity,
+    DEFEND_PRESSURE_BASELINE, DefendPressure, NanobotSimulationSet, OpportunityCategory,
+    RegionalServiceAges, SwarmId, pressure_map,
+};
+use crate::zones::zone_buffer_index_from_grid_point;
+use crate::{MAP_HEIGHT, MAP_WIDTH, ZONE_BLOCK_SIZE, ZONE_OVERLAY_Z, zone_overlay_transform};
+
+/// Key that cycles off -> each layer -> off.
+pub const HEATMAP_LAYER_KEY: KeyCode = KeyCode::KeyH;
+
+/// Fixed ticks between samples while a layer is shown: four per
+/// second at the runtime rate.
+pub const HEATMAP_REFRESH_TICKS: u64 = 15;
+
+/// Just in front of the intent zones, so paint stays readable through
+/// the heat and the swarm draws over both.
+pub const HEATMAP_OVERLAY_Z: f32 = ZONE_OVERLAY_Z + 0.5;
+
+/// Alpha of the coldest and hottest non-zero cells.
+pub const HEATMAP_MIN_ALPHA: f32 = 0.25;
+pub const HEATMAP_MAX_ALPHA: f32 = 0.7;
+
+const COLD: Vec3 = Vec3::new(0.1, 0.3, 1.0);
+const WARM: Vec3 = Vec3::new(1.0, 0.9, 0.1);
+const HOT: Vec3 = Vec3::new(1.0, 0.1, 0.05);
+
+/// Field drawn by the overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeatmapLayer {
+    /// Nanobots standing in each cell.
+    Crowding,
+    /// Defend pressure above baseline.
+    Threat,
+    /// Unclaimed work per region in one category.
+    Pressure(OpportunityCategory),
+    /// Allocation ticks the oldest waiting work in a region has waited.
+    Starvation,
+}
+
+impl HeatmapLayer {
+    pub const ALL: [HeatmapLayer; 3 + OpportunityCategory::COUNT] = [
+        HeatmapLayer::Crowding,
+        HeatmapLayer::Threat,
+        HeatmapLayer::Pressure(OpportunityCategory::ALL[0]),
+        HeatmapLayer::Pressure(OpportunityCategory::ALL[1]),
+        HeatmapLayer::Pressure(OpportunityCategory::ALL[2]),
+        HeatmapLayer::Pressure(OpportunityCategory::ALL[3]),
+        HeatmapLayer::Pressure(OpportunityCategory::ALL[4]),
+        HeatmapLayer::Pressure(OpportunityCategory::ALL[5]),
+        HeatmapLayer::Starvation,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HeatmapLayer::Crowding => "Crowding",
+            HeatmapLayer::Threat => "Threat",
+            HeatmapLayer::Pressure(OpportunityCategory::PlannedBuild) => "Build pressure",
+            HeatmapLayer::Pressure(OpportunityCategory::Repair) => "Repair pressure",
+            HeatmapLayer::Pressure(OpportunityCategory::Maintenance) => "Maintenance pressure",
+            HeatmapLayer::Pressure(OpportunityCategory::Gather) => "Gather pressure",
+            HeatmapLayer::Pressure(OpportunityCategory::Defend) => "Defend pressure",
+            HeatmapLayer::Pressure(OpportunityCategory::Haul) => "Haul pressure",
+            HeatmapLayer::Starvation => "Starvation",
+        }
+    }
+
+    /// Unit for the legend's maximum.
+    pub fn unit(self) -> &'static str {
+        match self {
+            HeatmapLayer::Crowding => "bots",
+            HeatmapLayer::Threat => "x",
+            HeatmapLayer::Pressure(_) => "work",
+            HeatmapLayer::Starvation => "ticks",
+        }
+    }
+
+    /// Step through `off, ALL[0], ..., ALL[n - 1]`, wrapping.
+    pub fn step(selection: Option<HeatmapLayer>, offset: isize) -> Option<HeatmapLayer> {
+        let len = Self::ALL.len() as isize + 1;
+        let index = selection
+            .and_then(|layer| Self::ALL.iter().position(|candidate| *candidate == layer))
+            .map_or(0, |position| position as isize + 1);
+        match (index + offset).rem_euclid(len) {
+            0 => None,
+            slot => Some(Self::ALL[slot as usize - 1]),
+        }
+    }
+}
+
+/// Selected layer. `None` hides the overlay.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct HeatmapOverlaySettings {
+    pub layer: Option<HeatmapLayer>,
+}
+
+/// Raw values of the shown layer per intent-grid cell, plus their
+/// maximum for normalisation and the legend. Cells without an entry
+/// are zero.
+#[derive(Debug, Default, Resource)]
+pub struct HeatmapField {
+    layer: Option<HeatmapLayer>,
+    values: HashMap<IVec2, f32>,
+    max: f32,
+}
+
+impl HeatmapField {
+    pub fn layer(&self) -> Option<HeatmapLayer> {
+        self.layer
+    }
+
+    pub fn value(&self, cell: IVec2) -> f32 {
+        self.values.get(&cell).copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// `value(cell) / max`, in `[0, 1]`.
+    pub fn normalized(&self, cell: IVec2) -> f32 {
+        if self.max <= 0.0 {
+            0.0
+        } else {
+            (self.value(cell) / self.max).clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, f32)> + '_ {
+        self.values.iter().map(|(cell, value)| (*cell, *value))
+    }
+
+    fn set(&mut self, cell: IVec2, value: f32) {
+        if value > 0.0 {
+            self.values.insert(cell, value);
+            self.max = self.max.max(value);
+        }
+    }
+
+    fn set_region(&mut self, region: AllocationRegion, value: f32) {
+        let min = region.min_cell();
+        for y in 0..ALLOCATION_REGION_CELLS {
+            for x in 0..ALLOCATION_REGION_CELLS {
+                self.set(min + IVec2::new(x, y), value);
+            }
+        }
+    }
+}
+
+/// Legend colour for a normalised value. Zero is transparent; the
+/// ramp runs cold -> warm -> hot, as in `heatmap_shader.wgsl`.
+pub fn heatmap_color(t: f32) -> Color {
+    if t <= 0.0 {
+        return Color::NONE;
+    }
+    let t = t.min(1.0);
+    let rgb = if t < 0.5 {
+        COLD.lerp(WARM, t * 2.0)
+    } else {
+        WARM.lerp(HOT, (t - 0.5) * 2.0)
+    };
+    let alpha = HEATMAP_MIN_ALPHA + (HEATMAP_MAX_ALPHA - HEATMAP_MIN_ALPHA) * t;
+    Color::srgba(rgb.x, rgb.y, rgb.z, alpha)
+}
+
+/// Full-map heat values uploaded to the heatmap shader, one `f32` in
+/// `[0, 1]` per cell in the zone material's layout.
+#[derive(AsBindGroup, Asset, TypePath, Debug, Clone)]
+pub struct HeatmapMaterial {
+    #[storage(2, read_only)]
+    pub values: Handle<ShaderStorageBuffer>,
+    #[uniform(3)]
+    pub width: u32,
+    #[uniform(4)]
+    pub height: u32,
+}
+
+impl Material2d for HeatmapMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/heatmap_shader.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+/// The overlay mesh and its material.
+#[derive(Debug, Component)]
+pub struct HeatmapOverlayMesh {
+    pub handle: Handle<HeatmapMaterial>,
+}
+
+pub fn heatmap_layer_key_system(
+    keyboard_input: Option<Res<ButtonInput<KeyCode>>>,
+    mut settings: ResMut<HeatmapOverlaySettings>,
+) {
+    if keyboard_input.is_some_and(|input| input.just_pressed(HEATMAP_LAYER_KEY)) {
+        settings.layer = HeatmapLayer::step(settings.layer, 1);
+    }
+}
+
+/// Sample the selected layer into [`HeatmapField`]. Only the player's
+/// entries are shown; unowned projected work counts as the player's.
+pub fn heatmap_field_system(
+    settings: Res<HeatmapOverlaySettings>,
+    mut field: ResMut<HeatmapField>,
+    mut tick: Local<u64>,
+    density: Option<Res<CellDensity>>,
+    pressure: Option<Res<DefendPressure>>,
+    projection: Option<Res<ActionableProjection>>,
+    ages: Option<Res<RegionalServiceAges>>,
+) {
+    *tick += 1;
+    if !settings.is_changed() && !tick.is_multiple_of(HEATMAP_REFRESH_TICKS) {
+        return;
+    }
+    if settings.layer.is_none() && field.layer.is_none() {
+        return;
+    }
+    field.layer = settings.layer;
+    field.values.clear();
+    field.max = 0.0;
+    match settings.layer {
+        None => {}
+        Some(HeatmapLayer::Crowding) => {
+            for (cell, count) in density.iter().flat_map(|density| density.iter()) {
+                field.set(cell, count as f32);
+            }
+        }
+        Some(HeatmapLayer::Threat) => {
+            for (cell, value) in pressure
+                .iter()
+                .flat_map(|pressure| pressure.iter_for(SwarmId::PLAYER))
+            {
+                field.set(cell, value - DEFEND_PRESSURE_BASELINE);
+            }
+        }
+        Some(HeatmapLayer::Pressure(category)) => {
+            let Some(projection) = projection else {
+                return;
+            };
+            let player_work = projection
+                .iter_regions()
+                .map(|(region, opportunities)| {
+                    let owned = opportunities
+                        .iter()
+                        .filter(|opportunity| {
+                            opportunity
+                                .owner
+                                .is_none_or(|owner| owner == SwarmId::PLAYER)
+                        })
+                        .copied()
+                        .collect::<Vec<_>>();
+                    (region, owned)
+                })
+                .collect::<Vec<_>>();
+            let pressures = pressure_map(
+                player_work
+                    .iter()
+                    .map(|(region, work)| (*region, work.as_slice())),
+                CategoryWeights::default(),
+            );
+            for (region, pressure) in pressures {
+                field.set_region(region, pressure.categories.get(category) as f32);
+            }
+        }
+        Some(HeatmapLayer::Starvation) => {
+            let mut oldest = HashMap::<AllocationRegion, u32>::new();
+            for (swarm, region, age) in ages.iter().flat_map(|ages| ages.iter()) {
+                if swarm == SwarmId::PLAYER {
+                    let entry = oldest.entry(region).or_default();
+                    *entry = (*entry).max(age);
+                }
+            }
+            for (region, age) in oldest {
+                field.set_region(region, age as f32);
+            }
+        }
+    }
+}
+
+/// Spawn the hidden overlay mesh. Skipped in apps without rendering
+/// assets, so simulation tests can add the plugin.
+pub fn spawn_heatmap_overlay_system(
+    mut commands: Commands,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<HeatmapMaterial>>>,
+    buffers: Option<ResMut<Assets<ShaderStorageBuffer>>>,
+) {
+    let (Some(mut meshes), Some(mut materials), Some(mut buffers)) = (meshes, materials, buffers)
+    else {
+        return;
+    };
+    let cells = (MAP_WIDTH * MAP_HEIGHT) as usize;
+    let handle = materials.add(HeatmapMaterial {
+        values: buffers.add(ShaderStorageBuffer::from(vec![0.0f32; cells])),
+        width: MAP_WIDTH,
+        height: MAP_HEIGHT,
+    });
+    let mut transform = zone_overlay_transform(
+        MAP_WIDTH as f32 * ZONE_BLOCK_SIZE,
+        MAP_HEIGHT as f32 * ZONE_BLOCK_SIZE,
+    );
+    transform.translation.z = HEATMAP_OVERLAY_Z;
+    commands.spawn((
+        Mesh2d(meshes.add(Mesh::from(Rectangle::default()))),
+        MeshMaterial2d(handle.clone()),
+        transform,
+        Visibility::Hidden,
+        HeatmapOverlayMesh { handle },
+    ));
+}
+
+/// Upload a changed [`HeatmapField`] and show the mesh only while a
+/// layer is selected.
+pub fn mirror_heatmap_to_material_system(
+    field: Res<HeatmapField>,
+    materials: Option<Res<Assets<HeatmapMaterial>>>,
+    buffers: Option<ResMut<Assets<ShaderStorageBuffer>>>,
+    mut mesh: Query<(&HeatmapOverlayMesh, &mut Visibility)>,
+) {
+    if !field.is_changed() {
+        return;
+    }
+    let Ok((overlay, mut visibility)) = mesh.single_mut() else {
+        return;
+    };
+    let wanted = if field.layer.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if *visibility != wanted {
+        *visibility = wanted;
+    }
+    let (Some(materials), Some(mut buffers)) = (materials, buffers) else {
+        return;
+    };
+    let Some(material) = materials.get(&overlay.handle) else {
+        return;
+    };
+    let width = material.width as i32;
+    let height = material.height as i32;
+    let mut packed = vec![0.0f32; (width * height) as usize];
+    for (cell, _) in field.iter() {
+        if let Some(index) = zone_buffer_index_from_grid_point(cell, width, height) {
+            packed[(index.y * width + index.x) as usize] = field.normalized(cell);
+        }
+    }
+    if let Some(buffer) = buffers.get_mut(&material.values) {
+        buffer.set_data(packed);
+    }
+}
+
+/// Plugin that samples and mirrors the heatmap. The material's
+/// render plugin is registered with the other materials in
+/// [`crate::build_app_with_presentation`].
+pub struct HeatmapOverlayPlugin;
+
+impl Plugin for HeatmapOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<HeatmapOverlaySettings>() {
+            app.init_resource::<HeatmapOverlaySettings>();
+        }
+        app.init_resource::<HeatmapField>()
+            .add_systems(Startup, spawn_heatmap_overlay_system)
+            .add_systems(
+                FixedUpdate,
+                heatmap_field_system.after(NanobotSimulationSet::Maintenance),
+            )
+            .add_systems(
+                Update,
+                (heatmap_layer_key_system, mirror_heatmap_to_material_system).chain(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Layer cycling, normalisation, and the legend ramp. Sampling from
+    //! the simulation is covered by `tests/behavior/heatmap_overlay.rs`.
+
+    use super::*;
+
+    #[test]
+    fn layer_cycle_passes_through_off() {
+        assert_eq!(HeatmapLayer::step(None, 1), Some(HeatmapLayer::Crowding));
+        assert_eq!(HeatmapLayer::step(Some(HeatmapLayer::Starvation), 1), None);
+        assert_eq!(HeatmapLayer::step(None, -1), Some(HeatmapLayer::Starvation));
+        let mut layer = None;
+        for _ in 0..=HeatmapLayer::ALL.len() {
+            layer = HeatmapLayer::step(layer, 1);
+        }
+        assert_eq!(layer, None, "full cycle returns to off");
+    }
+
+    #[test]
+    fn every_category_has_a_pressure_layer() {
+        for category in OpportunityCategory::ALL {
+            assert!(HeatmapLayer::ALL.contains(&HeatmapLayer::Pressure(category)));
+        }
+    }
+
+    #[test]
+    fn region_values_cover_every_cell_and_normalise_to_the_peak() {
+        let mut field = HeatmapField::default();
+        field.set_region(AllocationRegion::for_cell(IVec2::ZERO), 4.0);
+        field.set(IVec2::new(-1, -1), 2.0);
+        field.set(IVec2::new(-2, -2), 0.0);
+
+        assert_eq!(
+            field.iter().count(),
+            (ALLOCATION_REGION_CELLS * ALLOCATION_REGION_CELLS) as usize + 1
+        );
+        assert_eq!(field.max(), 4.0);
+        assert_eq!(
+            field.normalized(IVec2::splat(ALLOCATION_REGION_CELLS - 1)),
+            1.0
+        );
+        assert_eq!(field.normalized(IVec2::new(-1, -1)), 0.5);
+        assert_eq!(field.normalized(IVec2::new(-2, -2)), 0.0);
+    }
+
+    fn assert_close(actual: Color, expected: Color) {
+        let (actual, expected) = (actual.to_srgba(), expected.to_srgba());
+        for (a, e) in actual
+            .to_f32_array()
+            .into_iter()
+            .zip(expected.to_f32_array())
+        {
+            assert!((a - e).abs() < 1e-5, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn legend_ramp_is_transparent_at_zero_and_hot_at_peak() {
+        assert_eq!(heatmap_color(0.0), Color::NONE);
+        assert_close(
+            heatmap_color(1.0),
+            Color::srgba(HOT.x, HOT.y, HOT.z, HEATMAP_MAX_ALPHA),
+        );
+        assert_close(
+            heatmap_color(0.5),
+            Color::srgba(
+                WARM.x,
+                WARM.y,
+                WARM.z,
+                (HEATMAP_MIN_ALPHA + HEATMAP_MAX_ALPHA) / 2.0,
+            ),
+        );
+    }
+}
@@ -5,7 +5,7 @@
 //! shader storage buffers. The GPU zone material reads from this resource via a
 //! mirror system; the resource itself never reads from rendering.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use bevy::{
     input::{ButtonInput, keyboard::KeyCode},
@@ -22,12 +22,13 @@ pub enum IntentKind {
     Build,
     Defend,
     Corridor,
+    Reclaim,
 }
 
 impl IntentKind {
     /// Number of distinct intent kinds. Equal to the number of intent layers that
     /// can coexist at a single map cell.
-    pub const COUNT: usize = 4;
+    pub const COUNT: usize = 5;
 
     /// All intent kinds in stable shader-slot order.
     pub const ALL: [IntentKind; Self::COUNT] = [
@@ -35,6 +36,7 @@ impl IntentKind {
         IntentKind::Build,
         IntentKind::Defend,
         IntentKind::Corridor,
+        IntentKind::Reclaim,
     ];
 
     /// Stable per-kind index in `[0, COUNT)`. Used to address per-layer data
@@ -45,6 +47,7 @@ impl IntentKind {
             IntentKind::Build => 1,
             IntentKind::Defend => 2,
             IntentKind::Corridor => 3,
+            IntentKind::Reclaim => 4,
         }
     }
 
@@ -137,6 +140,18 @@ pub struct IntentGrid {
     render_dirty: HashSet<IVec2>,
     /// Cells awaiting actionable-projection consumption.
     projection_dirty: HashSet<IVec2>,
+    /// Cells awaiting idle-spread fit-index consumption.
+    spread_dirty: HashSet<IVec2>,
+    /// Cells whose Corridor paint, road, or danger changed, awaiting
+    /// the hauler route-cost cache.
+    route_dirty: HashSet<IVec2>,
+    /// Built road segments by cell, with the owning swarm. Roads are
+    /// structures, not paint, but route planning reads them through
+    /// the grid alongside Corridor paint.
+    roads: HashMap<IVec2, Option<SwarmId>>,
+    /// Danger level per threatened swarm and cell, derived from hostile
+    /// Defenders and recent structure damage. Absent entries are safe.
+    danger: HashMap<(SwarmId, IVec2), u8>,
 }
 
 impl IntentGrid {
@@ -150,6 +165,10 @@ impl IntentGrid {
             active_cells: Vec::new(),
             render_dirty: HashSet::new(),
             projection_dirty: HashSet::new(),
+            spread_dirty: HashSet::new(),
+            route_dirty: HashSet::new(),
+            roads: HashMap::new(),
+            danger: HashMap::new(),
         }
     }
 
@@ -201,7 +220,7 @@ impl IntentGrid {
             if self.cells[idx].is_empty() {
                 self.remove_active(point);
             }
-            self.mark_dirty(point);
+            self.mark_dirty(point, kind);
         }
         true
     }
@@ -251,6 +270,61 @@ impl IntentGrid {
         true
     }
 
+    /// Record a built road at `point` owned by `owner`. Returns whether
+    /// point is in bounds.
+    pub fn set_road(&mut self, point: IVec2, owner: Option<SwarmId>) -> bool {
+        if !self.in_bounds(point) {
+            return false;
+        }
+        if self.roads.insert(point, owner) != Some(owner) {
+            self.route_dirty.insert(point);
+        }
+        true
+    }
+
+    /// Forget the road at `point`. Clearing an absent road is a no-op.
+    pub fn clear_road(&mut self, point: IVec2) {
+        if self.roads.remove(&point).is_some() {
+            self.route_dirty.insert(point);
+        }
+    }
+
+    /// True when `point` holds a road usable by `swarm`. Unowned roads
+    /// are shared, like unowned paint.
+    pub fn road_visible_to(&self, point: IVec2, swarm: SwarmId) -> bool {
+        self.roads
+            .get(&point)
+            .is_some_and(|owner| owner.is_none_or(|owner| owner == swarm))
+    }
+
+    /// True when any road, of any owner, sits at `point`.
+    pub fn has_road(&self, point: IVec2) -> bool {
+        self.roads.contains_key(&point)
+    }
+
+    /// Replace the whole danger layer. Only cells whose level changed
+    /// for some swarm are marked route-dirty; out-of-bounds and zero
+    /// entries are dropped.
+    pub fn replace_danger(&mut self, mut next: HashMap<(SwarmId, IVec2), u8>) {
+        next.retain(|(_, point), level| *level > 0 && self.in_bounds(*point));
+        for (key, level) in &self.danger {
+            if next.get(key) != Some(level) {
+                self.route_dirty.insert(key.1);
+            }
+        }
+        for (key, level) in &next {
+            if self.danger.get(key) != Some(level) {
+                self.route_dirty.insert(key.1);
+            }
+        }
+        self.danger = next;
+    }
+
+    /// Danger level `swarm` faces at `point`; `0` is safe.
+    pub fn danger_for(&self, point: IVec2, swarm: SwarmId) -> u8 {
+        self.danger.get(&(swarm, point)).copied().unwrap_or(0)
+    }
+
     /// Number of changed cells awaiting the render mirror.
     pub fn render_dirty_count(&self) -> usize {
         self.render_dirty.len()
@@ -261,6 +335,16 @@ impl IntentGrid {
         self.projection_dirty.len()
     }
 
+    /// Number of changed cells awaiting the idle-spread fit index.
+    pub fn spread_dirty_count(&self) -> usize {
+        self.spread_dirty.len()
+    }
+
+    /// Number of cells with changed Corridor paint, roads, or danger awaiting the route-cost cache.
+    pub fn route_dirty_count(&self) -> usize {
+        self.route_dirty.len()
+    }
+
     /// Drain changed cells for the render mirror in deterministic `(y, x)` order.
     pub fn drain_render_dirty(&mut self) -> Vec<IVec2> {
         drain_sorted(&mut self.render_dirty)
@@ -271,6 +355,16 @@ impl IntentGrid {
         drain_sorted(&mut self.projection_dirty)
     }
 
+    /// Drain changed cells for the idle-spread fit index in deterministic `(y, x)` order.
+    pub fn drain_spread_dirty(&mut self) -> Vec<IVec2> {
+        drain_sorted(&mut self.spread_dirty)
+    }
+
+    /// Drain changed Corridor, road, and danger cells for the route-cost cache in deterministic `(y, x)` order.
+    pub fn drain_route_dirty(&mut self) -> Vec<IVec2> {
+        drain_sorted(&mut self.route_dirty)
+    }
+
     /// Iterate every cell in row-major order. Reserved for consumers that truly
     /// need empty cells too, such as full-grid serialization.
     pub fn iter_cells(&self) -> impl Iterator<Item = (IVec2, &IntentCell)> {
@@ -300,7 +394,7 @@ impl IntentGrid {
             if self.cells[idx].active == kind.bit() {
                 self.insert_active(point);
             }
-            self.mark_dirty(point);
+            self.mark_dirty(point, kind);
         }
         true
     }
@@ -325,9 +419,13 @@ impl IntentGrid {
         }
     }
 
-    fn mark_dirty(&mut self, point: IVec2) {
+    fn mark_dirty(&mut self, point: IVec2, kind: IntentKind) {
         self.render_dirty.insert(point);
         self.projection_dirty.insert(point);
+        self.spread_dirty.insert(point);
+        if kind == IntentKind::Corridor {
+            self.route_dirty.insert(point);
+        }
     }
 
     fn index(&self, point: IVec2) -> usize {
@@ -375,6 +473,65 @@ mod tests {
         assert_eq!(grid.projection_dirty_count(), 0);
     }
 
+    #[test]
+    fn only_corridor_changes_mark_route_dirty() {
+        let mut grid = IntentGrid::new(4, 4);
+        grid.paint(IVec2::ZERO, IntentKind::Gather);
+        assert_eq!(grid.route_dirty_count(), 0);
+
+        grid.paint(IVec2::new(1, 0), IntentKind::Corridor);
+        grid.paint(IVec2::new(1, 0), IntentKind::Corridor);
+        grid.erase(IVec2::new(-1, 0), IntentKind::Corridor);
+        assert_eq!(grid.drain_route_dirty(), vec![IVec2::new(1, 0)]);
+
+        grid.erase(IVec2::new(1, 0), IntentKind::Corridor);
+        assert_eq!(grid.drain_route_dirty(), vec![IVec2::new(1, 0)]);
+    }
+
+    #[test]
+    fn roads_mark_route_dirty_and_follow_owner_visibility() {
+        let mut grid = IntentGrid::new(4, 4);
+        let point = IVec2::new(1, 0);
+        assert!(grid.set_road(point, Some(SwarmId(7))));
+        assert!(grid.set_road(point, Some(SwarmId(7))));
+        assert!(!grid.set_road(IVec2::new(9, 9), None));
+        assert_eq!(grid.drain_route_dirty(), vec![point]);
+        assert_eq!(grid.render_dirty_count(), 0);
+
+        assert!(grid.road_visible_to(point, SwarmId(7)));
+        assert!(!grid.road_visible_to(point, SwarmId::PLAYER));
+        grid.set_road(point, None);
+        assert!(grid.road_visible_to(point, SwarmId::PLAYER));
+
+        grid.drain_route_dirty();
+        grid.clear_road(point);
+        grid.clear_road(point);
+        assert!(!grid.has_road(point));
+        assert_eq!(grid.drain_route_dirty(), vec![point]);
+    }
+
+    #[test]
+    fn danger_is_per_swarm_and_marks_only_changed_cells_route_dirty() {
+        let mut grid = IntentGrid::new(4, 4);
+        let point = IVec2::new(2, 1);
+        grid.replace_danger(HashMap::from([
+            ((SwarmId::PLAYER, point), 3),
+            ((SwarmId::PLAYER, IVec2::new(1, 1)), 0),
+            ((SwarmId::PLAYER, IVec2::new(9, 9)), 2),
+        ]));
+        assert_eq!(grid.danger_for(point, SwarmId::PLAYER), 3);
+        assert_eq!(grid.danger_for(point, SwarmId(4)), 0);
+        assert_eq!(grid.drain_route_dirty(), vec![point]);
+
+        grid.replace_danger(HashMap::from([((SwarmId::PLAYER, point), 3)]));
+        assert_eq!(grid.route_dirty_count(), 0);
+
+        grid.replace_danger(HashMap::new());
+        assert_eq!(grid.danger_for(point, SwarmId::PLAYER), 0);
+        assert_eq!(grid.drain_route_dirty(), vec![point]);
+        assert_eq!(grid.render_dirty_count(), 0);
+    }
+
     #[test]
     fn ownership_change_marks_binary_layer_dirty() {
         let mut grid = IntentGrid::new(4, 4);
@@ -443,6 +600,8 @@ mod tests {
         assert_eq!(grid.drain_render_dirty(), expected);
         assert_eq!(grid.projection_dirty_count(), 2);
         assert_eq!(grid.drain_projection_dirty(), expected);
+        assert_eq!(grid.spread_dirty_count(), 2);
+        assert_eq!(grid.drain_spread_dirty(), expected);
     }
 
     #[test]
@@ -504,7 +663,7 @@ mod tests {
 /// Which intent layer the player brush is currently writing. The brush
 /// systems read this resource and target the selected kind instead of a
 /// hard-coded one, so the player can switch between Gather, Build, Defend,
-/// and Corridor layers at runtime. Default is [`IntentKind::Gather`]
+/// Corridor, and Reclaim layers at runtime. Default is [`IntentKind::Gather`]
 /// because that is the most common production layer.
 #[derive(Debug, Clone, Copy, Resource, PartialEq, Eq)]
 pub struct BrushSelection {
@@ -526,7 +685,7 @@ impl BrushSelection {
 }
 
 /// Number-row bindings for the brush layer. `Digit1` selects Gather,
-/// `Digit2` Build, `3` Defend, `4` Corridor. Numpad variants are also
+/// `Digit2` Build, `3` Defend, `4` Corridor, `5` Reclaim. Numpad variants are also
 /// accepted. Uses `just_pressed` so holding the key does not strobe the
 /// selection; if multiple keys are pressed in one frame the first matching
 /// binding wins.
@@ -535,6 +694,7 @@ const BRUSH_KEY_BINDINGS: &[(KeyCode, KeyCode, IntentKind)] = &[
     (KeyCode::Digit2, KeyCode::Numpad2, IntentKind::Build),
     (KeyCode::Digit3, KeyCode::Numpad3, IntentKind::Defend),
     (KeyCode::Digit4, KeyCode::Numpad4, IntentKind::Corridor),
+    (KeyCode::Digit5, KeyCode::Numpad5, IntentKind::Reclaim),
 ];
 
 /// Primary number-row [`KeyCode`] for `kind`, or `None` if the kind has no
@@ -1,8 +1,9 @@
-pub mod ai;
 pub mod building;
 pub mod fly_camera;
 pub mod game_settings;
+pub mod heatmap_overlay;
 pub mod intent;
+pub mod logistics_overlay;
 pub mod materials;
 pub mod nanobot;
 pub mod resources;
@@ -14,7 +15,6 @@ pub mod tactical_overlay;
 pub mod ui;
 pub mod zones;
 
-use ai::AiPlugin;
 use anyhow::Result;
 use bevy::{
     app::TerminalCtrlCHandlerPlugin,
@@ -36,7 +36,9 @@ use bevy::{
 };
 use fly_camera::{Camera2dFlyPlugin, CameraZoom2d, FlyCamera2d};
 use game_settings::GameSettings;
+use heatmap_overlay::{HeatmapMaterial, HeatmapOverlayPlugin};
 use intent::IntentGrid;
+use logistics_overlay::LogisticsOverlayPlugin;
 use materials::BackgroundMaterial;
 use nanobot::{
     CollapsePlugin, CombatPlugin, NanobotPlugin, PlannedStructurePlugin, PopulationDemandPlugin,
@@ -143,7 +145,26 @@ pub fn build_app_with_presentation(presentation: Presentation) -> App {
         .init_resource::<ResourceLedger>()
         .insert_resource(scenario::default_player_priority())
         .init_resource::<nanobot::OpponentSwarmIdAlloc>()
+        // Maintenance shifts spend minerals from per-structure upkeep
+        // depots that haulers keep stocked.
+        .init_resource::<nanobot::MaintenanceUpkeep>()
+        // Planned structures need their mineral cost hauled to the
+        // site before the build can finish.
+        .init_resource::<nanobot::ConstructionCost>()
+        // Each nanobot type accelerates, turns, and brakes on its own
+        // movement profile; loaded haulers are slower.
+        .init_resource::<nanobot::NanobotSteering>()
+        // Owned Corridor paint is built into roads that speed up every
+        // bot on them and discount routes through them.
+        .init_resource::<nanobot::CorridorRoads>()
+        // Haulers carry minerals out to Defenders holding a Defend
+        // cell so a supplied front line does not walk back to recharge.
+        .init_resource::<nanobot::FieldResupply>()
+        // Destroyed structures and nanobots drop their minerals as
+        // wreckage any swarm can gather.
+        .init_resource::<nanobot::Salvage>()
         .add_plugins(Material2dPlugin::<BackgroundMaterial>::default())
+        .add_plugins(Material2dPlugin::<HeatmapMaterial>::default())
         // must be before NanobotPlugin because otherwise it receives events with despawned entities
         .add_plugins(NanoswarmUiSetupPlugin)
         // must be before NanobotPlugin because otherwise it receives events with despawned entities
@@ -153,40 +174,51 @@ pub fn build_app_with_presentation(presentation: Presentation) -> App {
         // chain orders itself behind `move_velocity_system`, which
         // only exists once NanobotPlugin is registered.
         .add_plugins(nanobot::GatherPlugin)
+        // DepositDynamicsPlugin regrows deposits and runs the
+        // scenario deposit spawn schedule before the gather chain,
+        // so extraction and the gather chain's depletion messages
+        // see this tick's regrowth.
+        .add_plugins(nanobot::DepositDynamicsPlugin)
         // HaulPlugin chains after `move_velocity_system`, which is
         // registered by NanobotPlugin above. The arrival signal the
         // hauler systems wait for is the same one the gather chain
         // uses, so they only need to run after the movement step.
         .add_plugins(nanobot::HaulPlugin)
-        // The legacy `BuildPlugin` (issue #10's `BuildSite` /
-        // `Structure` auto-creation) is intentionally NOT
-        // registered. After the four Planned Structure
-        // migration issues (#25 Source Stockpile, #26 Sink
-        // Stockpile, #27 Production Facility, #28 Charger)
-        // every demand-driven support structure lives on
-        // the `PlannedStructurePlugin` lifecycle. Issue #29
-        // removed the remaining "spontaneous Build-paint
-        // spawn" path -- a Build cell now plans a Sink
-        // Stockpile through the planned-structure chain,
-        // and never spawns a legacy `BuildSite`. The
-        // `BuildSite` and `BuildPlugin` types still exist
-        // in the codebase for the unit tests in
-        // `src/nanobot/build.rs`; the `Structure` type
-        // also stays because the maintenance test fixtures
-        // spawn `Structure` entities directly to drive the
-        // maintenance chain. No production system
-        // auto-spawns any of them.
+        // Every demand-driven support structure is built through the
+        // planned-structure lifecycle; a Build cell plans a Sink
+        // Stockpile rather than spawning a structure directly.
         .add_plugins(PlannedStructurePlugin)
         // MaintenancePlugin chains after planned-structure work so maintenance
         // can reset condition before degradation. Completed Stockpiles,
-        // Production Facilities, and Chargers receive the shared `Structure`
-        // condition sidecar and participate in this lifecycle.
+        // Production Facilities, Chargers, Research Labs, and Roads
+        // receive the shared `Structure` condition sidecar and
+        // participate in this lifecycle.
         .add_plugins(nanobot::MaintenancePlugin)
+        // RepairPlugin restores health lost to combat or neglect. Repair
+        // is its own allocation category, ranked by recent damage and
+        // Defend pressure.
+        .add_plugins(nanobot::RepairPlugin)
+        // StructureTierPlugin plans in-place upgrades for saturated
+        // support structures; workers build them through the same
+        // planned-structure lifecycle.
+        .add_plugins(nanobot::StructureTierPlugin)
+        // RoadPlugin plans roads on Corridor paint for workers to
+        // build, and mirrors built roads into the intent grid.
+        .add_plugins(nanobot::RoadPlugin)
+        // ReclaimPlugin turns Reclaim paint over owned structures into
+        // deconstruction plans and clears emptied reclaimed caches.
+        .add_plugins(nanobot::ReclaimPlugin)
+        // WreckagePlugin clears wrecks once Workers have gathered them.
+        .add_plugins(nanobot::WreckagePlugin)
         // ProductionPlugin chains after `move_velocity_system`
         // for the same reason; auto-creation runs last in its
         // own chain so it sees the post-pick / post-work state
         // before deciding to spawn a new facility.
         .add_plugins(ProductionPlugin)
+        // ResearchPlugin funds queued upgrades from lab hoppers
+        // and plans a lab between production auto-creation and
+        // sink-stockpile demand.
+        .add_plugins(nanobot::ResearchPlugin)
         // CollapsePlugin must run after the production work
         // system so the "is this facility currently busy?"
         // check sees the post-work state, not the pre-work
@@ -206,10 +238,16 @@ pub fn build_app_with_presentation(presentation: Presentation) -> App {
         .add_plugins(nanobot::ChargePlugin)
         // Combat consumes Defend holds and Charge-scaled stats after sustain updates.
         .add_plugins(CombatPlugin)
-        // Single allocator for Gather, Planned Build, Maintenance, Defend, and Haul.
+        // ThreatPlugin turns hostile Defenders into per-swarm danger that
+        // raises route costs and sends Workers and Haulers away from fights.
+        .add_plugins(nanobot::ThreatPlugin)
+        // Single allocator for Gather, Planned Build, Maintenance, Repair, Defend, and Haul.
         .add_plugins(RegionalAllocationPlugin)
         // Typed workload chooses required capacity; Production Priority orders shortages.
         .add_plugins(PopulationDemandPlugin)
+        // TelemetryPlugin samples per-swarm flows and population once a
+        // second for the telemetry panel and CSV export.
+        .add_plugins(nanobot::TelemetryPlugin)
         // StructureOverlayPlugin is a consumer of the
         // simulation's per-structure state. It registers
         // its spawn/update/visibility/cleanup systems on
@@ -224,7 +262,14 @@ pub fn build_app_with_presentation(presentation: Presentation) -> App {
         // status labels fade out exactly as the tactical
         // overlay fades in.
         .add_plugins(TacticalOverlayPlugin)
-        .add_plugins(AiPlugin)
+        // LogisticsOverlayPlugin draws reservations, last-minute
+        // flow, and starved terminals when toggled on. Flow comes
+        // from TelemetryPlugin's LogisticsFlowHistory.
+        .add_plugins(LogisticsOverlayPlugin)
+        // HeatmapOverlayPlugin samples one hidden simulation field
+        // (crowding, threat, category pressure, starvation) into the
+        // full-map heatmap mesh when a layer is selected.
+        .add_plugins(HeatmapOverlayPlugin)
         .add_plugins(Camera2dFlyPlugin)
         .add_systems(Startup, setup_things_startup.pipe(error_handler));
     app
@@ -337,14 +382,26 @@ fn setup_things_startup(
 
     commands.insert_resource(GameSettings::from_file_ron("config/game_settings.ron")?);
     commands.insert_resource(StructureSprites::load(&asset_server));
+    let deposit_dynamics =
+        nanobot::DepositDynamicsConfig::from_file_ron("config/deposit_dynamics.ron")?;
+    commands.insert_resource(nanobot::DepositSprite(
+        asset_server.load("resource_deposit.png"),
+    ));
 
-    scenario::spawn_default_player_scenario(&mut commands, &asset_server, &mut grid);
+    scenario::spawn_default_player_scenario(
+        &mut commands,
+        &asset_server,
+        &mut grid,
+        deposit_dynamics.starting_regeneration,
+    );
     scenario::spawn_default_opponent_scenario(
         &mut commands,
         &asset_server,
         &mut grid,
         opponent_id_alloc,
+        deposit_dynamics.starting_regeneration,
     );
+    commands.insert_resource(nanobot::DepositDynamics::new(deposit_dynamics));
 
     // background
     commands.spawn((
@@ -0,0 +1,290 @@
+//! Toggleable logistics network overlay.
+//!
+//! Haulers pick legs through source -> sink -> terminal tiers, and
+//! the overlay makes that flow visible. Three marker layers are
+//! reconciled each frame while the overlay is on:
+//!
+//! - a thin line per active [`LogisticsReservation`], from its source
+//!   to its destination;
+//! - a flow line per `(source, sink)` pair delivered to in the last
+//!   minute, as thick as its share of the busiest pair
+//!   ([`LogisticsFlowHistory`]);
+//! - a highlight on every terminal whose demand has waited at least
+//!   [`LogisticsOverlaySettings::starved_waiting_ticks`]
+//!   ([`TerminalDemandAges::waiting_ticks`]).
+//!
+//! Lines are unit-rectangle sprites stretched and rotated by
+//! [`segment_transform`], the same sprite-entity approach the
+//! tactical overlay uses. Turning the overlay off despawns every
+//! marker. [`LOGISTICS_OVERLAY_TOGGLE_KEY`] flips it.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::nanobot::{
+    Charger, LogisticsFlowHistory, LogisticsReservation, PlannedMaterial, ProductionFacility,
+    ResearchLab, TerminalDemandAges, UpkeepStock,
+};
+
+/// Key that shows or hides the overlay.
+pub const LOGISTICS_OVERLAY_TOGGLE_KEY: KeyCode = KeyCode::KeyL;
+
+/// Z-translation for overlay sprites. Below the tactical markers so
+/// a far-zoom icon is never crossed by a line.
+pub const LOGISTICS_OVERLAY_Z: f32 = 0.4;
+
+/// World-unit width of a single reservation line.
+pub const RESERVATION_LINE_THICKNESS: f32 = 3.0;
+
+/// World-unit width of the quietest and the busiest flow line.
+pub const FLOW_LINE_MIN_THICKNESS: f32 = 4.0;
+pub const FLOW_LINE_MAX_THICKNESS: f32 = 24.0;
+
+/// World-unit side of the square drawn over a starved terminal.
+pub const STARVED_TERMINAL_MARKER_SIZE: f32 = 72.0;
+
+/// Allocation ticks a terminal's demand may wait before it is
+/// highlighted. Five seconds of 100 ms allocation ticks.
+pub const DEFAULT_STARVED_WAITING_TICKS: u32 = 50;
+
+pub const RESERVATION_LINE_COLOR: Color = Color::srgba(0.55, 0.85, 1.0, 0.6);
+pub const FLOW_LINE_COLOR: Color = Color::srgba(1.0, 0.75, 0.2, 0.45);
+pub const STARVED_TERMINAL_COLOR: Color = Color::srgba(1.0, 0.15, 0.1, 0.35);
+
+/// Configuration for the logistics overlay. Hidden by default.
+#[derive(Debug, Resource, Clone, Copy, PartialEq)]
+pub struct LogisticsOverlaySettings {
+    pub visible: bool,
+    pub starved_waiting_ticks: u32,
+}
+
+impl Default for LogisticsOverlaySettings {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            starved_waiting_ticks: DEFAULT_STARVED_WAITING_TICKS,
+        }
+    }
+}
+
+/// Overlay sprite and the thing it draws. Doubles as the
+/// reconciliation key, so each reservation, pair, and terminal keeps
+/// one entity across frames.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogisticsOverlayMarker {
+    /// Keyed by the nanobot carrying the reservation.
+    Reservation {
+        carrier: Entity,
+    },
+    Flow {
+        source: Entity,
+        sink: Entity,
+    },
+    StarvedTerminal {
+        terminal: Entity,
+    },
+}
+
+/// Transform that stretches a unit-square sprite into a segment of
+/// `thickness` from `from` to `to`.
+pub fn segment_transform(from: Vec2, to: Vec2, thickness: f32, z: f32) -> Transform {
+    let delta = to - from;
+    Transform {
+        translation: ((from + to) * 0.5).extend(z),
+        rotation: Quat::from_rotation_z(delta.y.atan2(delta.x)),
+        scale: Vec3::new(delta.length(), thickness, 1.0),
+    }
+}
+
+/// Flow line width: linear in the pair's share of `max_flow`.
+pub fn flow_thickness(flow: u32, max_flow: u32) -> f32 {
+    if max_flow == 0 {
+        return FLOW_LINE_MIN_THICKNESS;
+    }
+    let share = (flow as f32 / max_flow as f32).clamp(0.0, 1.0);
+    FLOW_LINE_MIN_THICKNESS + (FLOW_LINE_MAX_THICKNESS - FLOW_LINE_MIN_THICKNESS) * share
+}
+
+pub fn is_starved(waiting_ticks: u32, threshold: u32) -> bool {
+    waiting_ticks >= threshold
+}
+
+pub fn logistics_overlay_toggle_system(
+    keyboard_input: Option<Res<ButtonInput<KeyCode>>>,
+    mut settings: ResMut<LogisticsOverlaySettings>,
+) {
+    if keyboard_input.is_some_and(|input| input.just_pressed(LOGISTICS_OVERLAY_TOGGLE_KEY)) {
+        settings.visible = !settings.visible;
+    }
+}
+
+/// Rebuild the wanted marker set and patch, spawn, or despawn
+/// overlay sprites to match it.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn logistics_overlay_update_system(
+    mut commands: Commands,
+    settings: Res<LogisticsOverlaySettings>,
+    flows: Option<Res<LogisticsFlowHistory>>,
+    ages: Option<Res<TerminalDemandAges>>,
+    reservations: Query<(Entity, &LogisticsReservation)>,
+    positions: Query<&Transform, Without<LogisticsOverlayMarker>>,
+    terminals: Query<
+        Entity,
+        (
+            Or<(
+                With<ProductionFacility>,
+                With<Charger>,
+                With<ResearchLab>,
+                With<UpkeepStock>,
+                With<PlannedMaterial>,
+            )>,
+            Without<LogisticsOverlayMarker>,
+        ),
+    >,
+    mut existing: Query<(Entity, &LogisticsOverlayMarker, &mut Transform, &mut Sprite)>,
+) {
+    let position = |entity: Entity| {
+        positions
+            .get(entity)
+            .ok()
+            .map(|transform| transform.translation.truncate())
+    };
+    let mut wanted = Vec::<(LogisticsOverlayMarker, Transform, Color)>::new();
+    if settings.visible {
+        if let Some(flows) = flows {
+            let totals = flows.totals();
+            let max_flow = totals.values().copied().max().unwrap_or(0);
+            for (&(source, sink), &flow) in &totals {
+                let (Some(from), Some(to)) = (position(source), position(sink)) else {
+                    continue;
+                };
+                wanted.push((
+                    LogisticsOverlayMarker::Flow { source, sink },
+                    segment_transform(
+                        from,
+                        to,
+                        flow_thickness(flow, max_flow),
+                        LOGISTICS_OVERLAY_Z,
+                    ),
+                    FLOW_LINE_COLOR,
+                ));
+            }
+        }
+        for (carrier, reservation) in &reservations {
+            let (Some(from), Some(to)) = (
+                position(reservation.source),
+                position(reservation.destination),
+            ) else {
+                continue;
+            };
+            wanted.push((
+                LogisticsOverlayMarker::Reservation { carrier },
+                segment_transform(from, to, RESERVATION_LINE_THICKNESS, LOGISTICS_OVERLAY_Z),
+                RESERVATION_LINE_COLOR,
+            ));
+        }
+        if let Some(ages) = ages {
+            for terminal in &terminals {
+                if !is_starved(ages.waiting_ticks(terminal), settings.starved_waiting_ticks) {
+                    continue;
+                }
+                let Some(at) = position(terminal) else {
+                    continue;
+                };
+                wanted.push((
+                    LogisticsOverlayMarker::StarvedTerminal { terminal },
+                    Transform::from_translation(at.extend(LOGISTICS_OVERLAY_Z))
+                        .with_scale(Vec3::splat(STARVED_TERMINAL_MARKER_SIZE)),
+                    STARVED_TERMINAL_COLOR,
+                ));
+            }
+        }
+    }
+
+    let by_marker: HashMap<LogisticsOverlayMarker, Entity> = existing
+        .iter()
+        .map(|(entity, marker, _, _)| (*marker, entity))
+        .collect();
+    let mut matched: HashSet<Entity> = HashSet::new();
+    for (marker, transform, color) in wanted {
+        if let Some(&entity) = by_marker.get(&marker) {
+            if let Ok((_, _, mut current, mut sprite)) = existing.get_mut(entity) {
+                *current = transform;
+                if sprite.color != color {
+                    sprite.color = color;
+                }
+            }
+            matched.insert(entity);
+        } else {
+            commands.spawn((
+                Sprite {
+                    color,
+                    custom_size: Some(Vec2::ONE),
+                    ..default()
+                },
+                transform,
+                marker,
+            ));
+        }
+    }
+    for (entity, _, _, _) in existing.iter() {
+        if !matched.contains(&entity) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Plugin that wires the overlay toggle and reconciliation into
+/// `Update`. Flow history comes from
+/// [`crate::nanobot::TelemetryPlugin`]; without it only reservations
+/// and starved terminals are drawn.
+pub struct LogisticsOverlayPlugin;
+
+impl Plugin for LogisticsOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<LogisticsOverlaySettings>() {
+            app.init_resource::<LogisticsOverlaySettings>();
+        }
+        app.add_systems(
+            Update,
+            (
+                logistics_overlay_toggle_system,
+                logistics_overlay_update_system,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Segment geometry and thresholds. Marker reconciliation is
+    //! covered by `tests/behavior/logistics_overlay.rs`.
+
+    use super::*;
+
+    #[test]
+    fn segment_spans_its_endpoints() {
+        let transform = segment_transform(Vec2::new(10.0, 0.0), Vec2::new(10.0, 40.0), 5.0, 0.4);
+        assert_eq!(transform.translation, Vec3::new(10.0, 20.0, 0.4));
+        assert_eq!(transform.scale, Vec3::new(40.0, 5.0, 1.0));
+        let end = transform.transform_point(Vec3::new(0.5, 0.0, 0.0));
+        assert!(end.truncate().distance(Vec2::new(10.0, 40.0)) < 1e-4);
+    }
+
+    #[test]
+    fn flow_thickness_scales_with_share_of_busiest_pair() {
+        assert_eq!(flow_thickness(0, 0), FLOW_LINE_MIN_THICKNESS);
+        assert_eq!(flow_thickness(10, 10), FLOW_LINE_MAX_THICKNESS);
+        assert_eq!(
+            flow_thickness(5, 10),
+            (FLOW_LINE_MIN_THICKNESS + FLOW_LINE_MAX_THICKNESS) / 2.0
+        );
+    }
+
+    #[test]
+    fn starved_at_threshold() {
+        assert!(!is_starved(49, 50));
+        assert!(is_starved(50, 50));
+    }
+}
@@ -1,14 +1,17 @@
 pub mod allocation;
 mod autonomy;
-mod build;
+mod behaviour;
 mod cargo;
 mod charge;
 mod collapse;
 mod combat;
 mod components;
+mod construction_cost;
 mod consts;
 mod debug;
 mod defend;
+mod deposit_dynamics;
+mod field_resupply;
 mod gather;
 mod haul;
 mod logistics_leg;
@@ -19,24 +22,44 @@ mod placement;
 mod planned;
 mod population;
 mod production;
+mod reclaim;
+mod repair;
+mod research;
+mod road;
 mod route;
+mod route_cache;
+mod spatial_index;
 mod spatial_pressure;
 mod spread;
 mod sprites;
+mod steering;
+mod structure;
+mod structure_tier;
+mod telemetry;
+mod threat;
+mod upkeep;
+mod wreckage;
 
 pub use allocation::*;
 pub use autonomy::*;
-pub use build::*;
+pub use behaviour::*;
 pub use cargo::*;
 pub use charge::*;
 pub use collapse::*;
 pub use combat::*;
 pub use components::*;
+pub use construction_cost::*;
 pub use consts::*;
 pub use debug::*;
 pub use defend::*;
+pub use deposit_dynamics::*;
+pub use field_resupply::*;
 pub use gather::*;
 pub use haul::*;
+pub use logistics_leg::{
+    HaulerContext, LogisticsLeg, StockpileCandidate, TerminalCandidate,
+    pick_logistics_leg_with_cost,
+};
 pub use maintenance::*;
 pub use move_system::*;
 pub use opponent::*;
@@ -44,20 +67,32 @@ pub use placement::*;
 pub use planned::*;
 pub use population::*;
 pub use production::*;
+pub use reclaim::*;
+pub use repair::*;
+pub use research::*;
+pub use road::*;
 pub use route::*;
+pub use route_cache::*;
+pub use spatial_index::*;
 pub use spatial_pressure::*;
 pub use spread::*;
 pub use sprites::*;
+pub use steering::*;
+pub use structure::*;
+pub use structure_tier::*;
+pub use telemetry::*;
+pub use threat::*;
+pub use upkeep::*;
+pub use wreckage::*;
 
 use bevy::prelude::*;
 
-use crate::ai::AiStateComponent;
-
 pub use self::components::{Health, Nanobot, SwarmId, SwarmMember, VelocityComponent};
 
 /// Bundle for a freshly spawned nanobot. The default is a Worker
 /// (the most common type for the first implementation) with zero
-/// velocity and a fresh AI state. Spawners can override individual
+/// velocity. Its [`Behaviour`] is attached by
+/// [`initialize_nanobot_type_components`]. Spawners can override individual
 /// fields to specialise the bot (e.g. tests spawn Haulers).
 ///
 /// `swarm_member` defaults to [`SwarmId::PLAYER`] so the test
@@ -72,7 +107,6 @@ pub struct NanobotBundle {
     pub nanobot: Nanobot,
     pub nanobot_type: NanobotType,
     pub velocity: VelocityComponent,
-    pub ai_state: AiStateComponent,
     pub health: Health,
     pub swarm_member: SwarmMember,
 }
@@ -83,15 +117,14 @@ impl Default for NanobotBundle {
             nanobot: Nanobot {},
             nanobot_type: NanobotType::Worker,
             velocity: VelocityComponent::default(),
-            ai_state: AiStateComponent::new(),
             health: Health::default(),
             swarm_member: SwarmMember::new(SwarmId::PLAYER),
         }
     }
 }
 
-/// Attach type-specific lifecycle state whenever a nanobot type is introduced.
-/// This is the single authority used by scenario, opponent, production, and tests.
+/// Attach the nanobot's [`Behaviour`] and type-specific lifecycle state
+/// whenever a nanobot type is introduced. This is the single authority used by scenario, opponent, production, and tests.
 pub fn initialize_nanobot_type_components(
     added: On<Add, NanobotType>,
     mut commands: Commands,
@@ -100,6 +133,7 @@ pub fn initialize_nanobot_type_components(
     let Ok(kind) = types.get(added.entity) else {
         return;
     };
+    commands.entity(added.entity).queue(initialize_behaviour);
     if *kind == NanobotType::Defender {
         commands.entity(added.entity).insert(Charge::default());
     } else {
@@ -138,6 +172,7 @@ impl Plugin for NanobotPlugin {
     fn build(&self, app: &mut App) {
         // Movement intent, local steering, and integration form one deterministic
         // fixed-tick pipeline. Presentation-only debug drawing remains frame-driven.
+        add_spatial_index(app);
         app.add_observer(initialize_nanobot_type_components)
             .configure_sets(
                 FixedUpdate,
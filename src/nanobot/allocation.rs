@@ -2,6 +2,7 @@
 
 pub mod allocator;
 pub mod lease;
+pub mod parallel;
 pub mod projection;
 pub mod runtime;
 
@@ -12,6 +13,7 @@ use crate::resources::ResourceKind;
 
 pub use allocator::*;
 pub use lease::*;
+pub use parallel::*;
 pub use projection::{ActionableProjection, project_actionable_opportunities_system};
 pub use runtime::*;
 
@@ -44,14 +46,16 @@ pub enum OpportunityCategory {
     Gather,
     PlannedBuild,
     Maintenance,
+    Repair,
     Defend,
     Haul,
 }
 
 impl OpportunityCategory {
-    pub const COUNT: usize = 5;
+    pub const COUNT: usize = 6;
     pub const ALL: [Self; Self::COUNT] = [
         Self::PlannedBuild,
+        Self::Repair,
         Self::Maintenance,
         Self::Gather,
         Self::Defend,
@@ -65,6 +69,7 @@ impl OpportunityCategory {
             Self::Maintenance => 2,
             Self::Defend => 3,
             Self::Haul => 4,
+            Self::Repair => 5,
         }
     }
 }
@@ -83,6 +88,9 @@ pub enum OpportunityTarget {
     Maintenance {
         structure: Entity,
     },
+    Repair {
+        structure: Entity,
+    },
     Defend {
         cell: IVec2,
     },
@@ -101,7 +109,13 @@ pub struct ActionableOpportunity {
     pub target: OpportunityTarget,
     pub cell: IVec2,
     pub owner: Option<SwarmId>,
+    /// Claimable units of work: slots, carried loads or ticks, per
+    /// category.
     pub available_work: u32,
+    /// Ranking among targets of the same category; the allocator
+    /// prefers the highest. Defend carries its cell's pressure and
+    /// Repair its [`crate::nanobot::repair_priority`]. Zero elsewhere.
+    pub priority: u32,
 }
 
 #[cfg(test)]
@@ -233,6 +233,7 @@ impl CategoryEligibility {
         values[OpportunityCategory::Gather.index()] = true;
         values[OpportunityCategory::PlannedBuild.index()] = true;
         values[OpportunityCategory::Maintenance.index()] = true;
+        values[OpportunityCategory::Repair.index()] = true;
         Self(values)
     }
 
@@ -331,11 +332,8 @@ where
             let Some(claims) = claim_count(*opportunity) else {
                 continue;
             };
-            let pressure_priority = if opportunity.category == OpportunityCategory::Defend {
-                u32::MAX - opportunity.available_work
-            } else {
-                0
-            };
+            // The most pressured target wins within its category.
+            let pressure_priority = u32::MAX - opportunity.priority;
             let score = (
                 category_priority(opportunity.category),
                 claims,
@@ -361,10 +359,11 @@ where
 fn category_priority(category: OpportunityCategory) -> usize {
     match category {
         OpportunityCategory::PlannedBuild => 0,
-        OpportunityCategory::Maintenance => 1,
-        OpportunityCategory::Gather => 2,
-        OpportunityCategory::Defend => 3,
-        OpportunityCategory::Haul => 4,
+        OpportunityCategory::Repair => 1,
+        OpportunityCategory::Maintenance => 2,
+        OpportunityCategory::Gather => 3,
+        OpportunityCategory::Defend => 4,
+        OpportunityCategory::Haul => 5,
     }
 }
 
@@ -5,18 +5,15 @@ use bevy::prelude::*;
 use super::{
     ActionableProjection, AllocationClock, AllocationRegion, OpportunityCategory, OpportunityTarget,
 };
-use crate::nanobot::{
-    ChargerAssignment, ChargerProgress, DefendAssignment, DefendHold, DirectMovementComponent,
-    ExtractProgress, GatherAssignment, HaulerAssignment, HaulerLoading, HaulerRoute,
-    LogisticsReservation, MaintenanceAssignment, MaintenanceProgress, PlannedStructureClaim,
-    PlannedStructureProgress, SwarmId,
-};
+use crate::nanobot::behaviour::{Behaviour, BehaviourRole, exit_role};
+use crate::nanobot::{DirectMovementComponent, LogisticsReservation, SwarmId};
 
-/// Charge override state for a regional lease.
+/// Charge and threat override state for a regional lease.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RegionalLeaseState {
     Active,
     SuspendedForCharge,
+    SuspendedForThreat,
     ResumePending,
 }
 
@@ -71,8 +68,15 @@ impl RegionalLease {
         self.state = RegionalLeaseState::SuspendedForCharge;
     }
 
+    pub fn suspend_for_threat(&mut self) {
+        self.state = RegionalLeaseState::SuspendedForThreat;
+    }
+
     pub fn request_resume(&mut self) {
-        if self.state == RegionalLeaseState::SuspendedForCharge {
+        if matches!(
+            self.state,
+            RegionalLeaseState::SuspendedForCharge | RegionalLeaseState::SuspendedForThreat
+        ) {
             self.state = RegionalLeaseState::ResumePending;
         }
     }
@@ -150,6 +154,18 @@ pub fn projection_supports_lease(projection: &ActionableProjection, lease: &Regi
         })
 }
 
+/// Behaviour role that carries a lease category's work on the nanobot.
+fn lease_role(category: OpportunityCategory) -> BehaviourRole {
+    match category {
+        OpportunityCategory::Gather => BehaviourRole::GATHER,
+        OpportunityCategory::PlannedBuild => BehaviourRole::BUILD,
+        OpportunityCategory::Maintenance => BehaviourRole::MAINTAIN,
+        OpportunityCategory::Repair => BehaviourRole::REPAIR,
+        OpportunityCategory::Defend => BehaviourRole::DEFEND,
+        OpportunityCategory::Haul => BehaviourRole::HAUL,
+    }
+}
+
 /// Revoke unsupported or stalled leases after projection refresh.
 #[allow(clippy::type_complexity)]
 pub fn maintain_regional_leases_system(
@@ -161,34 +177,16 @@ pub fn maintain_regional_leases_system(
         Entity,
         &mut RegionalLease,
         Option<&LeaseProgress>,
-        Option<&GatherAssignment>,
-        Option<&PlannedStructureClaim>,
-        Option<&MaintenanceAssignment>,
-        Option<&DefendAssignment>,
-        Option<&DefendHold>,
-        Option<&HaulerAssignment>,
+        Option<&Behaviour>,
         Option<&LogisticsReservation>,
     )>,
 ) {
-    for (
-        entity,
-        mut lease,
-        progress,
-        gather,
-        planned,
-        maintenance,
-        defend,
-        hold,
-        haul,
-        reservation,
-    ) in &mut leases
-    {
-        let lifecycle_active = gather.is_some()
-            || planned.is_some()
-            || maintenance.is_some()
-            || defend.is_some()
-            || hold.is_some()
-            || haul.is_some();
+    for (entity, mut lease, progress, behaviour, reservation) in &mut leases {
+        let lifecycle_active = behaviour.is_some_and(|behaviour| {
+            OpportunityCategory::ALL
+                .iter()
+                .any(|category| behaviour.is_active(lease_role(*category)))
+        });
         let observed_progress = progress.map_or_else(
             || {
                 lease
@@ -210,73 +208,34 @@ pub fn maintain_regional_leases_system(
             let mut entity_commands = commands.entity(entity);
             entity_commands
                 .remove::<RegionalLease>()
-                .remove::<DirectMovementComponent>();
-            match category {
-                OpportunityCategory::Gather => {
-                    entity_commands
-                        .remove::<GatherAssignment>()
-                        .remove::<ExtractProgress>()
-                        .remove::<LogisticsReservation>();
-                }
-                OpportunityCategory::PlannedBuild => {
-                    entity_commands
-                        .remove::<PlannedStructureClaim>()
-                        .remove::<PlannedStructureProgress>();
-                }
-                OpportunityCategory::Maintenance => {
-                    entity_commands
-                        .remove::<MaintenanceAssignment>()
-                        .remove::<MaintenanceProgress>();
-                }
-                OpportunityCategory::Defend => {
-                    entity_commands
-                        .remove::<DefendAssignment>()
-                        .remove::<DefendHold>();
-                }
-                OpportunityCategory::Haul => {
-                    entity_commands
-                        .remove::<HaulerAssignment>()
-                        .remove::<HaulerLoading>()
-                        .remove::<LogisticsReservation>()
-                        .remove::<HaulerRoute>();
-                }
+                .remove::<DirectMovementComponent>()
+                .queue(exit_role(lease_role(category)));
+            if matches!(
+                category,
+                OpportunityCategory::Gather | OpportunityCategory::Haul
+            ) {
+                entity_commands.remove::<LogisticsReservation>();
             }
         }
     }
 }
 
 /// Release capacity as soon as category lifecycle markers finish.
-#[allow(clippy::type_complexity)]
 pub fn release_finished_regional_leases_system(
     mut commands: Commands,
-    leases: Query<(
-        Entity,
-        &RegionalLease,
-        Option<&GatherAssignment>,
-        Option<&PlannedStructureClaim>,
-        Option<&MaintenanceAssignment>,
-        Option<&DefendAssignment>,
-        Option<&DefendHold>,
-        Option<&HaulerAssignment>,
-        Option<&ChargerAssignment>,
-        Option<&ChargerProgress>,
-    )>,
+    leases: Query<(Entity, &RegionalLease, Option<&Behaviour>)>,
 ) {
-    for (entity, lease, gather, planned, maintenance, defend, hold, haul, charger, charging) in
-        &leases
-    {
+    for (entity, lease, behaviour) in &leases {
         if lease.state != RegionalLeaseState::Active {
             continue;
         }
-        let active = match lease.category {
-            OpportunityCategory::Gather => gather.is_some(),
-            OpportunityCategory::PlannedBuild => planned.is_some(),
-            OpportunityCategory::Maintenance => maintenance.is_some(),
-            OpportunityCategory::Defend => {
-                defend.is_some() || hold.is_some() || charger.is_some() || charging.is_some()
-            }
-            OpportunityCategory::Haul => haul.is_some(),
-        };
+        // A defender that walked off to recharge still owns its
+        // Defend capacity; the charge chain suspends the lease.
+        let active = behaviour.is_some_and(|behaviour| {
+            behaviour.is_active(lease_role(lease.category))
+                || (lease.category == OpportunityCategory::Defend
+                    && behaviour.is_active(BehaviourRole::CHARGE))
+        });
         if !active {
             commands.entity(entity).remove::<RegionalLease>();
         }
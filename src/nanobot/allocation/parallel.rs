@@ -0,0 +1,356 @@
+//! Deterministic parallel acquisition.
+//!
+//! Idle nanobots are partitioned into independent batches that share one
+//! pull budget, one swarm, and one nanobot type. Each batch chooses
+//! concurrently against a snapshot of exact claims plus its own earlier
+//! choices. A sequential merge then walks every nanobot in stable entity
+//! order and either keeps the speculative choice or chooses again against
+//! the merged claims.
+//!
+//! Keeping a speculative choice is exact, not approximate. Claims only grow
+//! during a pass, and more claims only make work rank lower or become
+//! unavailable, so every alternative a nanobot rejected stays rejected. The
+//! choice therefore matches the sequential path unless another batch has
+//! since claimed the chosen target itself, or the batch diverged earlier.
+
+use std::collections::BTreeMap;
+
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+
+use super::{ActionableOpportunity, OpportunityTarget};
+
+/// Exact claim identity shared by every opportunity for one work target.
+pub type ClaimKey = (u8, u64, u64, u64);
+
+/// Exact claim counts keyed by [`ClaimKey`].
+pub type ClaimCounts = BTreeMap<ClaimKey, usize>;
+
+/// Claim identity for a target. Gather anchors on one deposit share a key.
+pub fn claim_key(target: OpportunityTarget) -> ClaimKey {
+    match target {
+        OpportunityTarget::Gather { deposit, .. } => (0, deposit.to_bits(), 0, 0),
+        OpportunityTarget::PlannedBuild { structure, .. } => (1, structure.to_bits(), 0, 0),
+        OpportunityTarget::Maintenance { structure } => (2, structure.to_bits(), 0, 0),
+        OpportunityTarget::Repair { structure } => (5, structure.to_bits(), 0, 0),
+        OpportunityTarget::Defend { cell } => {
+            (3, i64::from(cell.x) as u64, i64::from(cell.y) as u64, 0)
+        }
+        OpportunityTarget::Haul { source, sink, .. } => (4, source.to_bits(), sink.to_bits(), 0),
+    }
+}
+
+/// Claim counts seen by one speculative batch: the shared snapshot plus the
+/// batch's own earlier choices.
+#[derive(Debug)]
+pub struct BatchClaims<'a> {
+    shared: &'a ClaimCounts,
+    local: ClaimCounts,
+}
+
+impl<'a> BatchClaims<'a> {
+    pub fn new(shared: &'a ClaimCounts) -> Self {
+        Self {
+            shared,
+            local: ClaimCounts::new(),
+        }
+    }
+
+    pub fn get(&self, key: ClaimKey) -> usize {
+        self.shared.get(&key).copied().unwrap_or(0) + self.local.get(&key).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, key: ClaimKey) {
+        *self.local.entry(key).or_insert(0) += 1;
+    }
+}
+
+/// One nanobot's speculative choice and the claims it saw on that target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Speculation {
+    pub work: Option<ActionableOpportunity>,
+    pub seen_claims: usize,
+}
+
+/// Run `speculate` on every batch concurrently. Results keep batch order,
+/// so thread scheduling never reaches the merge.
+pub fn speculate_batches<B, F>(batches: &[B], speculate: F) -> Vec<Vec<Speculation>>
+where
+    B: Sync,
+    F: Fn(&B) -> Vec<Speculation> + Sync,
+{
+    if batches.len() <= 1 {
+        return batches.iter().map(&speculate).collect();
+    }
+    let speculate = &speculate;
+    ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+        for batch in batches {
+            scope.spawn(async move { speculate(batch) });
+        }
+    })
+}
+
+/// Sequential merge over speculative batches. The caller walks nanobots in
+/// stable entity order, asks [`SpeculativeMerge::settled`] for each, chooses
+/// again when it returns `None`, and records the final outcome.
+#[derive(Debug)]
+pub struct SpeculativeMerge {
+    speculations: Vec<Vec<Speculation>>,
+    diverged: Vec<bool>,
+    claims: ClaimCounts,
+}
+
+impl SpeculativeMerge {
+    pub fn new(speculations: Vec<Vec<Speculation>>, claims: ClaimCounts) -> Self {
+        Self {
+            diverged: vec![false; speculations.len()],
+            speculations,
+            claims,
+        }
+    }
+
+    /// Claims accepted so far, including the pre-pass snapshot.
+    pub fn claims(&self) -> &ClaimCounts {
+        &self.claims
+    }
+
+    /// The speculative choice for `slot` in `batch`, when it still holds.
+    pub fn settled(&self, batch: usize, slot: usize) -> Option<Option<ActionableOpportunity>> {
+        if self.diverged[batch] {
+            return None;
+        }
+        let speculation = self.speculations[batch][slot];
+        let Some(work) = speculation.work else {
+            return Some(None);
+        };
+        let current = self
+            .claims
+            .get(&claim_key(work.target))
+            .copied()
+            .unwrap_or(0);
+        (current == speculation.seen_claims).then_some(Some(work))
+    }
+
+    /// Record the accepted outcome for `slot` in `batch`. An outcome that
+    /// differs from the speculation invalidates the batch's later choices,
+    /// which were made against claims and pull that never happened.
+    pub fn record(&mut self, batch: usize, slot: usize, accepted: Option<ActionableOpportunity>) {
+        if self.speculations[batch][slot].work != accepted {
+            self.diverged[batch] = true;
+        }
+        if let Some(work) = accepted {
+            *self.claims.entry(claim_key(work.target)).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Speculative batches merged in entity order must reproduce the plain
+    //! sequential pass, including when batches contest exclusive work.
+
+    use bevy::prelude::{Entity, IVec2};
+
+    use super::*;
+    use crate::nanobot::{
+        AllocationCandidate, AllocationRegion, CandidateBounds, CategoryEligibility,
+        CategoryValues, OpportunityCategory, RegionalPullBudget,
+        choose_bounded_candidate_with_claims,
+    };
+
+    const BOUNDS: CandidateBounds = CandidateBounds {
+        max_regions: 16,
+        max_candidates: 128,
+    };
+
+    fn entity(id: u32) -> Entity {
+        Entity::from_raw_u32(id).expect("test entity")
+    }
+
+    fn region(x: i32) -> AllocationRegion {
+        AllocationRegion { x, y: 0 }
+    }
+
+    fn work() -> Vec<(AllocationRegion, Vec<ActionableOpportunity>)> {
+        (0..3)
+            .map(|x| {
+                let mut opportunities = (0..2)
+                    .map(|i| ActionableOpportunity {
+                        region: region(x),
+                        category: OpportunityCategory::Maintenance,
+                        target: OpportunityTarget::Maintenance {
+                            structure: entity(100 + (x * 2 + i) as u32),
+                        },
+                        cell: IVec2::new(x * 8, i),
+                        owner: None,
+                        available_work: 1,
+                        priority: 0,
+                    })
+                    .collect::<Vec<_>>();
+                opportunities.push(ActionableOpportunity {
+                    region: region(x),
+                    category: OpportunityCategory::Defend,
+                    target: OpportunityTarget::Defend {
+                        cell: IVec2::new(x * 8, 4),
+                    },
+                    cell: IVec2::new(x * 8, 4),
+                    owner: None,
+                    available_work: 2,
+                    priority: 2,
+                });
+                (region(x), opportunities)
+            })
+            .collect()
+    }
+
+    fn pull(x: i32) -> RegionalPullBudget {
+        RegionalPullBudget {
+            source_region: region(x),
+            categories: CategoryValues::new([0, 0, 3, 3, 0]),
+        }
+    }
+
+    fn bot(id: u32) -> AllocationCandidate {
+        AllocationCandidate {
+            entity_bits: u64::from(id),
+            region: region((id % 3) as i32),
+            owner: None,
+            eligibility: CategoryEligibility::all(),
+        }
+    }
+
+    fn choose(
+        bot: AllocationCandidate,
+        pull: RegionalPullBudget,
+        work: &[(AllocationRegion, Vec<ActionableOpportunity>)],
+        claims: impl Fn(ClaimKey) -> usize,
+    ) -> Option<ActionableOpportunity> {
+        let regions = work.iter().map(|(region, work)| (*region, work.as_slice()));
+        choose_bounded_candidate_with_claims(bot, pull, regions, BOUNDS, |work| {
+            let claims = claims(claim_key(work.target));
+            (work.category == OpportunityCategory::Defend || claims == 0).then_some(claims)
+        })
+        .map(|decision| decision.opportunity)
+    }
+
+    fn consume(pull: &mut RegionalPullBudget, work: ActionableOpportunity) {
+        let remaining = pull.categories.get(work.category).saturating_sub(1);
+        pull.categories.set(work.category, remaining);
+    }
+
+    #[test]
+    fn merged_speculation_matches_sequential_pass() {
+        let work = work();
+        let bots = (1..=12).map(bot).collect::<Vec<_>>();
+
+        let mut claims = ClaimCounts::new();
+        let mut pulls = [pull(0), pull(1), pull(2)];
+        let sequential = bots
+            .iter()
+            .map(|bot| {
+                let pull = &mut pulls[bot.region.x as usize];
+                let choice = choose(*bot, *pull, &work, |key| {
+                    claims.get(&key).copied().unwrap_or(0)
+                });
+                if let Some(choice) = choice {
+                    *claims.entry(claim_key(choice.target)).or_insert(0) += 1;
+                    consume(pull, choice);
+                }
+                choice
+            })
+            .collect::<Vec<_>>();
+
+        let batches = (0..3)
+            .map(|x| {
+                bots.iter()
+                    .copied()
+                    .filter(|bot| bot.region.x == x)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let shared = ClaimCounts::new();
+        let speculations = speculate_batches(&batches, |batch| {
+            let mut pull = pull(batch[0].region.x);
+            let mut claims = BatchClaims::new(&shared);
+            batch
+                .iter()
+                .map(|bot| {
+                    let choice = choose(*bot, pull, &work, |key| claims.get(key));
+                    let seen_claims =
+                        choice.map_or(0, |choice| claims.get(claim_key(choice.target)));
+                    if let Some(choice) = choice {
+                        claims.add(claim_key(choice.target));
+                        consume(&mut pull, choice);
+                    }
+                    Speculation {
+                        work: choice,
+                        seen_claims,
+                    }
+                })
+                .collect()
+        });
+        let mut merge = SpeculativeMerge::new(speculations, shared);
+        let mut pulls = [pull(0), pull(1), pull(2)];
+        let mut slots = [0; 3];
+        let merged = bots
+            .iter()
+            .map(|bot| {
+                let batch = bot.region.x as usize;
+                let slot = slots[batch];
+                slots[batch] += 1;
+                let choice = merge.settled(batch, slot).unwrap_or_else(|| {
+                    choose(*bot, pulls[batch], &work, |key| {
+                        merge.claims().get(&key).copied().unwrap_or(0)
+                    })
+                });
+                merge.record(batch, slot, choice);
+                if let Some(choice) = choice {
+                    consume(&mut pulls[batch], choice);
+                }
+                choice
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(merged, sequential);
+        assert!(
+            merge
+                .claims()
+                .iter()
+                .all(|((tag, ..), claims)| *tag != 2 || *claims == 1),
+            "exclusive Maintenance work is claimed at most once"
+        );
+    }
+
+    #[test]
+    fn speculation_is_dropped_once_another_batch_claims_its_target() {
+        let target = OpportunityTarget::Maintenance {
+            structure: entity(7),
+        };
+        let work = ActionableOpportunity {
+            region: region(0),
+            category: OpportunityCategory::Maintenance,
+            target,
+            cell: IVec2::ZERO,
+            owner: None,
+            available_work: 1,
+            priority: 0,
+        };
+        let speculation = Speculation {
+            work: Some(work),
+            seen_claims: 0,
+        };
+        let mut merge = SpeculativeMerge::new(
+            vec![vec![speculation], vec![speculation]],
+            ClaimCounts::new(),
+        );
+
+        assert_eq!(merge.settled(0, 0), Some(Some(work)));
+        merge.record(0, 0, Some(work));
+        assert_eq!(
+            merge.settled(1, 0),
+            None,
+            "the target was claimed meanwhile"
+        );
+        merge.record(1, 0, None);
+        assert_eq!(merge.claims().get(&claim_key(target)), Some(&1));
+    }
+}
@@ -11,8 +11,11 @@ use super::{
 use crate::ZONE_BLOCK_SIZE;
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::{
-    Charger, DefendPressure, OwnerSwarm, PlannedStructure, ProductionFacility, Structure,
-    SupportCondition, SwarmId, cell_overlaps_circle,
+    Charger, DEFEND_PRESSURE_BASELINE, DefendPressure, FIELD_RESUPPLY_KIND, FieldResupplyTargets,
+    MaintenanceUpkeep, OwnerSwarm, PlannedMaterial, PlannedReclaim, PlannedStructure,
+    ProductionFacility, RecentDamage, ResearchLab, Structure, SupportCondition, SwarmId,
+    UpkeepDepot, UpkeepStock, cell_overlaps_circle, material_allows_work, repair_blocked,
+    repair_priority, upkeep_covers,
 };
 use crate::resources::{ResourceDeposit, ResourceKind, Stockpile, StockpileRole};
 
@@ -22,6 +25,8 @@ use crate::resources::{ResourceDeposit, ResourceKind, Stockpile, StockpileRole};
 pub struct ActionableProjection {
     by_region: BTreeMap<AllocationRegion, Vec<ActionableOpportunity>>,
     dirty_regions: BTreeSet<AllocationRegion>,
+    /// Holding Defenders last projected as field resupply sinks.
+    resupply_sinks: BTreeSet<Entity>,
 }
 
 impl ActionableProjection {
@@ -97,8 +102,31 @@ pub fn project_actionable_opportunities_system(
         Option<Ref<OwnerSwarm>>,
     )>,
     mut removed_deposit_owners: RemovedComponents<OwnerSwarm>,
-    planned: Query<(Entity, Ref<PlannedStructure>, Option<&OwnerSwarm>)>,
-    structures: Query<(Entity, Ref<Structure>, Ref<Transform>, Option<&OwnerSwarm>)>,
+    planned: Query<(
+        Entity,
+        Ref<PlannedStructure>,
+        Option<&OwnerSwarm>,
+        Option<Ref<PlannedMaterial>>,
+    )>,
+    // Structures being reclaimed release their maintenance load.
+    structures: Query<
+        (
+            Entity,
+            Ref<Structure>,
+            Ref<Transform>,
+            Option<&OwnerSwarm>,
+            Option<&UpkeepDepot>,
+            Option<Ref<RecentDamage>>,
+        ),
+        Without<PlannedReclaim>,
+    >,
+    upkeep: Option<Res<MaintenanceUpkeep>>,
+    depots: Query<(
+        Entity,
+        Ref<UpkeepStock>,
+        Ref<Transform>,
+        Option<Ref<OwnerSwarm>>,
+    )>,
     stockpiles: Query<(
         Entity,
         Ref<Stockpile>,
@@ -119,6 +147,13 @@ pub fn project_actionable_opportunities_system(
         Option<Ref<OwnerSwarm>>,
         Option<Ref<SupportCondition>>,
     )>,
+    labs: Query<(
+        Entity,
+        Ref<ResearchLab>,
+        Option<Ref<OwnerSwarm>>,
+        Option<Ref<SupportCondition>>,
+    )>,
+    resupply: FieldResupplyTargets,
     swarms: Query<&SwarmId>,
     entities: Query<Entity>,
 ) {
@@ -164,9 +199,8 @@ pub fn project_actionable_opportunities_system(
                     OpportunityTarget::PlannedBuild { structure, .. } => {
                         planned.get(structure).is_err()
                     }
-                    OpportunityTarget::Maintenance { structure } => {
-                        structures.get(structure).is_err()
-                    }
+                    OpportunityTarget::Maintenance { structure }
+                    | OpportunityTarget::Repair { structure } => structures.get(structure).is_err(),
                     OpportunityTarget::Defend { .. } => false,
                     OpportunityTarget::Haul { source, sink, .. } => {
                         entities.get(source).is_err() || entities.get(sink).is_err()
@@ -200,20 +234,34 @@ pub fn project_actionable_opportunities_system(
             );
         }
     }
-    for (_, planned, _) in &planned {
+    let mut haul_sinks_changed = false;
+    for (_, planned, _, material) in &planned {
         if planned.is_changed() {
             projection.invalidate_cell(planned.cell);
         }
+        // Delivered material both unlocks build work on the site and
+        // shrinks what haulers may still bring to it.
+        if material
+            .as_ref()
+            .is_some_and(|material| material.is_changed())
+        {
+            projection.invalidate_cell(planned.cell);
+            haul_sinks_changed = true;
+        }
     }
-    for (_, structure, transform, _) in &structures {
-        if structure.is_changed() || transform.is_changed() {
+    let upkeep_changed = upkeep.as_ref().is_some_and(|upkeep| upkeep.is_changed());
+    for (_, structure, transform, _, _, recent) in &structures {
+        if structure.is_changed()
+            || transform.is_changed()
+            || upkeep_changed
+            || recent.as_ref().is_some_and(|recent| recent.is_changed())
+        {
             projection.invalidate_cell(crate::nanobot::world_to_cell(
                 transform.translation.truncate(),
             ));
         }
     }
 
-    let mut haul_sinks_changed = false;
     for (_, stockpile, transform, role, owner, condition) in &stockpiles {
         if stockpile.is_changed()
             || transform.is_changed()
@@ -243,6 +291,37 @@ pub fn project_actionable_opportunities_system(
                 .as_ref()
                 .is_some_and(|condition| condition.is_changed())
     });
+    haul_sinks_changed |= labs.iter().any(|(_, lab, owner, condition)| {
+        lab.is_changed()
+            || owner.as_ref().is_some_and(|owner| owner.is_changed())
+            || condition
+                .as_ref()
+                .is_some_and(|condition| condition.is_changed())
+    });
+    // A depot's stock gates maintenance on its structure, which
+    // shares the depot's position.
+    for (_, stock, transform, owner) in &depots {
+        if stock.is_changed()
+            || transform.is_changed()
+            || owner.as_ref().is_some_and(|owner| owner.is_changed())
+        {
+            projection.invalidate_cell(crate::nanobot::world_to_cell(
+                transform.translation.truncate(),
+            ));
+            haul_sinks_changed = true;
+        }
+    }
+    // Charge drains every tick, so only a change in which Defenders
+    // are waiting re-projects haul work.
+    let resupply_targets = resupply.iter().collect::<Vec<_>>();
+    let resupply_sinks = resupply_targets
+        .iter()
+        .map(|target| target.entity)
+        .collect::<BTreeSet<_>>();
+    if resupply_sinks != projection.resupply_sinks {
+        projection.resupply_sinks = resupply_sinks;
+        haul_sinks_changed = true;
+    }
     if haul_sinks_changed {
         for (_, stockpile, transform, _, _, _) in &stockpiles {
             if stockpile.amount > 0 {
@@ -313,6 +392,44 @@ pub fn project_actionable_opportunities_system(
                 })
             }),
     );
+    sinks.extend(labs.iter().filter_map(|(entity, lab, owner, condition)| {
+        if condition.is_some_and(|condition| !condition.is_operational()) {
+            return None;
+        }
+        Some(SinkSnapshot {
+            entity,
+            kind: lab.input_kind,
+            free_space: lab.input_free_space(),
+            owner: resolve_owner(owner.as_deref(), &swarms)?,
+            source_role: SourceRole::Sink,
+        })
+    }));
+    sinks.extend(depots.iter().filter_map(|(entity, stock, _, owner)| {
+        Some(SinkSnapshot {
+            entity,
+            kind: stock.kind,
+            free_space: stock.free_space(),
+            owner: resolve_owner(owner.as_deref(), &swarms)?,
+            source_role: SourceRole::Sink,
+        })
+    }));
+    sinks.extend(planned.iter().filter_map(|(entity, _, owner, material)| {
+        let material = material?;
+        Some(SinkSnapshot {
+            entity,
+            kind: material.kind,
+            free_space: material.free_space(),
+            owner: resolve_owner(owner, &swarms)?,
+            source_role: SourceRole::Sink,
+        })
+    }));
+    sinks.extend(resupply_targets.iter().map(|target| SinkSnapshot {
+        entity: target.entity,
+        kind: FIELD_RESUPPLY_KIND,
+        free_space: target.need,
+        owner: Some(target.swarm),
+        source_role: SourceRole::Sink,
+    }));
 
     let dirty_regions = projection.take_dirty_regions();
     for region in dirty_regions {
@@ -322,6 +439,8 @@ pub fn project_actionable_opportunities_system(
             &grid,
             &deposits,
             &structures,
+            upkeep.as_deref(),
+            &depots,
             &swarms,
             pressure.as_deref(),
             &mut opportunities,
@@ -343,7 +462,24 @@ fn project_intent_work(
         Ref<Transform>,
         Option<Ref<OwnerSwarm>>,
     )>,
-    structures: &Query<(Entity, Ref<Structure>, Ref<Transform>, Option<&OwnerSwarm>)>,
+    structures: &Query<
+        (
+            Entity,
+            Ref<Structure>,
+            Ref<Transform>,
+            Option<&OwnerSwarm>,
+            Option<&UpkeepDepot>,
+            Option<Ref<RecentDamage>>,
+        ),
+        Without<PlannedReclaim>,
+    >,
+    upkeep: Option<&MaintenanceUpkeep>,
+    depots: &Query<(
+        Entity,
+        Ref<UpkeepStock>,
+        Ref<Transform>,
+        Option<Ref<OwnerSwarm>>,
+    )>,
     swarms: &Query<&SwarmId>,
     pressure: Option<&DefendPressure>,
     out: &mut Vec<ActionableOpportunity>,
@@ -392,15 +528,57 @@ fn project_intent_work(
                     cell,
                     owner,
                     available_work: deposit.amount,
+                    priority: 0,
                 });
             }
         }
     }
 
-    for (entity, structure, transform, owner) in structures.iter() {
+    for (entity, structure, transform, owner, _, recent) in structures.iter() {
+        if !structure.needs_repair() {
+            continue;
+        }
+        let cell = crate::nanobot::world_to_cell(transform.translation.truncate());
+        if AllocationRegion::for_cell(cell) != region {
+            continue;
+        }
+        let Some(owner) = resolve_owner(owner, swarms) else {
+            continue;
+        };
+        // Owner Defend paint on the structure's cell counts as
+        // defended, so Workers repair under fire only there.
+        let defended = grid.cell(cell).is_some_and(|intent| {
+            intent.has(IntentKind::Defend)
+                && owners_compatible(intent.owner(IntentKind::Defend), owner)
+        });
+        let recent = recent.as_deref();
+        if repair_blocked(recent, defended) {
+            continue;
+        }
+        let cell_pressure = owner
+            .zip(pressure)
+            .map_or(DEFEND_PRESSURE_BASELINE, |(owner, pressure)| {
+                pressure.get_for(owner, cell)
+            });
+        out.push(ActionableOpportunity {
+            region,
+            category: OpportunityCategory::Repair,
+            target: OpportunityTarget::Repair { structure: entity },
+            cell,
+            owner,
+            available_work: 1,
+            priority: repair_priority(&structure, recent, cell_pressure),
+        });
+    }
+
+    for (entity, structure, transform, owner, depot, _) in structures.iter() {
         if !structure.needs_maintenance() {
             continue;
         }
+        let stock = depot.and_then(|depot| depots.get(depot.0).ok());
+        if !upkeep_covers(upkeep, stock.as_ref().map(|(_, stock, _, _)| &**stock)) {
+            continue;
+        }
         let cell = crate::nanobot::world_to_cell(transform.translation.truncate());
         if AllocationRegion::for_cell(cell) != region {
             continue;
@@ -415,6 +593,7 @@ fn project_intent_work(
             cell,
             owner,
             available_work: 1,
+            priority: 0,
         });
     }
 
@@ -428,18 +607,20 @@ fn project_intent_work(
 
             if intent.has(IntentKind::Defend) {
                 let owner = intent.owner(IntentKind::Defend);
+                let defenders = owner
+                    .and_then(|owner| {
+                        pressure.map(|pressure| pressure.get_for(owner, cell).ceil() as u32)
+                    })
+                    .unwrap_or(1)
+                    .max(1);
                 out.push(ActionableOpportunity {
                     region,
                     category: OpportunityCategory::Defend,
                     target: OpportunityTarget::Defend { cell },
                     cell,
                     owner,
-                    available_work: owner
-                        .and_then(|owner| {
-                            pressure.map(|pressure| pressure.get_for(owner, cell).ceil() as u32)
-                        })
-                        .unwrap_or(1)
-                        .max(1),
+                    available_work: defenders,
+                    priority: defenders,
                 });
             }
         }
@@ -448,14 +629,23 @@ fn project_intent_work(
 
 fn project_planned_work(
     region: AllocationRegion,
-    planned: &Query<(Entity, Ref<PlannedStructure>, Option<&OwnerSwarm>)>,
+    planned: &Query<(
+        Entity,
+        Ref<PlannedStructure>,
+        Option<&OwnerSwarm>,
+        Option<Ref<PlannedMaterial>>,
+    )>,
     swarms: &Query<&SwarmId>,
     out: &mut Vec<ActionableOpportunity>,
 ) {
-    for (entity, planned, owner) in planned.iter() {
+    for (entity, planned, owner, material) in planned.iter() {
         if AllocationRegion::for_cell(planned.cell) != region || planned.work_remaining == 0 {
             continue;
         }
+        // A site still waiting on material has no work to offer yet.
+        if !material_allows_work(material.as_deref(), &planned) {
+            continue;
+        }
         let Some(owner) = resolve_owner(owner, swarms) else {
             continue;
         };
@@ -469,6 +659,7 @@ fn project_planned_work(
             cell: planned.cell,
             owner,
             available_work: planned.work_remaining,
+            priority: 0,
         });
     }
 }
@@ -504,6 +695,7 @@ fn project_haul_work(
                 cell: source.cell,
                 owner: source.owner,
                 available_work: source.amount.min(sink.free_space),
+                priority: 0,
             });
         }
     }
@@ -548,16 +740,18 @@ fn source_role_matches(role: StockpileRole, required: SourceRole) -> bool {
 fn opportunity_sort_key(opportunity: &ActionableOpportunity) -> (u8, u8, u64, u64, i32, i32) {
     let category = match opportunity.category {
         OpportunityCategory::PlannedBuild => 0,
-        OpportunityCategory::Maintenance => 1,
-        OpportunityCategory::Gather => 2,
-        OpportunityCategory::Defend => 3,
-        OpportunityCategory::Haul => 4,
+        OpportunityCategory::Repair => 1,
+        OpportunityCategory::Maintenance => 2,
+        OpportunityCategory::Gather => 3,
+        OpportunityCategory::Defend => 4,
+        OpportunityCategory::Haul => 5,
     };
     let (target_kind, first, second) = match opportunity.target {
         OpportunityTarget::Gather { deposit, .. } => (0, deposit.to_bits(), 0),
         OpportunityTarget::PlannedBuild { structure, .. } => (1, structure.to_bits(), 0),
         OpportunityTarget::Maintenance { structure } => (2, structure.to_bits(), 0),
         OpportunityTarget::Defend { .. } => (3, 0, 0),
+        OpportunityTarget::Repair { structure } => (5, structure.to_bits(), 0),
         OpportunityTarget::Haul { source, sink, .. } => (4, source.to_bits(), sink.to_bits()),
     };
     (
@@ -10,26 +10,33 @@ use bevy::prelude::*;
 
 use super::{
     ActionableOpportunity, ActionableProjection, AllocationCandidate, AllocationClock,
-    AllocationRegion, CandidateBounds, CategoryEligibility, CategoryWeights, OpportunityCategory,
-    OpportunityTarget, RegionalLease, RegionalLeaseConfig, RegionalLeaseState,
-    choose_bounded_candidate_from_ordered_regions_with_claims, outward_pull_budgets, pressure_map,
+    AllocationRegion, BatchClaims, CandidateBounds, CategoryEligibility, CategoryWeights,
+    ClaimCounts, ClaimKey, OpportunityCategory, OpportunityTarget, RegionalLease,
+    RegionalLeaseConfig, RegionalLeaseState, RegionalPullBudget, Speculation, SpeculativeMerge,
+    choose_bounded_candidate_from_ordered_regions_with_claims, claim_key, outward_pull_budgets,
+    pressure_map, speculate_batches,
 };
 use crate::{
     ZONE_BLOCK_SIZE,
     intent::IntentGrid,
     nanobot::{
         BUILDING_FOOTPRINT_RADIUS, Commitment, DEFEND_IN_CELL_STOP_RADIUS, DefendAssignment,
-        DefendHold, DirectMovementComponent, ExtractProgress, GatherAssignment,
-        HAULER_CARRY_CAPACITY, HaulerAssignment, HaulerLoad, HaulerLoading, Health,
-        LogisticsReservation, MaintenanceAssignment, MaintenanceProgress, Nanobot, NanobotType,
-        PRODUCTION_COST_PER_BOT, PlannedStructure, PlannedStructureClaim, PlannedStructureProgress,
-        ProductionFacility, ReturningToStockpile, SwarmId, SwarmMember, WORKER_CARRY_CAPACITY,
-        WorkerLoad,
+        DefendHold, DirectMovementComponent, ExtractProgress, FieldResupplyTargets,
+        GatherAssignment, HAULER_CARRY_CAPACITY, HaulerAssignment, HaulerLoad, HaulerLoading,
+        HaulerRoute, HaulerTrip, HaulerTripPickup, HaulerTripStop, Health, LogisticsReservation,
+        MaintenanceAssignment, MaintenanceProgress, MaintenanceUpkeep, Nanobot, NanobotType,
+        PRODUCTION_COST_PER_BOT, PlannedMaterial, PlannedStructure, PlannedStructureClaim,
+        PlannedStructureProgress, ProductionFacility, RepairAssignment, RepairProgress,
+        ResearchLab, ResearchState, ResupplyTarget, ReturningToStockpile, SwarmId, SwarmMember,
+        UpkeepStock, WORKER_CARRY_CAPACITY, WorkerLoad,
+        behaviour::{Behaviour, BehaviourAppExt},
         charge::{
-            Charge, Charger, ChargerAssignment, ChargerProgress, LOW_CHARGE_THRESHOLD,
-            WEAKENED_CHARGE_THRESHOLD, minerals_to_fully_charge,
+            Charge, Charger, ChargerAssignment, ChargerProgress, ChargerWaiting, charge_urgency,
+            minerals_to_fully_charge,
         },
-        hauler_route_cost, planned_route_movement,
+        logistics_leg::{TripEndpoint, TripPair, TripStop, plan_trip_stop},
+        planned_route_movement, planned_work_slots, researched_hauler_carry_capacity,
+        route_cache::RouteCostCache,
     },
     resources::{ResourceDeposit, ResourceKind, Stockpile},
 };
@@ -65,6 +72,16 @@ pub struct RegionalServiceAges {
     waiting: BTreeMap<(SwarmId, AllocationRegion, usize), u32>,
 }
 
+impl RegionalServiceAges {
+    /// Waiting age per swarm and region, one entry per nanobot type
+    /// with work waiting there.
+    pub fn iter(&self) -> impl Iterator<Item = (SwarmId, AllocationRegion, u32)> + '_ {
+        self.waiting
+            .iter()
+            .map(|((swarm, region, _), age)| (*swarm, *region, *age))
+    }
+}
+
 /// Waiting age for terminal consumers with actionable Logistics Legs.
 #[derive(Debug, Default, Resource)]
 pub struct TerminalDemandAges {
@@ -106,12 +123,35 @@ pub struct RegionalAllocationPlugin;
 
 impl Plugin for RegionalAllocationPlugin {
     fn build(&self, app: &mut App) {
+        crate::nanobot::add_route_cost_cache(app);
         app.init_resource::<ActionableProjection>()
             .init_resource::<AllocationClock>()
             .init_resource::<RegionalLeaseConfig>()
             .init_resource::<AllocationTickDue>()
             .init_resource::<TerminalDemandAges>()
             .init_resource::<RegionalServiceAges>()
+            // Leases follow their chain through behaviour roles, so the
+            // states must be tracked even in apps that leave a chain's
+            // plugin out. Registration is idempotent.
+            .add_behaviour_state::<GatherAssignment>()
+            .add_behaviour_state::<ExtractProgress>()
+            .add_behaviour_state::<ReturningToStockpile>()
+            .add_behaviour_state::<PlannedStructureClaim>()
+            .add_behaviour_state::<PlannedStructureProgress>()
+            .add_behaviour_state::<MaintenanceAssignment>()
+            .add_behaviour_state::<MaintenanceProgress>()
+            .add_behaviour_state::<RepairAssignment>()
+            .add_behaviour_state::<RepairProgress>()
+            .add_behaviour_state::<DefendAssignment>()
+            .add_behaviour_state::<DefendHold>()
+            .add_behaviour_state::<HaulerAssignment>()
+            .add_behaviour_state::<HaulerLoading>()
+            .add_behaviour_state::<HaulerRoute>()
+            .add_behaviour_state::<HaulerTrip>()
+            .add_behaviour_state::<HaulerTripPickup>()
+            .add_behaviour_state::<ChargerAssignment>()
+            .add_behaviour_state::<ChargerProgress>()
+            .add_behaviour_state::<ChargerWaiting>()
             .configure_sets(
                 FixedUpdate,
                 (
@@ -176,6 +216,9 @@ struct BotSnapshot {
 pub struct TerminalLogisticsParams<'w, 's> {
     facilities: Query<'w, 's, (&'static ProductionFacility, &'static Transform)>,
     chargers: Query<'w, 's, (&'static Charger, &'static Transform)>,
+    labs: Query<'w, 's, (&'static ResearchLab, &'static Transform)>,
+    depots: Query<'w, 's, (&'static UpkeepStock, &'static Transform)>,
+    sites: Query<'w, 's, (&'static PlannedMaterial, &'static Transform)>,
     defenders: Query<
         'w,
         's,
@@ -186,9 +229,24 @@ pub struct TerminalLogisticsParams<'w, 's> {
             Option<&'static ChargerProgress>,
         ),
     >,
+    resupply: FieldResupplyTargets<'w, 's>,
+    upkeep: Option<Res<'w, MaintenanceUpkeep>>,
     ages: ResMut<'w, TerminalDemandAges>,
+    research: Option<Res<'w, ResearchState>>,
+    routes: Res<'w, RouteCostCache>,
 }
 
+/// Claim work for idle nanobots in stable entity order.
+///
+/// Workers and Defenders go through parallel speculation and the merge in
+/// [`super::parallel`]; Haulers decide afterwards, still in entity order
+/// among themselves. Moving Haulers to the end does not change any
+/// claim's priority against the earlier sequential pass. Only Haulers
+/// take Haul work, Haul work is ranked by logistics reservations rather
+/// than claim counts, and only Haul decisions read or write those
+/// reservations and the terminal ages. No Worker or Defender decision
+/// depends on a Hauler decision, so running them first gives the same
+/// leases, reservations, and assignments.
 #[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn regional_allocation_acquisition_system(
     mut commands: Commands,
@@ -204,6 +262,7 @@ pub fn regional_allocation_acquisition_system(
             &Commitment,
             &SwarmMember,
             Option<&RegionalLease>,
+            Option<&Behaviour>,
         ),
         With<Nanobot>,
     >,
@@ -213,22 +272,10 @@ pub fn regional_allocation_acquisition_system(
         (),
         Or<(
             With<DirectMovementComponent>,
-            With<GatherAssignment>,
-            With<ExtractProgress>,
             With<WorkerLoad>,
-            With<ReturningToStockpile>,
-            With<PlannedStructureClaim>,
-            With<PlannedStructureProgress>,
-            With<MaintenanceAssignment>,
-            With<MaintenanceProgress>,
-            With<DefendAssignment>,
-            With<DefendHold>,
-            With<HaulerAssignment>,
-            With<HaulerLoading>,
             With<HaulerLoad>,
         )>,
     >,
-    charge_busy: Query<(), Or<(With<ChargerAssignment>, With<ChargerProgress>)>>,
     deposits: Query<(&ResourceDeposit, &Transform)>,
     mut planned: Query<(Entity, &mut PlannedStructure, &Transform)>,
     structures: Query<&Transform>,
@@ -237,8 +284,11 @@ pub fn regional_allocation_acquisition_system(
 ) {
     let facilities = &terminal.facilities;
     let chargers = &terminal.chargers;
+    let labs = &terminal.labs;
+    let depots = &terminal.depots;
+    let sites = &terminal.sites;
 
-    let mut claim_counts = BTreeMap::new();
+    let mut claim_counts = ClaimCounts::new();
     for lease in active_leases
         .iter()
         .filter(|lease| lease.counts_toward_capacity())
@@ -262,16 +312,7 @@ pub fn regional_allocation_acquisition_system(
         let Some(charger) = charger else {
             continue;
         };
-        let urgency =
-            if charge.is_empty() || health.is_some_and(|health| health.current < health.max) {
-                0
-            } else if charge.current < WEAKENED_CHARGE_THRESHOLD {
-                1
-            } else if charge.current <= LOW_CHARGE_THRESHOLD {
-                2
-            } else {
-                4
-            };
+        let urgency = charge_urgency(charge, health);
         let entry = charger_demand.entry(charger).or_insert((urgency, 0));
         entry.0 = entry.0.min(urgency);
         entry.1 = entry
@@ -279,13 +320,25 @@ pub fn regional_allocation_acquisition_system(
             .saturating_add(minerals_to_fully_charge(charge.current, charge.max));
     }
 
+    let resupply_demand = terminal
+        .resupply
+        .iter()
+        .map(|target| (target.entity, target))
+        .collect::<BTreeMap<_, _>>();
+
     let mut active_terminals = BTreeMap::new();
     for (_, opportunities) in projection.iter_regions() {
         for opportunity in opportunities {
             let OpportunityTarget::Haul { sink, .. } = opportunity.target else {
                 continue;
             };
-            if facilities.get(sink).is_ok() || chargers.get(sink).is_ok() {
+            if facilities.get(sink).is_ok()
+                || chargers.get(sink).is_ok()
+                || labs.get(sink).is_ok()
+                || depots.get(sink).is_ok()
+                || sites.get(sink).is_ok()
+                || resupply_demand.contains_key(&sink)
+            {
                 active_terminals.insert(sink, ());
             }
         }
@@ -311,29 +364,32 @@ pub fn regional_allocation_acquisition_system(
 
     let mut candidates = bots
         .iter()
-        .filter_map(|(entity, transform, kind, commitment, swarm, lease)| {
-            if *commitment != Commitment::Idle {
-                return None;
-            }
-            let resume_pending =
-                lease.is_some_and(|lease| lease.state == RegionalLeaseState::ResumePending);
-            if lease.is_some() && !resume_pending {
-                return None;
-            }
-            if lease.is_none() && (busy.contains(entity) || charge_busy.contains(entity)) {
-                return None;
-            }
-            Some(BotSnapshot {
-                entity,
-                position: transform.translation.truncate(),
-                region: AllocationRegion::for_cell(crate::nanobot::world_to_cell(
-                    transform.translation.truncate(),
-                )),
-                swarm: swarm.0,
-                kind: *kind,
-                resume_pending,
-            })
-        })
+        .filter_map(
+            |(entity, transform, kind, commitment, swarm, lease, behaviour)| {
+                if *commitment != Commitment::Idle {
+                    return None;
+                }
+                let resume_pending =
+                    lease.is_some_and(|lease| lease.state == RegionalLeaseState::ResumePending);
+                if lease.is_some() && !resume_pending {
+                    return None;
+                }
+                let in_state = behaviour.is_some_and(|behaviour| !behaviour.is_idle());
+                if lease.is_none() && (busy.contains(entity) || in_state) {
+                    return None;
+                }
+                Some(BotSnapshot {
+                    entity,
+                    position: transform.translation.truncate(),
+                    region: AllocationRegion::for_cell(crate::nanobot::world_to_cell(
+                        transform.translation.truncate(),
+                    )),
+                    swarm: swarm.0,
+                    kind: *kind,
+                    resume_pending,
+                })
+            },
+        )
         .collect::<Vec<_>>();
     candidates.sort_by_key(|bot| bot.entity.to_bits());
 
@@ -441,106 +497,186 @@ pub fn regional_allocation_acquisition_system(
         max_candidates: RUNTIME_MAX_CANDIDATES,
     };
 
+    // Haulers share logistics reservations and terminal ages across every
+    // region, so they keep the sequential path. Everything else speculates
+    // in per-pull batches and merges in stable entity order.
+    let mut batches = Vec::<((SwarmId, AllocationRegion, usize), Vec<BotSnapshot>)>::new();
+    let mut batch_index = BTreeMap::new();
+    let mut order = Vec::with_capacity(candidates.len());
+    let mut haulers = Vec::new();
     for bot in candidates {
         let bot_key = (bot.swarm, bot.region, kind_index(bot.kind));
-        let Some(pull) = pulls.get(&bot_key).copied() else {
+        if !pulls.contains_key(&bot_key) {
             if bot.resume_pending {
                 commands.entity(bot.entity).remove::<RegionalLease>();
             }
             continue;
-        };
-        let Some(ordered) = ordered_regions.get(&bot_key) else {
+        }
+        if !ordered_regions.contains_key(&bot_key) {
             continue;
-        };
-        let decision = if bot.kind == NanobotType::Hauler {
-            choose_terminal_logistics_work(
+        }
+        if bot.kind == NanobotType::Hauler {
+            haulers.push(bot);
+            continue;
+        }
+        let batch = *batch_index.entry(bot_key).or_insert_with(|| {
+            batches.push((bot_key, Vec::new()));
+            batches.len() - 1
+        });
+        order.push((batch, batches[batch].1.len()));
+        batches[batch].1.push(bot);
+    }
+
+    let speculations = speculate_batches(&batches, |(bot_key, bots)| {
+        let mut pull = pulls[bot_key];
+        let ordered = &ordered_regions[bot_key];
+        let mut claims = BatchClaims::new(&claim_counts);
+        bots.iter()
+            .map(|bot| {
+                let work = choose_claimed_work(
+                    *bot,
+                    pull,
+                    ordered,
+                    bounds,
+                    |key| claims.get(key),
+                    &planned_workers,
+                    &deposits,
+                    &structures,
+                    &stockpiles,
+                );
+                let seen_claims = work.map_or(0, |work| claims.get(claim_key(work.target)));
+                if let Some(work) = work {
+                    claims.add(claim_key(work.target));
+                    let remaining = pull.categories.get(work.category).saturating_sub(1);
+                    pull.categories.set(work.category, remaining);
+                }
+                Speculation { work, seen_claims }
+            })
+            .collect()
+    });
+
+    let mut merge = SpeculativeMerge::new(speculations, claim_counts);
+    for (batch, slot) in order {
+        let (bot_key, bots) = &batches[batch];
+        let bot = bots[slot];
+        let work = merge.settled(batch, slot).unwrap_or_else(|| {
+            choose_claimed_work(
                 bot,
-                pull,
-                ordered,
+                pulls[bot_key],
+                &ordered_regions[bot_key],
                 bounds,
+                |key| merge.claims().get(&key).copied().unwrap_or(0),
+                &planned_workers,
+                &deposits,
+                &structures,
                 &stockpiles,
-                facilities,
-                chargers,
-                &grid,
-                &reserved_source,
-                &reserved_destination,
-                &charger_demand,
-                &terminal.ages,
             )
-            .map(|opportunity| super::CandidateDecision {
-                opportunity,
-                regions_examined: 0,
-                candidates_examined: 0,
-            })
-        } else {
-            choose_bounded_candidate_from_ordered_regions_with_claims(
-                allocation_candidate(bot),
-                pull,
-                ordered.iter().copied(),
-                bounds,
-                |work| {
-                    let claims = claim_counts
-                        .get(&claim_key(work.target))
-                        .copied()
-                        .unwrap_or(0);
-                    if !target_available(
-                        bot,
-                        work,
-                        claims,
-                        &planned_workers,
-                        &deposits,
-                        &structures,
-                        &stockpiles,
-                    ) {
-                        return None;
-                    }
-                    Some(claims)
-                },
-            )
-        };
-        let Some(work) = decision.map(|decision| decision.opportunity) else {
+        });
+        let Some(work) = work else {
+            merge.record(batch, slot, None);
             if bot.resume_pending {
                 commands.entity(bot.entity).remove::<RegionalLease>();
             }
             continue;
         };
+        if !adapt_decision(
+            &mut commands,
+            bot,
+            work,
+            &grid,
+            &terminal.routes,
+            &deposits,
+            &mut planned,
+            &structures,
+            &stockpiles,
+            researched_hauler_carry_capacity(terminal.research.as_deref(), bot.swarm),
+            facilities,
+            chargers,
+            labs,
+            depots,
+            sites,
+            &mut reserved_source,
+            &mut reserved_destination,
+            &charger_demand,
+            &resupply_demand,
+            &ordered_regions[bot_key],
+        ) {
+            merge.record(batch, slot, None);
+            continue;
+        }
+        merge.record(batch, slot, Some(work));
+        insert_lease(&mut commands, bot, work, clock.tick(), &mut region_ages);
+        if let Some(pull) = pulls.get_mut(bot_key) {
+            let remaining = pull.categories.get(work.category).saturating_sub(1);
+            pull.categories.set(work.category, remaining);
+        }
+    }
 
+    for bot in haulers {
+        let bot_key = (bot.swarm, bot.region, kind_index(bot.kind));
+        let carry_capacity =
+            researched_hauler_carry_capacity(terminal.research.as_deref(), bot.swarm);
+        let Some(work) = choose_terminal_logistics_work(
+            bot,
+            pulls[&bot_key],
+            &ordered_regions[&bot_key],
+            bounds,
+            carry_capacity,
+            &stockpiles,
+            facilities,
+            chargers,
+            labs,
+            depots,
+            sites,
+            &grid,
+            &terminal.routes,
+            &reserved_source,
+            &reserved_destination,
+            &charger_demand,
+            &resupply_demand,
+            terminal.upkeep.as_deref().map(|upkeep| upkeep.per_shift),
+            &terminal.ages,
+        ) else {
+            if bot.resume_pending {
+                commands.entity(bot.entity).remove::<RegionalLease>();
+            }
+            continue;
+        };
         if !adapt_decision(
             &mut commands,
             bot,
             work,
             &grid,
+            &terminal.routes,
             &deposits,
             &mut planned,
             &structures,
             &stockpiles,
+            carry_capacity,
             facilities,
             chargers,
+            labs,
+            depots,
+            sites,
             &mut reserved_source,
             &mut reserved_destination,
             &charger_demand,
+            &resupply_demand,
+            &ordered_regions[&bot_key],
         ) {
             continue;
         }
         if let OpportunityTarget::Haul { sink, .. } = work.target
-            && (facilities.get(sink).is_ok() || chargers.get(sink).is_ok())
+            && (facilities.get(sink).is_ok()
+                || chargers.get(sink).is_ok()
+                || labs.get(sink).is_ok()
+                || depots.get(sink).is_ok()
+                || sites.get(sink).is_ok()
+                || resupply_demand.contains_key(&sink))
         {
             terminal.ages.waiting.insert(sink, 0);
         }
-        region_ages
-            .waiting
-            .insert((bot.swarm, work.region, kind_index(bot.kind)), 0);
-        let lease = RegionalLease::new(
-            work.region,
-            work.category,
-            work.target,
-            work.owner,
-            clock.tick(),
-            0,
-            30,
-        );
-        commands.entity(bot.entity).insert(lease);
-        *claim_counts.entry(claim_key(work.target)).or_insert(0) += 1;
+        insert_lease(&mut commands, bot, work, clock.tick(), &mut region_ages);
         if let Some(pull) = pulls.get_mut(&bot_key) {
             let remaining = pull.categories.get(work.category).saturating_sub(1);
             pull.categories.set(work.category, remaining);
@@ -548,6 +684,64 @@ pub fn regional_allocation_acquisition_system(
     }
 }
 
+fn insert_lease(
+    commands: &mut Commands,
+    bot: BotSnapshot,
+    work: ActionableOpportunity,
+    now_tick: u64,
+    region_ages: &mut RegionalServiceAges,
+) {
+    region_ages
+        .waiting
+        .insert((bot.swarm, work.region, kind_index(bot.kind)), 0);
+    let lease = RegionalLease::new(
+        work.region,
+        work.category,
+        work.target,
+        work.owner,
+        now_tick,
+        0,
+        30,
+    );
+    commands.entity(bot.entity).insert(lease);
+}
+
+/// Bounded choice for claim-counted work. Speculation and the merge share
+/// it so both rank candidates identically.
+#[allow(clippy::too_many_arguments)]
+fn choose_claimed_work(
+    bot: BotSnapshot,
+    pull: RegionalPullBudget,
+    ordered: &[(AllocationRegion, &[ActionableOpportunity])],
+    bounds: CandidateBounds,
+    claims: impl Fn(ClaimKey) -> usize,
+    planned_workers: &BTreeMap<u64, Option<u64>>,
+    deposits: &Query<(&ResourceDeposit, &Transform)>,
+    structures: &Query<&Transform>,
+    stockpiles: &Query<(&Stockpile, &Transform)>,
+) -> Option<ActionableOpportunity> {
+    choose_bounded_candidate_from_ordered_regions_with_claims(
+        allocation_candidate(bot),
+        pull,
+        ordered.iter().copied(),
+        bounds,
+        |work| {
+            let claims = claims(claim_key(work.target));
+            target_available(
+                bot,
+                work,
+                claims,
+                planned_workers,
+                deposits,
+                structures,
+                stockpiles,
+            )
+            .then_some(claims)
+        },
+    )
+    .map(|decision| decision.opportunity)
+}
+
 #[derive(Clone, Copy)]
 struct TerminalLogisticsScore {
     urgency: u8,
@@ -576,13 +770,20 @@ fn choose_terminal_logistics_work(
     pull: super::RegionalPullBudget,
     ordered: &[(AllocationRegion, &[ActionableOpportunity])],
     bounds: CandidateBounds,
+    carry_capacity: u32,
     stockpiles: &Query<(&Stockpile, &Transform)>,
     facilities: &Query<(&ProductionFacility, &Transform)>,
     chargers: &Query<(&Charger, &Transform)>,
+    labs: &Query<(&ResearchLab, &Transform)>,
+    depots: &Query<(&UpkeepStock, &Transform)>,
+    sites: &Query<(&PlannedMaterial, &Transform)>,
     grid: &IntentGrid,
+    routes: &RouteCostCache,
     reserved_source: &BTreeMap<Entity, u32>,
     reserved_destination: &BTreeMap<Entity, u32>,
     charger_demand: &BTreeMap<Entity, (u8, u32)>,
+    resupply_demand: &BTreeMap<Entity, ResupplyTarget>,
+    upkeep_per_shift: Option<u32>,
     ages: &TerminalDemandAges,
 ) -> Option<ActionableOpportunity> {
     if pull.categories.get(OpportunityCategory::Haul) == 0 {
@@ -612,7 +813,7 @@ fn choose_terminal_logistics_work(
             let (base_urgency, destination_available, deficit, capacity, sink_pos) =
                 if let Ok((facility, transform)) = facilities.get(sink) {
                     let available = facility.input_free_space().saturating_sub(incoming);
-                    let amount = HAULER_CARRY_CAPACITY.min(source_available).min(available);
+                    let amount = carry_capacity.min(source_available).min(available);
                     let reaches_cycle = facility
                         .input_amount
                         .saturating_add(incoming)
@@ -644,6 +845,56 @@ fn choose_terminal_logistics_work(
                         charger.capacity,
                         transform.translation.truncate(),
                     )
+                } else if let Ok((lab, transform)) = labs.get(sink) {
+                    // Research never blocks a production cycle or a
+                    // starving defender, so labs share the lowest
+                    // terminal tier.
+                    let available = lab.input_free_space().saturating_sub(incoming);
+                    (
+                        4,
+                        available,
+                        available,
+                        lab.input_capacity,
+                        transform.translation.truncate(),
+                    )
+                } else if let Ok((stock, transform)) = depots.get(sink) {
+                    // An upkeep depot that cannot pay the next shift
+                    // is about to stall maintenance, which ranks with
+                    // a production cycle; topping up a funded depot
+                    // waits with the other terminals.
+                    let available = stock.free_space().saturating_sub(incoming);
+                    let unfunded = upkeep_per_shift
+                        .is_some_and(|per_shift| stock.amount.saturating_add(incoming) < per_shift);
+                    (
+                        if unfunded { 3 } else { 4 },
+                        available,
+                        available,
+                        stock.capacity,
+                        transform.translation.truncate(),
+                    )
+                } else if let Ok((material, transform)) = sites.get(sink) {
+                    // Construction material ranks with a production
+                    // cycle so expansion competes with unit
+                    // production for the same minerals.
+                    let available = material.free_space().saturating_sub(incoming);
+                    (
+                        3,
+                        available,
+                        available,
+                        material.required,
+                        transform.translation.truncate(),
+                    )
+                } else if let Some(target) = resupply_demand.get(&sink) {
+                    // A holding Defender ranks with a charger whose
+                    // Defenders are just as low.
+                    let available = target.need.saturating_sub(incoming);
+                    (
+                        target.urgency,
+                        available,
+                        available,
+                        target.capacity,
+                        target.pos,
+                    )
                 } else if let Ok((stockpile, transform)) = stockpiles.get(sink) {
                     let available = stockpile.free_space().saturating_sub(incoming);
                     (
@@ -656,7 +907,7 @@ fn choose_terminal_logistics_work(
                 } else {
                     continue;
                 };
-            let amount = HAULER_CARRY_CAPACITY
+            let amount = carry_capacity
                 .min(source_available)
                 .min(destination_available);
             if amount == 0 {
@@ -672,8 +923,8 @@ fn choose_terminal_logistics_work(
                 urgency,
                 age_key: u32::MAX - age,
                 deficit_key: u64::MAX - deficit_ratio,
-                route_cost: hauler_route_cost(bot.position, source_pos, grid, bot.swarm)
-                    + hauler_route_cost(source_pos, sink_pos, grid, bot.swarm),
+                route_cost: routes.cost(bot.position, source_pos, grid, bot.swarm)
+                    + routes.cost(source_pos, sink_pos, grid, bot.swarm),
                 terminal: sink.to_bits(),
                 source: source.to_bits(),
             };
@@ -702,6 +953,7 @@ fn kind_allows(kind: NanobotType, category: OpportunityCategory) -> bool {
         (NanobotType::Worker, OpportunityCategory::Gather)
             | (NanobotType::Worker, OpportunityCategory::PlannedBuild)
             | (NanobotType::Worker, OpportunityCategory::Maintenance)
+            | (NanobotType::Worker, OpportunityCategory::Repair)
             | (NanobotType::Defender, OpportunityCategory::Defend)
             | (NanobotType::Hauler, OpportunityCategory::Haul)
     )
@@ -736,10 +988,18 @@ fn target_available(
     }
     match work.target {
         OpportunityTarget::Gather { deposit, .. } => deposits.get(deposit).is_ok(),
+        // The lead holds the first slot even before its lease is
+        // counted; helpers only fill the slots left over.
         OpportunityTarget::PlannedBuild { structure, .. } => planned_workers
             .get(&structure.to_bits())
-            .is_some_and(|worker| worker.is_none() || *worker == Some(bot.entity.to_bits())),
-        OpportunityTarget::Maintenance { structure } => structures.get(structure).is_ok(),
+            .is_some_and(|worker| {
+                worker.is_none()
+                    || *worker == Some(bot.entity.to_bits())
+                    || claims.max(1) < opportunity_capacity(work)
+            }),
+        OpportunityTarget::Maintenance { structure } | OpportunityTarget::Repair { structure } => {
+            structures.get(structure).is_ok()
+        }
         OpportunityTarget::Defend { .. } => true,
         OpportunityTarget::Haul { source, .. } => stockpiles
             .get(source)
@@ -750,7 +1010,13 @@ fn target_available(
 fn opportunity_capacity(work: ActionableOpportunity) -> usize {
     let units = match work.category {
         OpportunityCategory::Gather => work.available_work.div_ceil(WORKER_CARRY_CAPACITY),
-        OpportunityCategory::PlannedBuild | OpportunityCategory::Maintenance => 1,
+        OpportunityCategory::PlannedBuild => match work.target {
+            OpportunityTarget::PlannedBuild { kind, .. } => {
+                planned_work_slots(kind, work.available_work)
+            }
+            _ => 1,
+        },
+        OpportunityCategory::Maintenance | OpportunityCategory::Repair => 1,
         OpportunityCategory::Defend => work.available_work,
         OpportunityCategory::Haul => work.available_work.div_ceil(HAULER_CARRY_CAPACITY),
     };
@@ -763,15 +1029,22 @@ fn adapt_decision(
     bot: BotSnapshot,
     work: ActionableOpportunity,
     grid: &IntentGrid,
+    routes: &RouteCostCache,
     deposits: &Query<(&ResourceDeposit, &Transform)>,
     planned: &mut Query<(Entity, &mut PlannedStructure, &Transform)>,
     structures: &Query<&Transform>,
     stockpiles: &Query<(&Stockpile, &Transform)>,
+    carry_capacity: u32,
     facilities: &Query<(&ProductionFacility, &Transform)>,
     chargers: &Query<(&Charger, &Transform)>,
+    labs: &Query<(&ResearchLab, &Transform)>,
+    depots: &Query<(&UpkeepStock, &Transform)>,
+    sites: &Query<(&PlannedMaterial, &Transform)>,
     reserved_source: &mut BTreeMap<Entity, u32>,
     reserved_destination: &mut BTreeMap<Entity, u32>,
     charger_demand: &BTreeMap<Entity, (u8, u32)>,
+    resupply_demand: &BTreeMap<Entity, ResupplyTarget>,
+    nearby_work: &[(AllocationRegion, &[ActionableOpportunity])],
 ) -> bool {
     match work.target {
         OpportunityTarget::Gather { deposit, cell } => {
@@ -790,12 +1063,14 @@ fn adapt_decision(
             let Ok((_, mut planned_state, transform)) = planned.get_mut(structure) else {
                 return false;
             };
-            if planned_state.active_worker.is_some()
-                && planned_state.active_worker != Some(bot.entity)
-            {
-                return false;
+            // A taken lead slot leaves room for helpers only on a
+            // build big enough to open more than one slot.
+            match planned_state.active_worker {
+                None => planned_state.active_worker = Some(bot.entity),
+                Some(lead) if lead == bot.entity => {}
+                Some(_) if planned_state.work_slots() > 1 => {}
+                Some(_) => return false,
             }
-            planned_state.active_worker = Some(bot.entity);
             commands.entity(bot.entity).insert((
                 PlannedStructureClaim {
                     cell: work.cell,
@@ -822,6 +1097,21 @@ fn adapt_decision(
                 },
             ));
         }
+        OpportunityTarget::Repair { structure } => {
+            let Ok(transform) = structures.get(structure) else {
+                return false;
+            };
+            commands.entity(bot.entity).insert((
+                RepairAssignment {
+                    cell: work.cell,
+                    target: structure,
+                },
+                DirectMovementComponent {
+                    xy: transform.translation.truncate(),
+                    stop_radius: BUILDING_FOOTPRINT_RADIUS,
+                },
+            ));
+        }
         OpportunityTarget::Defend { cell } => {
             let target = Vec2::new(
                 (cell.x as f32 + 0.5) * ZONE_BLOCK_SIZE,
@@ -840,49 +1130,129 @@ fn adapt_decision(
             sink,
             kind: ResourceKind::Minerals,
         } => {
-            let Ok((source_state, transform)) = stockpiles.get(source) else {
+            let Ok((source_state, _)) = stockpiles.get(source) else {
                 return false;
             };
-            let sink_free_space = stockpiles
-                .get(sink)
-                .map(|(stockpile, _)| stockpile.free_space())
-                .or_else(|_| {
-                    facilities
-                        .get(sink)
-                        .map(|(facility, _)| facility.input_free_space())
-                })
-                .or_else(|_| chargers.get(sink).map(|(charger, _)| charger.free_space()))
-                .unwrap_or(0);
-            let source_available = source_state
-                .amount
-                .saturating_sub(reserved_source.get(&source).copied().unwrap_or_default());
-            let incoming = reserved_destination.get(&sink).copied().unwrap_or_default();
-            let mut destination_available = sink_free_space.saturating_sub(incoming);
-            if let Ok((charger, _)) = chargers.get(sink) {
-                let (urgency, total_need) = charger_demand.get(&sink).copied().unwrap_or((4, 0));
-                let emergency_remaining = total_need
-                    .saturating_sub(charger.amount)
-                    .saturating_sub(incoming);
-                if urgency < 4 && emergency_remaining > 0 {
-                    destination_available = destination_available.min(emergency_remaining);
-                }
-            }
-            let amount = HAULER_CARRY_CAPACITY
-                .min(source_available)
-                .min(destination_available);
+            let source_endpoint =
+                |source| haul_source_endpoint(source, stockpiles, reserved_source);
+            let sink_endpoint = |sink| {
+                haul_sink_endpoint(
+                    sink,
+                    stockpiles,
+                    facilities,
+                    chargers,
+                    labs,
+                    depots,
+                    sites,
+                    reserved_destination,
+                    charger_demand,
+                    resupply_demand,
+                )
+            };
+            let (Some(leg_source), Some(leg_sink)) = (source_endpoint(source), sink_endpoint(sink))
+            else {
+                return false;
+            };
+            let amount = carry_capacity
+                .min(leg_source.available)
+                .min(leg_sink.available);
             if amount == 0 {
                 return false;
             }
+            // Pairs sharing an endpoint with this leg are the only
+            // candidates for a second pickup or dropoff.
+            let pairs = nearby_work
+                .iter()
+                .flat_map(|(_, opportunities)| opportunities.iter())
+                .filter_map(|other| {
+                    let OpportunityTarget::Haul {
+                        source: other_source,
+                        sink: other_sink,
+                        kind: ResourceKind::Minerals,
+                    } = other.target
+                    else {
+                        return None;
+                    };
+                    if other_source != source && other_sink != sink {
+                        return None;
+                    }
+                    Some(TripPair {
+                        source: source_endpoint(other_source)?,
+                        sink: sink_endpoint(other_sink)?,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let stop = plan_trip_stop(
+                TripPair {
+                    source: leg_source,
+                    sink: leg_sink,
+                },
+                amount,
+                carry_capacity,
+                &pairs,
+                |from, to| routes.cost(from, to, grid, bot.swarm),
+            );
             let (route, movement) = planned_route_movement(
                 bot.position,
-                transform.translation.truncate(),
+                leg_source.pos,
                 grid,
                 bot.swarm,
                 source_state.radius,
             );
+            let mut reservation =
+                LogisticsReservation::new(source, sink, ResourceKind::Minerals, amount);
+            match stop {
+                Some(TripStop::Pickup {
+                    source: pickup,
+                    amount: extra,
+                }) => {
+                    let stop = commands
+                        .spawn((
+                            HaulerTripStop { hauler: bot.entity },
+                            LogisticsReservation::new(pickup, sink, ResourceKind::Minerals, extra),
+                        ))
+                        .id();
+                    commands.entity(bot.entity).insert(HaulerTrip {
+                        pickups: vec![stop],
+                        dropoffs: Vec::new(),
+                    });
+                    *reserved_source.entry(pickup).or_default() += extra;
+                    *reserved_destination.entry(sink).or_default() += extra;
+                }
+                Some(TripStop::Dropoff {
+                    sink: dropoff,
+                    amount: extra,
+                }) => {
+                    // The first leg loads the whole trip; the stop
+                    // claims only its share of the destinations.
+                    reservation.amount += extra;
+                    reservation.source_remaining += extra;
+                    let stop = commands
+                        .spawn((
+                            HaulerTripStop { hauler: bot.entity },
+                            LogisticsReservation {
+                                source_remaining: 0,
+                                ..LogisticsReservation::new(
+                                    source,
+                                    dropoff,
+                                    ResourceKind::Minerals,
+                                    extra,
+                                )
+                            },
+                        ))
+                        .id();
+                    commands.entity(bot.entity).insert(HaulerTrip {
+                        pickups: Vec::new(),
+                        dropoffs: vec![stop],
+                    });
+                    *reserved_source.entry(source).or_default() += extra;
+                    *reserved_destination.entry(dropoff).or_default() += extra;
+                }
+                None => {}
+            }
             commands.entity(bot.entity).insert((
                 HaulerAssignment { source, sink },
-                LogisticsReservation::new(source, sink, ResourceKind::Minerals, amount),
+                reservation,
                 route,
                 movement,
             ));
@@ -893,20 +1263,93 @@ fn adapt_decision(
     true
 }
 
-fn region_distance_key(left: AllocationRegion, right: AllocationRegion) -> u32 {
-    left.x.abs_diff(right.x) + left.y.abs_diff(right.y)
+/// What `source` can still give after this tick's and earlier claims.
+fn haul_source_endpoint(
+    source: Entity,
+    stockpiles: &Query<(&Stockpile, &Transform)>,
+    reserved_source: &BTreeMap<Entity, u32>,
+) -> Option<TripEndpoint> {
+    let (stockpile, transform) = stockpiles.get(source).ok()?;
+    Some(TripEndpoint {
+        entity: source,
+        pos: transform.translation.truncate(),
+        available: stockpile
+            .amount
+            .saturating_sub(reserved_source.get(&source).copied().unwrap_or_default()),
+    })
 }
 
-fn claim_key(target: OpportunityTarget) -> (u8, u64, u64, u64) {
-    match target {
-        OpportunityTarget::Gather { deposit, .. } => (0, deposit.to_bits(), 0, 0),
-        OpportunityTarget::PlannedBuild { structure, .. } => (1, structure.to_bits(), 0, 0),
-        OpportunityTarget::Maintenance { structure } => (2, structure.to_bits(), 0, 0),
-        OpportunityTarget::Defend { cell } => {
-            (3, i64::from(cell.x) as u64, i64::from(cell.y) as u64, 0)
+/// What `sink` can still take after existing claims. A charger with
+/// defenders waiting takes only their emergency need, and a holding
+/// defender only what tops it up.
+#[allow(clippy::too_many_arguments)]
+fn haul_sink_endpoint(
+    sink: Entity,
+    stockpiles: &Query<(&Stockpile, &Transform)>,
+    facilities: &Query<(&ProductionFacility, &Transform)>,
+    chargers: &Query<(&Charger, &Transform)>,
+    labs: &Query<(&ResearchLab, &Transform)>,
+    depots: &Query<(&UpkeepStock, &Transform)>,
+    sites: &Query<(&PlannedMaterial, &Transform)>,
+    reserved_destination: &BTreeMap<Entity, u32>,
+    charger_demand: &BTreeMap<Entity, (u8, u32)>,
+    resupply_demand: &BTreeMap<Entity, ResupplyTarget>,
+) -> Option<TripEndpoint> {
+    let incoming = reserved_destination.get(&sink).copied().unwrap_or_default();
+    if let Some(target) = resupply_demand.get(&sink) {
+        return Some(TripEndpoint {
+            entity: sink,
+            pos: target.pos,
+            available: target.need.saturating_sub(incoming),
+        });
+    }
+    let (free_space, transform) = stockpiles
+        .get(sink)
+        .map(|(stockpile, transform)| (stockpile.free_space(), transform))
+        .or_else(|_| {
+            facilities
+                .get(sink)
+                .map(|(facility, transform)| (facility.input_free_space(), transform))
+        })
+        .or_else(|_| {
+            chargers
+                .get(sink)
+                .map(|(charger, transform)| (charger.free_space(), transform))
+        })
+        .or_else(|_| {
+            labs.get(sink)
+                .map(|(lab, transform)| (lab.input_free_space(), transform))
+        })
+        .or_else(|_| {
+            depots
+                .get(sink)
+                .map(|(stock, transform)| (stock.free_space(), transform))
+        })
+        .or_else(|_| {
+            sites
+                .get(sink)
+                .map(|(material, transform)| (material.free_space(), transform))
+        })
+        .ok()?;
+    let mut available = free_space.saturating_sub(incoming);
+    if let Ok((charger, _)) = chargers.get(sink) {
+        let (urgency, total_need) = charger_demand.get(&sink).copied().unwrap_or((4, 0));
+        let emergency_remaining = total_need
+            .saturating_sub(charger.amount)
+            .saturating_sub(incoming);
+        if urgency < 4 && emergency_remaining > 0 {
+            available = available.min(emergency_remaining);
         }
-        OpportunityTarget::Haul { source, sink, .. } => (4, source.to_bits(), sink.to_bits(), 0),
     }
+    Some(TripEndpoint {
+        entity: sink,
+        pos: transform.translation.truncate(),
+        available,
+    })
+}
+
+fn region_distance_key(left: AllocationRegion, right: AllocationRegion) -> u32 {
+    left.x.abs_diff(right.x) + left.y.abs_diff(right.y)
 }
 
 #[cfg(test)]
@@ -63,23 +63,27 @@ impl NanobotType {
     /// Corridor layer is hauler path guidance rather than a
     /// work-producing intent, so it scores 0 for every type -- no
     /// nanobot is "fit" for a corridor because corridors do not
-    /// create work.
+    /// create work. Reclaim is deconstruction work, so only Workers
+    /// fit it.
     pub fn fit_for(self, kind: IntentKind) -> f32 {
         match (self, kind) {
             (NanobotType::Worker, IntentKind::Gather) => 1.0,
             (NanobotType::Worker, IntentKind::Build) => 1.0,
             (NanobotType::Worker, IntentKind::Defend) => 0.0,
             (NanobotType::Worker, IntentKind::Corridor) => 0.0,
+            (NanobotType::Worker, IntentKind::Reclaim) => 1.0,
 
             (NanobotType::Hauler, IntentKind::Gather) => 0.0,
             (NanobotType::Hauler, IntentKind::Build) => 0.5,
             (NanobotType::Hauler, IntentKind::Defend) => 0.0,
             (NanobotType::Hauler, IntentKind::Corridor) => 1.0,
+            (NanobotType::Hauler, IntentKind::Reclaim) => 0.0,
 
             (NanobotType::Defender, IntentKind::Gather) => 0.0,
             (NanobotType::Defender, IntentKind::Build) => 0.0,
             (NanobotType::Defender, IntentKind::Defend) => 1.0,
             (NanobotType::Defender, IntentKind::Corridor) => 0.0,
+            (NanobotType::Defender, IntentKind::Reclaim) => 0.0,
         }
     }
 }
@@ -100,6 +104,9 @@ pub enum Commitment {
     /// Actively working on a short task at a cell. Usually finishes that
     /// short work chunk before reassessing.
     Working,
+    /// Backing away from hostile Defenders. Takes no new work until the
+    /// threat passes.
+    Fleeing,
 }
 
 impl Commitment {
@@ -119,6 +126,9 @@ impl Commitment {
             // still be tempted by a much higher score, which is the
             // "soft" part of the commitment contract.
             Commitment::Working => 0.4,
+            // Fleeing nanobots are skipped by every assignment path;
+            // the factor only keeps the score well-defined.
+            Commitment::Fleeing => 0.01,
         }
     }
 }
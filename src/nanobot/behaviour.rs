@@ -0,0 +1,336 @@
+//! Per-nanobot behaviour states.
+//!
+//! Every task chain keeps its lifecycle in typed marker components on the
+//! nanobot: an assignment while it walks to the target, a progress marker
+//! while it works there. Each marker implements [`BehaviourState`], which
+//! names the [`BehaviourRole`] it belongs to and optional enter / exit
+//! hooks, and the owning plugin registers it with
+//! [`BehaviourAppExt::add_behaviour_state`].
+//!
+//! Registration installs two observers per state. Entering a state (the
+//! marker is added) sets its bit on the nanobot's [`Behaviour`] and runs
+//! [`BehaviourState::on_enter`]; leaving it (the marker is removed, or the
+//! nanobot despawns) clears the bit and runs [`BehaviourState::on_exit`].
+//! Systems that only ask "is this nanobot busy, and in which role" read
+//! [`Behaviour`] instead of querying every marker, and [`exit_role`] tears a
+//! role down without naming its markers. A new role is a new
+//! [`BehaviourRole`] constant plus the states its plugin registers.
+
+use bevy::ecs::component::ComponentId;
+use bevy::prelude::*;
+
+/// Task chain a behaviour state belongs to. Roles are plain ids so a
+/// plugin can introduce its own without touching this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BehaviourRole(pub u8);
+
+impl BehaviourRole {
+    /// Walking to a deposit and extracting from it.
+    pub const GATHER: Self = Self(0);
+    /// Carrying a gathered load back to a stockpile.
+    pub const DELIVER: Self = Self(1);
+    /// Walking to and working on a planned structure.
+    pub const BUILD: Self = Self(2);
+    /// Walking to and maintaining a completed structure.
+    pub const MAINTAIN: Self = Self(3);
+    /// Moving into and holding a Defend cell.
+    pub const DEFEND: Self = Self(4);
+    /// Running one logistics leg between a source and a sink.
+    pub const HAUL: Self = Self(5);
+    /// Walking to and recharging at a charger.
+    pub const CHARGE: Self = Self(6);
+    /// Walking to and repairing a damaged structure.
+    pub const REPAIR: Self = Self(7);
+    /// Backing away from hostile Defenders until the cell is calm.
+    pub const FLEE: Self = Self(8);
+
+    /// Exclusive upper bound on role ids.
+    pub const LIMIT: u8 = 32;
+
+    const fn bit(self) -> u32 {
+        1 << self.0
+    }
+}
+
+/// A marker component that places a nanobot in one state of a role.
+///
+/// The hooks receive the nanobot's [`EntityCommands`]. `on_exit` also
+/// runs when the nanobot despawns, so exit hooks must only queue `try_`
+/// commands.
+pub trait BehaviourState: Component {
+    const ROLE: BehaviourRole;
+
+    fn on_enter(_nanobot: &mut EntityCommands) {}
+
+    fn on_exit(_nanobot: &mut EntityCommands) {}
+}
+
+/// Upper bound on registered behaviour states across every role.
+pub const MAX_BEHAVIOUR_STATES: usize = 64;
+
+/// States currently held by one nanobot. Kept in sync by the observers
+/// [`BehaviourAppExt::add_behaviour_state`] installs; inserted on every
+/// nanobot by [`crate::nanobot::initialize_nanobot_type_components`].
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Behaviour {
+    states: u64,
+    roles: u32,
+}
+
+impl Behaviour {
+    /// Snapshot of the registered states `has` reports present.
+    pub fn from_present(states: &BehaviourStates, has: impl Fn(ComponentId) -> bool) -> Self {
+        let mut behaviour = Self::default();
+        for (slot, (id, role)) in states.states.iter().enumerate() {
+            if has(*id) {
+                behaviour.enter(slot, *role);
+            }
+        }
+        behaviour
+    }
+
+    /// True while the nanobot holds any state of `role`.
+    pub fn is_active(&self, role: BehaviourRole) -> bool {
+        self.roles & role.bit() != 0
+    }
+
+    /// True when the nanobot holds no registered state at all.
+    pub fn is_idle(&self) -> bool {
+        self.states == 0
+    }
+
+    fn enter(&mut self, slot: usize, role: BehaviourRole) {
+        self.states |= 1 << slot;
+        self.roles |= role.bit();
+    }
+
+    fn exit(&mut self, slot: usize, role: BehaviourRole, role_states: u64) {
+        self.states &= !(1 << slot);
+        if self.states & role_states == 0 {
+            self.roles &= !role.bit();
+        }
+    }
+}
+
+/// Registered behaviour states in registration order. A state's index is
+/// its bit in [`Behaviour`].
+#[derive(Debug, Default, Resource)]
+pub struct BehaviourStates {
+    states: Vec<(ComponentId, BehaviourRole)>,
+}
+
+impl BehaviourStates {
+    fn slot(&self, id: ComponentId) -> Option<usize> {
+        self.states
+            .iter()
+            .position(|(registered, _)| *registered == id)
+    }
+
+    fn role_states(&self, role: BehaviourRole) -> u64 {
+        self.states
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, state_role))| *state_role == role)
+            .fold(0, |mask, (slot, _)| mask | (1 << slot))
+    }
+
+    /// Component ids of every registered state of `role`.
+    pub fn components(&self, role: BehaviourRole) -> impl Iterator<Item = ComponentId> + '_ {
+        self.states
+            .iter()
+            .filter(move |(_, state_role)| *state_role == role)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// Registers behaviour states on an [`App`].
+pub trait BehaviourAppExt {
+    /// Track `T` as a state of `T::ROLE` and run its hooks. Registering
+    /// the same state again is a no-op, so every plugin that depends on
+    /// a state can register it.
+    fn add_behaviour_state<T: BehaviourState>(&mut self) -> &mut Self;
+}
+
+impl BehaviourAppExt for App {
+    fn add_behaviour_state<T: BehaviourState>(&mut self) -> &mut Self {
+        assert!(
+            T::ROLE.0 < BehaviourRole::LIMIT,
+            "behaviour role id out of range"
+        );
+        let world = self.world_mut();
+        let id = world.register_component::<T>();
+        let mut states = world.get_resource_or_init::<BehaviourStates>();
+        if states.slot(id).is_some() {
+            return self;
+        }
+        let slot = states.states.len();
+        assert!(
+            slot < MAX_BEHAVIOUR_STATES,
+            "too many behaviour states registered"
+        );
+        states.states.push((id, T::ROLE));
+
+        self.add_observer(
+            move |added: On<Add, T>,
+                  mut commands: Commands,
+                  mut behaviours: Query<&mut Behaviour>| {
+                if let Ok(mut behaviour) = behaviours.get_mut(added.entity) {
+                    behaviour.enter(slot, T::ROLE);
+                }
+                T::on_enter(&mut commands.entity(added.entity));
+            },
+        )
+        .add_observer(
+            move |removed: On<Remove, T>,
+                  mut commands: Commands,
+                  states: Res<BehaviourStates>,
+                  mut behaviours: Query<&mut Behaviour>| {
+                if let Ok(mut behaviour) = behaviours.get_mut(removed.entity) {
+                    behaviour.exit(slot, T::ROLE, states.role_states(T::ROLE));
+                }
+                T::on_exit(&mut commands.entity(removed.entity));
+            },
+        )
+    }
+}
+
+/// Insert a [`Behaviour`] that reflects the states the nanobot already
+/// carries. Spawns that include a state marker add it before the
+/// nanobot's type observer runs, so the snapshot catches them.
+pub fn initialize_behaviour(mut nanobot: EntityWorldMut) {
+    if nanobot.contains::<Behaviour>() {
+        return;
+    }
+    let behaviour = nanobot
+        .world()
+        .get_resource::<BehaviourStates>()
+        .map(|states| Behaviour::from_present(states, |id| nanobot.contains_id(id)))
+        .unwrap_or_default();
+    nanobot.insert(behaviour);
+}
+
+/// Leave every state of `role` at once.
+pub fn exit_role(role: BehaviourRole) -> impl FnOnce(EntityWorldMut) + Send + 'static {
+    move |mut nanobot: EntityWorldMut| {
+        let ids = nanobot
+            .world()
+            .get_resource::<BehaviourStates>()
+            .map(|states| states.components(role).collect::<Vec<_>>())
+            .unwrap_or_default();
+        for id in ids {
+            nanobot.remove_by_id(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Enter / exit bookkeeping, role teardown, and hook dispatch on a
+    //! bare app. The chains' own states are exercised end to end by
+    //! the behavior suite.
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Walking;
+
+    #[derive(Component)]
+    struct Working;
+
+    #[derive(Component)]
+    struct Guarding;
+
+    #[derive(Component)]
+    struct Entered;
+
+    impl BehaviourState for Walking {
+        const ROLE: BehaviourRole = BehaviourRole::GATHER;
+    }
+
+    impl BehaviourState for Working {
+        const ROLE: BehaviourRole = BehaviourRole::GATHER;
+
+        fn on_enter(nanobot: &mut EntityCommands) {
+            nanobot.insert(Entered);
+        }
+
+        fn on_exit(nanobot: &mut EntityCommands) {
+            nanobot.try_remove::<Entered>();
+        }
+    }
+
+    impl BehaviourState for Guarding {
+        const ROLE: BehaviourRole = BehaviourRole::DEFEND;
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_behaviour_state::<Walking>()
+            .add_behaviour_state::<Working>()
+            .add_behaviour_state::<Guarding>()
+            .add_behaviour_state::<Walking>();
+        app
+    }
+
+    fn behaviour(app: &App, entity: Entity) -> Behaviour {
+        *app.world().get::<Behaviour>(entity).unwrap()
+    }
+
+    #[test]
+    fn role_stays_active_until_its_last_state_exits() {
+        let mut app = app();
+        let bot = app.world_mut().spawn(Behaviour::default()).id();
+        assert_eq!(app.world().resource::<BehaviourStates>().states.len(), 3);
+
+        app.world_mut().entity_mut(bot).insert((Walking, Working));
+        assert!(behaviour(&app, bot).is_active(BehaviourRole::GATHER));
+        assert!(app.world().get::<Entered>(bot).is_some(), "enter hook ran");
+
+        app.world_mut().entity_mut(bot).remove::<Walking>();
+        assert!(behaviour(&app, bot).is_active(BehaviourRole::GATHER));
+        app.world_mut().entity_mut(bot).remove::<Working>();
+        assert!(!behaviour(&app, bot).is_active(BehaviourRole::GATHER));
+        assert!(behaviour(&app, bot).is_idle());
+        assert!(app.world().get::<Entered>(bot).is_none(), "exit hook ran");
+    }
+
+    #[test]
+    fn exit_role_removes_only_that_roles_states() {
+        let mut app = app();
+        let bot = app
+            .world_mut()
+            .spawn((Behaviour::default(), Walking, Guarding))
+            .id();
+
+        exit_role(BehaviourRole::GATHER)(app.world_mut().entity_mut(bot));
+
+        let world = app.world();
+        assert!(world.get::<Walking>(bot).is_none());
+        assert!(world.get::<Guarding>(bot).is_some());
+        assert!(behaviour(&app, bot).is_active(BehaviourRole::DEFEND));
+        assert!(!behaviour(&app, bot).is_active(BehaviourRole::GATHER));
+    }
+
+    #[test]
+    fn late_behaviour_snapshots_states_already_present() {
+        let mut app = app();
+        let bot = app.world_mut().spawn((Walking, Guarding)).id();
+
+        initialize_behaviour(app.world_mut().entity_mut(bot));
+
+        let snapshot = behaviour(&app, bot);
+        assert!(snapshot.is_active(BehaviourRole::GATHER));
+        assert!(snapshot.is_active(BehaviourRole::DEFEND));
+        app.world_mut().entity_mut(bot).remove::<Walking>();
+        assert!(!behaviour(&app, bot).is_active(BehaviourRole::GATHER));
+    }
+
+    #[test]
+    fn despawn_runs_exit_hooks_without_panicking() {
+        let mut app = app();
+        let bot = app.world_mut().spawn((Behaviour::default(), Working)).id();
+        app.world_mut().entity_mut(bot).despawn();
+        app.world_mut().flush();
+        assert!(app.world().get_entity(bot).is_err());
+    }
+}
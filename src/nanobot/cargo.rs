@@ -3,7 +3,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    nanobot::{Health, Nanobot, SwarmMember},
+    nanobot::{
+        Health, Nanobot, PRODUCTION_COST_PER_BOT, Salvage, SwarmMember, WRECKAGE_KIND,
+        merge_wreckage,
+    },
     resources::{ResourceKind, ResourceLedger},
 };
 
@@ -55,19 +58,42 @@ impl LogisticsReservation {
 /// All gameplay nanobot death paths set `Health.current` to zero and let this
 /// system release reservations, remove exact remaining cargo from its swarm,
 /// then despawn. Transfers and invalid cancellations never use this path.
+/// With [`Salvage`] present the cargo and the salvage share of the bot's
+/// production cost are left behind as a wreck, merged into any wreck
+/// already lying close by.
 #[allow(clippy::type_complexity)]
 pub fn nanobot_death_cleanup_system(
     mut commands: Commands,
-    dead: Query<(Entity, &Health, &SwarmMember, Option<&Cargo>), (With<Nanobot>, Changed<Health>)>,
+    dead: Query<
+        (
+            Entity,
+            &Health,
+            &SwarmMember,
+            Option<&Cargo>,
+            Option<&Transform>,
+        ),
+        (With<Nanobot>, Changed<Health>),
+    >,
     mut ledger: ResMut<ResourceLedger>,
+    salvage: Option<Res<Salvage>>,
 ) {
-    for (entity, health, swarm, cargo) in &dead {
+    for (entity, health, swarm, cargo, transform) in &dead {
         if health.current > 0 {
             continue;
         }
         if let Some(cargo) = cargo {
             ledger.remove_for(swarm.0, cargo.kind, cargo.amount);
         }
+        if let (Some(salvage), Some(transform)) = (salvage.as_deref(), transform) {
+            let carried = cargo
+                .filter(|cargo| cargo.kind == WRECKAGE_KIND)
+                .map_or(0, |cargo| cargo.amount);
+            let amount = salvage.wreckage(carried, PRODUCTION_COST_PER_BOT);
+            let pos = transform.translation.truncate();
+            commands.queue(move |world: &mut World| {
+                merge_wreckage(world, pos, amount);
+            });
+        }
         commands
             .entity(entity)
             .remove::<LogisticsReservation>()
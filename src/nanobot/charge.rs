@@ -17,7 +17,10 @@
 //!     -> (charge low + working charger available)
 //!     -> Moving (ChargerAssignment + DMC, DefendHold removed)
 //!   Moving
-//!     -> (arrive at charger)
+//!     -> (arrive at charger, every slot taken)
+//!     -> Waiting (ChargerAssignment + ChargerWaiting)
+//!   Moving / Waiting
+//!     -> (arrive at charger, slot free)
 //!     -> Charging (ChargerAssignment + ChargerProgress)
 //!   Charging
 //!     -> (charge full OR charger empty)
@@ -31,6 +34,14 @@
 //! a working charger reachable: the defender leaves hold,
 //! walks to the charger, and starts charging.
 //!
+//! Each charger has [`MAX_DEFENDERS_PER_CHARGER`] charging
+//! slots. A `ChargerAssignment` is the defender's reservation:
+//! the rotation system weighs nearby chargers by predicted
+//! wait (travel time against the sessions already reserved
+//! ahead) rather than distance alone, and defenders that find
+//! every slot taken queue in arrival order. [`ChargerQueue`]
+//! summarises each charger for the structure overlay.
+//!
 //! Soft work slot occupancy is reused: a defender holding a
 //! Defend cell occupies `(cell, Defend)`. The rotation system
 //! releases the slot when the defender leaves hold. A separate
@@ -47,21 +58,31 @@
 //! stays charged. A defended cell with no haulers reaching it
 //! gradually loses charger material and the defenders degrade.
 
+use std::collections::BTreeMap;
+
 use bevy::prelude::*;
 
+use crate::game_settings::GameSettings;
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::allocation::RegionalLease;
 use crate::nanobot::autonomy::NanobotType;
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState};
 use crate::nanobot::components::{
     DirectMovementComponent, Health, Nanobot, Swarm, SwarmId, SwarmMember,
 };
 use crate::nanobot::defend::{DefendAssignment, DefendHold};
+use crate::nanobot::haul::HAULER_CARRY_CAPACITY;
 use crate::nanobot::maintenance::SupportCondition;
 use crate::nanobot::placement::{
     BUILDING_FOOTPRINT_RADIUS, find_defend_zone_placement, scaled_building_footprint_radius,
 };
 use crate::nanobot::planned::{PlannedKind, PlannedStructure, planned_visual_components};
 use crate::nanobot::production::{OwnerSwarm, ProductionFacility};
+use crate::nanobot::reclaim::PlannedReclaim;
+use crate::nanobot::spatial_index::{SpatialIndex, SpatialLayer, SwarmFilter};
+use crate::nanobot::steering::NanobotSteering;
+use crate::nanobot::structure_tier::PlannedUpgrade;
+use crate::nanobot::telemetry::SwarmTelemetry;
 use crate::resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile};
 use crate::structure_sprites::StructureSprites;
 
@@ -97,6 +118,13 @@ pub const CHARGE_DRAIN_PER_TICK: f32 = 0.005;
 /// defender recovers faster.
 pub const CHARGE_REFILL_PER_TICK: f32 = 0.05;
 
+/// Charge refilled per tick at a charger of structure `tier`. Each tier
+/// adds one base refill step, so an upgraded charger turns defenders
+/// around faster for the same per-tick material drain.
+pub fn charger_refill_per_tick(tier: u32) -> f32 {
+    CHARGE_REFILL_PER_TICK * (1 + tier) as f32
+}
+
 /// Charge per tick of `ResourceKind::Minerals` drained from a
 /// charger's `amount` while a defender is charging from it.
 /// The 1:1 ratio keeps the math obvious in the tests: a
@@ -156,9 +184,17 @@ pub const AUTO_CHARGER_INITIAL_AMOUNT: u32 = 0;
 /// single charger at once before a new charger is allowed to
 /// emerge. The "busyness" half of the issue's charger
 /// auto-creation contract: a cell whose existing charger is
-/// already at this cap spawns an additional charger.
+/// already at this cap spawns an additional charger. It is
+/// also the charger's slot count: arrivals beyond it wait in
+/// the charger's queue until a slot frees up.
 pub const MAX_DEFENDERS_PER_CHARGER: u32 = 3;
 
+/// Nearest working chargers the rotation system weighs
+/// against each other by predicted wait. Small, so a busy
+/// charger sheds defenders to a neighbour without sending
+/// them across the map.
+pub const CHARGER_CANDIDATES: usize = 4;
+
 /// Maximum chargers a single Defend cell can hold. The
 /// emergence rule spawns chargers to satisfy
 /// `ceil(load / MAX_DEFENDERS_PER_CHARGER)` up to this cap,
@@ -306,6 +342,44 @@ pub struct ChargerProgress {
     pub charger: Entity,
 }
 
+impl BehaviourState for ChargerAssignment {
+    const ROLE: BehaviourRole = BehaviourRole::CHARGE;
+
+    /// A defender leaves its hold the moment it commits to a
+    /// charger; the Defend lease stays suspended, not released.
+    fn on_enter(nanobot: &mut EntityCommands) {
+        nanobot.remove::<DefendHold>();
+    }
+}
+
+/// Marks a Defender that has reached its assigned charger
+/// while every slot is taken. `position` is its place in the
+/// charger's queue, `0` being next in; the arrive system
+/// admits waiting defenders in that order as slots free up.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct ChargerWaiting {
+    pub position: u32,
+}
+
+/// Per-charger occupancy, refreshed every tick by
+/// [`charger_queue_system`]. `reserved` counts every defender
+/// assigned to the charger, whether walking, waiting, or
+/// charging; the structure overlay draws `waiting`.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChargerQueue {
+    pub reserved: u32,
+    pub charging: u32,
+    pub waiting: u32,
+}
+
+impl BehaviourState for ChargerProgress {
+    const ROLE: BehaviourRole = BehaviourRole::CHARGE;
+}
+
+impl BehaviourState for ChargerWaiting {
+    const ROLE: BehaviourRole = BehaviourRole::CHARGE;
+}
+
 // ---------------------------------------------------------------------------
 // Pure helpers
 // ---------------------------------------------------------------------------
@@ -325,6 +399,41 @@ pub fn minerals_to_fully_charge(current: f32, max: f32) -> u32 {
     refill_ticks.saturating_mul(CHARGER_MATERIAL_DRAIN_PER_TICK)
 }
 
+/// Ticks one empty defender spends charging at a charger that
+/// refills `refill_per_tick`, net of the passive drain. The
+/// queue estimate treats every reservation as a full session.
+pub fn charge_session_ticks(refill_per_tick: f32) -> u32 {
+    let net_refill = refill_per_tick - CHARGE_DRAIN_PER_TICK;
+    debug_assert!(net_refill > 0.0);
+    (MAX_CHARGE / net_refill).ceil() as u32
+}
+
+/// Ticks until a defender `travel_ticks` away starts charging
+/// at a charger with `reserved` defenders already assigned.
+/// Those defenders fill [`MAX_DEFENDERS_PER_CHARGER`] slots a
+/// session at a time; the newcomer starts once it has walked
+/// there and the sessions ahead of it have finished.
+pub fn predicted_charge_start_ticks(travel_ticks: f32, reserved: u32, session_ticks: u32) -> f32 {
+    let sessions_ahead = reserved / MAX_DEFENDERS_PER_CHARGER;
+    travel_ticks.max((sessions_ahead * session_ticks) as f32)
+}
+
+/// Logistics urgency tier for a defender waiting on minerals,
+/// lowest first: `0` when empty or wounded, `1` when weakened,
+/// `2` when low, and `4` (the ordinary terminal tier) otherwise.
+/// Charger deliveries and field resupply rank on the same tiers.
+pub fn charge_urgency(charge: &Charge, health: Option<&Health>) -> u8 {
+    if charge.is_empty() || health.is_some_and(|health| health.current < health.max) {
+        0
+    } else if charge.current < WEAKENED_CHARGE_THRESHOLD {
+        1
+    } else if charge.needs_rotation() {
+        2
+    } else {
+        4
+    }
+}
+
 /// Linear multiplier in `[0, 1]` derived from `charge`. A
 /// defender at full charge has a `1.0` multiplier; a defender
 /// at or above [`WEAKENED_CHARGE_THRESHOLD`] is treated as
@@ -481,7 +590,11 @@ pub fn charger_auto_creation_system(
     chargers: Query<(Entity, &Charger, &Transform, Option<&OwnerSwarm>)>,
     planned_chargers: Query<
         (&PlannedStructure, &Transform, Option<&OwnerSwarm>),
-        With<PlannedStructure>,
+        (
+            With<PlannedStructure>,
+            Without<PlannedUpgrade>,
+            Without<PlannedReclaim>,
+        ),
     >,
     structure_obstacles: Query<&Transform, Or<(With<Stockpile>, With<ProductionFacility>)>>,
     deposits: Query<(&ResourceDeposit, &Transform)>,
@@ -593,12 +706,24 @@ pub fn charger_auto_creation_system(
     }
 }
 
-/// Find the nearest supplied charger owned by `swarm`. Unowned chargers retain
-/// the legacy player ownership used by older fixtures.
-#[allow(clippy::type_complexity)]
-pub fn find_nearest_working_charger(
+/// Pick the supplied charger owned by `swarm` where a defender
+/// at `pos` would start charging soonest. Unowned chargers
+/// retain the legacy player ownership used by older fixtures.
+///
+/// The [`CHARGER_CANDIDATES`] nearest working chargers come
+/// from the shared [`SpatialIndex`]; each is scored by
+/// [`predicted_charge_start_ticks`] with `reserved` holding
+/// the defenders already assigned to it, so a full charger
+/// sheds newcomers to a neighbour that would free up sooner.
+/// Travel time uses `bot_speed`, the defender's own top speed.
+/// Ties go to the nearer charger.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn find_working_charger(
     pos: Vec2,
     swarm: SwarmId,
+    bot_speed: f32,
+    reserved: &BTreeMap<Entity, u32>,
+    index: &SpatialIndex,
     chargers: &Query<(
         Entity,
         &Charger,
@@ -608,29 +733,52 @@ pub fn find_nearest_working_charger(
     )>,
     swarms: &Query<&SwarmId, With<Swarm>>,
 ) -> Option<(Entity, Vec2)> {
-    let mut best: Option<(f32, Entity, Vec2)> = None;
-    for (entity, charger, transform, owner, condition) in chargers.iter() {
-        let charger_swarm = owner
-            .and_then(|owner| swarms.get(owner.0).ok())
-            .copied()
-            .unwrap_or(SwarmId::PLAYER);
-        if charger_swarm != swarm
-            || !charger.has_supply()
-            || condition.is_some_and(|condition| !condition.is_operational())
-        {
-            continue;
-        }
-        let position = transform.translation.truncate();
-        let distance = pos.distance(position);
-        if best.is_none_or(|(best_distance, _, _)| distance < best_distance) {
-            best = Some((distance, entity, position));
-        }
-    }
-    best.map(|(_, entity, position)| (entity, position))
+    let candidates = index.nearest_k(
+        pos,
+        CHARGER_CANDIDATES,
+        SpatialLayer::Structure,
+        SwarmFilter::Any,
+        |entry| {
+            chargers
+                .get(entry.entity)
+                .is_ok_and(|(_, charger, _, owner, condition)| {
+                    let charger_swarm = owner
+                        .and_then(|owner| swarms.get(owner.0).ok())
+                        .copied()
+                        .unwrap_or(SwarmId::PLAYER);
+                    charger_swarm == swarm
+                        && charger.has_supply()
+                        && condition.is_none_or(|condition| condition.is_operational())
+                })
+        },
+    );
+    candidates
+        .into_iter()
+        .map(|entry| {
+            let distance = pos.distance(entry.position);
+            let tier = chargers
+                .get(entry.entity)
+                .ok()
+                .and_then(|(_, _, _, _, condition)| condition)
+                .map_or(0, |condition| condition.tier);
+            let start = predicted_charge_start_ticks(
+                distance / bot_speed.max(f32::EPSILON),
+                reserved.get(&entry.entity).copied().unwrap_or_default(),
+                charge_session_ticks(charger_refill_per_tick(tier)),
+            );
+            (start, distance, entry)
+        })
+        .min_by(|(a_start, a_distance, a), (b_start, b_distance, b)| {
+            a_start
+                .total_cmp(b_start)
+                .then(a_distance.total_cmp(b_distance))
+                .then(a.entity.cmp(&b.entity))
+        })
+        .map(|(_, _, entry)| (entry.entity, entry.position))
 }
 
 /// For every holding defender whose charge is low, walk to
-/// the nearest working charger. The system releases the
+/// the working charger picked by [`find_working_charger`]. The system releases the
 /// `(cell, Defend)` soft work slot and the `DefendHold`
 /// marker, then inserts a `ChargerAssignment` and a
 /// `DirectMovementComponent` aimed at the charger. A holding
@@ -672,8 +820,18 @@ pub fn defender_rotation_to_charger_system(
         Option<&OwnerSwarm>,
         Option<&SupportCondition>,
     )>,
+    assigned: Query<&ChargerAssignment>,
     swarms: Query<&SwarmId, With<Swarm>>,
+    index: Res<SpatialIndex>,
+    settings: Res<GameSettings>,
+    steering: Option<Res<NanobotSteering>>,
 ) {
+    // Reservations taken this tick count toward the next
+    // defender's pick, so a batch rotating together spreads out.
+    let mut reserved = BTreeMap::<Entity, u32>::new();
+    for assignment in &assigned {
+        *reserved.entry(assignment.charger).or_default() += 1;
+    }
     for (entity, _hold, transform, charge, nanobot_type, member, lease) in &mut defenders {
         if *nanobot_type != NanobotType::Defender {
             continue;
@@ -682,17 +840,22 @@ pub fn defender_rotation_to_charger_system(
             continue;
         }
         let pos = transform.translation.truncate();
+        // Same speed the move system gives this defender.
+        let speed = steering.as_deref().map_or(settings.bot_speed, |steering| {
+            steering.top_speed(*nanobot_type, 0, HAULER_CARRY_CAPACITY)
+        });
         let Some((charger_entity, charger_pos)) =
-            find_nearest_working_charger(pos, member.0, &chargers, &swarms)
+            find_working_charger(pos, member.0, speed, &reserved, &index, &chargers, &swarms)
         else {
             continue;
         };
+        *reserved.entry(charger_entity).or_default() += 1;
         // Issue #38 / ADR-0004: stop on the
         // charger's physical edge so the defender
         // lands at the charger centre, matching the
         // arrive guard's `charger.radius` check.
         // The lookup here is a defensive second pass
-        // after `find_nearest_working_charger` so
+        // after `find_working_charger` so
         // the DMC carries the same extent the
         // arrive guard reads.
         let charger_radius = chargers
@@ -702,7 +865,6 @@ pub fn defender_rotation_to_charger_system(
         if let Some(mut lease) = lease {
             lease.suspend_for_charge();
         }
-        commands.entity(entity).remove::<DefendHold>();
         commands.entity(entity).insert((
             ChargerAssignment {
                 charger: charger_entity,
@@ -715,12 +877,19 @@ pub fn defender_rotation_to_charger_system(
     }
 }
 
-/// Detect a defender that has arrived at its assigned charger
-/// and transition it into the `ChargerProgress` state. The
-/// arrival trigger is the same as the rest of the
-/// simulation: the movement system removes the
-/// `DirectMovementComponent` when the bot is within
-/// [`STOP_THRESHOLD`] of its target.
+/// Detect defenders that have arrived at their assigned
+/// charger and admit them into the `ChargerProgress` state
+/// while the charger has a free slot. The arrival trigger is
+/// the same as the rest of the simulation: the movement system
+/// removes the `DirectMovementComponent` when the bot is
+/// within [`STOP_THRESHOLD`] of its target.
+///
+/// A charger admits at most [`MAX_DEFENDERS_PER_CHARGER`]
+/// defenders at once. Arrivals beyond that wait in hold with a
+/// `ChargerWaiting` marker whose position is renumbered every
+/// tick; the queue drains in position order, new arrivals
+/// joining at the back. An arrived defender that has been
+/// pushed outside the radius is sent back in.
 ///
 /// The `Without<ChargerProgress>` filter makes arrival
 /// idempotent. The `ChargerAssignment` is kept on the entity
@@ -730,7 +899,12 @@ pub fn defender_rotation_to_charger_system(
 pub fn defender_charger_arrive_system(
     mut commands: Commands,
     defenders: Query<
-        (Entity, &ChargerAssignment, &Transform),
+        (
+            Entity,
+            &ChargerAssignment,
+            &Transform,
+            Option<&ChargerWaiting>,
+        ),
         (
             With<Nanobot>,
             With<ChargerAssignment>,
@@ -738,25 +912,61 @@ pub fn defender_charger_arrive_system(
             Without<ChargerProgress>,
         ),
     >,
+    charging: Query<&ChargerAssignment, With<ChargerProgress>>,
     chargers: Query<(&Charger, &Transform, Option<&SupportCondition>)>,
 ) {
-    for (entity, assignment, transform) in &defenders {
+    let mut occupied = BTreeMap::<Entity, u32>::new();
+    for assignment in &charging {
+        *occupied.entry(assignment.charger).or_default() += 1;
+    }
+    let mut arrived = BTreeMap::<Entity, Vec<(u32, Entity, Option<u32>)>>::new();
+    for (entity, assignment, transform, waiting) in &defenders {
         let Ok((charger, charger_transform, condition)) = chargers.get(assignment.charger) else {
-            commands.entity(entity).remove::<ChargerAssignment>();
+            commands
+                .entity(entity)
+                .remove::<(ChargerAssignment, ChargerWaiting)>();
             continue;
         };
         if condition.is_some_and(|condition| !condition.is_operational()) {
-            commands.entity(entity).remove::<ChargerAssignment>();
+            commands
+                .entity(entity)
+                .remove::<(ChargerAssignment, ChargerWaiting)>();
             continue;
         }
-        let distance = transform
-            .translation
-            .truncate()
-            .distance(charger_transform.translation.truncate());
-        if distance <= charger.radius {
-            commands.entity(entity).insert(ChargerProgress {
-                charger: assignment.charger,
+        let charger_pos = charger_transform.translation.truncate();
+        if transform.translation.truncate().distance(charger_pos) > charger.radius {
+            commands.entity(entity).insert(DirectMovementComponent {
+                xy: charger_pos,
+                stop_radius: charger.radius,
             });
+            continue;
+        }
+        let position = waiting.map(|waiting| waiting.position);
+        arrived.entry(assignment.charger).or_default().push((
+            position.unwrap_or(u32::MAX),
+            entity,
+            position,
+        ));
+    }
+    for (charger, mut queue) in arrived {
+        queue.sort_by_key(|(order, entity, _)| (*order, *entity));
+        let free = MAX_DEFENDERS_PER_CHARGER
+            .saturating_sub(occupied.get(&charger).copied().unwrap_or_default())
+            as usize;
+        for (index, (_, entity, position)) in queue.into_iter().enumerate() {
+            if index < free {
+                commands
+                    .entity(entity)
+                    .insert(ChargerProgress { charger })
+                    .remove::<ChargerWaiting>();
+                continue;
+            }
+            let queued = (index - free) as u32;
+            if position != Some(queued) {
+                commands
+                    .entity(entity)
+                    .insert(ChargerWaiting { position: queued });
+            }
         }
     }
 }
@@ -791,6 +1001,7 @@ pub fn defender_charger_work_system(
     mut chargers: Query<(&mut Charger, Option<&OwnerSwarm>, Option<&SupportCondition>)>,
     swarms: Query<&SwarmId, With<Swarm>>,
     mut ledger: ResMut<ResourceLedger>,
+    mut telemetry: Option<ResMut<SwarmTelemetry>>,
 ) {
     for (entity, mut charge, assignment, member, lease) in &mut defenders {
         let Ok((mut charger, owner, condition)) = chargers.get_mut(assignment.charger) else {
@@ -848,10 +1059,14 @@ pub fn defender_charger_work_system(
         // The drain is per-tick and per-defender; multiple
         // defenders at the same charger each drain one unit
         // per tick.
-        charge.current = (charge.current + CHARGE_REFILL_PER_TICK).min(charge.max);
+        let refill = charger_refill_per_tick(condition.map_or(0, |condition| condition.tier));
+        charge.current = (charge.current + refill).min(charge.max);
         let consumed = CHARGER_MATERIAL_DRAIN_PER_TICK.min(charger.amount);
         charger.amount -= consumed;
         ledger.remove_for(charger_swarm, charger.kind, consumed);
+        if let Some(telemetry) = telemetry.as_deref_mut() {
+            telemetry.counters_mut(charger_swarm).minerals_to_chargers += consumed;
+        }
         if charge.is_full() {
             // Refill brought the charge to max. Release
             // immediately so the defender returns to the
@@ -865,6 +1080,33 @@ pub fn defender_charger_work_system(
     }
 }
 
+/// Refresh every charger's [`ChargerQueue`] from the
+/// defenders assigned to it. Runs last in the consumer chain
+/// so the counts reflect this tick's admissions and releases.
+pub fn charger_queue_system(
+    mut commands: Commands,
+    chargers: Query<(Entity, Option<&ChargerQueue>), With<Charger>>,
+    defenders: Query<(
+        &ChargerAssignment,
+        Has<ChargerProgress>,
+        Has<ChargerWaiting>,
+    )>,
+) {
+    let mut queues = BTreeMap::<Entity, ChargerQueue>::new();
+    for (assignment, charging, waiting) in &defenders {
+        let queue = queues.entry(assignment.charger).or_default();
+        queue.reserved += 1;
+        queue.charging += u32::from(charging);
+        queue.waiting += u32::from(waiting);
+    }
+    for (entity, current) in &chargers {
+        let queue = queues.get(&entity).copied().unwrap_or_default();
+        if current != Some(&queue) {
+            commands.entity(entity).insert(queue);
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Plugin
 // ---------------------------------------------------------------------------
@@ -901,14 +1143,20 @@ pub fn defender_charger_work_system(
 ///    that have not been picked up by the rotation chain.
 /// 3. [`defender_rotation_to_charger_system`] -- rotate
 ///    low-charge holding defenders to working chargers.
-/// 4. [`defender_charger_arrive_system`] -- transition
-///    arrived defenders into the charging state.
+/// 4. [`defender_charger_arrive_system`] -- admit arrived
+///    defenders into free charging slots and queue the rest.
 /// 5. [`defender_charger_work_system`] -- refill charge and
 ///    drain charger material.
+/// 6. [`charger_queue_system`] -- refresh each charger's
+///    [`ChargerQueue`] counts.
 pub struct ChargePlugin;
 
 impl Plugin for ChargePlugin {
     fn build(&self, app: &mut App) {
+        crate::nanobot::add_spatial_index(app);
+        app.add_behaviour_state::<ChargerAssignment>()
+            .add_behaviour_state::<ChargerProgress>()
+            .add_behaviour_state::<ChargerWaiting>();
         // Demand: spawn planned chargers from current load
         // before the planned-structure claim system runs so
         // the claim system can pick up a freshly planned
@@ -924,18 +1172,21 @@ impl Plugin for ChargePlugin {
                 .after(crate::nanobot::defend::defender_hold_system),
         );
         // Consumer: drain, health-loss, rotation, arrive,
-        // work. The chain runs after the planned-structure
-        // work system so a freshly promoted charger is
-        // visible to the rotation system's "find nearest
-        // working charger" scan in the same tick.
+        // work, queue. The chain runs after the planned-structure
+        // work system and refreshes the spatial index first,
+        // so a freshly promoted charger is visible to the
+        // rotation system's "find nearest working charger"
+        // lookup in the same tick.
         app.add_systems(
             FixedUpdate,
             (
                 defender_charge_drain_system,
                 defender_health_loss_when_empty_system,
+                crate::nanobot::spatial_index_update_system,
                 defender_rotation_to_charger_system,
                 defender_charger_arrive_system,
                 defender_charger_work_system,
+                charger_queue_system,
             )
                 .chain()
                 .after(crate::nanobot::NanobotSimulationSet::Movement)
@@ -1017,6 +1268,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn upgraded_chargers_refill_faster() {
+        assert_eq!(charger_refill_per_tick(0), CHARGE_REFILL_PER_TICK);
+        assert!(charger_refill_per_tick(1) > charger_refill_per_tick(0));
+        assert!(charger_refill_per_tick(2) > charger_refill_per_tick(1));
+    }
+
     #[test]
     fn charge_mineral_need_clamps_empty_and_out_of_range_charge() {
         let net_refill = CHARGE_REFILL_PER_TICK - CHARGE_DRAIN_PER_TICK;
@@ -1183,4 +1441,34 @@ mod tests {
         const { assert!(MAX_CHARGERS_PER_CELL >= 1) };
         const { assert!(MAX_DEFENDERS_PER_CHARGER >= 1) };
     }
+
+    #[test]
+    fn charge_session_shortens_with_charger_tier() {
+        assert_eq!(charge_session_ticks(charger_refill_per_tick(0)), 23);
+        assert_eq!(charge_session_ticks(charger_refill_per_tick(1)), 11);
+    }
+
+    #[test]
+    fn predicted_start_waits_out_full_sessions_ahead() {
+        let session = 20;
+        // Free slots: only the walk counts.
+        assert_eq!(predicted_charge_start_ticks(5.0, 0, session), 5.0);
+        assert_eq!(
+            predicted_charge_start_ticks(5.0, MAX_DEFENDERS_PER_CHARGER - 1, session),
+            5.0
+        );
+        // Every slot reserved: one session ahead, overlapping the walk.
+        assert_eq!(
+            predicted_charge_start_ticks(5.0, MAX_DEFENDERS_PER_CHARGER, session),
+            20.0
+        );
+        assert_eq!(
+            predicted_charge_start_ticks(30.0, MAX_DEFENDERS_PER_CHARGER, session),
+            30.0
+        );
+        assert_eq!(
+            predicted_charge_start_ticks(5.0, MAX_DEFENDERS_PER_CHARGER * 2, session),
+            40.0
+        );
+    }
 }
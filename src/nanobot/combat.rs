@@ -1,15 +1,15 @@
 //! Deterministic Defender combat and Defend-cell threat pressure.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bevy::prelude::*;
 
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::{
-    Charge, DefendHold, DefendPressure, Health, Nanobot, NanobotType, OwnerSwarm, Structure, Swarm,
-    SwarmId, SwarmMember, effective_attack, effective_defense, world_to_cell,
+    Charge, DefendHold, DefendPressure, Health, Nanobot, NanobotType, OwnerSwarm, RecentDamage,
+    ResearchState, SpatialIndex, SpatialLayer, Structure, SwarmFilter, SwarmId, SwarmMember,
+    collapse_structure, effective_attack, effective_defense, world_to_cell,
 };
-use crate::spatial::FixedSpatialBuckets;
 
 /// Defender attack reach in world units.
 pub const DEFENDER_ATTACK_RANGE: f32 = 96.0;
@@ -17,20 +17,12 @@ pub const DEFENDER_ATTACK_RANGE: f32 = 96.0;
 #[derive(Clone, Copy)]
 struct Combatant {
     entity: Entity,
-    position: Vec2,
     swarm: SwarmId,
     kind: NanobotType,
     charge: Option<f32>,
     holding: bool,
 }
 
-#[derive(Clone, Copy)]
-struct StructureTarget {
-    entity: Entity,
-    position: Vec2,
-    swarm: SwarmId,
-}
-
 fn damage_after_defense(attack: f32, defense: f32) -> u32 {
     if attack <= 0.0 {
         return 0;
@@ -41,19 +33,19 @@ fn damage_after_defense(attack: f32, defense: f32) -> u32 {
 /// Rebuild pressure from hostile nanobots physically occupying owned Defend cells.
 pub fn defend_threat_pressure_system(
     grid: Res<IntentGrid>,
-    nanobots: Query<(&Transform, &SwarmMember), With<Nanobot>>,
+    index: Res<SpatialIndex>,
     mut pressure: ResMut<DefendPressure>,
 ) {
     let mut hostile_counts = HashMap::<(SwarmId, IVec2), u32>::new();
-    for (transform, member) in &nanobots {
-        let cell = world_to_cell(transform.translation.truncate());
+    for entry in index.entries(SpatialLayer::Nanobot) {
+        let cell = world_to_cell(entry.position);
         let Some(intent) = grid.cell(cell) else {
             continue;
         };
         let Some(owner) = intent.owner(IntentKind::Defend) else {
             continue;
         };
-        if member.0 != owner {
+        if entry.swarm != Some(owner) {
             *hostile_counts.entry((owner, cell)).or_default() += 1;
         }
     }
@@ -70,13 +62,14 @@ pub fn defend_threat_pressure_system(
 /// Resolve one simultaneous attack snapshot. Every holding Defender chooses a
 /// hostile nanobot first, then a hostile support structure; damage is applied
 /// after target selection so entity iteration order cannot change the exchange.
+/// Researched [`crate::nanobot::Upgrade::DefenderAttack`] scales the attacker's
+/// charge-adjusted attack.
 #[allow(clippy::type_complexity)]
 pub fn defender_combat_system(
     mut combatants: ParamSet<(
         Query<
             (
                 Entity,
-                &Transform,
                 &SwarmMember,
                 &NanobotType,
                 Option<&Charge>,
@@ -84,69 +77,56 @@ pub fn defender_combat_system(
             ),
             With<Nanobot>,
         >,
-        Query<(Entity, &Transform, &OwnerSwarm), With<Structure>>,
+        Query<Entity, (With<Structure>, With<OwnerSwarm>)>,
         Query<&mut Health, With<Nanobot>>,
-        Query<&mut Structure>,
+        Query<(&mut Structure, Option<&mut RecentDamage>)>,
     )>,
-    swarms: Query<&SwarmId, With<Swarm>>,
+    index: Res<SpatialIndex>,
+    research: Option<Res<ResearchState>>,
     mut commands: Commands,
 ) {
     let snapshot = combatants
         .p0()
         .iter()
-        .map(
-            |(entity, transform, member, kind, charge, hold)| Combatant {
-                entity,
-                position: transform.translation.truncate(),
-                swarm: member.0,
-                kind: *kind,
-                charge: charge.map(|charge| charge.current),
-                holding: hold.is_some(),
-            },
-        )
-        .collect::<Vec<_>>();
-    let structures = combatants
-        .p1()
-        .iter()
-        .filter_map(|(entity, transform, owner)| {
-            Some(StructureTarget {
+        .map(|(entity, member, kind, charge, hold)| {
+            (
                 entity,
-                position: transform.translation.truncate(),
-                swarm: swarms.get(owner.0).ok().copied()?,
-            })
+                Combatant {
+                    entity,
+                    swarm: member.0,
+                    kind: *kind,
+                    charge: charge.map(|charge| charge.current),
+                    holding: hold.is_some(),
+                },
+            )
         })
+        .collect::<HashMap<_, _>>();
+    let structures = combatants.p1().iter().collect::<HashSet<_>>();
+
+    let mut attackers = snapshot
+        .values()
+        .filter(|combatant| combatant.kind == NanobotType::Defender && combatant.holding)
+        .filter_map(|combatant| Some((*combatant, index.get(combatant.entity)?.position)))
         .collect::<Vec<_>>();
-    let mut nanobot_buckets = FixedSpatialBuckets::new(DEFENDER_ATTACK_RANGE);
-    for target in snapshot.iter().copied() {
-        nanobot_buckets.insert(target.position, target);
-    }
-    let mut structure_buckets = FixedSpatialBuckets::new(DEFENDER_ATTACK_RANGE);
-    for target in structures.iter().copied() {
-        structure_buckets.insert(target.position, target);
-    }
+    attackers.sort_by_key(|(attacker, _)| attacker.entity.to_bits());
 
     let mut nanobot_damage = HashMap::<Entity, u32>::new();
     let mut structure_damage = HashMap::<Entity, u32>::new();
-    for attacker in snapshot
-        .iter()
-        .filter(|combatant| combatant.kind == NanobotType::Defender && combatant.holding)
-    {
-        let attack = effective_attack(attacker.charge.unwrap_or_default());
-        let attacker_bucket = nanobot_buckets.bucket_for_position(attacker.position);
-        let nanobot_target = nanobot_buckets
-            .neighbourhood(attacker_bucket, 1)
-            .flat_map(|(_, targets)| targets)
-            .filter(|target| target.swarm != attacker.swarm)
-            .filter_map(|target| {
-                let distance = attacker.position.distance(target.position);
-                (distance <= DEFENDER_ATTACK_RANGE).then_some((distance, target))
-            })
-            .min_by(|(left_distance, left), (right_distance, right)| {
-                left_distance
-                    .total_cmp(right_distance)
-                    .then_with(|| left.entity.to_bits().cmp(&right.entity.to_bits()))
-            })
-            .map(|(_, target)| target);
+    for (attacker, position) in attackers {
+        let attack = effective_attack(attacker.charge.unwrap_or_default())
+            * research
+                .as_deref()
+                .map_or(1.0, |research| research.attack_multiplier(attacker.swarm));
+        let hostile = SwarmFilter::Except(attacker.swarm);
+        let nanobot_target = index
+            .within_radius(
+                position,
+                DEFENDER_ATTACK_RANGE,
+                SpatialLayer::Nanobot,
+                hostile,
+            )
+            .into_iter()
+            .find_map(|entry| snapshot.get(&entry.entity));
         if let Some(target) = nanobot_target {
             let defense = if target.kind == NanobotType::Defender {
                 effective_defense(target.charge.unwrap_or_default())
@@ -158,20 +138,16 @@ pub fn defender_combat_system(
             continue;
         }
 
-        let structure_target = structure_buckets
-            .neighbourhood(attacker_bucket, 1)
-            .flat_map(|(_, targets)| targets)
-            .filter(|target| target.swarm != attacker.swarm)
-            .filter_map(|target| {
-                let distance = attacker.position.distance(target.position);
-                (distance <= DEFENDER_ATTACK_RANGE).then_some((distance, target.entity))
-            })
-            .min_by(|(left_distance, left), (right_distance, right)| {
-                left_distance
-                    .total_cmp(right_distance)
-                    .then_with(|| left.to_bits().cmp(&right.to_bits()))
-            })
-            .map(|(_, entity)| entity);
+        let structure_target = index
+            .within_radius(
+                position,
+                DEFENDER_ATTACK_RANGE,
+                SpatialLayer::Structure,
+                hostile,
+            )
+            .into_iter()
+            .map(|entry| entry.entity)
+            .find(|entity| structures.contains(entity));
         if let Some(target) = structure_target {
             *structure_damage.entry(target).or_default() += damage_after_defense(attack, 0.0);
         }
@@ -187,10 +163,21 @@ pub fn defender_combat_system(
     }
     let mut conditions = combatants.p3();
     for (entity, amount) in structure_damage {
-        if let Ok(mut target) = conditions.get_mut(entity) {
+        if let Ok((mut target, recent)) = conditions.get_mut(entity) {
             target.health = target.health.saturating_sub(amount);
             if target.health == 0 {
-                commands.entity(entity).despawn();
+                commands.entity(entity).queue(collapse_structure);
+                continue;
+            }
+            // Recent damage raises repair priority and keeps Workers
+            // away while the structure is under attack.
+            match recent {
+                Some(mut recent) => recent.record_hit(amount),
+                None => {
+                    let mut recent = RecentDamage::default();
+                    recent.record_hit(amount);
+                    commands.entity(entity).insert(recent);
+                }
             }
         }
     }
@@ -200,6 +187,7 @@ pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
+        crate::nanobot::add_spatial_index(app);
         app.init_resource::<DefendPressure>()
             .add_systems(
                 FixedUpdate,
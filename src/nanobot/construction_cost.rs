@@ -0,0 +1,205 @@
+//! Optional mineral cost for Planned Structure construction.
+//!
+//! Without a [`ConstructionCost`] resource construction costs only
+//! worker time. With it, every owned new-build Planned Structure gets
+//! a [`PlannedMaterial`] stock sized by [`PlannedKind::mineral_cost`].
+//! The planned site is an inflow-only terminal: haulers fill it from
+//! Sink Stockpiles like a Production Facility's hopper, so expansion
+//! and unit production draw on the same mineral flow. Worker time
+//! only advances as far as the delivered material pays for.
+//!
+//! ```text
+//!   Planned Structure (owned, new build)
+//!     -> attach system inserts PlannedMaterial { delivered: 0 }
+//!     -> haulers deliver minerals into the planned site
+//!     -> each work tick needs its share of the cost delivered
+//!     -> short on material: the worker is released, the site waits
+//!     -> promotion spends the stock out of the owner's ledger
+//!     -> an abandoned plan drops its delivered stock as a cache
+//! ```
+//!
+//! Tier upgrades and reclaim plans reuse the planned lifecycle on an
+//! already-built structure and keep their own cost models, so they
+//! get no stock. Unowned plans keep the worker-time-only model, since
+//! no swarm's haulers serve them.
+
+use bevy::prelude::*;
+
+use crate::nanobot::OwnerSwarm;
+use crate::nanobot::planned::PlannedStructure;
+use crate::nanobot::reclaim::{PlannedReclaim, spawn_reclaimed_cache, structure_swarm};
+use crate::nanobot::structure_tier::PlannedUpgrade;
+use crate::resources::{ResourceKind, ResourceLedger};
+use crate::structure_sprites::StructureSprites;
+
+/// Material cost for new builds. While present, an owned new-build
+/// plan only finishes once haulers deliver its
+/// [`crate::nanobot::PlannedKind::mineral_cost`] in `kind`; without it
+/// building costs only worker time.
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq)]
+pub struct ConstructionCost {
+    pub kind: ResourceKind,
+}
+
+impl Default for ConstructionCost {
+    fn default() -> Self {
+        Self {
+            kind: ResourceKind::Minerals,
+        }
+    }
+}
+
+/// Material delivered to a planned site so far, against its kind's
+/// cost.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedMaterial {
+    pub kind: ResourceKind,
+    pub delivered: u32,
+    pub required: u32,
+}
+
+impl PlannedMaterial {
+    pub fn new(kind: ResourceKind, required: u32) -> Self {
+        Self {
+            kind,
+            delivered: 0,
+            required,
+        }
+    }
+
+    pub fn free_space(&self) -> u32 {
+        self.required.saturating_sub(self.delivered)
+    }
+
+    /// Work ticks out of `total` the delivered material pays for.
+    pub fn funded_work_ticks(&self, total: u32) -> u32 {
+        if self.required == 0 {
+            return total;
+        }
+        let funded = u64::from(self.delivered.min(self.required)) * u64::from(total)
+            / u64::from(self.required);
+        funded as u32
+    }
+}
+
+/// True when the next work tick on `planned` is paid for. A planned
+/// structure without a stock is exempt.
+pub fn material_allows_work(
+    material: Option<&PlannedMaterial>,
+    planned: &PlannedStructure,
+) -> bool {
+    material.is_none_or(|material| {
+        let total = planned.kind.build_work_ticks();
+        let done = total.saturating_sub(planned.work_remaining);
+        material.funded_work_ticks(total) > done
+    })
+}
+
+/// Give every owned new-build planned structure an empty material
+/// stock for its kind.
+#[allow(clippy::type_complexity)]
+pub fn attach_planned_material_system(
+    mut commands: Commands,
+    cost: Option<Res<ConstructionCost>>,
+    planned: Query<
+        (Entity, &PlannedStructure),
+        (
+            With<OwnerSwarm>,
+            Without<PlannedMaterial>,
+            Without<PlannedUpgrade>,
+            Without<PlannedReclaim>,
+        ),
+    >,
+) {
+    let Some(cost) = cost else {
+        return;
+    };
+    for (entity, planned) in &planned {
+        commands
+            .entity(entity)
+            .insert(PlannedMaterial::new(cost.kind, planned.kind.mineral_cost()));
+    }
+}
+
+/// Spend a finished site's delivered material: it leaves the owner's
+/// ledger with the stock. Queued by planned-structure promotion.
+pub fn spend_planned_material(mut entity: EntityWorldMut) {
+    let Some(mut material) = entity.get_mut::<PlannedMaterial>() else {
+        return;
+    };
+    let spent = std::mem::take(&mut material.delivered);
+    let kind = material.kind;
+    let id = entity.id();
+    entity.world_scope(|world| {
+        let swarm = structure_swarm(world, id);
+        if let Some(mut ledger) = world.get_resource_mut::<ResourceLedger>() {
+            ledger.remove_for(swarm, kind, spent);
+        }
+    });
+    entity.remove::<PlannedMaterial>();
+}
+
+/// A plan erased or despawned before completion drops whatever was
+/// delivered as a [`crate::nanobot::ReclaimedCache`] at the site. The
+/// material stays in the owner's custody and haulers bring it back.
+pub fn drop_planned_material(
+    removed: On<Remove, PlannedMaterial>,
+    mut commands: Commands,
+    sites: Query<(&PlannedMaterial, &Transform, Option<&OwnerSwarm>)>,
+    sprites: Option<Res<StructureSprites>>,
+) {
+    let Ok((material, transform, owner)) = sites.get(removed.entity) else {
+        return;
+    };
+    spawn_reclaimed_cache(
+        &mut commands,
+        transform.translation.truncate(),
+        material.kind,
+        material.delivered,
+        owner.copied(),
+        sprites.as_deref(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    //! Funding math. Delivery, gating, and promotion live in
+    //! `tests/behavior/planned_material.rs`.
+
+    use super::*;
+    use crate::nanobot::PlannedKind;
+
+    #[test]
+    fn work_is_funded_in_proportion_to_delivered_material() {
+        let mut material = PlannedMaterial::new(ResourceKind::Minerals, 10);
+        assert_eq!(material.funded_work_ticks(5), 0);
+        material.delivered = 4;
+        assert_eq!(material.funded_work_ticks(5), 2);
+        material.delivered = 10;
+        assert_eq!(material.funded_work_ticks(5), 5);
+        assert_eq!(material.free_space(), 0);
+    }
+
+    #[test]
+    fn the_final_work_tick_needs_the_full_cost() {
+        let mut planned = PlannedStructure::new(PlannedKind::Charger, IVec2::ZERO);
+        planned.work_remaining = 1;
+        let mut material = PlannedMaterial::new(ResourceKind::Minerals, 20);
+        material.delivered = 19;
+        assert!(!material_allows_work(Some(&material), &planned));
+        material.delivered = 20;
+        assert!(material_allows_work(Some(&material), &planned));
+        assert!(
+            material_allows_work(None, &planned),
+            "plans without a stock are exempt"
+        );
+    }
+
+    #[test]
+    fn every_kind_costs_less_than_a_stockpile_holds() {
+        for kind in PlannedKind::ALL {
+            assert!(kind.mineral_cost() > 0);
+            assert!(kind.mineral_cost() <= crate::nanobot::DEFAULT_STOCKPILE_CAPACITY);
+        }
+    }
+}
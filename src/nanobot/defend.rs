@@ -55,6 +55,7 @@ use bevy::prelude::*;
 use crate::ZONE_BLOCK_SIZE;
 use crate::intent::{IntentCell, IntentGrid, IntentKind};
 use crate::nanobot::autonomy::{Commitment, IntentCandidate, NanobotType, SoftWorkSlots};
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState};
 use crate::nanobot::charge::{ChargerAssignment, ChargerProgress};
 use crate::nanobot::components::{DirectMovementComponent, Nanobot, SwarmMember};
 use crate::nanobot::gather::world_to_cell;
@@ -124,7 +125,7 @@ pub fn is_enemy_territory(cell: IVec2, swarm_cell: IVec2, home_radius_cells: i32
 }
 
 /// World position of the center of `cell`. Matches
-/// `ai::get_world_from_zone` so the assignment system and the test
+/// [`crate::nanobot::gather::get_world_from_zone`] so the assignment system and the test
 /// seam agree on the center.
 fn cell_center_world(cell: IVec2) -> Vec2 {
     Vec2::new(
@@ -168,6 +169,14 @@ pub struct DefendHold {
     pub cell: IVec2,
 }
 
+impl BehaviourState for DefendAssignment {
+    const ROLE: BehaviourRole = BehaviourRole::DEFEND;
+}
+
+impl BehaviourState for DefendHold {
+    const ROLE: BehaviourRole = BehaviourRole::DEFEND;
+}
+
 /// Per-cell defend-pressure hook. Each Defend cell's score is
 /// multiplied by its pressure value (acting as the cell's need
 /// factor); cells with no explicit entry use
@@ -217,6 +226,18 @@ impl DefendPressure {
             .remove(&(crate::nanobot::components::SwarmId::PLAYER, cell));
     }
 
+    /// Explicit entries for `swarm`, in no particular order. Cells
+    /// at baseline are not listed.
+    pub fn iter_for(
+        &self,
+        swarm: crate::nanobot::components::SwarmId,
+    ) -> impl Iterator<Item = (IVec2, f32)> + '_ {
+        self.map
+            .iter()
+            .filter(move |((owner, _), _)| *owner == swarm)
+            .map(|((_, cell), value)| (*cell, *value))
+    }
+
     /// Reset all explicit pressure before rebuilding the current threat snapshot.
     pub fn clear(&mut self) {
         self.map.clear();
@@ -667,6 +688,8 @@ impl Plugin for DefendPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CellDensity>();
         app.init_resource::<DefendPressure>();
+        app.add_behaviour_state::<DefendAssignment>()
+            .add_behaviour_state::<DefendHold>();
         app.add_systems(
             FixedUpdate,
             (
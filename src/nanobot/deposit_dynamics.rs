@@ -0,0 +1,581 @@
+//! Deposit regeneration and map-wide resource dynamics.
+//!
+//! A plain [`ResourceDeposit`] still drains to zero and stays there. Two
+//! opt-in mechanisms change the map over time:
+//!
+//!   - [`DepositRegeneration`] attaches a [`RegenerationCurve`] to a single
+//!     deposit. Every fixed tick the curve adds material back toward the
+//!     deposit's `capacity`; a full deposit does not grow.
+//!   - [`DepositDynamics`] carries scenario-authored spawns: scripted
+//!     deposits that appear at a fixed tick and seeded random deposits
+//!     that appear on an interval inside a cell rectangle.
+//!
+//! Both are configured through [`DepositDynamicsConfig`], which the game
+//! loads from `config/deposit_dynamics.ron` next to the game settings.
+//! A spawn never lands on a cell that already holds a deposit, a
+//! structure or a planned site, nor outside the intent grid: a blocked
+//! scripted spawn is skipped and a random spawn retries a few cells.
+//!
+//! Depletion is reported by the gather chain, not here: the
+//! [`DepositDepleted`] / [`DepositReplenished`] messages are written by
+//! [`deposit_depletion_events_system`] whenever a deposit crosses zero in
+//! either direction, regardless of whether extraction, regeneration, or a
+//! test changed the amount. Regrowth only writes to `ResourceDeposit` when
+//! at least one whole unit is added, so a slow curve does not invalidate
+//! the allocation projection every tick.
+
+use std::collections::HashSet;
+
+use bevy::{math::vec3, prelude::*};
+use rand::{RngExt, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GAMEPLAY_SPRITE_Z,
+    building::Minerals,
+    intent::IntentGrid,
+    nanobot::{
+        get_world_from_zone, planned::PlannedStructure, spatial_index::StructureMarkers,
+        world_to_cell,
+    },
+    resources::{ResourceDeposit, ResourceKind},
+};
+
+/// Cells a random spawn tries before giving up until the next interval.
+const RANDOM_SPAWN_ATTEMPTS: usize = 8;
+
+/// How a regenerating deposit refills. Rates are per fixed simulation
+/// tick (60 Hz at runtime); fractional growth carries over between ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RegenerationCurve {
+    /// Constant growth of `per_tick` units until the deposit is full.
+    Linear { per_tick: f32 },
+    /// Logistic growth: `rate * amount * (1 - amount / capacity)`. Slow
+    /// when nearly empty or nearly full, fastest at half capacity. A
+    /// drained deposit grows as if it held `seed` units, so a logistic
+    /// deposit can recover from zero instead of staying extinct.
+    Logistic { rate: f32, seed: u32 },
+    /// Adds `amount` units at once every `interval_ticks` ticks while the
+    /// deposit is below capacity. The interval restarts whenever the
+    /// deposit is full, so the first burst lands one full interval after
+    /// extraction begins.
+    Burst { interval_ticks: u32, amount: u32 },
+}
+
+impl RegenerationCurve {
+    /// Raw growth for one tick. `elapsed_ticks` counts ticks spent below
+    /// capacity, including the current one. The caller clamps the result
+    /// to the deposit's free capacity.
+    pub fn growth(self, amount: u32, capacity: u32, elapsed_ticks: u32) -> f32 {
+        match self {
+            RegenerationCurve::Linear { per_tick } => per_tick.max(0.0),
+            RegenerationCurve::Logistic { rate, seed } => {
+                if capacity == 0 {
+                    return 0.0;
+                }
+                let base = amount.max(seed.min(capacity)) as f32;
+                let headroom = 1.0 - amount as f32 / capacity as f32;
+                rate.max(0.0) * base * headroom.max(0.0)
+            }
+            RegenerationCurve::Burst {
+                interval_ticks,
+                amount: burst,
+            } => {
+                if elapsed_ticks > 0 && elapsed_ticks.is_multiple_of(interval_ticks.max(1)) {
+                    burst as f32
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Opt-in regrowth for one [`ResourceDeposit`]. The deposit's `capacity`
+/// is the ceiling; the component only tracks the curve's running state.
+#[derive(Debug, Component, Clone, Copy, PartialEq)]
+pub struct DepositRegeneration {
+    pub curve: RegenerationCurve,
+    /// Fractional units produced but not yet added to the deposit.
+    pub carry: f32,
+    /// Consecutive ticks the deposit has spent below capacity.
+    pub elapsed_ticks: u32,
+}
+
+impl DepositRegeneration {
+    pub fn new(curve: RegenerationCurve) -> Self {
+        Self {
+            curve,
+            carry: 0.0,
+            elapsed_ticks: 0,
+        }
+    }
+
+    /// Advance the curve by one tick and return the whole units to add to
+    /// a deposit holding `amount` of `capacity`. Never returns more than
+    /// the deposit's free capacity. A full deposit resets the running
+    /// state so the next drain starts a fresh curve.
+    pub fn step(&mut self, amount: u32, capacity: u32) -> u32 {
+        if amount >= capacity {
+            self.carry = 0.0;
+            self.elapsed_ticks = 0;
+            return 0;
+        }
+        self.elapsed_ticks = self.elapsed_ticks.saturating_add(1);
+        self.carry += self.curve.growth(amount, capacity, self.elapsed_ticks);
+        let whole = self.carry.floor();
+        self.carry -= whole;
+        (whole as u32).min(capacity - amount)
+    }
+}
+
+/// Marker on a [`ResourceDeposit`] whose `amount` is currently zero.
+/// Inserted and removed by [`deposit_depletion_events_system`] so
+/// depletion transitions are detected exactly once per crossing.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct DepletedDeposit;
+
+/// A deposit's `amount` reached zero. Written once per depletion.
+#[derive(Debug, Message, Clone, Copy, PartialEq, Eq)]
+pub struct DepositDepleted {
+    pub deposit: Entity,
+    pub kind: ResourceKind,
+}
+
+/// A previously depleted deposit has material again, from regrowth or
+/// any other refill. Written once per recovery.
+#[derive(Debug, Message, Clone, Copy, PartialEq, Eq)]
+pub struct DepositReplenished {
+    pub deposit: Entity,
+    pub kind: ResourceKind,
+    pub amount: u32,
+}
+
+/// Scenario deposit that appears at a fixed simulation tick.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptedDepositSpawn {
+    /// Fixed tick (counted from the first simulation tick) at which the
+    /// deposit appears.
+    pub at_tick: u64,
+    /// Intent-grid cell whose centre the deposit is placed on.
+    pub cell: (i32, i32),
+    pub amount: u32,
+    pub capacity: u32,
+    pub radius: f32,
+    #[serde(default)]
+    pub regeneration: Option<RegenerationCurve>,
+}
+
+/// Seeded random deposit spawns. Every `interval_ticks`, a deposit is
+/// placed on a uniformly chosen cell inside the inclusive rectangle
+/// `min_cell..=max_cell`, unless `max_active` spawned deposits still have
+/// material.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RandomDepositSpawns {
+    pub seed: u64,
+    pub interval_ticks: u64,
+    pub max_active: u32,
+    pub min_cell: (i32, i32),
+    pub max_cell: (i32, i32),
+    pub amount: u32,
+    pub radius: f32,
+    #[serde(default)]
+    pub regeneration: Option<RegenerationCurve>,
+}
+
+/// Scenario data for map-wide resource dynamics. Every field is optional
+/// so an empty config keeps the legacy "deposits never respawn" economy.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DepositDynamicsConfig {
+    /// Regrowth applied to the scenario's starting deposits.
+    #[serde(default)]
+    pub starting_regeneration: Option<RegenerationCurve>,
+    #[serde(default)]
+    pub scripted_spawns: Vec<ScriptedDepositSpawn>,
+    #[serde(default)]
+    pub random_spawns: Option<RandomDepositSpawns>,
+}
+
+impl DepositDynamicsConfig {
+    pub fn from_file_ron<P: AsRef<std::path::Path>>(path: P) -> anyhow::Result<Self> {
+        let str = std::fs::read_to_string(path)?;
+        Ok(ron::from_str(str.as_ref())?)
+    }
+}
+
+/// Runtime state for the scenario spawn schedule. The default is an
+/// empty config, so registering the plugin alone spawns nothing.
+#[derive(Debug, Resource)]
+pub struct DepositDynamics {
+    pub config: DepositDynamicsConfig,
+    tick: u64,
+    next_scripted: usize,
+    rng: StdRng,
+}
+
+impl DepositDynamics {
+    pub fn new(mut config: DepositDynamicsConfig) -> Self {
+        config.scripted_spawns.sort_by_key(|spawn| spawn.at_tick);
+        let seed = config
+            .random_spawns
+            .as_ref()
+            .map_or(0, |random| random.seed);
+        Self {
+            config,
+            tick: 0,
+            next_scripted: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Simulation ticks the spawn schedule has observed.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+}
+
+impl Default for DepositDynamics {
+    fn default() -> Self {
+        Self::new(DepositDynamicsConfig::default())
+    }
+}
+
+/// Texture used for deposits spawned at runtime. Optional so headless
+/// tests spawn bare deposits without an asset server.
+#[derive(Debug, Resource, Clone)]
+pub struct DepositSprite(pub Handle<Image>);
+
+/// Marker for deposits created by the random spawn schedule. Used to
+/// count live random spawns against `max_active`.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct SpawnedDeposit;
+
+/// Marker for deposits created by a [`ScriptedDepositSpawn`]. Scripted
+/// deposits never count against the random `max_active` quota.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct ScriptedDeposit;
+
+/// Anything that keeps a new deposit off its cell.
+type DepositBlockers = Or<(
+    With<ResourceDeposit>,
+    With<PlannedStructure>,
+    StructureMarkers,
+)>;
+
+/// Spawn a neutral Minerals deposit at `cell`'s centre. Shared by the
+/// scripted and random spawn paths, which tag the deposit with their
+/// own marker.
+pub fn spawn_dynamic_deposit(
+    commands: &mut Commands,
+    sprite: Option<&DepositSprite>,
+    cell: IVec2,
+    amount: u32,
+    capacity: u32,
+    radius: f32,
+    regeneration: Option<RegenerationCurve>,
+) -> Entity {
+    let pos = get_world_from_zone(cell);
+    let mut entity = commands.spawn((
+        Minerals {},
+        ResourceDeposit {
+            kind: ResourceKind::Minerals,
+            amount,
+            capacity: capacity.max(amount),
+            radius,
+        },
+        Transform::from_translation(vec3(pos.x, pos.y, GAMEPLAY_SPRITE_Z))
+            .with_scale(vec3(2., 2., 1.)),
+    ));
+    if let Some(sprite) = sprite {
+        entity.insert(Sprite::from_image(sprite.0.clone()));
+    }
+    if let Some(curve) = regeneration {
+        entity.insert(DepositRegeneration::new(curve));
+    }
+    entity.id()
+}
+
+/// Advance every [`DepositRegeneration`] by one tick. The deposit is only
+/// written (and so only marked changed) when whole units are added.
+pub fn deposit_regeneration_system(
+    mut deposits: Query<(&mut ResourceDeposit, &mut DepositRegeneration)>,
+) {
+    for (mut deposit, mut regeneration) in &mut deposits {
+        let added = regeneration.step(deposit.amount, deposit.capacity);
+        if added > 0 {
+            deposit.amount += added;
+        }
+    }
+}
+
+/// Run the scenario spawn schedule for one tick: scripted spawns whose
+/// tick has arrived, then at most one random spawn. Blocked scripted
+/// spawns are skipped; a random spawn tries up to
+/// [`RANDOM_SPAWN_ATTEMPTS`] cells.
+pub fn deposit_spawn_system(
+    mut commands: Commands,
+    mut dynamics: ResMut<DepositDynamics>,
+    sprite: Option<Res<DepositSprite>>,
+    grid: Option<Res<IntentGrid>>,
+    spawned: Query<&ResourceDeposit, With<SpawnedDeposit>>,
+    blockers: Query<&Transform, DepositBlockers>,
+) {
+    let dynamics = &mut *dynamics;
+    dynamics.tick += 1;
+    let tick = dynamics.tick;
+
+    let scripted_due = dynamics
+        .config
+        .scripted_spawns
+        .get(dynamics.next_scripted)
+        .is_some_and(|spawn| spawn.at_tick <= tick);
+    let random_due = dynamics
+        .config
+        .random_spawns
+        .as_ref()
+        .is_some_and(|random| {
+            random.interval_ticks > 0 && tick.is_multiple_of(random.interval_ticks)
+        });
+    if !scripted_due && !random_due {
+        return;
+    }
+    let mut occupied: HashSet<IVec2> = blockers
+        .iter()
+        .map(|transform| world_to_cell(transform.translation.truncate()))
+        .collect();
+    let is_free = |cell: IVec2, occupied: &HashSet<IVec2>| {
+        !occupied.contains(&cell) && grid.as_ref().is_none_or(|grid| grid.in_bounds(cell))
+    };
+
+    while let Some(spawn) = dynamics.config.scripted_spawns.get(dynamics.next_scripted) {
+        if spawn.at_tick > tick {
+            break;
+        }
+        dynamics.next_scripted += 1;
+        let cell = IVec2::new(spawn.cell.0, spawn.cell.1);
+        if !is_free(cell, &occupied) {
+            continue;
+        }
+        let deposit = spawn_dynamic_deposit(
+            &mut commands,
+            sprite.as_deref(),
+            cell,
+            spawn.amount,
+            spawn.capacity,
+            spawn.radius,
+            spawn.regeneration,
+        );
+        commands.entity(deposit).insert(ScriptedDeposit);
+        occupied.insert(cell);
+    }
+
+    let Some(random) = dynamics.config.random_spawns.as_ref() else {
+        return;
+    };
+    if !random_due {
+        return;
+    }
+    let active = spawned.iter().filter(|deposit| deposit.has_work()).count();
+    if active >= random.max_active as usize {
+        return;
+    }
+    let (min_x, max_x) = ordered(random.min_cell.0, random.max_cell.0);
+    let (min_y, max_y) = ordered(random.min_cell.1, random.max_cell.1);
+    for _ in 0..RANDOM_SPAWN_ATTEMPTS {
+        let cell = IVec2::new(
+            dynamics.rng.random_range(min_x..=max_x),
+            dynamics.rng.random_range(min_y..=max_y),
+        );
+        if !is_free(cell, &occupied) {
+            continue;
+        }
+        let deposit = spawn_dynamic_deposit(
+            &mut commands,
+            sprite.as_deref(),
+            cell,
+            random.amount,
+            random.amount,
+            random.radius,
+            random.regeneration,
+        );
+        commands.entity(deposit).insert(SpawnedDeposit);
+        return;
+    }
+}
+
+fn ordered(a: i32, b: i32) -> (i32, i32) {
+    (a.min(b), a.max(b))
+}
+
+/// Report zero crossings of every changed deposit as [`DepositDepleted`]
+/// and [`DepositReplenished`] messages. Registered by the gather plugin
+/// after extraction so the gather chain reacts on the same tick.
+pub fn deposit_depletion_events_system(
+    mut commands: Commands,
+    deposits: Query<(Entity, &ResourceDeposit, Has<DepletedDeposit>), Changed<ResourceDeposit>>,
+    mut depleted: MessageWriter<DepositDepleted>,
+    mut replenished: MessageWriter<DepositReplenished>,
+) {
+    for (entity, deposit, was_depleted) in &deposits {
+        match (deposit.has_work(), was_depleted) {
+            (false, false) => {
+                commands.entity(entity).insert(DepletedDeposit);
+                depleted.write(DepositDepleted {
+                    deposit: entity,
+                    kind: deposit.kind,
+                });
+            }
+            (true, true) => {
+                commands.entity(entity).remove::<DepletedDeposit>();
+                replenished.write(DepositReplenished {
+                    deposit: entity,
+                    kind: deposit.kind,
+                    amount: deposit.amount,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Regrowth and scenario deposit spawns. Runs before the gather chain so
+/// a deposit regrown this tick is visible to extraction and to the
+/// depletion messages on the same tick. Depletion messages themselves are
+/// owned by the gather plugin, which works without this plugin.
+pub struct DepositDynamicsPlugin;
+
+impl Plugin for DepositDynamicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DepositDynamics>().add_systems(
+            FixedUpdate,
+            (deposit_spawn_system, deposit_regeneration_system)
+                .chain()
+                .after(crate::nanobot::RegionalAllocationSet::Acquire)
+                .after(crate::nanobot::NanobotSimulationSet::Movement)
+                .before(crate::nanobot::gather::worker_gather_arrive_system),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the pure regeneration curves and the scenario
+    //! config format. The gather reactivation contract is covered by
+    //! `tests/behavior/deposit_regeneration.rs`.
+
+    use super::*;
+
+    #[test]
+    fn linear_curve_carries_fractional_growth_between_ticks() {
+        let mut regeneration =
+            DepositRegeneration::new(RegenerationCurve::Linear { per_tick: 0.5 });
+        assert_eq!(regeneration.step(0, 10), 0);
+        assert_eq!(regeneration.step(0, 10), 1);
+        assert_eq!(regeneration.step(1, 10), 0);
+        assert_eq!(regeneration.step(1, 10), 1);
+    }
+
+    #[test]
+    fn growth_never_exceeds_free_capacity() {
+        let mut regeneration =
+            DepositRegeneration::new(RegenerationCurve::Linear { per_tick: 50.0 });
+        assert_eq!(regeneration.step(95, 100), 5);
+    }
+
+    #[test]
+    fn full_deposit_does_not_grow_and_resets_the_curve() {
+        let mut regeneration = DepositRegeneration::new(RegenerationCurve::Burst {
+            interval_ticks: 3,
+            amount: 10,
+        });
+        regeneration.step(0, 100);
+        regeneration.step(0, 100);
+        assert_eq!(regeneration.step(100, 100), 0);
+        assert_eq!(regeneration.elapsed_ticks, 0);
+        assert_eq!(regeneration.carry, 0.0);
+    }
+
+    #[test]
+    fn logistic_curve_recovers_from_zero_through_seed() {
+        let curve = RegenerationCurve::Logistic {
+            rate: 0.5,
+            seed: 10,
+        };
+        assert_eq!(curve.growth(0, 100, 1), 5.0);
+        assert!(curve.growth(50, 100, 1) > curve.growth(10, 100, 1));
+        assert!(curve.growth(50, 100, 1) > curve.growth(90, 100, 1));
+        assert_eq!(curve.growth(100, 100, 1), 0.0);
+    }
+
+    #[test]
+    fn burst_curve_adds_only_on_the_interval() {
+        let mut regeneration = DepositRegeneration::new(RegenerationCurve::Burst {
+            interval_ticks: 3,
+            amount: 7,
+        });
+        let added: Vec<u32> = (0..6).map(|_| regeneration.step(0, 100)).collect();
+        assert_eq!(added, vec![0, 0, 7, 0, 0, 7]);
+    }
+
+    #[test]
+    fn empty_config_parses_to_legacy_static_deposits() {
+        let config: DepositDynamicsConfig = ron::from_str("()").unwrap();
+        assert_eq!(config, DepositDynamicsConfig::default());
+    }
+
+    #[test]
+    fn config_parses_scripted_and_random_spawns() {
+        let config: DepositDynamicsConfig = ron::from_str(
+            "(
+                starting_regeneration: Some(Linear(per_tick: 0.25)),
+                scripted_spawns: [
+                    (at_tick: 600, cell: (4, 1), amount: 500, capacity: 800, radius: 48.0,
+                     regeneration: Some(Burst(interval_ticks: 120, amount: 40))),
+                ],
+                random_spawns: Some((
+                    seed: 7, interval_ticks: 1200, max_active: 2,
+                    min_cell: (2, -2), max_cell: (8, 2), amount: 300, radius: 48.0,
+                )),
+            )",
+        )
+        .unwrap();
+        assert_eq!(
+            config.starting_regeneration,
+            Some(RegenerationCurve::Linear { per_tick: 0.25 })
+        );
+        assert_eq!(config.scripted_spawns[0].cell, (4, 1));
+        assert_eq!(
+            config.scripted_spawns[0].regeneration,
+            Some(RegenerationCurve::Burst {
+                interval_ticks: 120,
+                amount: 40
+            })
+        );
+        let random = config.random_spawns.unwrap();
+        assert_eq!(random.max_active, 2);
+        assert_eq!(random.regeneration, None);
+    }
+
+    #[test]
+    fn scripted_spawns_are_ordered_by_tick() {
+        let spawn = |at_tick| ScriptedDepositSpawn {
+            at_tick,
+            cell: (0, 0),
+            amount: 1,
+            capacity: 1,
+            radius: 1.0,
+            regeneration: None,
+        };
+        let dynamics = DepositDynamics::new(DepositDynamicsConfig {
+            scripted_spawns: vec![spawn(30), spawn(10), spawn(20)],
+            ..Default::default()
+        });
+        let ticks: Vec<u64> = dynamics
+            .config
+            .scripted_spawns
+            .iter()
+            .map(|spawn| spawn.at_tick)
+            .collect();
+        assert_eq!(ticks, vec![10, 20, 30]);
+    }
+}
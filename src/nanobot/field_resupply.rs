@@ -0,0 +1,182 @@
+//! Field resupply: Haulers carrying minerals out to Defenders holding
+//! a Defend cell and turning them into Charge in place.
+//!
+//! ```text
+//!   holding Defender at or below FieldResupply::resupply_below
+//!     -> projected as a sink-tier Haul terminal for its own swarm
+//!     -> ranked with charger deliveries on charge_urgency tiers
+//!     -> Hauler walks cargo from a sink Stockpile to the Defender
+//!     -> each mineral restores FIELD_RESUPPLY_CHARGE_PER_MINERAL
+//! ```
+//!
+//! A Defender that leaves its hold (rotating to a Charger, say) stops
+//! being a valid destination and the Hauler's cargo is redirected.
+//! Without a [`FieldResupply`] resource Defenders only recharge at
+//! Chargers.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::nanobot::charge::{
+    CHARGE_REFILL_PER_TICK, CHARGER_MATERIAL_DRAIN_PER_TICK, Charge, charge_urgency,
+};
+use crate::nanobot::components::{Health, SwarmId, SwarmMember};
+use crate::nanobot::defend::DefendHold;
+use crate::nanobot::placement::BUILDING_FOOTPRINT_RADIUS;
+use crate::resources::ResourceKind;
+
+/// Charge one delivered mineral restores. Matches a Charger's base
+/// refill for each mineral it drains.
+pub const FIELD_RESUPPLY_CHARGE_PER_MINERAL: f32 =
+    CHARGE_REFILL_PER_TICK / CHARGER_MATERIAL_DRAIN_PER_TICK as f32;
+
+/// Resource Haulers carry to a Defender in the field.
+pub const FIELD_RESUPPLY_KIND: ResourceKind = ResourceKind::Minerals;
+
+/// How close a Hauler must stand to a Defender to hand over cargo.
+pub const FIELD_RESUPPLY_RADIUS: f32 = BUILDING_FOOTPRINT_RADIUS;
+
+/// Field resupply by haulers. While present, a holding Defender low on
+/// charge becomes a Haul terminal and is topped up in place; without
+/// it Defenders recharge only at Chargers.
+#[derive(Debug, Resource, Clone, Copy, PartialEq)]
+pub struct FieldResupply {
+    /// Charge at or below which a holding Defender asks for a
+    /// delivery. Sits above [`crate::nanobot::LOW_CHARGE_THRESHOLD`]
+    /// so supply is under way before the Defender would rotate to a
+    /// Charger.
+    pub resupply_below: f32,
+}
+
+impl Default for FieldResupply {
+    fn default() -> Self {
+        Self {
+            resupply_below: 0.75,
+        }
+    }
+}
+
+impl FieldResupply {
+    /// Minerals a holding Defender with `charge` asks for; `0` above
+    /// the resupply threshold.
+    pub fn need(&self, charge: &Charge) -> u32 {
+        if charge.current > self.resupply_below {
+            return 0;
+        }
+        minerals_to_resupply(charge)
+    }
+}
+
+/// A holding Defender waiting on a field delivery.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResupplyTarget {
+    pub entity: Entity,
+    pub swarm: SwarmId,
+    pub pos: Vec2,
+    /// Minerals that would top the Defender up.
+    pub need: u32,
+    /// Minerals that would fill an empty Defender.
+    pub capacity: u32,
+    /// Tier from [`charge_urgency`].
+    pub urgency: u8,
+}
+
+/// Holding Defenders eligible for field resupply.
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct FieldResupplyTargets<'w, 's> {
+    config: Option<Res<'w, FieldResupply>>,
+    defenders: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Charge,
+            Option<&'static Health>,
+            &'static Transform,
+            &'static SwarmMember,
+        ),
+        With<DefendHold>,
+    >,
+}
+
+impl FieldResupplyTargets<'_, '_> {
+    /// Every holding Defender that wants minerals. Empty while
+    /// [`FieldResupply`] is absent.
+    pub fn iter(&self) -> impl Iterator<Item = ResupplyTarget> + '_ {
+        let config = self.config.as_deref().copied();
+        self.defenders
+            .iter()
+            .filter_map(move |(entity, charge, health, transform, member)| {
+                let need = config?.need(charge);
+                (need > 0).then(|| ResupplyTarget {
+                    entity,
+                    swarm: member.0,
+                    pos: transform.translation.truncate(),
+                    need,
+                    capacity: minerals_to_resupply(&Charge {
+                        current: 0.0,
+                        ..*charge
+                    }),
+                    urgency: charge_urgency(charge, health),
+                })
+            })
+    }
+}
+
+/// Minerals that raise `charge` to its max at
+/// [`FIELD_RESUPPLY_CHARGE_PER_MINERAL`].
+pub fn minerals_to_resupply(charge: &Charge) -> u32 {
+    let missing = charge.max - charge.current.max(0.0);
+    if missing <= 0.0 {
+        return 0;
+    }
+    let minerals = missing / FIELD_RESUPPLY_CHARGE_PER_MINERAL;
+    let rounding_tolerance = f32::EPSILON * minerals.max(1.0) * 8.0;
+    (minerals - rounding_tolerance).ceil() as u32
+}
+
+/// Convert up to `minerals` into Charge, stopping once `charge` is
+/// full. Returns the minerals used.
+pub fn resupply_charge(charge: &mut Charge, minerals: u32) -> u32 {
+    let used = minerals.min(minerals_to_resupply(charge));
+    charge.current =
+        (charge.current.max(0.0) + used as f32 * FIELD_RESUPPLY_CHARGE_PER_MINERAL).min(charge.max);
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    //! Need and conversion math. Haulers supplying holding Defenders
+    //! live in `tests/behavior/field_resupply.rs`.
+
+    use super::*;
+    use crate::nanobot::MAX_CHARGE;
+
+    fn charge(current: f32) -> Charge {
+        Charge {
+            current,
+            max: MAX_CHARGE,
+        }
+    }
+
+    #[test]
+    fn need_starts_at_the_threshold_and_covers_a_full_top_up() {
+        let config = FieldResupply::default();
+        assert_eq!(config.need(&charge(0.8)), 0);
+        assert_eq!(config.need(&charge(0.75)), 5);
+        assert_eq!(config.need(&charge(0.0)), 20);
+        assert_eq!(minerals_to_resupply(&charge(MAX_CHARGE)), 0);
+    }
+
+    #[test]
+    fn resupply_stops_at_full_charge() {
+        let mut low = charge(0.5);
+        assert_eq!(resupply_charge(&mut low, 4), 4);
+        assert!((low.current - 0.7).abs() < 1e-5);
+
+        assert_eq!(resupply_charge(&mut low, 20), 6);
+        assert!(low.is_full());
+        assert_eq!(resupply_charge(&mut low, 4), 0);
+    }
+}
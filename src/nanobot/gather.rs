@@ -25,12 +25,15 @@
 use bevy::prelude::*;
 
 use crate::ZONE_BLOCK_SIZE;
-use crate::ai::get_world_from_zone;
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::SupportCondition;
 use crate::nanobot::autonomy::{Commitment, NanobotType, SoftWorkSlots, best_candidate};
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState};
 use crate::nanobot::cargo::{Cargo, LogisticsReservation};
 use crate::nanobot::components::{DirectMovementComponent, Nanobot, Swarm, SwarmId, SwarmMember};
+use crate::nanobot::deposit_dynamics::{
+    DepositDepleted, DepositReplenished, deposit_depletion_events_system,
+};
 use crate::nanobot::haul::HAULER_TRANSFER_PER_TICK;
 use crate::nanobot::placement::{
     SOURCE_STOCKPILE_FOOTPRINT_RADIUS, SOURCE_STOCKPILE_JITTER_AMPLITUDE, SOURCE_STOCKPILE_PADDING,
@@ -42,6 +45,9 @@ use crate::nanobot::planned::{
     planned_visual_components,
 };
 use crate::nanobot::production::OwnerSwarm;
+use crate::nanobot::research::ResearchState;
+use crate::nanobot::spatial_index::{SpatialIndex, SpatialLayer, SwarmFilter};
+use crate::nanobot::telemetry::SwarmTelemetry;
 use crate::resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile, StockpileRole};
 use crate::structure_sprites::StructureSprites;
 
@@ -115,6 +121,18 @@ pub struct ReturningToStockpile {
     pub stockpile: Entity,
 }
 
+impl BehaviourState for GatherAssignment {
+    const ROLE: BehaviourRole = BehaviourRole::GATHER;
+}
+
+impl BehaviourState for ExtractProgress {
+    const ROLE: BehaviourRole = BehaviourRole::GATHER;
+}
+
+impl BehaviourState for ReturningToStockpile {
+    const ROLE: BehaviourRole = BehaviourRole::DELIVER;
+}
+
 pub fn world_to_cell(world: Vec2) -> IVec2 {
     IVec2::new(
         (world.x / ZONE_BLOCK_SIZE).floor() as i32,
@@ -122,6 +140,12 @@ pub fn world_to_cell(world: Vec2) -> IVec2 {
     )
 }
 
+/// World-space center of the intent-grid cell `cell`; the inverse of
+/// [`world_to_cell`] for any point inside the cell.
+pub fn get_world_from_zone(cell: IVec2) -> Vec2 {
+    (cell.as_vec2() + 0.5) * ZONE_BLOCK_SIZE
+}
+
 /// True when the circle (`circle_center`, `circle_radius`) visually
 /// overlaps the rectangle of `cell` in the intent grid. A cell at
 /// `(i, j)` spans world coordinates
@@ -202,6 +226,9 @@ fn reserved_source_amount(
         .sum()
 }
 
+/// Nearest non-Sink stockpile of `kind` owned by `worker_swarm` with
+/// at least `minimum_capacity` unreserved space, and that space.
+/// Candidates come from the shared [`SpatialIndex`] nearest first.
 #[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn find_nearest_stockpile(
     kind: ResourceKind,
@@ -210,6 +237,7 @@ fn find_nearest_stockpile(
     minimum_capacity: u32,
     excluding_worker: Option<Entity>,
     excluding_stockpile: Option<Entity>,
+    index: &SpatialIndex,
     stockpiles: &Query<(
         Entity,
         &Stockpile,
@@ -222,31 +250,28 @@ fn find_nearest_stockpile(
     reservations: &Query<(Entity, &LogisticsReservation)>,
     same_tick_reserved: &std::collections::HashMap<Entity, u32>,
 ) -> Option<(Entity, u32)> {
-    let mut best: Option<(f32, Entity, u32)> = None;
-    for (entity, stockpile, transform, role, owner, condition) in stockpiles.iter() {
+    let available = |entity: Entity| -> Option<u32> {
+        let (_, stockpile, _, role, owner, condition) = stockpiles.get(entity).ok()?;
         if Some(entity) == excluding_stockpile
             || stockpile.kind != kind
             || matches!(role, Some(StockpileRole::Sink))
             || !stockpile_owned_by(owner, worker_swarm, swarms)
             || condition.is_some_and(|condition| !condition.is_operational())
         {
-            continue;
+            return None;
         }
         let reserved = reserved_destination_capacity(reservations, entity, excluding_worker)
             .saturating_add(same_tick_reserved.get(&entity).copied().unwrap_or(0));
         let available = stockpile.free_space().saturating_sub(reserved);
-        if available < minimum_capacity || available == 0 {
-            continue;
-        }
-        let distance = worker_pos.distance(transform.translation.truncate());
-        if best.is_none_or(|(best_distance, best_entity, _)| {
-            distance < best_distance
-                || (distance == best_distance && entity.to_bits() < best_entity.to_bits())
-        }) {
-            best = Some((distance, entity, available));
-        }
-    }
-    best.map(|(_, entity, available)| (entity, available))
+        (available >= minimum_capacity && available > 0).then_some(available)
+    };
+    let nearest = index.nearest(
+        worker_pos,
+        SpatialLayer::Structure,
+        SwarmFilter::Any,
+        |entry| available(entry.entity).is_some(),
+    )?;
+    available(nearest.entity).map(|available| (nearest.entity, available))
 }
 
 /// True when a stockpile (built or planned) is usable by
@@ -777,6 +802,7 @@ pub fn worker_gather_arrive_system(
         Option<&SupportCondition>,
     )>,
     swarms: Query<&SwarmId, With<Swarm>>,
+    index: Res<SpatialIndex>,
     planned_structures: Query<&PlannedStructure>,
     reservations: Query<(Entity, &LogisticsReservation)>,
 ) {
@@ -817,6 +843,7 @@ pub fn worker_gather_arrive_system(
             1,
             None,
             None,
+            &index,
             &stockpiles,
             &swarms,
             &reservations,
@@ -850,7 +877,9 @@ pub fn worker_gather_arrive_system(
 /// Drain `EXTRACT_PER_TICK` units from the assigned deposit every
 /// tick while the worker is at the deposit and the load is not
 /// full. When the load is full or the deposit empties (or
-/// disappears), transition the worker to Carrying.
+/// disappears), transition the worker to Carrying. A swarm that
+/// has researched [`crate::nanobot::Upgrade::ExtractRate`]
+/// extracts at the researched rate instead.
 #[allow(clippy::type_complexity)]
 pub fn worker_gather_extract_system(
     mut commands: Commands,
@@ -867,6 +896,8 @@ pub fn worker_gather_extract_system(
     >,
     mut deposits: Query<&mut ResourceDeposit>,
     mut ledger: ResMut<ResourceLedger>,
+    research: Option<Res<ResearchState>>,
+    mut telemetry: Option<ResMut<SwarmTelemetry>>,
 ) {
     for (entity, mut progress, assignment, mut cargo, mut reservation, swarm) in &mut workers {
         if reservation.is_added() {
@@ -882,7 +913,10 @@ pub fn worker_gather_extract_system(
             transition_worker_to_carrying(&mut commands, entity, cargo.amount);
             continue;
         };
-        let actual = EXTRACT_PER_TICK
+        let extract_per_tick = research.as_deref().map_or(EXTRACT_PER_TICK, |research| {
+            research.extract_per_tick(swarm.0)
+        });
+        let actual = extract_per_tick
             .min(deposit.amount)
             .min(reservation.source_remaining)
             .min(WORKER_CARRY_CAPACITY.saturating_sub(cargo.amount));
@@ -897,6 +931,37 @@ pub fn worker_gather_extract_system(
         deposit.amount -= actual;
         reservation.source_remaining -= actual;
         ledger.add_for(swarm.0, deposit.kind, actual);
+        if let Some(telemetry) = telemetry.as_deref_mut() {
+            telemetry.counters_mut(swarm.0).minerals_extracted += actual;
+        }
+    }
+}
+
+/// Release Workers still walking to a deposit that has just run dry.
+/// Workers holding a [`LogisticsReservation`] are left alone: extraction
+/// already turns an empty deposit into a carry or release on its own.
+/// Without this, a Worker without a lease walks all the way to an empty
+/// deposit before the arrive system notices. The Gather Zone stays
+/// painted, so a later [`DepositReplenished`] deposit is projected as
+/// Gather work again and idle Workers reactivate through the allocator.
+#[allow(clippy::type_complexity)]
+pub fn worker_gather_depleted_deposit_system(
+    mut commands: Commands,
+    mut depleted: MessageReader<DepositDepleted>,
+    workers: Query<(Entity, &GatherAssignment), (With<Nanobot>, Without<LogisticsReservation>)>,
+) {
+    let depleted: std::collections::HashSet<Entity> =
+        depleted.read().map(|message| message.deposit).collect();
+    if depleted.is_empty() {
+        return;
+    }
+    for (entity, assignment) in &workers {
+        if depleted.contains(&assignment.deposit) {
+            commands
+                .entity(entity)
+                .remove::<GatherAssignment>()
+                .remove::<DirectMovementComponent>();
+        }
     }
 }
 
@@ -940,6 +1005,7 @@ pub fn worker_gather_reroute_system(
         Option<&SupportCondition>,
     )>,
     swarms: Query<&SwarmId, With<Swarm>>,
+    index: Res<SpatialIndex>,
     reservations: Query<(Entity, &LogisticsReservation)>,
 ) {
     let mut same_tick_claims = std::collections::HashMap::new();
@@ -977,6 +1043,7 @@ pub fn worker_gather_reroute_system(
             cargo.amount,
             Some(entity),
             (reservation.destination_remaining > 0).then_some(reservation.destination),
+            &index,
             &stockpiles,
             &swarms,
             &reservations,
@@ -1029,6 +1096,7 @@ pub fn worker_gather_carry_assign_system(
             With<WorkerLoad>,
             Without<ReturningToStockpile>,
             Without<ExtractProgress>,
+            Without<crate::nanobot::Fleeing>,
         ),
     >,
     stockpiles: Query<(
@@ -1040,6 +1108,7 @@ pub fn worker_gather_carry_assign_system(
         Option<&SupportCondition>,
     )>,
     swarms: Query<&SwarmId, With<Swarm>>,
+    index: Res<SpatialIndex>,
     reservations: Query<(Entity, &LogisticsReservation)>,
 ) {
     let mut same_tick_claims = std::collections::HashMap::new();
@@ -1083,6 +1152,7 @@ pub fn worker_gather_carry_assign_system(
                 load.amount,
                 None,
                 None,
+                &index,
                 &stockpiles,
                 &swarms,
                 &reservations,
@@ -1205,23 +1275,37 @@ pub fn worker_gather_delivery_system(
 /// arrive and delivery systems wait for).
 ///
 /// Internal order: assignment -> source stockpile demand ->
-/// arrive -> extract -> carry-assign -> delivery. The demand
+/// arrive -> extract -> depletion messages -> carry-assign ->
+/// delivery. Depletion messages are written right after
+/// extraction so Workers still walking to a drained deposit are
+/// released on the tick it empties. The demand
 /// system runs after assignment so it sees the current tick's
 /// new assignments and plans a Source Stockpile on the same
 /// tick the Worker is routed to the deposit; the planned
 /// structure's claim system (in `PlannedStructurePlugin`,
 /// registered after this one) then picks up the planned
-/// structure for the same tick.
+/// structure for the same tick. The spatial index is refreshed
+/// before arrival so stockpile lookups see every stockpile that
+/// exists when the chain starts.
 pub struct GatherPlugin;
 
 impl Plugin for GatherPlugin {
     fn build(&self, app: &mut App) {
+        crate::nanobot::add_spatial_index(app);
+        app.add_message::<DepositDepleted>()
+            .add_message::<DepositReplenished>()
+            .add_behaviour_state::<GatherAssignment>()
+            .add_behaviour_state::<ExtractProgress>()
+            .add_behaviour_state::<ReturningToStockpile>();
         app.add_systems(
             FixedUpdate,
             (
                 source_stockpile_demand_system,
+                crate::nanobot::spatial_index_update_system,
                 worker_gather_arrive_system,
                 worker_gather_extract_system,
+                deposit_depletion_events_system,
+                worker_gather_depleted_deposit_system,
                 worker_gather_reroute_system,
                 worker_gather_carry_assign_system,
                 worker_gather_delivery_system,
@@ -2,24 +2,30 @@
 //!
 //! Haulers move large physical loads between logistics buffers:
 //! source stockpiles, sink stockpiles, and terminal consumers
-//! (production facilities / chargers). Deposits are worker-only
-//! sources under the tiered logistics model; legacy manual hauler
-//! assignments can still drain them defensively for tests.
+//! (production facilities / chargers / research labs / planned
+//! sites awaiting construction material / holding defenders under
+//! field resupply). Deposits are worker-only sources under the
+//! tiered logistics model; legacy manual hauler assignments can
+//! still drain them defensively for tests.
 
 use bevy::prelude::*;
 
 use crate::intent::IntentGrid;
 use crate::nanobot::{
-    Cargo, LogisticsReservation, NanobotType, OwnerSwarm, ProductionFacility, STOP_THRESHOLD,
-    SupportCondition,
-    charge::Charger,
+    Cargo, DefendHold, FIELD_RESUPPLY_KIND, FIELD_RESUPPLY_RADIUS, LogisticsFlowHistory,
+    LogisticsReservation, NanobotType, OwnerSwarm, ProductionFacility, ResearchLab, ResearchState,
+    STOP_THRESHOLD, SupportCondition, SwarmTelemetry, UpkeepStock,
+    behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState},
+    charge::{Charge, Charger},
     components::{DirectMovementComponent, Nanobot, SwarmId, SwarmMember},
-    hauler_route_cost,
+    construction_cost::PlannedMaterial,
     logistics_leg::{
         HaulerContext, StockpileCandidate, TerminalCandidate, pick_logistics_leg_with_cost,
     },
+    minerals_to_resupply,
     placement::BUILDING_FOOTPRINT_RADIUS,
-    plan_hauler_route,
+    plan_hauler_route, researched_hauler_carry_capacity, resupply_charge,
+    route_cache::RouteCostCache,
 };
 use crate::resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile, StockpileRole};
 
@@ -68,6 +74,48 @@ pub struct HaulerRoute {
     pub final_stop_radius: f32,
 }
 
+/// Stops still ahead on a multi-stop trip, in visiting order: every
+/// pickup comes before every dropoff. Each stop is its own
+/// [`HaulerTripStop`] entity carrying a [`LogisticsReservation`], so
+/// other haulers and the allocator see its claim like any other.
+#[derive(Debug, Component, Clone, Default)]
+pub struct HaulerTrip {
+    pub pickups: Vec<Entity>,
+    pub dropoffs: Vec<Entity>,
+}
+
+/// Reservation holder for one queued stop of `hauler`'s trip.
+/// Despawned once the hauler reaches the stop or drops the trip.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct HaulerTripStop {
+    pub hauler: Entity,
+}
+
+/// Marks a hauler carrying part of its load on to the next pickup.
+/// Keeps the carry and delivery systems off it until it has loaded.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct HaulerTripPickup;
+
+impl BehaviourState for HaulerAssignment {
+    const ROLE: BehaviourRole = BehaviourRole::HAUL;
+}
+
+impl BehaviourState for HaulerLoading {
+    const ROLE: BehaviourRole = BehaviourRole::HAUL;
+}
+
+impl BehaviourState for HaulerRoute {
+    const ROLE: BehaviourRole = BehaviourRole::HAUL;
+}
+
+impl BehaviourState for HaulerTrip {
+    const ROLE: BehaviourRole = BehaviourRole::HAUL;
+}
+
+impl BehaviourState for HaulerTripPickup {
+    const ROLE: BehaviourRole = BehaviourRole::HAUL;
+}
+
 impl HaulerRoute {
     pub fn new(waypoints: Vec<Vec2>, final_stop_radius: f32) -> Self {
         Self {
@@ -259,9 +307,14 @@ pub fn hauler_assignment_system(
     )>,
     facilities: Query<(Entity, &ProductionFacility, &Transform, Option<&OwnerSwarm>)>,
     chargers: Query<(Entity, &Charger, &Transform, Option<&OwnerSwarm>)>,
+    labs: Query<(Entity, &ResearchLab, &Transform, Option<&OwnerSwarm>)>,
+    depots: Query<(Entity, &UpkeepStock, &Transform, Option<&OwnerSwarm>)>,
+    sites: Query<(Entity, &PlannedMaterial, &Transform, Option<&OwnerSwarm>)>,
     conditions: Query<&SupportCondition>,
     swarms: Query<&SwarmId>,
     grid: Res<IntentGrid>,
+    routes: Res<RouteCostCache>,
+    research: Option<Res<ResearchState>>,
 ) {
     let stockpile_candidates: Vec<StockpileCandidate> = stockpiles
         .iter()
@@ -312,6 +365,47 @@ pub fn hauler_assignment_system(
             })
         },
     ));
+    terminal_candidates.extend(labs.iter().filter_map(|(entity, lab, transform, owner)| {
+        if !endpoint_is_operational(entity, &conditions) {
+            return None;
+        }
+        let owner = candidate_owner(owner, &swarms)?;
+        Some(TerminalCandidate::ResearchLab {
+            entity,
+            pos: transform.translation.truncate(),
+            kind: lab.input_kind,
+            free_space: lab.input_free_space(),
+            owner,
+        })
+    }));
+    terminal_candidates.extend(
+        depots
+            .iter()
+            .filter_map(|(entity, stock, transform, owner)| {
+                let owner = candidate_owner(owner, &swarms)?;
+                Some(TerminalCandidate::Upkeep {
+                    entity,
+                    pos: transform.translation.truncate(),
+                    kind: stock.kind,
+                    free_space: stock.free_space(),
+                    owner,
+                })
+            }),
+    );
+    terminal_candidates.extend(
+        sites
+            .iter()
+            .filter_map(|(entity, material, transform, owner)| {
+                let owner = candidate_owner(owner, &swarms)?;
+                Some(TerminalCandidate::PlannedSite {
+                    entity,
+                    pos: transform.translation.truncate(),
+                    kind: material.kind,
+                    free_space: material.free_space(),
+                    owner,
+                })
+            }),
+    );
 
     for (entity, transform, nanobot_type, swarm_member) in &haulers {
         if *nanobot_type != NanobotType::Hauler {
@@ -324,11 +418,11 @@ pub fn hauler_assignment_system(
                 pos: hauler_pos,
                 swarm,
                 kind: ResourceKind::Minerals,
-                carry_capacity: HAULER_CARRY_CAPACITY,
+                carry_capacity: researched_hauler_carry_capacity(research.as_deref(), swarm),
             },
             &stockpile_candidates,
             &terminal_candidates,
-            |from, to| hauler_route_cost(from, to, &grid, swarm),
+            |from, to| routes.cost(from, to, &grid, swarm),
         ) else {
             continue;
         };
@@ -364,7 +458,9 @@ pub fn hauler_assignment_system(
 /// own radius (deposit or stockpile), matching the gather chain.
 /// The `Without<HaulerLoading>` filter makes arrival idempotent; the
 /// `Without<HaulerLoad>` filter keeps a Carrying hauler from being
-/// re-loaded when it happens to be at the source between trips.
+/// re-loaded when it happens to be at the source between trips. A
+/// hauler heading to a trip's next pickup ([`HaulerTripPickup`])
+/// already holds cargo and targets its reservation's source instead.
 #[allow(clippy::type_complexity)]
 pub fn hauler_arrive_source_system(
     mut commands: Commands,
@@ -375,38 +471,58 @@ pub fn hauler_arrive_source_system(
             &HaulerAssignment,
             Option<&HaulerRoute>,
             Option<&LogisticsReservation>,
+            Option<&Cargo>,
+            Has<HaulerTripPickup>,
+            &SwarmMember,
         ),
         (
             With<Nanobot>,
             With<HaulerAssignment>,
             Without<DirectMovementComponent>,
             Without<HaulerLoading>,
-            Without<HaulerLoad>,
+            Or<(Without<HaulerLoad>, With<HaulerTripPickup>)>,
         ),
     >,
     deposits: Query<(&ResourceDeposit, &Transform)>,
     stockpiles: Query<(&Stockpile, &Transform)>,
     chargers: Query<(&Charger, &Transform)>,
     conditions: Query<&SupportCondition>,
+    grid: Res<IntentGrid>,
 ) {
-    for (entity, transform, assignment, route, reservation) in &haulers {
-        if !endpoint_is_operational(assignment.source, &conditions) {
-            commands
-                .entity(entity)
-                .remove::<HaulerAssignment>()
-                .remove::<LogisticsReservation>()
-                .remove::<HaulerRoute>();
-            continue;
-        }
-        let (source_pos, source_radius) = if let Ok((d, t)) = deposits.get(assignment.source) {
-            (t.translation.truncate(), d.radius)
-        } else if let Ok((s, t)) = stockpiles.get(assignment.source) {
-            (t.translation.truncate(), s.radius)
-        } else if let Ok((c, t)) = chargers.get(assignment.source) {
-            (t.translation.truncate(), c.radius)
+    for (entity, transform, assignment, route, reservation, cargo, trip_pickup, swarm_member) in
+        &haulers
+    {
+        let source = if trip_pickup {
+            reservation.map_or(assignment.source, |reservation| reservation.source)
+        } else {
+            assignment.source
+        };
+        let endpoint = if !endpoint_is_operational(source, &conditions) {
+            None
+        } else if let Ok((d, t)) = deposits.get(source) {
+            Some((t.translation.truncate(), d.radius))
+        } else if let Ok((s, t)) = stockpiles.get(source) {
+            Some((t.translation.truncate(), s.radius))
+        } else if let Ok((c, t)) = chargers.get(source) {
+            Some((t.translation.truncate(), c.radius))
         } else {
-            // Source entity disappeared; drop the assignment and
-            // let a later tick reassign.
+            None
+        };
+        let Some((source_pos, source_radius)) = endpoint else {
+            if trip_pickup {
+                // The next pickup is gone; carry what is already
+                // loaded to the sink instead.
+                skip_trip_pickup(
+                    &mut commands,
+                    entity,
+                    assignment.source,
+                    reservation,
+                    cargo.map_or(0, |cargo| cargo.amount),
+                );
+                continue;
+            }
+            // Source entity disappeared or stopped operating; drop the
+            // assignment and let a later tick reassign.
             commands
                 .entity(entity)
                 .remove::<HaulerAssignment>()
@@ -418,13 +534,31 @@ pub fn hauler_arrive_source_system(
             let kind = reservation
                 .map(|reservation| reservation.kind)
                 .unwrap_or(ResourceKind::Minerals);
-            commands
-                .entity(entity)
-                .insert((HaulerLoading, Cargo::empty(kind)))
-                .remove::<HaulerRoute>();
+            let mut arrived = commands.entity(entity);
+            arrived.insert(HaulerLoading).remove::<HaulerRoute>();
+            if cargo.is_none() {
+                arrived.insert(Cargo::empty(kind));
+            }
+            if trip_pickup {
+                arrived
+                    .insert(HaulerAssignment {
+                        source,
+                        sink: assignment.sink,
+                    })
+                    .remove::<HaulerTripPickup>();
+            }
         } else if route.is_some() {
             // The route follower owns movement restoration while a
             // route is active.
+        } else if trip_pickup {
+            let (route, movement) = planned_route_movement(
+                transform.translation.truncate(),
+                source_pos,
+                &grid,
+                swarm_member.0,
+                source_radius,
+            );
+            commands.entity(entity).insert((route, movement));
         } else {
             // `ProgressChecker` can remove `DirectMovementComponent`
             // before true arrival when congestion leaves the hauler
@@ -440,11 +574,44 @@ pub fn hauler_arrive_source_system(
     }
 }
 
+/// Abandon the pickup a hauler was heading to and deliver the cargo
+/// it already holds from `loaded_from`.
+fn skip_trip_pickup(
+    commands: &mut Commands,
+    entity: Entity,
+    loaded_from: Entity,
+    reservation: Option<&LogisticsReservation>,
+    carried: u32,
+) {
+    let mut hauler = commands.entity(entity);
+    hauler
+        .remove::<HaulerTripPickup>()
+        .remove::<HaulerRoute>()
+        .remove::<DirectMovementComponent>();
+    if carried == 0 {
+        hauler
+            .remove::<Cargo>()
+            .remove::<HaulerAssignment>()
+            .remove::<LogisticsReservation>()
+            .remove::<HaulerTrip>();
+        return;
+    }
+    if let Some(reservation) = reservation {
+        let mut updated = *reservation;
+        updated.source = loaded_from;
+        updated.amount = carried;
+        updated.source_remaining = 0;
+        updated.destination_remaining = carried;
+        hauler.insert(updated);
+    }
+}
+
 /// Drain `HAULER_EXTRACT_PER_TICK` units from the assigned source
 /// every tick while the hauler is at the source and the load is
 /// not full. When the load is full or the source empties (or
-/// disappears), transition the hauler to Carrying.
-#[allow(clippy::type_complexity)]
+/// disappears), move on to the trip's next pickup if one is queued,
+/// otherwise transition the hauler to Carrying.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn hauler_load_system(
     mut commands: Commands,
     mut haulers: Query<
@@ -453,37 +620,46 @@ pub fn hauler_load_system(
             &mut Cargo,
             &HaulerAssignment,
             Option<&mut LogisticsReservation>,
+            Option<&mut HaulerTrip>,
             &SwarmMember,
         ),
         (With<Nanobot>, With<HaulerLoading>),
     >,
+    mut trip_stops: Query<&mut LogisticsReservation, (With<HaulerTripStop>, Without<Nanobot>)>,
     mut deposits: Query<&mut ResourceDeposit>,
     mut source_stockpiles: Query<&mut Stockpile>,
     source_chargers: Query<&mut Charger>,
     conditions: Query<&SupportCondition>,
     mut ledger: ResMut<ResourceLedger>,
+    mut telemetry: Option<ResMut<SwarmTelemetry>>,
 ) {
-    for (entity, mut cargo, assignment, mut reservation, swarm) in &mut haulers {
+    for (entity, mut cargo, assignment, mut reservation, mut trip, swarm) in &mut haulers {
         let target_amount = reservation
             .as_ref()
             .map(|reservation| reservation.amount)
             .unwrap_or(HAULER_CARRY_CAPACITY);
-        let finish_reservation = |reservation: Option<&mut LogisticsReservation>, carried| {
-            if let Some(reservation) = reservation {
-                reservation.source_remaining = 0;
-                reservation.destination_remaining = carried;
-            }
-        };
         if cargo.amount >= target_amount {
-            finish_reservation(reservation.as_deref_mut(), cargo.amount);
-            transition_to_carrying(&mut commands, entity, cargo.amount);
+            finish_loading(
+                &mut commands,
+                entity,
+                cargo.amount,
+                reservation.as_deref_mut(),
+                trip.as_deref_mut(),
+                &mut trip_stops,
+            );
             continue;
         }
 
         if let Ok(mut deposit) = deposits.get_mut(assignment.source) {
             if deposit.amount == 0 {
-                finish_reservation(reservation.as_deref_mut(), cargo.amount);
-                transition_to_carrying(&mut commands, entity, cargo.amount);
+                finish_loading(
+                    &mut commands,
+                    entity,
+                    cargo.amount,
+                    reservation.as_deref_mut(),
+                    trip.as_deref_mut(),
+                    &mut trip_stops,
+                );
                 continue;
             }
             let can_still_carry = target_amount - cargo.amount;
@@ -493,6 +669,9 @@ pub fn hauler_load_system(
             cargo.amount += actual;
             deposit.amount -= actual;
             ledger.add_for(swarm.0, deposit.kind, actual);
+            if let Some(telemetry) = telemetry.as_deref_mut() {
+                telemetry.counters_mut(swarm.0).minerals_extracted += actual;
+            }
             if let Some(reservation) = reservation.as_deref_mut() {
                 reservation.source_remaining = reservation.source_remaining.saturating_sub(actual);
             }
@@ -500,14 +679,26 @@ pub fn hauler_load_system(
         }
 
         if !endpoint_is_operational(assignment.source, &conditions) {
-            finish_reservation(reservation.as_deref_mut(), cargo.amount);
-            transition_to_carrying(&mut commands, entity, cargo.amount);
+            finish_loading(
+                &mut commands,
+                entity,
+                cargo.amount,
+                reservation.as_deref_mut(),
+                trip.as_deref_mut(),
+                &mut trip_stops,
+            );
             continue;
         }
         if let Ok(mut stockpile) = source_stockpiles.get_mut(assignment.source) {
             if stockpile.amount == 0 {
-                finish_reservation(reservation.as_deref_mut(), cargo.amount);
-                transition_to_carrying(&mut commands, entity, cargo.amount);
+                finish_loading(
+                    &mut commands,
+                    entity,
+                    cargo.amount,
+                    reservation.as_deref_mut(),
+                    trip.as_deref_mut(),
+                    &mut trip_stops,
+                );
                 continue;
             }
             let can_still_carry = target_amount - cargo.amount;
@@ -523,14 +714,74 @@ pub fn hauler_load_system(
         }
 
         if source_chargers.get(assignment.source).is_ok() {
-            finish_reservation(reservation.as_deref_mut(), cargo.amount);
+            if let Some(reservation) = reservation.as_deref_mut() {
+                reservation.source_remaining = 0;
+                reservation.destination_remaining = cargo.amount;
+            }
             transition_to_carrying(&mut commands, entity, 0);
             continue;
         }
 
-        finish_reservation(reservation.as_deref_mut(), cargo.amount);
-        transition_to_carrying(&mut commands, entity, cargo.amount);
+        finish_loading(
+            &mut commands,
+            entity,
+            cargo.amount,
+            reservation.as_deref_mut(),
+            trip.as_deref_mut(),
+            &mut trip_stops,
+        );
+    }
+}
+
+/// Close the loading phase at the current source.
+///
+/// With another pickup queued the reservation moves on to that
+/// source, keeping the cargo already loaded, and the hauler heads
+/// there. Otherwise the hauler carries what it holds. Queued dropoffs
+/// then keep only the share of it the first sink has not claimed.
+fn finish_loading(
+    commands: &mut Commands,
+    entity: Entity,
+    carried: u32,
+    reservation: Option<&mut LogisticsReservation>,
+    trip: Option<&mut HaulerTrip>,
+    trip_stops: &mut Query<&mut LogisticsReservation, (With<HaulerTripStop>, Without<Nanobot>)>,
+) {
+    if let Some(reservation) = reservation {
+        let expected_here = reservation.destination_remaining;
+        reservation.source_remaining = 0;
+        reservation.destination_remaining = carried;
+        if let Some(trip) = trip {
+            while !trip.pickups.is_empty() {
+                let stop = trip.pickups.remove(0);
+                let Ok(pickup) = trip_stops.get(stop) else {
+                    continue;
+                };
+                reservation.source = pickup.source;
+                reservation.amount = carried + pickup.source_remaining;
+                reservation.source_remaining = pickup.source_remaining;
+                reservation.destination_remaining = carried + pickup.destination_remaining;
+                commands.entity(stop).despawn();
+                commands
+                    .entity(entity)
+                    .remove::<HaulerLoading>()
+                    .insert(HaulerTripPickup);
+                return;
+            }
+            if !trip.dropoffs.is_empty() {
+                let due_here = carried.min(expected_here);
+                reservation.destination_remaining = due_here;
+                let mut rest = carried - due_here;
+                for stop in &trip.dropoffs {
+                    if let Ok(mut dropoff) = trip_stops.get_mut(*stop) {
+                        dropoff.destination_remaining = dropoff.destination_remaining.min(rest);
+                        rest -= dropoff.destination_remaining;
+                    }
+                }
+            }
+        }
     }
+    transition_to_carrying(commands, entity, carried);
 }
 
 fn transition_to_carrying(commands: &mut Commands, entity: Entity, amount: u32) {
@@ -540,7 +791,24 @@ fn transition_to_carrying(commands: &mut Commands, entity: Entity, amount: u32)
             .entity(entity)
             .remove::<Cargo>()
             .remove::<HaulerAssignment>()
-            .remove::<LogisticsReservation>();
+            .remove::<LogisticsReservation>()
+            .remove::<HaulerTrip>();
+    }
+}
+
+/// Cargo owed to the current sink. While dropoffs are still queued
+/// only the share reserved there is; the rest rides on to the next
+/// stop.
+fn amount_due_here(
+    carried: u32,
+    reservation: Option<&LogisticsReservation>,
+    trip: Option<&HaulerTrip>,
+) -> u32 {
+    match (reservation, trip) {
+        (Some(reservation), Some(trip)) if !trip.dropoffs.is_empty() => {
+            carried.min(reservation.destination_remaining)
+        }
+        _ => carried,
     }
 }
 
@@ -628,6 +896,10 @@ fn valid_destination_snapshot(
     )>,
     facilities: &Query<(Entity, &ProductionFacility, &Transform, Option<&OwnerSwarm>)>,
     chargers: &Query<(Entity, &Charger, &Transform, Option<&OwnerSwarm>)>,
+    labs: &Query<(Entity, &ResearchLab, &Transform, Option<&OwnerSwarm>)>,
+    depots: &Query<(Entity, &UpkeepStock, &Transform, Option<&OwnerSwarm>)>,
+    sites: &Query<(Entity, &PlannedMaterial, &Transform, Option<&OwnerSwarm>)>,
+    defenders: &Query<(Entity, &Charge, &Transform, &SwarmMember), With<DefendHold>>,
     swarms: &Query<&SwarmId>,
     conditions: &Query<&SupportCondition>,
 ) -> Option<SinkEndpointSnapshot> {
@@ -665,6 +937,42 @@ fn valid_destination_snapshot(
                 radius: charger.radius,
             });
     }
+    if let Ok((_, lab, transform, owner)) = labs.get(destination) {
+        return (lab.input_kind == kind
+            && owner_is_swarm(owner, swarms, swarm)
+            && lab.input_free_space().saturating_sub(incoming_claims) >= amount)
+            .then_some(SinkEndpointSnapshot {
+                pos: transform.translation.truncate(),
+                radius: BUILDING_FOOTPRINT_RADIUS,
+            });
+    }
+    if let Ok((_, stock, transform, owner)) = depots.get(destination) {
+        return (stock.kind == kind
+            && owner_is_swarm(owner, swarms, swarm)
+            && stock.free_space().saturating_sub(incoming_claims) >= amount)
+            .then_some(SinkEndpointSnapshot {
+                pos: transform.translation.truncate(),
+                radius: BUILDING_FOOTPRINT_RADIUS,
+            });
+    }
+    if let Ok((_, material, transform, owner)) = sites.get(destination) {
+        return (material.kind == kind
+            && owner_is_swarm(owner, swarms, swarm)
+            && material.free_space().saturating_sub(incoming_claims) >= amount)
+            .then_some(SinkEndpointSnapshot {
+                pos: transform.translation.truncate(),
+                radius: BUILDING_FOOTPRINT_RADIUS,
+            });
+    }
+    if let Ok((_, charge, transform, member)) = defenders.get(destination) {
+        return (kind == FIELD_RESUPPLY_KIND
+            && member.0 == swarm
+            && minerals_to_resupply(charge).saturating_sub(incoming_claims) >= amount)
+            .then_some(SinkEndpointSnapshot {
+                pos: transform.translation.truncate(),
+                radius: FIELD_RESUPPLY_RADIUS,
+            });
+    }
     None
 }
 
@@ -680,8 +988,13 @@ pub fn hauler_reroute_system(
             &mut HaulerAssignment,
             &SwarmMember,
             Option<&LogisticsReservation>,
+            Option<&HaulerTrip>,
+        ),
+        (
+            With<Nanobot>,
+            Without<HaulerLoading>,
+            Without<HaulerTripPickup>,
         ),
-        (With<Nanobot>, Without<HaulerLoading>),
     >,
     stockpiles: Query<(
         Entity,
@@ -692,13 +1005,18 @@ pub fn hauler_reroute_system(
     )>,
     facilities: Query<(Entity, &ProductionFacility, &Transform, Option<&OwnerSwarm>)>,
     chargers: Query<(Entity, &Charger, &Transform, Option<&OwnerSwarm>)>,
+    labs: Query<(Entity, &ResearchLab, &Transform, Option<&OwnerSwarm>)>,
+    depots: Query<(Entity, &UpkeepStock, &Transform, Option<&OwnerSwarm>)>,
+    sites: Query<(Entity, &PlannedMaterial, &Transform, Option<&OwnerSwarm>)>,
+    defenders: Query<(Entity, &Charge, &Transform, &SwarmMember), With<DefendHold>>,
     conditions: Query<&SupportCondition>,
     swarms: Query<&SwarmId>,
     reservations: Query<(Entity, &LogisticsReservation)>,
     grid: Res<IntentGrid>,
 ) {
     let mut same_tick_claims = std::collections::HashMap::<Entity, u32>::new();
-    for (entity, transform, cargo, mut assignment, swarm_member, reservation) in &mut haulers {
+    for (entity, transform, cargo, mut assignment, swarm_member, reservation, trip) in &mut haulers
+    {
         if cargo.amount == 0 {
             continue;
         }
@@ -720,19 +1038,23 @@ pub fn hauler_reroute_system(
                         .copied()
                         .unwrap_or_default(),
                 );
-        let current_claim_valid =
-            reservation_covers_destination(reservation, assignment.sink, cargo.amount);
+        let due = amount_due_here(cargo.amount, reservation, trip);
+        let current_claim_valid = reservation_covers_destination(reservation, assignment.sink, due);
         if current_claim_valid
             && valid_destination_snapshot(
                 assignment.sink,
                 tier,
                 cargo.kind,
-                cargo.amount,
+                due,
                 swarm_member.0,
                 current_incoming,
                 &stockpiles,
                 &facilities,
                 &chargers,
+                &labs,
+                &depots,
+                &sites,
+                &defenders,
                 &swarms,
                 &conditions,
             )
@@ -771,6 +1093,10 @@ pub fn hauler_reroute_system(
                             &stockpiles,
                             &facilities,
                             &chargers,
+                            &labs,
+                            &depots,
+                            &sites,
+                            &defenders,
                             &swarms,
                             &conditions,
                         )?;
@@ -802,6 +1128,115 @@ pub fn hauler_reroute_system(
                             &stockpiles,
                             &facilities,
                             &chargers,
+                            &labs,
+                            &depots,
+                            &sites,
+                            &defenders,
+                            &swarms,
+                            &conditions,
+                        )?;
+                        Some((
+                            hauler_pos.distance(transform.translation.truncate()),
+                            candidate,
+                            endpoint,
+                        ))
+                    }))
+                    .chain(labs.iter().filter_map(|(candidate, _, transform, _)| {
+                        if candidate == assignment.sink && keep_away_from_old_destination {
+                            return None;
+                        }
+                        let incoming =
+                            reserved_destination_capacity(&reservations, candidate, Some(entity))
+                                .saturating_add(
+                                    same_tick_claims
+                                        .get(&candidate)
+                                        .copied()
+                                        .unwrap_or_default(),
+                                );
+                        let endpoint = valid_destination_snapshot(
+                            candidate,
+                            tier,
+                            cargo.kind,
+                            cargo.amount,
+                            swarm_member.0,
+                            incoming,
+                            &stockpiles,
+                            &facilities,
+                            &chargers,
+                            &labs,
+                            &depots,
+                            &sites,
+                            &defenders,
+                            &swarms,
+                            &conditions,
+                        )?;
+                        Some((
+                            hauler_pos.distance(transform.translation.truncate()),
+                            candidate,
+                            endpoint,
+                        ))
+                    }))
+                    .chain(depots.iter().filter_map(|(candidate, _, transform, _)| {
+                        if candidate == assignment.sink && keep_away_from_old_destination {
+                            return None;
+                        }
+                        let incoming =
+                            reserved_destination_capacity(&reservations, candidate, Some(entity))
+                                .saturating_add(
+                                    same_tick_claims
+                                        .get(&candidate)
+                                        .copied()
+                                        .unwrap_or_default(),
+                                );
+                        let endpoint = valid_destination_snapshot(
+                            candidate,
+                            tier,
+                            cargo.kind,
+                            cargo.amount,
+                            swarm_member.0,
+                            incoming,
+                            &stockpiles,
+                            &facilities,
+                            &chargers,
+                            &labs,
+                            &depots,
+                            &sites,
+                            &defenders,
+                            &swarms,
+                            &conditions,
+                        )?;
+                        Some((
+                            hauler_pos.distance(transform.translation.truncate()),
+                            candidate,
+                            endpoint,
+                        ))
+                    }))
+                    .chain(sites.iter().filter_map(|(candidate, _, transform, _)| {
+                        if candidate == assignment.sink && keep_away_from_old_destination {
+                            return None;
+                        }
+                        let incoming =
+                            reserved_destination_capacity(&reservations, candidate, Some(entity))
+                                .saturating_add(
+                                    same_tick_claims
+                                        .get(&candidate)
+                                        .copied()
+                                        .unwrap_or_default(),
+                                );
+                        let endpoint = valid_destination_snapshot(
+                            candidate,
+                            tier,
+                            cargo.kind,
+                            cargo.amount,
+                            swarm_member.0,
+                            incoming,
+                            &stockpiles,
+                            &facilities,
+                            &chargers,
+                            &labs,
+                            &depots,
+                            &sites,
+                            &defenders,
                             &swarms,
                             &conditions,
                         )?;
@@ -842,6 +1277,10 @@ pub fn hauler_reroute_system(
                     &stockpiles,
                     &facilities,
                     &chargers,
+                    &labs,
+                    &depots,
+                    &sites,
+                    &defenders,
                     &swarms,
                     &conditions,
                 )?;
@@ -878,9 +1317,12 @@ pub fn hauler_reroute_system(
             swarm_member.0,
             endpoint.radius,
         );
+        // A redirected load goes to one destination; queued stops
+        // are dropped with their claims.
         commands
             .entity(entity)
-            .insert((redirected, route, movement));
+            .insert((redirected, route, movement))
+            .remove::<HaulerTrip>();
     }
 }
 
@@ -897,7 +1339,8 @@ fn release_destination_claim(
     commands
         .entity(entity)
         .remove::<DirectMovementComponent>()
-        .remove::<HaulerRoute>();
+        .remove::<HaulerRoute>()
+        .remove::<HaulerTrip>();
 }
 
 /// Route loaded haulers only after revalidating the committed destination.
@@ -912,12 +1355,14 @@ pub fn hauler_carry_assign_system(
             &HaulerAssignment,
             &SwarmMember,
             Option<&HaulerRoute>,
+            Option<&HaulerTrip>,
         ),
         (
             With<Nanobot>,
             With<Cargo>,
             Without<DirectMovementComponent>,
             Without<HaulerLoading>,
+            Without<HaulerTripPickup>,
         ),
     >,
     stockpiles: Query<(
@@ -929,12 +1374,16 @@ pub fn hauler_carry_assign_system(
     )>,
     facilities: Query<(Entity, &ProductionFacility, &Transform, Option<&OwnerSwarm>)>,
     chargers: Query<(Entity, &Charger, &Transform, Option<&OwnerSwarm>)>,
+    labs: Query<(Entity, &ResearchLab, &Transform, Option<&OwnerSwarm>)>,
+    depots: Query<(Entity, &UpkeepStock, &Transform, Option<&OwnerSwarm>)>,
+    sites: Query<(Entity, &PlannedMaterial, &Transform, Option<&OwnerSwarm>)>,
+    defenders: Query<(Entity, &Charge, &Transform, &SwarmMember), With<DefendHold>>,
     conditions: Query<&SupportCondition>,
     swarms: Query<&SwarmId>,
     reservations: Query<(Entity, &LogisticsReservation)>,
     grid: Res<IntentGrid>,
 ) {
-    for (entity, transform, cargo, assignment, swarm_member, route) in &haulers {
+    for (entity, transform, cargo, assignment, swarm_member, route, trip) in &haulers {
         let Some(tier) = source_tier(
             assignment.source,
             cargo.kind,
@@ -944,14 +1393,12 @@ pub fn hauler_carry_assign_system(
         ) else {
             continue;
         };
-        if !reservation_covers_destination(
-            reservations
-                .get(entity)
-                .ok()
-                .map(|(_, reservation)| reservation),
-            assignment.sink,
-            cargo.amount,
-        ) {
+        let reservation = reservations
+            .get(entity)
+            .ok()
+            .map(|(_, reservation)| reservation);
+        let due = amount_due_here(cargo.amount, reservation, trip);
+        if !reservation_covers_destination(reservation, assignment.sink, due) {
             continue;
         }
         let incoming = reserved_destination_capacity(&reservations, assignment.sink, Some(entity));
@@ -959,12 +1406,16 @@ pub fn hauler_carry_assign_system(
             assignment.sink,
             tier,
             cargo.kind,
-            cargo.amount,
+            due,
             swarm_member.0,
             incoming,
             &stockpiles,
             &facilities,
             &chargers,
+            &labs,
+            &depots,
+            &sites,
+            &defenders,
             &swarms,
             &conditions,
         ) else {
@@ -991,6 +1442,7 @@ pub fn hauler_delivery_system(
             &mut Cargo,
             &HaulerAssignment,
             Option<&LogisticsReservation>,
+            Option<&mut HaulerTrip>,
             &SwarmMember,
         ),
         (
@@ -999,6 +1451,7 @@ pub fn hauler_delivery_system(
             With<HaulerAssignment>,
             Without<DirectMovementComponent>,
             Without<HaulerLoading>,
+            Without<HaulerTripPickup>,
         ),
     >,
     stockpiles: Query<(
@@ -1010,11 +1463,20 @@ pub fn hauler_delivery_system(
     )>,
     facilities: Query<(Entity, &ProductionFacility, &Transform, Option<&OwnerSwarm>)>,
     chargers: Query<(Entity, &Charger, &Transform, Option<&OwnerSwarm>)>,
+    labs: Query<(Entity, &ResearchLab, &Transform, Option<&OwnerSwarm>)>,
+    depots: Query<(Entity, &UpkeepStock, &Transform, Option<&OwnerSwarm>)>,
+    sites: Query<(Entity, &PlannedMaterial, &Transform, Option<&OwnerSwarm>)>,
+    mut defenders: Query<(Entity, &mut Charge, &Transform, &SwarmMember), With<DefendHold>>,
     conditions: Query<&SupportCondition>,
     swarms: Query<&SwarmId>,
     reservations: Query<(Entity, &LogisticsReservation)>,
+    mut ledger: ResMut<ResourceLedger>,
+    mut telemetry: Option<ResMut<SwarmTelemetry>>,
+    mut flows: Option<ResMut<LogisticsFlowHistory>>,
 ) {
-    for (entity, transform, mut load, assignment, reservation, swarm_member) in &mut haulers {
+    for (entity, transform, mut load, assignment, reservation, mut trip, swarm_member) in
+        &mut haulers
+    {
         let Some(tier) = source_tier(
             assignment.source,
             load.kind,
@@ -1024,7 +1486,20 @@ pub fn hauler_delivery_system(
         ) else {
             continue;
         };
-        if !reservation_covers_destination(reservation, assignment.sink, load.amount) {
+        let due = amount_due_here(load.amount, reservation, trip.as_deref());
+        if due == 0 {
+            advance_to_dropoff(
+                &mut commands,
+                entity,
+                load.amount,
+                assignment,
+                reservation,
+                trip.as_deref_mut(),
+                &reservations,
+            );
+            continue;
+        }
+        if !reservation_covers_destination(reservation, assignment.sink, due) {
             continue;
         }
         let incoming = reserved_destination_capacity(&reservations, assignment.sink, Some(entity));
@@ -1032,12 +1507,16 @@ pub fn hauler_delivery_system(
             assignment.sink,
             tier,
             load.kind,
-            load.amount,
+            due,
             swarm_member.0,
             incoming,
             &stockpiles,
             &facilities,
             &chargers,
+            &labs,
+            &depots,
+            &sites,
+            &defenders.as_readonly(),
             &swarms,
             &conditions,
         ) else {
@@ -1046,7 +1525,7 @@ pub fn hauler_delivery_system(
         if transform.translation.truncate().distance(endpoint.pos) > endpoint.radius {
             continue;
         }
-        let transfer_limit = load.amount.min(HAULER_TRANSFER_PER_TICK);
+        let transfer_limit = due.min(HAULER_TRANSFER_PER_TICK);
         let actual = if let Ok((_, stockpile, _, _, _)) = stockpiles.get(assignment.sink) {
             let actual = transfer_limit.min(stockpile.free_space());
             let mut updated = *stockpile;
@@ -1065,12 +1544,44 @@ pub fn hauler_delivery_system(
             updated.amount += actual;
             commands.entity(assignment.sink).insert(updated);
             actual
+        } else if let Ok((_, lab, _, _)) = labs.get(assignment.sink) {
+            let actual = transfer_limit.min(lab.input_free_space());
+            let mut updated = *lab;
+            updated.input_amount += actual;
+            commands.entity(assignment.sink).insert(updated);
+            actual
+        } else if let Ok((_, stock, _, _)) = depots.get(assignment.sink) {
+            let actual = transfer_limit.min(stock.free_space());
+            let mut updated = *stock;
+            updated.amount += actual;
+            commands.entity(assignment.sink).insert(updated);
+            actual
+        } else if let Ok((_, material, _, _)) = sites.get(assignment.sink) {
+            let actual = transfer_limit.min(material.free_space());
+            let mut updated = *material;
+            updated.delivered += actual;
+            commands.entity(assignment.sink).insert(updated);
+            actual
+        } else if let Ok((_, mut charge, _, _)) = defenders.get_mut(assignment.sink) {
+            // Minerals turned into charge are consumed, as at a charger.
+            let used = resupply_charge(&mut charge, transfer_limit);
+            ledger.remove_for(swarm_member.0, load.kind, used);
+            if let Some(telemetry) = telemetry.as_deref_mut() {
+                telemetry.counters_mut(swarm_member.0).minerals_to_resupply += used;
+            }
+            used
         } else {
             0
         };
         if actual == 0 {
             continue;
         }
+        if let Some(telemetry) = telemetry.as_deref_mut() {
+            telemetry.counters_mut(swarm_member.0).minerals_hauled += actual;
+        }
+        if let Some(flows) = flows.as_deref_mut() {
+            flows.record(assignment.source, assignment.sink, actual);
+        }
         load.amount -= actual;
         if load.amount == 0 {
             commands
@@ -1078,11 +1589,85 @@ pub fn hauler_delivery_system(
                 .remove::<HaulerAssignment>()
                 .remove::<Cargo>()
                 .remove::<LogisticsReservation>()
-                .remove::<HaulerRoute>();
+                .remove::<HaulerRoute>()
+                .remove::<HaulerTrip>();
         } else if let Some(reservation) = reservation {
             let mut updated = *reservation;
             updated.destination_remaining = updated.destination_remaining.saturating_sub(actual);
-            commands.entity(entity).insert(updated);
+            if due == actual && trip.as_ref().is_some_and(|trip| !trip.dropoffs.is_empty()) {
+                advance_to_dropoff(
+                    &mut commands,
+                    entity,
+                    load.amount,
+                    assignment,
+                    Some(&updated),
+                    trip.as_deref_mut(),
+                    &reservations,
+                );
+            } else {
+                commands.entity(entity).insert(updated);
+            }
+        }
+    }
+}
+
+/// Hand the rest of the load over to the trip's next dropoff once
+/// the current sink has its share. Without a queued dropoff the
+/// reservation is left for the reroute system to redirect.
+fn advance_to_dropoff(
+    commands: &mut Commands,
+    entity: Entity,
+    carried: u32,
+    assignment: &HaulerAssignment,
+    reservation: Option<&LogisticsReservation>,
+    trip: Option<&mut HaulerTrip>,
+    reservations: &Query<(Entity, &LogisticsReservation)>,
+) {
+    let (Some(reservation), Some(trip)) = (reservation, trip) else {
+        return;
+    };
+    let mut updated = *reservation;
+    while !trip.dropoffs.is_empty() {
+        let stop = trip.dropoffs.remove(0);
+        let Ok((_, dropoff)) = reservations.get(stop) else {
+            continue;
+        };
+        updated.destination = dropoff.destination;
+        updated.destination_remaining = if trip.dropoffs.is_empty() {
+            carried
+        } else {
+            carried.min(dropoff.destination_remaining)
+        };
+        commands.entity(stop).despawn();
+        commands
+            .entity(entity)
+            .insert((
+                HaulerAssignment {
+                    source: assignment.source,
+                    sink: dropoff.destination,
+                },
+                updated,
+            ))
+            .remove::<HaulerRoute>();
+        return;
+    }
+    updated.destination_remaining = 0;
+    commands.entity(entity).insert(updated);
+}
+
+/// Despawn trip stops their hauler no longer queues: reached, dropped
+/// with a redirect or role exit, or left behind by a dead hauler.
+pub fn hauler_trip_stop_cleanup_system(
+    mut commands: Commands,
+    stops: Query<(Entity, &HaulerTripStop)>,
+    trips: Query<&HaulerTrip>,
+) {
+    for (entity, stop) in &stops {
+        let queued = trips
+            .get(stop.hauler)
+            .is_ok_and(|trip| trip.pickups.contains(&entity) || trip.dropoffs.contains(&entity));
+        if !queued {
+            commands.entity(entity).despawn();
         }
     }
 }
@@ -1107,6 +1692,12 @@ pub struct HaulPlugin;
 
 impl Plugin for HaulPlugin {
     fn build(&self, app: &mut App) {
+        crate::nanobot::add_route_cost_cache(app);
+        app.add_behaviour_state::<HaulerAssignment>()
+            .add_behaviour_state::<HaulerLoading>()
+            .add_behaviour_state::<HaulerRoute>()
+            .add_behaviour_state::<HaulerTrip>()
+            .add_behaviour_state::<HaulerTripPickup>();
         app.add_systems(
             FixedUpdate,
             (
@@ -1116,6 +1707,7 @@ impl Plugin for HaulPlugin {
                 hauler_carry_assign_system,
                 hauler_delivery_system,
                 hauler_route_follow_system,
+                hauler_trip_stop_cleanup_system,
             )
                 .chain()
                 .after(crate::nanobot::RegionalAllocationSet::Acquire)
@@ -3,10 +3,12 @@
 //! This module owns ADR-0005's downstream-first, sink-first
 //! ranking without depending on Bevy `Query` shapes. Callers build
 //! small candidate snapshots from ECS, then ask for the best
-//! [`LogisticsLeg`] for a Hauler.
+//! [`LogisticsLeg`] for a Hauler, and whether one more stop
+//! ([`plan_trip_stop`]) fits on the same trip.
 
 use bevy::prelude::{Entity, Vec2};
 
+use crate::ZONE_BLOCK_SIZE;
 use crate::nanobot::SwarmId;
 use crate::resources::{ResourceKind, StockpileRole};
 
@@ -51,41 +53,79 @@ pub enum TerminalCandidate {
         free_space: u32,
         owner: Option<SwarmId>,
     },
+    ResearchLab {
+        entity: Entity,
+        pos: Vec2,
+        kind: ResourceKind,
+        free_space: u32,
+        owner: Option<SwarmId>,
+    },
+    /// A structure's maintenance upkeep depot.
+    Upkeep {
+        entity: Entity,
+        pos: Vec2,
+        kind: ResourceKind,
+        free_space: u32,
+        owner: Option<SwarmId>,
+    },
+    /// A Planned Structure still waiting on construction material.
+    PlannedSite {
+        entity: Entity,
+        pos: Vec2,
+        kind: ResourceKind,
+        free_space: u32,
+        owner: Option<SwarmId>,
+    },
 }
 
 impl TerminalCandidate {
     fn entity(self) -> Entity {
         match self {
             TerminalCandidate::Facility { entity, .. }
-            | TerminalCandidate::Charger { entity, .. } => entity,
+            | TerminalCandidate::Charger { entity, .. }
+            | TerminalCandidate::ResearchLab { entity, .. }
+            | TerminalCandidate::Upkeep { entity, .. }
+            | TerminalCandidate::PlannedSite { entity, .. } => entity,
         }
     }
 
     fn pos(self) -> Vec2 {
         match self {
-            TerminalCandidate::Facility { pos, .. } | TerminalCandidate::Charger { pos, .. } => pos,
+            TerminalCandidate::Facility { pos, .. }
+            | TerminalCandidate::Charger { pos, .. }
+            | TerminalCandidate::ResearchLab { pos, .. }
+            | TerminalCandidate::Upkeep { pos, .. }
+            | TerminalCandidate::PlannedSite { pos, .. } => pos,
         }
     }
 
     fn kind(self) -> ResourceKind {
         match self {
-            TerminalCandidate::Facility { kind, .. } | TerminalCandidate::Charger { kind, .. } => {
-                kind
-            }
+            TerminalCandidate::Facility { kind, .. }
+            | TerminalCandidate::Charger { kind, .. }
+            | TerminalCandidate::ResearchLab { kind, .. }
+            | TerminalCandidate::Upkeep { kind, .. }
+            | TerminalCandidate::PlannedSite { kind, .. } => kind,
         }
     }
 
     fn free_space(self) -> u32 {
         match self {
             TerminalCandidate::Facility { free_space, .. }
-            | TerminalCandidate::Charger { free_space, .. } => free_space,
+            | TerminalCandidate::Charger { free_space, .. }
+            | TerminalCandidate::ResearchLab { free_space, .. }
+            | TerminalCandidate::Upkeep { free_space, .. }
+            | TerminalCandidate::PlannedSite { free_space, .. } => free_space,
         }
     }
 
     fn owner(self) -> Option<SwarmId> {
         match self {
             TerminalCandidate::Facility { owner, .. }
-            | TerminalCandidate::Charger { owner, .. } => owner,
+            | TerminalCandidate::Charger { owner, .. }
+            | TerminalCandidate::ResearchLab { owner, .. }
+            | TerminalCandidate::Upkeep { owner, .. }
+            | TerminalCandidate::PlannedSite { owner, .. } => owner,
         }
     }
 
@@ -112,7 +152,7 @@ enum StockpileSourceFilter {
 ///
 /// Ranking is ADR-0005: terminal sinks beat buffer sinks; within
 /// a tier the shortest `hauler -> source -> sink` trip wins.
-/// Both terminal kinds draw only from Sink Stockpiles; Sink
+/// Every terminal kind draws only from Sink Stockpiles; Sink
 /// Stockpiles draw only from Source Stockpiles.
 #[cfg(test)]
 pub fn pick_logistics_leg(
@@ -255,6 +295,110 @@ fn owner_matches_hauler(owner: Option<SwarmId>, hauler_swarm: SwarmId) -> bool {
     owner == Some(hauler_swarm)
 }
 
+/// Extra travel a Hauler accepts to fold one more stop into a trip:
+/// one zone block.
+pub const TRIP_STOP_MAX_DETOUR: f32 = ZONE_BLOCK_SIZE;
+
+/// One end of a valid `(source, sink)` pair. `available` is what a
+/// source can still give or a sink can still take after every
+/// other hauler's claims.
+#[derive(Debug, Clone, Copy)]
+pub struct TripEndpoint {
+    pub entity: Entity,
+    pub pos: Vec2,
+    pub available: u32,
+}
+
+/// A `(source, sink)` pair the caller has already checked is a valid
+/// Logistics Leg for the hauler's swarm and resource tier.
+#[derive(Debug, Clone, Copy)]
+pub struct TripPair {
+    pub source: TripEndpoint,
+    pub sink: TripEndpoint,
+}
+
+/// Second stop folded into a hauler trip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TripStop {
+    /// Top the load up at another source feeding the same sink.
+    Pickup { source: Entity, amount: u32 },
+    /// Drop the rest of the load at another sink fed by the same
+    /// source, after the first sink is served.
+    Dropoff { sink: Entity, amount: u32 },
+}
+
+/// Extend a committed leg carrying `amount` with one more stop.
+///
+/// A leg cut short by its source picks up the rest of the load from
+/// the pair sharing its sink whose source adds the least travel. A
+/// leg cut short by its sink drops the rest at the nearest other sink
+/// sharing its source. Either stop must add at most
+/// [`TRIP_STOP_MAX_DETOUR`]; otherwise the trip stays a single leg.
+pub fn plan_trip_stop(
+    leg: TripPair,
+    amount: u32,
+    carry_capacity: u32,
+    pairs: &[TripPair],
+    travel_cost: impl Fn(Vec2, Vec2) -> f32,
+) -> Option<TripStop> {
+    let spare = carry_capacity.saturating_sub(amount);
+    if spare == 0 {
+        return None;
+    }
+    let sink_room = spare.min(leg.sink.available.saturating_sub(amount));
+    if sink_room > 0 {
+        let direct = travel_cost(leg.source.pos, leg.sink.pos);
+        let best = pairs
+            .iter()
+            .filter(|pair| {
+                pair.sink.entity == leg.sink.entity && pair.source.entity != leg.source.entity
+            })
+            .filter_map(|pair| {
+                let extra = sink_room.min(pair.source.available);
+                let detour = travel_cost(leg.source.pos, pair.source.pos)
+                    + travel_cost(pair.source.pos, leg.sink.pos)
+                    - direct;
+                (extra > 0 && detour <= TRIP_STOP_MAX_DETOUR).then_some((
+                    detour,
+                    pair.source.entity,
+                    extra,
+                ))
+            })
+            .min_by(|left, right| {
+                left.0
+                    .total_cmp(&right.0)
+                    .then_with(|| left.1.to_bits().cmp(&right.1.to_bits()))
+            });
+        if let Some((_, source, amount)) = best {
+            return Some(TripStop::Pickup { source, amount });
+        }
+    }
+    let source_rest = spare.min(leg.source.available.saturating_sub(amount));
+    if source_rest == 0 {
+        return None;
+    }
+    pairs
+        .iter()
+        .filter(|pair| {
+            pair.source.entity == leg.source.entity && pair.sink.entity != leg.sink.entity
+        })
+        .filter_map(|pair| {
+            let extra = source_rest.min(pair.sink.available);
+            let detour = travel_cost(leg.sink.pos, pair.sink.pos);
+            (extra > 0 && detour <= TRIP_STOP_MAX_DETOUR).then_some((
+                detour,
+                pair.sink.entity,
+                extra,
+            ))
+        })
+        .min_by(|left, right| {
+            left.0
+                .total_cmp(&right.0)
+                .then_with(|| left.1.to_bits().cmp(&right.1.to_bits()))
+        })
+        .map(|(_, sink, amount)| TripStop::Dropoff { sink, amount })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -337,6 +481,25 @@ mod tests {
         assert_eq!(leg.sink, e(3));
     }
 
+    #[test]
+    fn planned_site_takes_only_its_remaining_cost() {
+        let stockpiles = [
+            source(1, Vec2::new(1.0, 0.0), 100),
+            sink(2, Vec2::new(100.0, 0.0), 100, 0),
+        ];
+        let terminals = [TerminalCandidate::PlannedSite {
+            entity: e(3),
+            pos: Vec2::new(110.0, 0.0),
+            kind: ResourceKind::Minerals,
+            free_space: 6,
+            owner: Some(SwarmId::PLAYER),
+        }];
+
+        let leg = pick_logistics_leg(hauler(Vec2::ZERO), &stockpiles, &terminals).unwrap();
+
+        assert_eq!((leg.source, leg.sink, leg.amount), (e(2), e(3), 6));
+    }
+
     #[test]
     fn buffer_sink_draws_only_from_source_stockpile() {
         let stockpiles = [
@@ -447,4 +610,74 @@ mod tests {
         assert_eq!(leg.source, e(2));
         assert_eq!(leg.sink, e(3));
     }
+
+    fn endpoint(id: u32, pos: Vec2, available: u32) -> TripEndpoint {
+        TripEndpoint {
+            entity: e(id),
+            pos,
+            available,
+        }
+    }
+
+    fn pair(source: TripEndpoint, sink: TripEndpoint) -> TripPair {
+        TripPair { source, sink }
+    }
+
+    #[test]
+    fn short_source_picks_up_from_nearby_source_for_the_same_sink() {
+        let first = endpoint(1, Vec2::ZERO, 8);
+        let sink = endpoint(2, Vec2::new(2_000.0, 0.0), 100);
+        let near = endpoint(3, Vec2::new(100.0, 50.0), 30);
+        let far = endpoint(4, Vec2::new(0.0, 3_000.0), 30);
+        let pairs = [pair(first, sink), pair(near, sink), pair(far, sink)];
+
+        let stop = plan_trip_stop(pair(first, sink), 8, 20, &pairs, |a, b| a.distance(b));
+
+        assert_eq!(
+            stop,
+            Some(TripStop::Pickup {
+                source: e(3),
+                amount: 12
+            })
+        );
+    }
+
+    #[test]
+    fn short_sink_drops_the_rest_at_a_nearby_sink_for_the_same_source() {
+        let source = endpoint(1, Vec2::ZERO, 100);
+        let charger = endpoint(2, Vec2::new(1_000.0, 0.0), 6);
+        let facility = endpoint(3, Vec2::new(1_200.0, 0.0), 50);
+        let pairs = [pair(source, charger), pair(source, facility)];
+
+        let stop = plan_trip_stop(pair(source, charger), 6, 20, &pairs, |a, b| a.distance(b));
+
+        assert_eq!(
+            stop,
+            Some(TripStop::Dropoff {
+                sink: e(3),
+                amount: 14
+            })
+        );
+    }
+
+    #[test]
+    fn full_load_or_distant_stop_keeps_a_single_leg() {
+        let source = endpoint(1, Vec2::ZERO, 100);
+        let sink = endpoint(2, Vec2::new(100.0, 0.0), 100);
+        let other = endpoint(3, Vec2::new(200.0, 0.0), 100);
+        let pairs = [pair(source, sink), pair(source, other)];
+        assert_eq!(
+            plan_trip_stop(pair(source, sink), 20, 20, &pairs, |a, b| a.distance(b)),
+            None
+        );
+
+        let small_sink = endpoint(2, Vec2::new(100.0, 0.0), 5);
+        let distant = endpoint(3, Vec2::new(100.0 + 2.0 * ZONE_BLOCK_SIZE, 0.0), 100);
+        let pairs = [pair(source, small_sink), pair(source, distant)];
+        assert_eq!(
+            plan_trip_stop(pair(source, small_sink), 5, 20, &pairs, |a, b| a
+                .distance(b)),
+            None
+        );
+    }
 }
@@ -8,15 +8,19 @@
 //!     -> increments every fixed tick
 //!     -> reset to 0 each tick a worker spends maintaining
 //!     -> past the buffer, health falls at a fixed degradation cadence
-//!     -> at 0 health, the structure collapses and is despawned
+//!     -> at 0 health, the structure collapses and is despawned,
+//!        leaving any salvage as wreckage
 //! ```
 //!
-//! Maintenance consumes only worker time. The maintenance work
+//! By default maintenance consumes only worker time: the work
 //! system never reads a stockpile, never pulls from the resource
 //! ledger, and never advances a build site. A worker assigned to
 //! maintenance spends `MAINTENANCE_WORK_DURATION_TICKS` ticks
 //! holding the structure's `ticks_since_maintained` at 0 and
 //! restoring a small amount of health per tick, then is freed.
+//! With [`crate::nanobot::MaintenanceUpkeep`] present each shift
+//! first pays minerals from the structure's upkeep depot, so a
+//! swarm whose mineral flow stops watches its structures decay.
 //!
 //! Worker state machine for the maintenance path:
 //!
@@ -30,29 +34,33 @@
 //!
 //! The build layer (issue #10) is the demand signal: a worker
 //! scoring a Build cell first looks for a build site, then a
-//! damaged structure (repair), and now also looks for a structure
-//! that needs maintenance. The maintenance work is the cheapest
-//! of the three (no material, no travel budget pressure) so the
-//! build cell's soft work slot is reused for it. A future
+//! damaged structure (repair, see [`crate::nanobot::RepairPlugin`]),
+//! and now also looks for a structure that needs maintenance. The
+//! maintenance work is the cheapest of the three (no material, no
+//! travel budget pressure) so the build cell's soft work slot is
+//! reused for it. A future
 //! tracking issue can split maintenance into its own slot if
 //! player feedback shows crowding.
 
 use bevy::prelude::*;
 
+use crate::ZONE_BLOCK_SIZE;
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::autonomy::{Commitment, NanobotType, SoftWorkSlots, best_candidate};
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState};
 use crate::nanobot::components::SwarmMember;
-// `StructureKind` is only used by the unit tests in this
-// module. Marked `allow(unused_imports)` so the lib build does
-// not warn; the tests do pick the import up via `use super::*`.
-use crate::ZONE_BLOCK_SIZE;
-#[allow(unused_imports)]
-use crate::nanobot::build::{Structure, StructureKind};
-use crate::nanobot::components::{DirectMovementComponent, Nanobot};
+use crate::nanobot::components::{DirectMovementComponent, Nanobot, Swarm, SwarmId};
 use crate::nanobot::gather::world_to_cell;
 use crate::nanobot::placement::BUILDING_FOOTPRINT_RADIUS;
-use crate::nanobot::{Charger, ProductionFacility};
-use crate::resources::Stockpile;
+use crate::nanobot::structure::{Structure, StructureKind};
+use crate::nanobot::upkeep::{
+    MaintenanceUpkeep, UpkeepDepot, UpkeepStock, attach_upkeep_depots_system, despawn_upkeep_depot,
+};
+use crate::nanobot::wreckage::collapse_structure;
+use crate::nanobot::{
+    Charger, OwnerSwarm, ProductionFacility, ReclaimedCache, ResearchLab, ResearchState, Road,
+};
+use crate::resources::{ResourceLedger, Stockpile};
 
 /// How many ticks a structure stays stable after a maintenance
 /// shift. The buffer gives the swarm room to come back later
@@ -83,20 +91,31 @@ pub const DEGRADATION_INTERVAL_TICKS: u32 = crate::SIMULATION_HZ as u32 / 10;
 /// buffer counter reaches this value. The threshold sits well
 /// below `MAINTENANCE_BUFFER_TICKS` so a worker can finish a
 /// shift and return before the structure actually starts
-/// losing health. A damaged structure (`health < max`) is also
-/// "needy" so combat damage pulls workers back.
+/// losing health. Lost health is repair work, not maintenance; see
+/// [`Structure::needs_repair`].
 pub const MAINTENANCE_NEEDS_THRESHOLD: u32 = crate::SIMULATION_HZ as u32 * 60 / 2;
 
+/// Maintenance buffer for a structure at `tier`. Tier 0 is
+/// [`MAINTENANCE_BUFFER_TICKS`]; each tier adds half the base upkeep
+/// rate.
+pub const fn maintenance_buffer_ticks(tier: u32) -> u32 {
+    MAINTENANCE_BUFFER_TICKS * 2 / (2 + tier)
+}
+
 /// Shared condition carried by every completed support structure.
 pub type SupportCondition = Structure;
 
 /// Completed support structures operate at any positive health.
 pub const SUPPORT_OPERATIONAL_HEALTH_THRESHOLD: u32 = 1;
 
+/// Entities that must not receive a fresh condition: ones that already
+/// carry it, and reclaimed caches, which are never maintained.
+type ConditionExempt = Or<(With<Structure>, With<ReclaimedCache>)>;
+
 fn attach_support_condition(
     entity: Entity,
     commands: &mut Commands,
-    conditions: &Query<(), With<Structure>>,
+    conditions: &Query<(), ConditionExempt>,
 ) {
     if conditions.get(entity).is_err() {
         commands
@@ -108,7 +127,7 @@ fn attach_support_condition(
 fn initialize_stockpile_condition(
     added: On<Add, Stockpile>,
     mut commands: Commands,
-    conditions: Query<(), With<Structure>>,
+    conditions: Query<(), ConditionExempt>,
 ) {
     attach_support_condition(added.entity, &mut commands, &conditions);
 }
@@ -116,7 +135,7 @@ fn initialize_stockpile_condition(
 fn initialize_facility_condition(
     added: On<Add, ProductionFacility>,
     mut commands: Commands,
-    conditions: Query<(), With<Structure>>,
+    conditions: Query<(), ConditionExempt>,
 ) {
     attach_support_condition(added.entity, &mut commands, &conditions);
 }
@@ -124,20 +143,45 @@ fn initialize_facility_condition(
 fn initialize_charger_condition(
     added: On<Add, Charger>,
     mut commands: Commands,
-    conditions: Query<(), With<Structure>>,
+    conditions: Query<(), ConditionExempt>,
+) {
+    attach_support_condition(added.entity, &mut commands, &conditions);
+}
+
+fn initialize_research_lab_condition(
+    added: On<Add, ResearchLab>,
+    mut commands: Commands,
+    conditions: Query<(), ConditionExempt>,
+) {
+    attach_support_condition(added.entity, &mut commands, &conditions);
+}
+
+fn initialize_road_condition(
+    added: On<Add, Road>,
+    mut commands: Commands,
+    conditions: Query<(), ConditionExempt>,
 ) {
     attach_support_condition(added.entity, &mut commands, &conditions);
 }
 
 impl Structure {
-    /// True when the structure is a valid maintenance target.
-    /// Either the buffer is expired (`ticks_since_maintained`
-    /// has reached the threshold) or the structure has been
-    /// damaged and is below max health. The maintenance
-    /// assignment system uses this to filter the world query.
+    /// True when the structure is a valid maintenance target:
+    /// its buffer counter has reached the threshold. Damage alone
+    /// does not qualify; it is projected as repair work instead.
     pub fn needs_maintenance(&self) -> bool {
-        self.ticks_since_maintained >= MAINTENANCE_NEEDS_THRESHOLD
-            || self.health < super::build::STRUCTURE_MAX_HEALTH
+        self.ticks_since_maintained >= self.maintenance_needs_threshold()
+    }
+
+    /// Ticks the structure stays stable after maintenance. Each
+    /// upgrade tier shortens the buffer, so bigger structures
+    /// cost more worker time to keep up.
+    pub fn maintenance_buffer_ticks(&self) -> u32 {
+        maintenance_buffer_ticks(self.tier)
+    }
+
+    /// Tier-scaled [`MAINTENANCE_NEEDS_THRESHOLD`].
+    pub fn maintenance_needs_threshold(&self) -> u32 {
+        MAINTENANCE_NEEDS_THRESHOLD * 2 / (2 + self.tier)
     }
 
     /// Whether this structure can currently perform its gameplay function.
@@ -169,6 +213,14 @@ pub struct MaintenanceProgress {
     pub ticks_worked: u32,
 }
 
+impl BehaviourState for MaintenanceAssignment {
+    const ROLE: BehaviourRole = BehaviourRole::MAINTAIN;
+}
+
+impl BehaviourState for MaintenanceProgress {
+    const ROLE: BehaviourRole = BehaviourRole::MAINTAIN;
+}
+
 /// Increment `ticks_since_maintained` for every structure every
 /// tick, then degrade the structure once the buffer has been
 /// exceeded. A structure that has been freshly maintained sees
@@ -181,25 +233,39 @@ pub struct MaintenanceProgress {
 /// Structures that have just collapsed (health reached zero on
 /// the previous tick) are despawned here so the rest of the
 /// chain does not see a zero-health structure lingering in
-/// queries. The despawn is a hard remove: there is no
-/// `Collapsed` marker, no debris, no recoverable corpse in the
-/// first implementation. A future issue can add a collapse
-/// animation or recovery flow without changing the public
-/// contract.
+/// queries. The despawn is a hard remove through
+/// [`collapse_structure`]: there is no `Collapsed` marker, and
+/// with [`crate::nanobot::Salvage`] present the structure's
+/// buffered minerals are left behind as a wreck.
+///
+/// An owner swarm that has researched
+/// [`crate::nanobot::Upgrade::Durability`] degrades on the
+/// slower cadence from [`ResearchState::degradation_interval_ticks`].
+/// Unowned structures follow the player's research.
 #[allow(clippy::type_complexity)]
 pub fn structure_degradation_system(
     mut commands: Commands,
-    mut structures: Query<(Entity, &mut Structure)>,
+    research: Option<Res<ResearchState>>,
+    mut structures: Query<(Entity, &mut Structure, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId, With<Swarm>>,
 ) {
-    for (entity, mut structure) in &mut structures {
+    for (entity, mut structure, owner) in &mut structures {
         // Always advance the buffer counter. Workers reset it
         // to 0 in the same tick; everything else sees it grow.
         structure.ticks_since_maintained = structure.ticks_since_maintained.saturating_add(1);
 
         let overdue_ticks = structure
             .ticks_since_maintained
-            .saturating_sub(MAINTENANCE_BUFFER_TICKS);
-        if overdue_ticks > 0 && overdue_ticks.is_multiple_of(DEGRADATION_INTERVAL_TICKS) {
+            .saturating_sub(structure.maintenance_buffer_ticks());
+        let interval = research
+            .as_deref()
+            .map_or(DEGRADATION_INTERVAL_TICKS, |research| {
+                let swarm = owner
+                    .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+                    .unwrap_or(SwarmId::PLAYER);
+                research.degradation_interval_ticks(swarm)
+            });
+        if overdue_ticks > 0 && overdue_ticks.is_multiple_of(interval) {
             // Buffer expired; the structure is unstable and loses health at the
             // fixed degradation cadence.
             let next_health = structure.health.saturating_sub(DEGRADATION_PER_STEP);
@@ -209,14 +275,13 @@ pub fn structure_degradation_system(
                 // world. The cell becomes a valid build site
                 // again because auto-creation skips cells
                 // that already hold a Structure.
-                commands.entity(entity).despawn();
+                commands.entity(entity).queue(collapse_structure);
             }
         }
     }
 }
 
-/// For each idle Worker with no in-flight build, repair, or
-/// maintenance work, pick a Build cell through the autonomy
+/// For each idle Worker with no in-flight maintenance work, pick a Build cell through the autonomy
 /// scorer and, if the cell contains a structure that needs
 /// maintenance, assign the worker to the nearest such
 /// structure. The (cell, Build) soft work slot is occupied so
@@ -237,8 +302,6 @@ pub fn worker_maintenance_assignment_system(
         (Entity, &Transform, &Commitment, &NanobotType, &SwarmMember),
         (
             With<Nanobot>,
-            Without<super::build::BuildAssignment>,
-            Without<super::build::BuildProgress>,
             Without<MaintenanceAssignment>,
             Without<MaintenanceProgress>,
             Without<DirectMovementComponent>,
@@ -395,8 +458,11 @@ pub fn worker_maintenance_arrive_system(
 /// The system does **not** read a stockpile, does **not** touch
 /// the resource ledger, and does **not** create a build site.
 /// That is the "consumes Worker time only, not extra resources"
-/// contract from issue #12. A worker doing maintenance
-/// produces zero resource churn.
+/// contract from issue #12. The one exception is the optional
+/// [`MaintenanceUpkeep`] model: the first tick of a shift pays
+/// `per_shift` from the structure's [`UpkeepStock`] depot and out of
+/// the owner's ledger, and a depot that cannot pay ends the shift
+/// before any maintenance.
 ///
 /// The system must run **before** [`structure_degradation_system`]
 /// within the same tick so the freshly-reset buffer counter is
@@ -406,11 +472,15 @@ pub fn worker_maintenance_arrive_system(
 #[allow(clippy::type_complexity)]
 pub fn worker_maintenance_work_system(
     mut commands: Commands,
+    upkeep: Option<Res<MaintenanceUpkeep>>,
     mut workers: Query<(Entity, &mut MaintenanceProgress), With<Nanobot>>,
-    mut structures: Query<&mut Structure>,
+    mut structures: Query<(&mut Structure, Option<&UpkeepDepot>)>,
+    mut depots: Query<(&mut UpkeepStock, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut ledger: ResMut<ResourceLedger>,
 ) {
     for (entity, mut progress) in &mut workers {
-        let Ok(mut structure) = structures.get_mut(progress.target) else {
+        let Ok((mut structure, depot)) = structures.get_mut(progress.target) else {
             // Target collapsed between arrival and work (e.g.
             // a previous test or a future system despawned it).
             // Release the worker; the cell becomes a valid
@@ -418,13 +488,28 @@ pub fn worker_maintenance_work_system(
             release_maintenance_worker(&mut commands, entity);
             continue;
         };
+        if progress.ticks_worked == 0
+            && let Some(upkeep) = upkeep.as_deref()
+            && let Some((mut stock, owner)) = depot.and_then(|depot| depots.get_mut(depot.0).ok())
+        {
+            if !upkeep.covers(Some(&*stock)) {
+                release_maintenance_worker(&mut commands, entity);
+                continue;
+            }
+            stock.amount -= upkeep.per_shift;
+            let swarm = owner
+                .and_then(|owner| swarms.get(owner.0).ok())
+                .copied()
+                .unwrap_or(SwarmId::PLAYER);
+            ledger.remove_for(swarm, stock.kind, upkeep.per_shift);
+        }
 
         // Reset the buffer counter to 0 BEFORE the
         // degradation system runs. The worker's "I just
         // maintained this" stamp is the reset.
         structure.ticks_since_maintained = 0;
-        structure.health = (structure.health + MAINTENANCE_HEALTH_PER_TICK)
-            .min(super::build::STRUCTURE_MAX_HEALTH);
+        structure.health =
+            (structure.health + MAINTENANCE_HEALTH_PER_TICK).min(structure.max_health());
 
         progress.ticks_worked += 1;
         if progress.ticks_worked >= MAINTENANCE_WORK_DURATION_TICKS {
@@ -446,28 +531,30 @@ fn release_maintenance_worker(commands: &mut Commands, entity: Entity) {
 /// the movement system has already pruned arrived bots, and
 /// the maintenance work system runs before the degradation
 /// system so a fresh "I just maintained this" stamp is not
-/// also a "degrade one health" tick. The chain also runs
-/// after the build work system so the build layer gets first
-/// pick on workers and maintenance only catches the ones
-/// build did not need.
+/// also a "degrade one health" tick.
 pub struct MaintenancePlugin;
 
 impl Plugin for MaintenancePlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(initialize_stockpile_condition)
+        app.add_behaviour_state::<MaintenanceAssignment>()
+            .add_behaviour_state::<MaintenanceProgress>()
+            .add_observer(initialize_stockpile_condition)
             .add_observer(initialize_facility_condition)
             .add_observer(initialize_charger_condition)
+            .add_observer(initialize_research_lab_condition)
+            .add_observer(initialize_road_condition)
+            .add_observer(despawn_upkeep_depot)
             .add_systems(
                 FixedUpdate,
                 (
+                    attach_upkeep_depots_system,
                     worker_maintenance_arrive_system,
                     worker_maintenance_work_system,
                     structure_degradation_system,
                 )
                     .chain()
                     .in_set(crate::nanobot::NanobotSimulationSet::Maintenance)
-                    .after(crate::nanobot::RegionalAllocationSet::Acquire)
-                    .after(super::build::worker_build_work_system),
+                    .after(crate::nanobot::RegionalAllocationSet::Acquire),
             );
     }
 }
@@ -546,16 +633,15 @@ mod tests {
     }
 
     #[test]
-    fn damaged_structure_needs_maintenance_even_if_fresh() {
-        // A structure with health below the max is a valid
-        // maintenance target regardless of the buffer
-        // counter. Combat damage or repair shortfalls pull
-        // workers back even if the buffer has not yet
-        // expired.
+    fn damaged_fresh_structure_needs_repair_not_maintenance() {
+        // Combat damage pulls workers back through the separate
+        // repair activity; a fresh buffer still means no
+        // maintenance is due.
         let mut s = Structure::new(StructureKind::Basic);
-        s.health = super::super::build::STRUCTURE_MAX_HEALTH - 1;
+        s.health = super::super::structure::STRUCTURE_MAX_HEALTH - 1;
         s.ticks_since_maintained = 0;
-        assert!(s.needs_maintenance());
+        assert!(!s.needs_maintenance());
+        assert!(s.needs_repair());
     }
 
     #[test]
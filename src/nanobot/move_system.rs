@@ -1,12 +1,19 @@
 use bevy::{
-    prelude::{Commands, Entity, Quat, Query, Res, Transform, Vec2, Vec3, With},
+    prelude::{Commands, Entity, Quat, Query, Res, Transform, Vec2, Vec3, With, Without},
     time::Time,
 };
 
 use crate::{
     game_settings::GameSettings,
-    nanobot::consts::{BOT_RADIUS, BOT_SEPARATION_FORCE},
-    spatial::FixedSpatialBuckets,
+    intent::IntentGrid,
+    nanobot::{
+        Cargo, NanobotType, SwarmId, SwarmMember,
+        consts::{BOT_RADIUS, BOT_SEPARATION_FORCE},
+        research::{ResearchState, researched_hauler_carry_capacity},
+        road::road_speed_multiplier,
+        spatial_index::{SpatialIndex, SpatialLayer, SwarmFilter},
+        steering::{NanobotSteering, Steering, steer},
+    },
 };
 
 use super::{
@@ -14,6 +21,14 @@ use super::{
     consts::STOP_THRESHOLD,
 };
 
+/// Turn each bot's [`DirectMovementComponent`] into this tick's
+/// velocity. Without [`NanobotSteering`] every bot moves at
+/// `GameSettings::bot_speed`; with it, typed bots follow their
+/// [`crate::nanobot::MovementProfile`] and keep their velocity in
+/// [`Steering`] between ticks. A bot standing on a road its swarm may
+/// use gets [`crate::nanobot::ROAD_SPEED_MULTIPLIER`] on either speed.
+/// A hauler's cargo weighs against its swarm's researched hold.
+#[allow(clippy::type_complexity)]
 pub fn move_velocity_system(
     time: Res<Time>,
     mut commands: Commands,
@@ -23,11 +38,37 @@ pub fn move_velocity_system(
         &Transform,
         &mut VelocityComponent,
         Option<&mut ProgressChecker>,
+        Option<&NanobotType>,
+        Option<&Cargo>,
+        Option<&mut Steering>,
+        Option<&SwarmMember>,
     )>,
+    mut resting: Query<&mut Steering, Without<DirectMovementComponent>>,
     game_settings: Res<GameSettings>,
+    steering: Option<Res<NanobotSteering>>,
+    research: Option<Res<ResearchState>>,
+    grid: Res<IntentGrid>,
 ) {
+    // A bot that lost its destination anywhere has stopped; it
+    // starts its next trip from rest.
+    for mut rest in &mut resting {
+        if rest.velocity != Vec2::ZERO {
+            rest.velocity = Vec2::ZERO;
+        }
+    }
     let speed = game_settings.bot_speed;
-    for (entity, bot_destination, transform, mut velocity, progress_checker) in bots.iter_mut() {
+    for (
+        entity,
+        bot_destination,
+        transform,
+        mut velocity,
+        progress_checker,
+        kind,
+        cargo,
+        mut steered,
+        member,
+    ) in bots.iter_mut()
+    {
         let dest: Vec3 = [bot_destination.xy.x, bot_destination.xy.y, 0.].into();
         let translation = transform.translation;
         let direction = dest - translation;
@@ -50,8 +91,33 @@ pub fn move_velocity_system(
         // Check if the distance is less than the threshold
         let distance = dest.distance(translation);
         if distance > stop_threshold {
-            let new_velocity = direction.normalize() * speed.min(distance);
-            velocity.value += new_velocity.truncate();
+            let swarm = member.map_or(SwarmId::PLAYER, |member| member.0);
+            let road = road_speed_multiplier(&grid, translation.truncate(), swarm);
+            if let (Some(steering), Some(kind)) = (steering.as_deref(), kind) {
+                let capacity = researched_hauler_carry_capacity(research.as_deref(), swarm);
+                let top_speed =
+                    steering.top_speed(*kind, cargo.map_or(0, |cargo| cargo.amount), capacity)
+                        * road;
+                let current = steered
+                    .as_ref()
+                    .map_or(Vec2::ZERO, |steered| steered.velocity);
+                let next = steer(
+                    steering.profile(*kind),
+                    top_speed,
+                    current,
+                    direction.truncate(),
+                );
+                velocity.value += next;
+                match steered.as_mut() {
+                    Some(steered) => steered.velocity = next,
+                    None => {
+                        commands.entity(entity).insert(Steering { velocity: next });
+                    }
+                }
+            } else {
+                let new_velocity = direction.normalize() * (speed * road).min(distance);
+                velocity.value += new_velocity.truncate();
+            }
 
             // If the bot is not already moving, add a ProgressChecker
             if progress_checker.is_none() {
@@ -63,6 +129,9 @@ pub fn move_velocity_system(
         } else {
             commands.entity(entity).remove::<DirectMovementComponent>();
             commands.entity(entity).remove::<ProgressChecker>();
+            if let Some(steered) = steered.as_mut() {
+                steered.velocity = Vec2::ZERO;
+            }
         }
 
         // Check if the bot has not made any significant progress for a long time
@@ -87,12 +156,6 @@ pub fn move_velocity_system(
     }
 }
 
-#[derive(Clone, Copy)]
-struct SeparationEntry {
-    entity: Entity,
-    position: Vec2,
-}
-
 fn coincident_pair_direction(first: Entity, second: Entity) -> Vec2 {
     let mixed =
         first.to_bits().wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ second.to_bits().rotate_left(32);
@@ -100,64 +163,59 @@ fn coincident_pair_direction(first: Entity, second: Entity) -> Vec2 {
     Vec2::new(angle.cos(), angle.sin())
 }
 
-fn separation_deltas(entries: &[SeparationEntry]) -> Vec<(Entity, Vec2)> {
-    let mut sorted = entries.to_vec();
-    sorted.sort_by_key(|entry| entry.entity.to_bits());
-
-    let mut buckets = FixedSpatialBuckets::new(BOT_RADIUS * 2.0);
-    for entry in &sorted {
-        buckets.insert(entry.position, *entry);
-    }
-    buckets.sort_entries_by(|left, right| left.entity.to_bits().cmp(&right.entity.to_bits()));
-
-    let mut deltas: Vec<(Entity, Vec2)> = sorted
-        .iter()
+/// Pairwise separation pushes for every indexed nanobot, sorted by
+/// entity bits. Each overlapping pair is pushed once, from the lower
+/// entity's side.
+fn separation_deltas(index: &SpatialIndex) -> Vec<(Entity, Vec2)> {
+    let mut deltas: Vec<(Entity, Vec2)> = index
+        .entries(SpatialLayer::Nanobot)
         .map(|entry| (entry.entity, Vec2::ZERO))
         .collect();
-    for entry in &sorted {
-        let bucket = buckets.bucket_for_position(entry.position);
-        for (_, neighbours) in buckets.neighbourhood(bucket, 1) {
-            for other in neighbours {
-                if other.entity.to_bits() <= entry.entity.to_bits() {
-                    continue;
-                }
-                let offset = entry.position - other.position;
-                if offset.length_squared() >= (BOT_RADIUS * 2.0).powi(2) {
-                    continue;
-                }
-                let direction = if offset.length_squared() < 1e-6 {
-                    coincident_pair_direction(entry.entity, other.entity)
-                } else {
-                    offset.normalize()
-                };
-                let force = direction * BOT_SEPARATION_FORCE;
-                let first = deltas
-                    .binary_search_by_key(&entry.entity.to_bits(), |(entity, _)| entity.to_bits())
-                    .expect("snapshot entity must have a delta");
-                let second = deltas
-                    .binary_search_by_key(&other.entity.to_bits(), |(entity, _)| entity.to_bits())
-                    .expect("neighbour entity must have a delta");
-                deltas[first].1 += force;
-                deltas[second].1 -= force;
+    deltas.sort_by_key(|(entity, _)| entity.to_bits());
+    let slot = |deltas: &[(Entity, Vec2)], entity: Entity| {
+        deltas
+            .binary_search_by_key(&entity.to_bits(), |(entity, _)| entity.to_bits())
+            .expect("indexed entity must have a delta")
+    };
+    for entry in index.entries(SpatialLayer::Nanobot) {
+        for other in index.within_radius(
+            entry.position,
+            BOT_RADIUS * 2.0,
+            SpatialLayer::Nanobot,
+            SwarmFilter::Any,
+        ) {
+            if other.entity.to_bits() <= entry.entity.to_bits() {
+                continue;
+            }
+            let offset = entry.position - other.position;
+            if offset.length_squared() >= (BOT_RADIUS * 2.0).powi(2) {
+                continue;
             }
+            let direction = if offset.length_squared() < 1e-6 {
+                coincident_pair_direction(entry.entity, other.entity)
+            } else {
+                offset.normalize()
+            };
+            let force = direction * BOT_SEPARATION_FORCE;
+            let first = slot(&deltas, entry.entity);
+            let second = slot(&deltas, other.entity);
+            deltas[first].1 += force;
+            deltas[second].1 -= force;
         }
     }
     deltas
 }
 
+/// Push overlapping nanobots apart, using the shared
+/// [`SpatialIndex`] for neighbour lookups.
 pub fn separation_system(
-    snapshots: Query<(Entity, &Transform), With<Nanobot>>,
+    index: Res<SpatialIndex>,
     mut velocities: Query<&mut VelocityComponent, With<Nanobot>>,
 ) {
-    let entries: Vec<_> = snapshots
-        .iter()
-        .map(|(entity, transform)| SeparationEntry {
-            entity,
-            position: transform.translation.truncate(),
-        })
-        .collect();
-
-    for (entity, delta) in separation_deltas(&entries) {
+    for (entity, delta) in separation_deltas(&index) {
+        if delta == Vec2::ZERO {
+            continue;
+        }
         if let Ok(mut velocity) = velocities.get_mut(entity) {
             velocity.value += delta;
         }
@@ -176,6 +234,11 @@ pub fn rotation_for_direction(direction: Vec2) -> Option<Quat> {
 
 pub fn velocity_system(mut query: Query<(&mut VelocityComponent, &mut Transform)>) {
     for (mut velocity, mut transform) in query.iter_mut() {
+        // Resting bots keep their transform untouched so change
+        // detection (and the spatial index) only sees real movement.
+        if velocity.value == Vec2::ZERO {
+            continue;
+        }
         transform.translation += velocity.value.extend(0.);
         if let Some(rotation) = rotation_for_direction(velocity.value) {
             transform.rotation = rotation;
@@ -191,6 +254,7 @@ mod tests {
     use bevy::prelude::EulerRot;
 
     use super::*;
+    use crate::nanobot::spatial_index::SpatialEntry;
 
     fn rotation_z(direction: Vec2) -> f32 {
         let rotation = rotation_for_direction(direction).expect("moving direction should rotate");
@@ -229,25 +293,29 @@ mod tests {
         Entity::from_bits(bits)
     }
 
+    fn index_of(bots: &[(Entity, Vec2)]) -> SpatialIndex {
+        let mut index = SpatialIndex::default();
+        for (entity, position) in bots {
+            index.insert(SpatialEntry {
+                entity: *entity,
+                position: *position,
+                layer: SpatialLayer::Nanobot,
+                swarm: None,
+            });
+        }
+        index
+    }
+
     #[test]
     fn separation_only_pushes_nearby_pairs_once() {
         let first = entity(1);
         let second = entity(2);
         let distant = entity(3);
-        let deltas = separation_deltas(&[
-            SeparationEntry {
-                entity: first,
-                position: Vec2::ZERO,
-            },
-            SeparationEntry {
-                entity: second,
-                position: Vec2::X,
-            },
-            SeparationEntry {
-                entity: distant,
-                position: Vec2::splat(BOT_RADIUS * 10.0),
-            },
-        ]);
+        let deltas = separation_deltas(&index_of(&[
+            (first, Vec2::ZERO),
+            (second, Vec2::X),
+            (distant, Vec2::splat(BOT_RADIUS * 10.0)),
+        ]));
 
         let first_delta = deltas.iter().find(|(id, _)| *id == first).unwrap().1;
         let second_delta = deltas.iter().find(|(id, _)| *id == second).unwrap().1;
@@ -259,18 +327,9 @@ mod tests {
 
     #[test]
     fn coincident_pair_separation_is_deterministic_and_finite() {
-        let entries = [
-            SeparationEntry {
-                entity: entity(1),
-                position: Vec2::ZERO,
-            },
-            SeparationEntry {
-                entity: entity(2),
-                position: Vec2::ZERO,
-            },
-        ];
-        let first = separation_deltas(&entries);
-        let second = separation_deltas(&entries);
+        let index = index_of(&[(entity(1), Vec2::ZERO), (entity(2), Vec2::ZERO)]);
+        let first = separation_deltas(&index);
+        let second = separation_deltas(&index);
 
         assert_eq!(first, second);
         assert!(first.iter().all(|(_, delta)| delta.is_finite()));
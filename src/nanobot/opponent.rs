@@ -16,7 +16,6 @@
 
 use bevy::prelude::*;
 
-use crate::ai::AiStateComponent;
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::autonomy::Commitment;
 use crate::nanobot::components::{Health, Nanobot, Swarm, SwarmId, SwarmMember, VelocityComponent};
@@ -120,7 +119,6 @@ pub fn spawn_opponent_swarm(
                     nanobot: Nanobot {},
                     nanobot_type: seed.kind,
                     velocity: VelocityComponent::default(),
-                    ai_state: AiStateComponent::new(),
                     health: Health::default(),
                     swarm_member: SwarmMember::new(swarm_id),
                 },
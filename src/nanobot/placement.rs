@@ -291,7 +291,7 @@ fn seeded_build_zone_candidates(build_cells: &[IVec2], kind_seed: u32) -> Vec<(u
     let mut candidates =
         Vec::with_capacity(build_cells.len() * BUILD_ZONE_RANDOM_CANDIDATES_PER_CELL as usize);
     for &cell in build_cells {
-        let center = crate::ai::get_world_from_zone(cell);
+        let center = crate::nanobot::get_world_from_zone(cell);
         for candidate_index in 0..BUILD_ZONE_RANDOM_CANDIDATES_PER_CELL {
             let index = kind_seed
                 .wrapping_mul(0x9E37_79B9)
@@ -326,7 +326,7 @@ fn find_dense_build_zone_placement(
         )
     });
     for cell in cells {
-        let center = crate::ai::get_world_from_zone(cell);
+        let center = crate::nanobot::get_world_from_zone(cell);
         let start = (splitmix64(mix_hash(kind_seed, cell)) % candidate_count as u64) as u32;
         for step in 0..candidate_count {
             // 353 is coprime with the 27x27 lattice, so every point is visited.
@@ -380,7 +380,7 @@ pub fn find_defend_zone_placement(
     obstacles: &[(Vec2, f32)],
     kind_seed: u32,
 ) -> Option<Vec2> {
-    let center = crate::ai::get_world_from_zone(cell);
+    let center = crate::nanobot::get_world_from_zone(cell);
     let radii = [0.0, 96.0, 160.0, BUILD_ZONE_PLACEMENT_MAX_OFFSET];
     let angles = placement_angles(8);
     for (radius_index, radius) in radii.iter().enumerate() {
@@ -950,7 +950,7 @@ mod tests {
         let cell = IVec2::new(3, -2);
         let first = find_build_zone_placement(&[cell], &[], 27).unwrap();
         let second = find_build_zone_placement(&[cell], &[], 27).unwrap();
-        let center = crate::ai::get_world_from_zone(cell);
+        let center = crate::nanobot::get_world_from_zone(cell);
 
         assert_eq!(first, second);
         assert_eq!(first.0, cell);
@@ -10,12 +10,18 @@
 //! 1. A demand system creates a [`PlannedStructure`] in a
 //!    cell. It is visible from the moment it exists, with a
 //!    distinct "planned" visual (see [`planned_visual_color`]).
-//! 2. A single Worker can claim the planned structure by
-//!    becoming its `active_worker`. Other Workers see a
-//!    claimed planned structure as unavailable.
-//! 3. While the worker is at the site, build progress is
-//!    `work_remaining` ticks of worker time. V1 consumes no
-//!    minerals; the only cost is worker time.
+//! 2. A Worker claims the planned structure by becoming its
+//!    `active_worker`. Small builds stay single-worker; a
+//!    build with enough work left opens extra work slots
+//!    ([`PlannedStructure::work_slots`]) that further Workers
+//!    fill as helpers, each adding less than the last
+//!    ([`crew_work_rate`]).
+//! 3. While the crew is at the site, build progress is
+//!    `work_remaining` ticks of worker time. By default that is
+//!    the only cost; with [`crate::nanobot::ConstructionCost`]
+//!    enabled, haulers must also deliver the kind's
+//!    [`PlannedKind::mineral_cost`] to the site, and work only
+//!    advances as far as the delivered material pays for.
 //! 4. When `work_remaining` reaches 0, the planned structure
 //!    is replaced by the appropriate completed structure for
 //!    its kind. The foundation slice ships four kinds:
@@ -26,14 +32,18 @@
 //!    [`PlannedKind::ProductionFacility`] (completes into a
 //!    [`crate::nanobot::ProductionFacility`], issue #27), and
 //!    [`PlannedKind::Charger`] (completes into a
-//!    [`crate::nanobot::Charger`], issue #28).
+//!    [`crate::nanobot::Charger`], issue #28). Research adds
+//!    [`PlannedKind::ResearchLab`] (completes into a
+//!    [`crate::nanobot::ResearchLab`]). Corridor paint adds
+//!    [`PlannedKind::Road`] (completes into a
+//!    [`crate::nanobot::Road`]).
 //!
 //! State machine carried on the worker by marker components:
 //!
 //! ```text
 //!   Idle -> (claim system) -> Moving (PlannedStructureClaim + DMC)
 //!   Moving -> (arrive system) -> Working (PlannedStructureProgress)
-//!   Working -> (work system) -> Working (work_remaining -= 1 each tick)
+//!   Working -> (work system) -> Working (work_remaining -= crew rate)
 //!   Working -> (work_remaining == 0) -> Idle (planned promoted)
 //! ```
 //!
@@ -61,24 +71,39 @@ use bevy::prelude::*;
 
 use crate::GAMEPLAY_SPRITE_Z;
 use crate::intent::{IntentGrid, IntentKind};
-use crate::nanobot::autonomy::NanobotType;
+use crate::nanobot::autonomy::{NanobotType, SoftWorkSlots};
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState};
 use crate::nanobot::components::{DirectMovementComponent, Nanobot, Swarm, SwarmId, SwarmMember};
+use crate::nanobot::construction_cost::{
+    PlannedMaterial, attach_planned_material_system, drop_planned_material, material_allows_work,
+    spend_planned_material,
+};
 use crate::nanobot::gather::world_to_cell;
 use crate::nanobot::placement::{
     BUILDING_FOOTPRINT_RADIUS, find_build_zone_placement, scaled_building_footprint_radius,
 };
 use crate::nanobot::production::{OwnerSwarm, ProductionFacility};
+use crate::nanobot::reclaim::{PlannedReclaim, complete_reclaim};
+use crate::nanobot::structure_tier::{PlannedUpgrade, apply_structure_tier};
 use crate::resources::{ResourceDeposit, ResourceKind, Stockpile, StockpileRole};
 use crate::structure_sprites::{StructureSprites, StructureVisual, StructureVisualState};
 
-/// Number of worker-time ticks required to finish a planned
-/// structure. V1 consumes no minerals; the only cost is this
-/// counter decrementing each tick the worker is at the
-/// planned structure. Picked to be small enough that a single
-/// worker finishes the demo build in a handful of ticks so
-/// the test math is obvious.
+/// Number of worker-time ticks required to finish a one-bot
+/// planned structure (a stockpile or road segment). Without
+/// material costs this counter decrementing each tick the worker
+/// is at the planned structure is the only cost. Picked to be
+/// small enough that a single worker finishes the demo build in a
+/// handful of ticks so the test math is obvious. Larger kinds
+/// budget several times this; see [`PlannedKind::build_work_ticks`].
 pub const DEFAULT_PLANNED_WORK_TICKS: u32 = 5;
 
+/// Worker-time ticks of remaining work each build work slot
+/// stands for. A build opens one slot per full chunk, capped by
+/// [`PlannedKind::build_work_slots`], so a helper is only sent
+/// where there is enough work left to be worth the walk. One-bot
+/// kinds budget exactly one chunk.
+pub const PLANNED_WORK_TICKS_PER_SLOT: u32 = DEFAULT_PLANNED_WORK_TICKS;
+
 /// Footprint (world units) used for both the planned and
 /// completed visuals. A square so the structure is clearly
 /// bounded on the map and tests can compare positions without
@@ -138,6 +163,16 @@ pub enum PlannedKind {
     /// the enum because `PlannedStructure` already records
     /// it.
     Charger,
+    /// Completes into a [`crate::nanobot::ResearchLab`]. The kind
+    /// emerges from queued research: a swarm with a non-empty
+    /// research queue and no lab plans one inside an owned
+    /// Build Zone cell.
+    ResearchLab,
+    /// Completes into a [`crate::nanobot::Road`] segment. The kind
+    /// emerges from owned Corridor paint when
+    /// [`crate::nanobot::CorridorRoads`] is enabled: each painted
+    /// cell without a road plans one.
+    Road,
 }
 
 impl PlannedKind {
@@ -149,12 +184,14 @@ impl PlannedKind {
             PlannedKind::SinkStockpile => 1,
             PlannedKind::ProductionFacility => 2,
             PlannedKind::Charger => 3,
+            PlannedKind::ResearchLab => 4,
+            PlannedKind::Road => 5,
         }
     }
 
     /// Number of distinct planned kinds the foundation slice
     /// models.
-    pub const COUNT: usize = 4;
+    pub const COUNT: usize = 6;
 
     /// Every planned kind in stable declaration order. Useful
     /// for tests and future "iterate every kind" loops.
@@ -163,48 +200,124 @@ impl PlannedKind {
         PlannedKind::SinkStockpile,
         PlannedKind::ProductionFacility,
         PlannedKind::Charger,
+        PlannedKind::ResearchLab,
+        PlannedKind::Road,
     ];
+
+    /// Minerals haulers must deliver to a planned site of this kind
+    /// before its build finishes, when
+    /// [`crate::nanobot::ConstructionCost`] is
+    /// enabled. Stockpiles cost half a hauler load; a Production
+    /// Facility or Research Lab costs as much as two nanobots. A road
+    /// segment is a quarter of a hauler load.
+    pub const fn mineral_cost(self) -> u32 {
+        match self {
+            PlannedKind::Road => 5,
+            PlannedKind::SourceStockpile | PlannedKind::SinkStockpile => 10,
+            PlannedKind::Charger => 20,
+            PlannedKind::ProductionFacility | PlannedKind::ResearchLab => 40,
+        }
+    }
+
+    /// Worker-time ticks a fresh site of this kind needs. One-bot
+    /// kinds are a single [`PLANNED_WORK_TICKS_PER_SLOT`] chunk; a
+    /// Charger or a Production Facility / Research Lab budgets
+    /// enough chunks that a full crew stays busy for most of the
+    /// build.
+    pub const fn build_work_ticks(self) -> u32 {
+        match self {
+            PlannedKind::SourceStockpile | PlannedKind::SinkStockpile | PlannedKind::Road => {
+                DEFAULT_PLANNED_WORK_TICKS
+            }
+            PlannedKind::Charger => PLANNED_WORK_TICKS_PER_SLOT * 4,
+            PlannedKind::ProductionFacility | PlannedKind::ResearchLab => {
+                PLANNED_WORK_TICKS_PER_SLOT * 10
+            }
+        }
+    }
+
+    /// Most Workers that can build a site of this kind at once.
+    /// Stockpiles and roads are a one-bot job; larger footprints leave room
+    /// for a crew.
+    pub const fn build_work_slots(self) -> u32 {
+        match self {
+            PlannedKind::SourceStockpile | PlannedKind::SinkStockpile | PlannedKind::Road => 1,
+            PlannedKind::Charger => 2,
+            PlannedKind::ProductionFacility | PlannedKind::ResearchLab => 3,
+        }
+    }
+}
+
+/// Work slots a `kind` site with `work_remaining` ticks left
+/// offers: one per [`PLANNED_WORK_TICKS_PER_SLOT`], at least one,
+/// at most [`PlannedKind::build_work_slots`].
+pub const fn planned_work_slots(kind: PlannedKind, work_remaining: u32) -> u32 {
+    let slots = work_remaining / PLANNED_WORK_TICKS_PER_SLOT;
+    let max = kind.build_work_slots();
+    if slots == 0 {
+        1
+    } else if slots > max {
+        max
+    } else {
+        slots
+    }
+}
+
+/// Worker-time ticks a crew of `workers` adds per tick. The first
+/// Worker adds a full tick and each extra one adds the next
+/// [`SoftWorkSlots::crowding_factor`] share (1, 1/2, 1/3, ...), so a
+/// crew always helps but never scales linearly.
+pub fn crew_work_rate(workers: u32) -> f32 {
+    (0..workers).map(SoftWorkSlots::crowding_factor).sum()
 }
 
 /// A visible, not-yet-built support structure. Lives in a
-/// single intent cell. The `active_worker` field is the
-/// one-Worker reservation the lifecycle promises: it is
-/// `Some(worker)` while a Worker is committed to the build,
-/// and `None` while the planned structure is unclaimed.
+/// single intent cell. The `active_worker` field is the lead
+/// Worker's reservation: it is `Some(worker)` while a Worker is
+/// committed to the build, and `None` while the planned
+/// structure is unclaimed. Helpers filling the extra
+/// [`PlannedStructure::work_slots`] are counted by the
+/// allocator's claims, not recorded here.
 ///
 /// `work_remaining` is the build budget in worker-time
-/// ticks. The work system decrements it by 1 each tick the
-/// assigned worker is in working state; reaching 0 triggers
-/// the promotion to the completed structure.
+/// ticks. The work system lowers it by [`crew_work_rate`] each
+/// tick the crew is in working state, keeping the fractional
+/// part in `work_carry`; reaching 0 triggers the promotion to
+/// the completed structure.
 #[derive(Debug, Component, Clone, Copy)]
 pub struct PlannedStructure {
     pub kind: PlannedKind,
     pub cell: IVec2,
     pub work_remaining: u32,
     pub active_worker: Option<Entity>,
+    pub work_carry: f32,
 }
 
 impl PlannedStructure {
     /// Build a fresh planned structure of `kind` in `cell` with
-    /// the default work budget and no active worker.
+    /// the kind's work budget and no active worker.
     pub fn new(kind: PlannedKind, cell: IVec2) -> Self {
         Self {
             kind,
             cell,
-            work_remaining: DEFAULT_PLANNED_WORK_TICKS,
+            work_remaining: kind.build_work_ticks(),
             active_worker: None,
+            work_carry: 0.0,
         }
     }
 
-    /// True when no Worker has claimed this planned structure.
-    /// The "at most one Worker" contract is enforced by the
-    /// claim system only targeting unclaimed planned
-    /// structures, so a `true` return is the only state in
-    /// which a new claim is allowed.
+    /// True when no lead Worker has claimed this planned
+    /// structure. A single-slot build only accepts a new claim
+    /// in this state; a multi-slot build also takes helpers.
     pub fn is_unclaimed(&self) -> bool {
         self.active_worker.is_none()
     }
 
+    /// Workers that may build this site at once right now.
+    pub fn work_slots(&self) -> u32 {
+        planned_work_slots(self.kind, self.work_remaining)
+    }
+
     /// True when build progress has finished and the planned
     /// structure is ready to be promoted to the completed
     /// structure for its kind.
@@ -253,6 +366,14 @@ pub struct PlannedStructureProgress {
     pub target: Entity,
 }
 
+impl BehaviourState for PlannedStructureClaim {
+    const ROLE: BehaviourRole = BehaviourRole::BUILD;
+}
+
+impl BehaviourState for PlannedStructureProgress {
+    const ROLE: BehaviourRole = BehaviourRole::BUILD;
+}
+
 /// Planning-time snapshot of the type most under target when a
 /// [`PlannedKind::ProductionFacility`] plan is created.
 ///
@@ -265,9 +386,10 @@ pub struct PlannedProductionTarget(pub NanobotType);
 /// Plan Sink Stockpiles only when sink-side storage has a real
 /// nearby consumer. Raw Build paint is only a placement constraint:
 /// it does not create construction demand by itself. A pending or
-/// completed Production Facility / Charger in a Build cell asks for
-/// one local Sink Stockpile, placed in that same owned Build cell
-/// without overlapping deposits or other support structures.
+/// completed Production Facility or Research Lab in a Build cell
+/// asks for one local Sink Stockpile, placed in that same owned
+/// Build cell without overlapping deposits or other support
+/// structures.
 #[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn sink_stockpile_demand_system(
     mut commands: Commands,
@@ -282,6 +404,7 @@ pub fn sink_stockpile_demand_system(
     )>,
     facilities: Query<(&Transform, Option<&OwnerSwarm>), With<ProductionFacility>>,
     chargers: Query<(&Transform, Option<&OwnerSwarm>), With<crate::nanobot::Charger>>,
+    labs: Query<(&Transform, Option<&OwnerSwarm>), With<crate::nanobot::ResearchLab>>,
     deposits: Query<(&ResourceDeposit, &Transform)>,
     swarms: Query<(Entity, &SwarmId), With<Swarm>>,
 ) {
@@ -299,16 +422,19 @@ pub fn sink_stockpile_demand_system(
     // Planned Structures of any kind are in the obstacle
     // list so a fresh Sink Stockpile cannot overlap a
     // pending Production Facility or Charger plan. Only
-    // Production Facility plans satisfy sink-side demand:
-    // chargers are direct-delivery terminals and do not
-    // auto-plan Sink Stockpiles (ADR-0005).
+    // Production Facility and Research Lab plans create
+    // sink-side demand: chargers are direct-delivery terminals
+    // and do not auto-plan Sink Stockpiles (ADR-0005).
     let mut demand_sites: Vec<(IVec2, Option<Entity>)> = Vec::new();
     for (planned_structure, transform, owner) in &planned {
         obstacles.push((
             transform.translation.truncate(),
             scaled_building_footprint_radius(transform),
         ));
-        if planned_structure.kind == PlannedKind::ProductionFacility {
+        if matches!(
+            planned_structure.kind,
+            PlannedKind::ProductionFacility | PlannedKind::ResearchLab
+        ) {
             demand_sites.push((
                 world_to_cell(transform.translation.truncate()),
                 owner.map(|o| o.0),
@@ -328,7 +454,14 @@ pub fn sink_stockpile_demand_system(
         ));
     }
 
-    for (transform, owner) in &facilities {
+    for (transform, _) in &labs {
+        obstacles.push((
+            transform.translation.truncate(),
+            scaled_building_footprint_radius(transform),
+        ));
+    }
+
+    for (transform, owner) in facilities.iter().chain(labs.iter()) {
         demand_sites.push((
             world_to_cell(transform.translation.truncate()),
             owner.map(|o| o.0),
@@ -617,17 +750,20 @@ pub fn worker_planned_structure_arrive_system(
     }
 }
 
-/// Worker planned-structure work system. For each worker with
-/// a [`PlannedStructureProgress`], decrement the planned
-/// structure's `work_remaining` by 1 and, on the tick it
-/// reaches 0, promote the planned structure to its completed
-/// form.
+/// Worker planned-structure work system. Workers with a
+/// [`PlannedStructureProgress`] are grouped by target; each
+/// site's `work_remaining` drops by its crew's
+/// [`crew_work_rate`] and, on the tick it reaches 0, the
+/// planned structure is promoted to its completed form.
 ///
-/// The worker stays at the site until the build finishes or
+/// The crew stays at the site until the build finishes or
 /// the planned structure is removed; the reservation is
-/// cleared on promotion so the worker returns to idle. V1
-/// does not consume any minerals, so the resource ledger and
-/// local stockpiles are untouched.
+/// cleared on promotion so every worker returns to idle. Local
+/// stockpiles are untouched: with material costs enabled,
+/// minerals arrive by hauler into the site's [`PlannedMaterial`],
+/// a tick the delivered material does not pay for releases the
+/// crew and the claim instead, and promotion spends the
+/// delivered material out of the owner's ledger.
 #[allow(clippy::type_complexity)]
 pub fn worker_planned_structure_work_system(
     mut commands: Commands,
@@ -641,36 +777,76 @@ pub fn worker_planned_structure_work_system(
         &mut PlannedStructure,
         &Transform,
         Option<&PlannedProductionTarget>,
+        Option<&PlannedUpgrade>,
+        Has<PlannedReclaim>,
+        Option<&PlannedMaterial>,
     )>,
 ) {
+    let mut crews = std::collections::BTreeMap::<Entity, Vec<Entity>>::new();
     for (worker_entity, progress) in &workers {
-        let Ok((planned_entity, mut planned_state, planned_transform, first_target)) =
-            planned.get_mut(progress.target)
+        crews
+            .entry(progress.target)
+            .or_default()
+            .push(worker_entity);
+    }
+    for (target, crew) in crews {
+        let Ok((
+            planned_entity,
+            mut planned_state,
+            planned_transform,
+            first_target,
+            upgrade,
+            reclaim,
+            material,
+        )) = planned.get_mut(target)
         else {
-            commands
-                .entity(worker_entity)
-                .remove::<PlannedStructureClaim>()
-                .remove::<PlannedStructureProgress>();
+            for worker_entity in crew {
+                release_planned_worker(&mut commands, worker_entity);
+            }
             continue;
         };
         let first_target = first_target.copied().map(|target| target.0);
 
-        if planned_state.is_complete() {
-            promote_planned_to_completion(
-                &mut commands,
-                planned_entity,
-                planned_state.kind,
-                planned_transform.translation.truncate(),
-                planned_state.cell,
-                first_target,
-                &structure_sprites,
-            );
-            release_planned_worker(&mut commands, worker_entity);
-            continue;
+        if !planned_state.is_complete() {
+            if !material_allows_work(material, &planned_state) {
+                // Hand the site back until haulers catch up; the
+                // projection offers it again once material lands.
+                planned_state.active_worker = None;
+                planned_state.work_carry = 0.0;
+                for worker_entity in crew {
+                    release_planned_worker(&mut commands, worker_entity);
+                }
+                continue;
+            }
+            let carry = planned_state.work_carry + crew_work_rate(crew.len() as u32);
+            let ticks = carry.floor();
+            planned_state.work_carry = carry - ticks;
+            // Material is checked per tick so a crew never works
+            // past what has been delivered.
+            for _ in 0..ticks as u32 {
+                if planned_state.is_complete() || !material_allows_work(material, &planned_state) {
+                    break;
+                }
+                planned_state.work_remaining -= 1;
+            }
+            if !planned_state.is_complete() {
+                continue;
+            }
         }
 
-        planned_state.work_remaining = planned_state.work_remaining.saturating_sub(1);
-        if planned_state.is_complete() {
+        if let Some(&PlannedUpgrade { tier }) = upgrade {
+            // An upgrade plan sits on a structure that is already built.
+            // Keep its buffer and visual; only the tier changes.
+            commands
+                .entity(planned_entity)
+                .remove::<(PlannedStructure, PlannedUpgrade)>()
+                .queue(move |entity: EntityWorldMut| apply_structure_tier(entity, tier));
+        } else if reclaim {
+            commands
+                .entity(planned_entity)
+                .remove::<(PlannedStructure, PlannedReclaim)>()
+                .queue(complete_reclaim);
+        } else {
             promote_planned_to_completion(
                 &mut commands,
                 planned_entity,
@@ -680,6 +856,8 @@ pub fn worker_planned_structure_work_system(
                 first_target,
                 &structure_sprites,
             );
+        }
+        for worker_entity in crew {
             release_planned_worker(&mut commands, worker_entity);
         }
     }
@@ -687,10 +865,9 @@ pub fn worker_planned_structure_work_system(
 
 /// Release a worker that was building a planned structure:
 /// clear both the claim and the progress markers so the
-/// worker returns to the idle state. The build cell's slot is
-/// not modelled for v1 (the planned structure is consumed
-/// before the worker is free), so there is no slot to
-/// release.
+/// worker returns to the idle state. Build work slots are
+/// counted from the allocator's leases, which end with the
+/// markers, so there is no slot to release here.
 fn release_planned_worker(commands: &mut Commands, worker_entity: Entity) {
     commands
         .entity(worker_entity)
@@ -726,6 +903,10 @@ fn release_planned_worker(commands: &mut Commands, worker_entity: Entity) {
 ///   `first_target` is unused for this kind; the
 ///   pre-existing test fixtures that pre-spawn a Charger
 ///   already establish the default-shape contract.
+/// - [`PlannedKind::ResearchLab`] completes into an empty
+///   [`crate::nanobot::ResearchLab`]. `OwnerSwarm` is preserved.
+/// - [`PlannedKind::Road`] completes into a [`crate::nanobot::Road`]
+///   on the planned cell. `OwnerSwarm` is preserved.
 fn promote_planned_to_completion(
     commands: &mut Commands,
     planned_entity: Entity,
@@ -736,6 +917,10 @@ fn promote_planned_to_completion(
     structure_sprites: &StructureSprites,
 ) {
     let visual = completed_visual_bundle(kind, structure_sprites, world_pos);
+    // Delivered material is spent by the build.
+    commands
+        .entity(planned_entity)
+        .queue(spend_planned_material);
     match kind {
         PlannedKind::SourceStockpile => {
             commands.entity(planned_entity).remove::<PlannedStructure>();
@@ -782,6 +967,21 @@ fn promote_planned_to_completion(
             commands.entity(planned_entity).remove::<PlannedStructure>();
             commands.entity(planned_entity).insert((charger, visual));
         }
+        PlannedKind::ResearchLab => {
+            // Like a Production Facility, a completed lab is an empty
+            // terminal hopper; haulers must deliver before research
+            // can be funded.
+            commands.entity(planned_entity).remove::<PlannedStructure>();
+            commands
+                .entity(planned_entity)
+                .insert((crate::nanobot::ResearchLab::new(), visual));
+        }
+        PlannedKind::Road => {
+            commands.entity(planned_entity).remove::<PlannedStructure>();
+            commands
+                .entity(planned_entity)
+                .insert((crate::nanobot::Road { cell }, visual));
+        }
     }
 }
 
@@ -810,7 +1010,7 @@ pub(crate) fn planned_visual_components(
 /// planned-structure kind. Bevy replaces the planned
 /// `Sprite` on `insert`, so the planned visual does not
 /// leak through to the completed entity.
-fn completed_visual_bundle(
+pub(crate) fn completed_visual_bundle(
     kind: PlannedKind,
     structure_sprites: &StructureSprites,
     world_pos: Vec2,
@@ -851,10 +1051,14 @@ pub struct PlannedStructurePlugin;
 
 impl Plugin for PlannedStructurePlugin {
     fn build(&self, app: &mut App) {
+        app.add_behaviour_state::<PlannedStructureClaim>()
+            .add_behaviour_state::<PlannedStructureProgress>()
+            .add_observer(drop_planned_material);
         app.add_systems(
             FixedUpdate,
             (
                 sink_stockpile_demand_system,
+                attach_planned_material_system,
                 worker_planned_structure_arrive_system,
                 worker_planned_structure_work_system,
             )
@@ -910,6 +1114,41 @@ mod tests {
         assert!(p.is_complete());
     }
 
+    #[test]
+    fn work_slots_open_with_remaining_work_up_to_the_kind_cap() {
+        let mut p = PlannedStructure::new(PlannedKind::ProductionFacility, IVec2::ZERO);
+        assert_eq!(
+            p.work_slots(),
+            PlannedKind::ProductionFacility.build_work_slots(),
+            "a fresh facility opens its full crew"
+        );
+        p.work_remaining = PLANNED_WORK_TICKS_PER_SLOT * 2;
+        assert_eq!(p.work_slots(), 2);
+        p.work_remaining = PLANNED_WORK_TICKS_PER_SLOT * 10;
+        assert_eq!(
+            p.work_slots(),
+            PlannedKind::ProductionFacility.build_work_slots()
+        );
+        p.work_remaining = 0;
+        assert_eq!(p.work_slots(), 1);
+        assert_eq!(
+            planned_work_slots(PlannedKind::SinkStockpile, PLANNED_WORK_TICKS_PER_SLOT * 10),
+            1
+        );
+    }
+
+    #[test]
+    fn each_extra_crew_member_adds_less_than_the_last() {
+        assert_eq!(crew_work_rate(0), 0.0);
+        assert!((crew_work_rate(1) - 1.0).abs() < 1e-6);
+        let mut previous_gain = 1.0;
+        for workers in 2..=4 {
+            let gain = crew_work_rate(workers) - crew_work_rate(workers - 1);
+            assert!(gain > 0.0 && gain < previous_gain);
+            previous_gain = gain;
+        }
+    }
+
     #[test]
     fn planned_visual_color_is_distinct_from_completed() {
         // The visual contract is "visibly distinct from
@@ -944,6 +1183,17 @@ mod tests {
         const { assert!(DEFAULT_PLANNED_WORK_TICKS <= 32) };
     }
 
+    #[test]
+    fn every_crew_sized_kind_starts_with_its_full_crew() {
+        for kind in PlannedKind::ALL {
+            assert_eq!(
+                planned_work_slots(kind, kind.build_work_ticks()),
+                kind.build_work_slots(),
+                "{kind:?}"
+            );
+        }
+    }
+
     #[test]
     fn footprint_is_a_finite_positive_square() {
         // The visual footprint must be positive so the planned
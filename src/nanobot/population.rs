@@ -67,7 +67,8 @@ pub fn population_demand_system(
                 let (kind, slots) = match opportunity.category {
                     OpportunityCategory::Gather
                     | OpportunityCategory::PlannedBuild
-                    | OpportunityCategory::Maintenance => (NanobotType::Worker, 1),
+                    | OpportunityCategory::Maintenance
+                    | OpportunityCategory::Repair => (NanobotType::Worker, 1),
                     OpportunityCategory::Defend => {
                         (NanobotType::Defender, opportunity.available_work.max(1))
                     }
@@ -34,12 +34,23 @@
 //! Defender) cost the same number of minerals and take the same
 //! number of ticks to produce. Differentiated costs are a
 //! follow-up issue per the PRD.
+//!
+//! ## Per-facility control
+//!
+//! The default stays intent-first: an idle facility builds whatever
+//! typed demand needs most. A facility can optionally be narrowed or
+//! overridden through [`ProductionFacilityCommand`]:
+//!
+//! - a specialization limits demand picks to one type,
+//! - queued orders start ahead of demand, head first,
+//! - a pinned type is rebuilt every cycle regardless of demand,
+//! - cancelling the current target returns its cost to the hopper,
+//! - a speed upgrade spends delivered minerals to shorten cycles.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use bevy::prelude::*;
 
-use crate::ai::AiStateComponent;
 use crate::intent::{IntentGrid, IntentKind};
 use crate::nanobot::autonomy::{Commitment, NanobotType};
 use crate::nanobot::components::{Health, Nanobot, Swarm, SwarmId, SwarmMember, VelocityComponent};
@@ -49,6 +60,9 @@ use crate::nanobot::placement::{find_build_zone_placement, scaled_building_footp
 use crate::nanobot::planned::{
     PlannedKind, PlannedProductionTarget, PlannedStructure, planned_visual_components,
 };
+use crate::nanobot::reclaim::{PlannedReclaim, spawn_reclaimed_cache};
+use crate::nanobot::structure_tier::PlannedUpgrade;
+use crate::nanobot::telemetry::SwarmTelemetry;
 use crate::nanobot::{NanobotBundle, NanobotSprites};
 use crate::resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile};
 use crate::structure_sprites::StructureSprites;
@@ -71,6 +85,25 @@ pub const PRODUCTION_TICKS_PER_BOT: u32 = 120;
 /// stockpile scale.
 pub const PRODUCTION_INPUT_CAPACITY: u32 = 40;
 
+/// Highest speed level a [`ProductionFacility`] can be upgraded to.
+pub const PRODUCTION_FACILITY_MAX_LEVEL: u32 = 2;
+
+/// Minerals drawn from the input hopper to raise a facility's speed
+/// level by one. Fits in a full hopper so an upgrade never needs more
+/// than one batch of deliveries.
+pub const PRODUCTION_UPGRADE_COST: u32 = PRODUCTION_INPUT_CAPACITY;
+
+/// Most orders a facility queue holds. Keeps the queue inspectable at a
+/// glance and stops a held button from filling it without bound.
+pub const PRODUCTION_QUEUE_LIMIT: usize = 5;
+
+/// Cycle length for a facility at `level`. Level 0 is
+/// [`PRODUCTION_TICKS_PER_BOT`]; each level adds half the base rate, so
+/// level 1 takes two thirds and level 2 half of the base time.
+pub const fn production_ticks_per_bot(level: u32) -> u32 {
+    PRODUCTION_TICKS_PER_BOT * 2 / (2 + level)
+}
+
 /// Priority-share deficit threshold for the legacy no-`PopulationDemand`
 /// facility-emergence fallback.
 pub const FACILITY_EMERGE_DEFICIT_THRESHOLD: i32 = 5;
@@ -274,6 +307,19 @@ pub struct ProductionFacility {
     /// reports zero free space, so the hauler sink matcher skips
     /// it until production drains some.
     pub input_capacity: u32,
+    /// Only type demand picks may choose, or `None` for the
+    /// intent-first default. Queued and pinned orders must match it.
+    pub specialization: Option<NanobotType>,
+    /// Explicit build orders, head first. An idle facility starts the
+    /// head before consulting demand.
+    pub queue: VecDeque<NanobotType>,
+    /// Type rebuilt every cycle regardless of demand and queue.
+    pub pinned: Option<NanobotType>,
+    /// Speed level in `0..=PRODUCTION_FACILITY_MAX_LEVEL`.
+    pub level: u32,
+    /// Set when an upgrade was requested but the hopper has not yet
+    /// held [`PRODUCTION_UPGRADE_COST`] while the facility was idle.
+    pub upgrade_pending: bool,
 }
 
 impl ProductionFacility {
@@ -289,6 +335,11 @@ impl ProductionFacility {
             input_kind: ResourceKind::Minerals,
             input_amount: 0,
             input_capacity: PRODUCTION_INPUT_CAPACITY,
+            specialization: None,
+            queue: VecDeque::new(),
+            pinned: None,
+            level: 0,
+            upgrade_pending: false,
         }
     }
 
@@ -312,6 +363,97 @@ impl ProductionFacility {
     pub fn input_free_space(&self) -> u32 {
         self.input_capacity.saturating_sub(self.input_amount)
     }
+
+    /// Cycle length at the facility's current speed level.
+    pub fn ticks_per_bot(&self) -> u32 {
+        production_ticks_per_bot(self.level)
+    }
+
+    /// True when `kind` is allowed by the facility's specialization.
+    pub fn accepts(&self, kind: NanobotType) -> bool {
+        self.specialization.is_none_or(|only| only == kind)
+    }
+
+    /// Set or clear the specialization. Queued orders and a pin that no
+    /// longer match are dropped; a running cycle is left to finish.
+    pub fn specialize(&mut self, specialization: Option<NanobotType>) {
+        self.specialization = specialization;
+        self.queue
+            .retain(|kind| specialization.is_none_or(|only| only == *kind));
+        if self.pinned.is_some_and(|kind| !self.accepts(kind)) {
+            self.pinned = None;
+        }
+    }
+
+    /// Append an order. Returns `false` when the queue is full or the
+    /// type does not match the specialization.
+    pub fn enqueue(&mut self, kind: NanobotType) -> bool {
+        if !self.accepts(kind) || self.queue.len() >= PRODUCTION_QUEUE_LIMIT {
+            return false;
+        }
+        self.queue.push_back(kind);
+        true
+    }
+
+    /// Pin the type currently in production. Returns `false` when idle.
+    pub fn pin_current(&mut self) -> bool {
+        let Some(kind) = self.current_target else {
+            return false;
+        };
+        self.pinned = Some(kind);
+        true
+    }
+
+    /// Abort the running cycle and return its cost to the hopper, up to
+    /// the hopper's capacity. A pin on the cancelled type is released so
+    /// the facility does not restart it next tick. Returns where the
+    /// refund went; the part the hopper had no room for is spilled.
+    pub fn cancel_current(&mut self) -> CancelRefund {
+        let Some(kind) = self.current_target.take() else {
+            return CancelRefund::default();
+        };
+        if self.pinned == Some(kind) {
+            self.pinned = None;
+        }
+        self.progress = 0;
+        self.blocked_types.clear();
+        let hopper = PRODUCTION_COST_PER_BOT.min(self.input_free_space());
+        self.input_amount += hopper;
+        CancelRefund {
+            hopper,
+            spilled: PRODUCTION_COST_PER_BOT - hopper,
+        }
+    }
+
+    /// Mark a speed upgrade as wanted. Returns `false` at max level or
+    /// when one is already pending.
+    pub fn request_upgrade(&mut self) -> bool {
+        if self.level >= PRODUCTION_FACILITY_MAX_LEVEL || self.upgrade_pending {
+            return false;
+        }
+        self.upgrade_pending = true;
+        true
+    }
+
+    /// Next type this facility must start regardless of demand: the
+    /// pin, else the queue head.
+    pub fn ordered_target(&self) -> Option<NanobotType> {
+        self.pinned.or_else(|| self.queue.front().copied())
+    }
+}
+
+/// Material a cancelled production cycle returns: what went back into
+/// the hopper and what spilled over its capacity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CancelRefund {
+    pub hopper: u32,
+    pub spilled: u32,
+}
+
+impl CancelRefund {
+    pub fn total(&self) -> u32 {
+        self.hopper + self.spilled
+    }
 }
 
 impl Default for ProductionFacility {
@@ -419,7 +561,7 @@ pub fn total_deficit(
 /// integer percent in `[0, 100]`. An idle facility
 /// (`current_target = None`) reports 0% so the label
 /// formatter does not have to special-case it. A working
-/// facility's percent is `progress / PRODUCTION_TICKS_PER_BOT`
+/// facility's percent is `progress / ticks_per_bot()`
 /// floored to an integer; the label uses this directly.
 ///
 /// The function is pure and lives next to the
@@ -430,10 +572,11 @@ pub fn production_progress_percent(facility: &ProductionFacility) -> u32 {
     if facility.current_target.is_none() {
         return 0;
     }
-    if PRODUCTION_TICKS_PER_BOT == 0 {
+    let ticks = facility.ticks_per_bot();
+    if ticks == 0 {
         return 100;
     }
-    let pct = (facility.progress as u64 * 100 / PRODUCTION_TICKS_PER_BOT as u64) as u32;
+    let pct = (facility.progress as u64 * 100 / ticks as u64) as u32;
     pct.min(100)
 }
 
@@ -544,9 +687,13 @@ pub fn production_facility_auto_creation_system(
             With<ProductionFacility>,
             With<Stockpile>,
             With<crate::nanobot::Charger>,
+            With<crate::nanobot::ResearchLab>,
         )>,
     >,
-    planned_facilities: Query<(&PlannedStructure, Option<&OwnerSwarm>)>,
+    planned_facilities: Query<
+        (&PlannedStructure, Option<&OwnerSwarm>),
+        (Without<PlannedUpgrade>, Without<PlannedReclaim>),
+    >,
     deposits: Query<(&ResourceDeposit, &Transform)>,
     swarm_productions: Query<&SwarmProduction>,
     swarms: Query<(Entity, &SwarmId), With<Swarm>>,
@@ -671,6 +818,86 @@ pub fn production_facility_auto_creation_system(
     }
 }
 
+/// Player-issued change to one facility's production. Written by the
+/// facility panel and applied before the pick step, so an order or
+/// cancel takes effect on the same tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Message)]
+pub struct ProductionFacilityCommand {
+    pub facility: Entity,
+    pub action: FacilityAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FacilityAction {
+    /// Restrict demand picks to one type, or clear the restriction.
+    Specialize(Option<NanobotType>),
+    /// Append an explicit order to the queue.
+    Enqueue(NanobotType),
+    /// Drop every queued order.
+    ClearQueue,
+    /// Keep rebuilding the type currently in production.
+    PinCurrent,
+    Unpin,
+    /// Abort the running cycle and refund its cost to the hopper.
+    CancelCurrent,
+    /// Spend [`PRODUCTION_UPGRADE_COST`] on the next speed level.
+    Upgrade,
+}
+
+/// Apply queued [`ProductionFacilityCommand`]s. A cancel refund goes back
+/// into the hopper and the owner's ledger, so the material stays in the
+/// same custody it was drawn from. Whatever a full hopper cannot take
+/// spills into a [`crate::nanobot::ReclaimedCache`] beside the facility.
+pub fn production_facility_command_system(
+    mut commands: Commands,
+    mut messages: MessageReader<ProductionFacilityCommand>,
+    mut facilities: Query<(
+        &mut ProductionFacility,
+        Option<&OwnerSwarm>,
+        Option<&Transform>,
+    )>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut ledger: ResMut<ResourceLedger>,
+    sprites: Option<Res<StructureSprites>>,
+) {
+    for command in messages.read() {
+        let Ok((mut facility, owner, transform)) = facilities.get_mut(command.facility) else {
+            continue;
+        };
+        match command.action {
+            FacilityAction::Specialize(kind) => facility.specialize(kind),
+            FacilityAction::Enqueue(kind) => {
+                facility.enqueue(kind);
+            }
+            FacilityAction::ClearQueue => facility.queue.clear(),
+            FacilityAction::PinCurrent => {
+                facility.pin_current();
+            }
+            FacilityAction::Unpin => facility.pinned = None,
+            FacilityAction::CancelCurrent => {
+                let refund = facility.cancel_current();
+                if refund.total() > 0 {
+                    let owner_id = owner
+                        .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+                        .unwrap_or(SwarmId::PLAYER);
+                    ledger.add_for(owner_id, facility.input_kind, refund.total());
+                }
+                spawn_reclaimed_cache(
+                    &mut commands,
+                    transform.map_or(Vec2::ZERO, |transform| transform.translation.truncate()),
+                    facility.input_kind,
+                    refund.spilled,
+                    owner.copied(),
+                    sprites.as_deref(),
+                );
+            }
+            FacilityAction::Upgrade => {
+                facility.request_upgrade();
+            }
+        }
+    }
+}
+
 /// Pick the next production target for every idle facility and
 /// consume the material up-front from the facility's own input
 /// hopper. If the hopper does not hold a full
@@ -692,7 +919,7 @@ pub fn production_facility_auto_creation_system(
 /// Issue #38 / ADR-0004: counts now match the per-swarm
 /// `SwarmId` rather than walking the swarm's `Children`,
 /// because nanobots are top-level entities.
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn production_facility_pick_target_system(
     global_priority: Res<ProductionPriority>,
     population_demand: Option<Res<crate::nanobot::PopulationDemand>>,
@@ -708,6 +935,7 @@ pub fn production_facility_pick_target_system(
         )>,
     )>,
     mut ledger: ResMut<ResourceLedger>,
+    mut telemetry: Option<ResMut<SwarmTelemetry>>,
 ) {
     let mut available_by_swarm = HashMap::<SwarmId, HashMap<NanobotType, u32>>::new();
     for swarm_id in &swarms {
@@ -785,22 +1013,63 @@ pub fn production_facility_pick_target_system(
         // sole type stays blocked. This is the "blocked
         // types are skipped temporarily instead of stalling
         // all production" half of the contract.
+        // A requested speed upgrade is paid before any new cycle, so
+        // the hopper can fill to the upgrade cost instead of being
+        // drained one bot at a time.
+        if facility.upgrade_pending {
+            if facility.input_amount < PRODUCTION_UPGRADE_COST {
+                continue;
+            }
+            facility.input_amount -= PRODUCTION_UPGRADE_COST;
+            ledger.remove_for(owner_id, facility.input_kind, PRODUCTION_UPGRADE_COST);
+            if let Some(telemetry) = telemetry.as_deref_mut() {
+                telemetry.counters_mut(owner_id).minerals_to_upgrades += PRODUCTION_UPGRADE_COST;
+            }
+            facility.level += 1;
+            facility.upgrade_pending = false;
+        }
+
+        // Pinned and queued orders override demand. They wait for
+        // material instead of falling back to a demand pick, so an
+        // order is never starved by the cheaper default path.
+        if let Some(kind) = facility.ordered_target() {
+            if facility.input_amount >= PRODUCTION_COST_PER_BOT {
+                facility.input_amount -= PRODUCTION_COST_PER_BOT;
+                ledger.remove_for(owner_id, facility.input_kind, PRODUCTION_COST_PER_BOT);
+                if let Some(telemetry) = telemetry.as_deref_mut() {
+                    telemetry.counters_mut(owner_id).minerals_to_production +=
+                        PRODUCTION_COST_PER_BOT;
+                }
+                facility.current_target = Some(kind);
+                facility.progress = 0;
+                if facility.pinned.is_none() {
+                    facility.queue.pop_front();
+                }
+                *counts.entry(kind).or_default() += 1;
+            }
+            continue;
+        }
+
+        // A specialized facility treats every other type as
+        // permanently blocked for demand picks.
         let mut picked = false;
         loop {
+            let mut blocked = facility.blocked_types.clone();
+            blocked.extend(
+                NanobotType::ALL
+                    .into_iter()
+                    .filter(|kind| !facility.accepts(*kind)),
+            );
             let kind = if let Some(demand) = population_demand.as_deref() {
                 let desired = NanobotType::ALL
                     .into_iter()
                     .map(|kind| (kind, demand.desired_for(owner_id, kind)))
                     .collect();
-                pick_type_for_demand(priority, counts, &desired, &facility.blocked_types)
+                pick_type_for_demand(priority, counts, &desired, &blocked)
             } else {
                 // Compatibility seam for isolated tests and callers without
                 // the runtime typed-demand resource.
-                pick_type_for_legacy_priority_share_fallback(
-                    priority,
-                    counts,
-                    &facility.blocked_types,
-                )
+                pick_type_for_legacy_priority_share_fallback(priority, counts, &blocked)
             };
             let Some(kind) = kind else {
                 break;
@@ -808,6 +1077,10 @@ pub fn production_facility_pick_target_system(
             if facility.input_amount >= PRODUCTION_COST_PER_BOT {
                 facility.input_amount -= PRODUCTION_COST_PER_BOT;
                 ledger.remove_for(owner_id, facility.input_kind, PRODUCTION_COST_PER_BOT);
+                if let Some(telemetry) = telemetry.as_deref_mut() {
+                    telemetry.counters_mut(owner_id).minerals_to_production +=
+                        PRODUCTION_COST_PER_BOT;
+                }
                 facility.current_target = Some(kind);
                 facility.progress = 0;
                 *counts.entry(kind).or_default() += 1;
@@ -823,7 +1096,7 @@ pub fn production_facility_pick_target_system(
 }
 
 /// Advance each busy facility's progress counter. When progress
-/// reaches [`PRODUCTION_TICKS_PER_BOT`], spawn a new nanobot of
+/// reaches the facility's [`ProductionFacility::ticks_per_bot`], spawn a new nanobot of
 /// the facility's `current_target` as a child of the owning
 /// [`Swarm`] (or the first swarm in the world for unowned
 /// facilities, matching the pre-multi-swarm behaviour), then
@@ -841,6 +1114,7 @@ pub fn production_facility_work_system(
     swarms: Query<(Entity, Option<&SwarmId>), With<Swarm>>,
     opponent_swarms: Query<(), With<OpponentSwarm>>,
     sprites: Option<Res<NanobotSprites>>,
+    mut telemetry: Option<ResMut<SwarmTelemetry>>,
 ) {
     for (mut facility, transform, owner, condition) in &mut facilities {
         if condition.is_some_and(|condition| !condition.is_operational()) {
@@ -850,7 +1124,7 @@ pub fn production_facility_work_system(
             continue;
         };
         facility.progress = facility.progress.saturating_add(1);
-        if facility.progress < PRODUCTION_TICKS_PER_BOT {
+        if facility.progress < facility.ticks_per_bot() {
             continue;
         }
         // Cycle complete: spawn the nanobot. The owner
@@ -897,7 +1171,6 @@ pub fn production_facility_work_system(
                     nanobot: Nanobot {},
                     nanobot_type: target,
                     velocity: VelocityComponent::default(),
-                    ai_state: AiStateComponent::new(),
                     health: Health::default(),
                     swarm_member: SwarmMember::new(swarm_id),
                 },
@@ -907,6 +1180,9 @@ pub fn production_facility_work_system(
             if let Some(sprites) = sprites.as_deref() {
                 entity.insert(Sprite::from_image(sprites.handle(target, is_opponent)));
             }
+            if let Some(telemetry) = telemetry.as_deref_mut() {
+                telemetry.counters_mut(swarm_id).record_produced(target);
+            }
         }
         // Reset for the next cycle. Clearing the blocked set
         // is the "blocked types are skipped temporarily"
@@ -921,7 +1197,8 @@ pub fn production_facility_work_system(
 /// Plugin that wires the production systems into the Update
 /// schedule. The chain runs after `move_velocity_system` so the
 /// movement step has settled before production picks targets and
-/// spawns new nanobots. Auto-creation runs last in its own
+/// spawns new nanobots. Player facility commands are applied first
+/// so the pick step already sees them. Auto-creation runs last in its own
 /// internal chain so it sees the post-pick / post-work state of
 /// the swarm and only spawns a new facility when the existing
 /// ones are all busy.
@@ -942,17 +1219,20 @@ pub struct ProductionPlugin;
 
 impl Plugin for ProductionPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ProductionPressure>().add_systems(
-            FixedUpdate,
-            (
-                production_facility_pick_target_system,
-                production_facility_work_system,
-                production_facility_auto_creation_system
-                    .before(crate::nanobot::planned::sink_stockpile_demand_system),
-            )
-                .chain()
-                .after(crate::nanobot::NanobotSimulationSet::Movement),
-        );
+        app.init_resource::<ProductionPressure>()
+            .add_message::<ProductionFacilityCommand>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    production_facility_command_system,
+                    production_facility_pick_target_system,
+                    production_facility_work_system,
+                    production_facility_auto_creation_system
+                        .before(crate::nanobot::planned::sink_stockpile_demand_system),
+                )
+                    .chain()
+                    .after(crate::nanobot::NanobotSimulationSet::Movement),
+            );
     }
 }
 
@@ -1204,6 +1484,94 @@ mod tests {
         assert_eq!(production_progress_percent(&f), 100);
     }
 
+    #[test]
+    fn upgraded_levels_shorten_the_cycle() {
+        assert_eq!(production_ticks_per_bot(0), PRODUCTION_TICKS_PER_BOT);
+        assert_eq!(production_ticks_per_bot(1), 80);
+        assert_eq!(production_ticks_per_bot(PRODUCTION_FACILITY_MAX_LEVEL), 60);
+
+        let mut f = ProductionFacility::new();
+        f.level = 2;
+        f.current_target = Some(NanobotType::Worker);
+        f.progress = 30;
+        assert_eq!(production_progress_percent(&f), 50);
+    }
+
+    #[test]
+    fn specialization_filters_queue_and_pin() {
+        let mut f = ProductionFacility::new();
+        assert!(f.enqueue(NanobotType::Worker));
+        assert!(f.enqueue(NanobotType::Defender));
+        f.pinned = Some(NanobotType::Worker);
+
+        f.specialize(Some(NanobotType::Defender));
+        assert_eq!(f.queue, VecDeque::from([NanobotType::Defender]));
+        assert_eq!(f.pinned, None);
+        assert!(!f.enqueue(NanobotType::Hauler));
+        assert_eq!(f.ordered_target(), Some(NanobotType::Defender));
+    }
+
+    #[test]
+    fn queue_is_bounded() {
+        let mut f = ProductionFacility::new();
+        for _ in 0..PRODUCTION_QUEUE_LIMIT {
+            assert!(f.enqueue(NanobotType::Hauler));
+        }
+        assert!(!f.enqueue(NanobotType::Hauler));
+    }
+
+    #[test]
+    fn cancel_refunds_cost_and_releases_matching_pin() {
+        let mut f = ProductionFacility::new();
+        assert_eq!(
+            f.cancel_current(),
+            CancelRefund::default(),
+            "idle facility has nothing to cancel"
+        );
+
+        f.current_target = Some(NanobotType::Hauler);
+        f.progress = 50;
+        assert!(f.pin_current());
+        assert_eq!(
+            f.cancel_current(),
+            CancelRefund {
+                hopper: PRODUCTION_COST_PER_BOT,
+                spilled: 0,
+            }
+        );
+        assert_eq!(f.input_amount, PRODUCTION_COST_PER_BOT);
+        assert_eq!(f.current_target, None);
+        assert_eq!(f.progress, 0);
+        assert_eq!(f.pinned, None);
+    }
+
+    #[test]
+    fn cancel_with_a_full_hopper_spills_the_refund_over_capacity() {
+        let mut f = ProductionFacility::new();
+        f.current_target = Some(NanobotType::Worker);
+        f.input_amount = f.input_capacity - 2;
+
+        let refund = f.cancel_current();
+
+        assert_eq!(
+            f.input_amount, f.input_capacity,
+            "the hopper never overfills"
+        );
+        assert_eq!(refund.hopper, 2);
+        assert_eq!(refund.spilled, PRODUCTION_COST_PER_BOT - 2);
+        assert_eq!(refund.total(), PRODUCTION_COST_PER_BOT);
+    }
+
+    #[test]
+    fn upgrade_request_stops_at_max_level() {
+        let mut f = ProductionFacility::new();
+        assert!(f.request_upgrade());
+        assert!(!f.request_upgrade(), "one pending upgrade at a time");
+        f.upgrade_pending = false;
+        f.level = PRODUCTION_FACILITY_MAX_LEVEL;
+        assert!(!f.request_upgrade());
+    }
+
     // ---- Production Priority weights and adjustment bounds ----
 
     #[test]
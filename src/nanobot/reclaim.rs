@@ -0,0 +1,368 @@
+//! Structure deconstruction through Reclaim intent.
+//!
+//! An owned completed structure inside visible Reclaim paint is planned
+//! for deconstruction. Like a tier upgrade, the plan reuses the Planned
+//! Structure lifecycle on the same entity: a [`PlannedStructure`] of the
+//! structure's own kind plus a [`PlannedReclaim`] sidecar, built by a
+//! Worker through the normal Planned Build allocation.
+//!
+//! ```text
+//!   Reclaim paint over an owned structure
+//!     -> PlannedStructure + PlannedReclaim (maintenance released)
+//!     -> a Worker spends RECLAIM_WORK_TICKS of worker time
+//!     -> the structure becomes a ReclaimedCache Source Stockpile
+//!        holding its buffer plus the build-cost refund
+//!     -> haulers move the cache to owned Sink Stockpiles
+//!     -> the empty cache despawns
+//! ```
+//!
+//! Erasing the paint before the work finishes cancels the plan and the
+//! structure keeps operating.
+
+use bevy::prelude::*;
+
+use crate::GAMEPLAY_SPRITE_Z;
+use crate::intent::{IntentGrid, IntentKind};
+use crate::nanobot::components::{Swarm, SwarmId};
+use crate::nanobot::construction_cost::ConstructionCost;
+use crate::nanobot::gather::world_to_cell;
+use crate::nanobot::planned::{
+    DEFAULT_PLANNED_WORK_TICKS, PlannedKind, PlannedStructure, completed_visual_bundle,
+};
+use crate::nanobot::structure::Structure;
+use crate::nanobot::structure_tier::{SaturationTicks, structure_upgrade_cost};
+use crate::nanobot::{Charger, OwnerSwarm, ProductionFacility, ResearchLab, Road};
+use crate::resources::{ResourceKind, ResourceLedger, Stockpile, StockpileRole};
+use crate::structure_sprites::StructureSprites;
+
+/// Worker-time budget to deconstruct a structure. Twice a fresh build,
+/// so reclaiming is a real commitment rather than a free undo.
+pub const RECLAIM_WORK_TICKS: u32 = DEFAULT_PLANNED_WORK_TICKS * 2;
+
+/// Share of a structure's material build cost returned on reclaim.
+pub const RECLAIM_REFUND_PERCENT: u32 = 50;
+
+/// Pickup radius of the cache a reclaimed structure leaves behind.
+/// Matches completed Source and Sink Stockpiles.
+pub const RECLAIMED_CACHE_RADIUS: f32 = 32.0;
+
+/// Minerals spent to bring a structure of `kind` to `tier`: the kind's
+/// construction material, plus the sum of the paid upgrades. `kind` is
+/// `None` when the structure was built for worker time only.
+pub const fn structure_build_cost(kind: Option<PlannedKind>, tier: u32) -> u32 {
+    let mut cost = match kind {
+        Some(kind) => kind.mineral_cost(),
+        None => 0,
+    };
+    let mut step = 1;
+    while step <= tier {
+        cost += structure_upgrade_cost(step);
+        step += 1;
+    }
+    cost
+}
+
+/// Minerals refunded when a structure of `kind` at `tier` is reclaimed.
+pub const fn reclaim_refund(kind: Option<PlannedKind>, tier: u32) -> u32 {
+    structure_build_cost(kind, tier) * RECLAIM_REFUND_PERCENT / 100
+}
+
+/// Sidecar on a completed structure that also carries a
+/// [`PlannedStructure`] for its deconstruction. Promotion reads it and
+/// converts the structure into a [`ReclaimedCache`].
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedReclaim;
+
+/// Marks the Source Stockpile left behind by a reclaimed structure.
+/// The cache only drains: its capacity shrinks with every pickup, so
+/// nothing delivers into it, and it despawns once empty. It carries no
+/// [`Structure`] condition, so it never needs maintenance.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct ReclaimedCache;
+
+/// Spawn a [`ReclaimedCache`] holding `amount` of `kind` at `pos` for
+/// `owner`'s haulers to collect. The material stays in the owner's
+/// custody. Nothing is spawned for an empty cache.
+pub fn spawn_reclaimed_cache(
+    commands: &mut Commands,
+    pos: Vec2,
+    kind: ResourceKind,
+    amount: u32,
+    owner: Option<OwnerSwarm>,
+    sprites: Option<&StructureSprites>,
+) -> Option<Entity> {
+    if amount == 0 {
+        return None;
+    }
+    let mut cache = commands.spawn((
+        ReclaimedCache,
+        Stockpile {
+            kind,
+            amount,
+            capacity: amount,
+            radius: RECLAIMED_CACHE_RADIUS,
+        },
+        StockpileRole::Source,
+        Transform::from_translation(pos.extend(GAMEPLAY_SPRITE_Z)),
+    ));
+    if let Some(owner) = owner {
+        cache.insert(owner);
+    }
+    if let Some(sprites) = sprites {
+        cache.insert(completed_visual_bundle(
+            PlannedKind::SourceStockpile,
+            sprites,
+            pos,
+        ));
+    }
+    Some(cache.id())
+}
+
+/// Swarm owning a structure. Unowned structures belong to the player.
+pub(crate) fn structure_swarm(world: &World, entity: Entity) -> SwarmId {
+    world
+        .get::<OwnerSwarm>(entity)
+        .and_then(|OwnerSwarm(owner)| world.get::<SwarmId>(*owner).copied())
+        .unwrap_or(SwarmId::PLAYER)
+}
+
+/// Minerals sitting in a structure's stockpile, charger, production or
+/// research hopper buffers. Still in the owner's custody.
+pub(crate) fn buffered_minerals(entity: EntityRef) -> u32 {
+    entity
+        .get::<Stockpile>()
+        .map_or(0, |stockpile| stockpile.amount)
+        + entity.get::<Charger>().map_or(0, |charger| charger.amount)
+        + entity
+            .get::<ProductionFacility>()
+            .map_or(0, |facility| facility.input_amount)
+        + entity
+            .get::<ResearchLab>()
+            .map_or(0, |lab| lab.input_amount)
+}
+
+/// Kind a completed structure was built as, read off its components.
+pub(crate) fn built_kind(entity: EntityRef) -> Option<PlannedKind> {
+    if entity.contains::<Stockpile>() {
+        return Some(match entity.get::<StockpileRole>() {
+            Some(StockpileRole::Sink) => PlannedKind::SinkStockpile,
+            _ => PlannedKind::SourceStockpile,
+        });
+    }
+    entity
+        .contains::<Charger>()
+        .then_some(PlannedKind::Charger)
+        .or(entity
+            .contains::<ProductionFacility>()
+            .then_some(PlannedKind::ProductionFacility))
+        .or(entity
+            .contains::<ResearchLab>()
+            .then_some(PlannedKind::ResearchLab))
+        .or(entity.contains::<Road>().then_some(PlannedKind::Road))
+}
+
+/// Kind whose construction material an owner paid for when building
+/// the structure: only with [`ConstructionCost`] on and only for owned
+/// structures, since unowned plans are built for worker time alone.
+/// Pass to [`structure_build_cost`] or [`reclaim_refund`].
+pub(crate) fn paid_material_kind(entity: &EntityWorldMut) -> Option<PlannedKind> {
+    if !entity.world().contains_resource::<ConstructionCost>() || !entity.contains::<OwnerSwarm>() {
+        return None;
+    }
+    built_kind(entity.as_readonly())
+}
+
+/// Plan deconstruction for owned structures under visible Reclaim paint,
+/// and cancel pending plans whose paint was erased. Structures already
+/// under another plan (a build or an upgrade) wait until it finishes.
+#[allow(clippy::type_complexity)]
+pub fn reclaim_intent_system(
+    mut commands: Commands,
+    grid: Res<IntentGrid>,
+    structures: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&StockpileRole>,
+            Option<&OwnerSwarm>,
+            (
+                Has<Stockpile>,
+                Has<Charger>,
+                Has<ProductionFacility>,
+                Has<ResearchLab>,
+            ),
+        ),
+        (
+            With<Structure>,
+            Without<PlannedStructure>,
+            Without<ReclaimedCache>,
+        ),
+    >,
+    pending: Query<(Entity, &PlannedStructure, Option<&OwnerSwarm>), With<PlannedReclaim>>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+) {
+    let swarm_of = |owner: Option<&OwnerSwarm>| {
+        owner
+            .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+            .unwrap_or(SwarmId::PLAYER)
+    };
+    let painted = |cell: IVec2, swarm: SwarmId| {
+        grid.cell(cell)
+            .is_some_and(|intent| intent.visible_to(IntentKind::Reclaim, swarm))
+    };
+
+    for (entity, transform, role, owner, (stockpile, charger, facility, lab)) in &structures {
+        let cell = world_to_cell(transform.translation.truncate());
+        if !painted(cell, swarm_of(owner)) {
+            continue;
+        }
+        let kind = match role {
+            _ if !stockpile => None,
+            Some(StockpileRole::Sink) => Some(PlannedKind::SinkStockpile),
+            _ => Some(PlannedKind::SourceStockpile),
+        }
+        .or(charger.then_some(PlannedKind::Charger))
+        .or(facility.then_some(PlannedKind::ProductionFacility))
+        .or(lab.then_some(PlannedKind::ResearchLab));
+        let Some(kind) = kind else {
+            continue;
+        };
+        let mut planned = PlannedStructure::new(kind, cell);
+        planned.work_remaining = RECLAIM_WORK_TICKS;
+        commands
+            .entity(entity)
+            .insert((planned, PlannedReclaim))
+            .remove::<SaturationTicks>();
+    }
+
+    for (entity, planned, owner) in &pending {
+        if !planned.is_complete() && !painted(planned.cell, swarm_of(owner)) {
+            commands
+                .entity(entity)
+                .remove::<(PlannedStructure, PlannedReclaim)>();
+        }
+    }
+}
+
+/// Deconstruct a structure in place. Queued by planned-structure
+/// promotion when the plan carries a [`PlannedReclaim`]. Buffered
+/// material stays in swarm custody; the build-cost refund re-enters the
+/// ledger. Both land in a [`ReclaimedCache`] for haulers to collect.
+pub fn complete_reclaim(mut entity: EntityWorldMut) {
+    let Some(structure) = entity.get::<Structure>().copied() else {
+        return;
+    };
+    let buffered = buffered_minerals(entity.as_readonly());
+    let refund = reclaim_refund(paid_material_kind(&entity), structure.tier);
+    let id = entity.id();
+    let pos = entity
+        .get::<Transform>()
+        .map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+    let visual = entity.world_scope(|world| {
+        let swarm = structure_swarm(world, id);
+        world
+            .resource_mut::<ResourceLedger>()
+            .add_for(swarm, ResourceKind::Minerals, refund);
+        world
+            .get_resource::<StructureSprites>()
+            .map(|sprites| completed_visual_bundle(PlannedKind::SourceStockpile, sprites, pos))
+    });
+
+    entity.remove::<(
+        Structure,
+        SaturationTicks,
+        Stockpile,
+        StockpileRole,
+        Charger,
+        ProductionFacility,
+        ResearchLab,
+    )>();
+    let amount = buffered + refund;
+    if amount == 0 {
+        entity.despawn();
+        return;
+    }
+    entity.insert((
+        ReclaimedCache,
+        Stockpile {
+            kind: ResourceKind::Minerals,
+            amount,
+            capacity: amount,
+            radius: RECLAIMED_CACHE_RADIUS,
+        },
+        StockpileRole::Source,
+    ));
+    if let Some(visual) = visual {
+        entity.insert(visual);
+    }
+}
+
+/// Keep each cache drain-only and despawn it once haulers have emptied
+/// it.
+pub fn reclaimed_cache_system(
+    mut commands: Commands,
+    mut caches: Query<(Entity, &mut Stockpile), With<ReclaimedCache>>,
+) {
+    for (entity, mut stockpile) in &mut caches {
+        if stockpile.amount == 0 {
+            commands.entity(entity).despawn();
+        } else if stockpile.capacity != stockpile.amount {
+            stockpile.capacity = stockpile.amount;
+        }
+    }
+}
+
+/// Plugin that wires Reclaim planning and cache cleanup into the fixed
+/// schedule. Planning runs before regional allocation projects work, so
+/// a new reclaim plan is claimable on the same tick.
+pub struct ReclaimPlugin;
+
+impl Plugin for ReclaimPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                reclaim_intent_system.before(crate::nanobot::RegionalAllocationSet::Project),
+                reclaimed_cache_system.after(crate::nanobot::NanobotSimulationSet::Movement),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Pure-helper unit tests. The paint -> plan -> deconstruct -> haul
+    //! flow is covered by `tests/behavior/reclaim.rs`.
+
+    use super::*;
+
+    #[test]
+    fn build_cost_sums_paid_upgrades() {
+        assert_eq!(structure_build_cost(None, 0), 0);
+        assert_eq!(structure_build_cost(None, 1), structure_upgrade_cost(1));
+        assert_eq!(
+            structure_build_cost(None, 2),
+            structure_upgrade_cost(1) + structure_upgrade_cost(2)
+        );
+    }
+
+    #[test]
+    fn build_cost_includes_the_construction_material() {
+        let kind = PlannedKind::Charger;
+        assert_eq!(structure_build_cost(Some(kind), 0), kind.mineral_cost());
+        assert_eq!(
+            structure_build_cost(Some(kind), 2),
+            kind.mineral_cost() + structure_build_cost(None, 2)
+        );
+    }
+
+    #[test]
+    fn refund_is_a_fraction_of_build_cost() {
+        assert_eq!(reclaim_refund(None, 0), 0);
+        assert!(reclaim_refund(None, 2) < structure_build_cost(None, 2));
+        assert!(reclaim_refund(None, 2) > reclaim_refund(None, 1));
+        let kind = Some(PlannedKind::ProductionFacility);
+        assert!(reclaim_refund(kind, 0) > 0);
+        assert!(reclaim_refund(kind, 0) < structure_build_cost(kind, 0));
+    }
+}
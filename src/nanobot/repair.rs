@@ -0,0 +1,318 @@
+//! Structure repair, prioritized by threat.
+//!
+//! Maintenance keeps a structure's buffer counter fresh; repair restores
+//! health lost to combat or neglect. The two are separate Worker
+//! activities with separate allocation categories, so a swarm under
+//! attack can pull Workers onto damaged defences without starving
+//! routine upkeep elsewhere.
+//!
+//! ```text
+//!   Defender hits a structure
+//!     -> RecentDamage { amount, ticks_since_hit: 0 }
+//!     -> projection: one-Worker Repair opportunity, priority =
+//!        repair_priority (missing health + recent damage + Defend
+//!        pressure on the cell)
+//!     -> Worker: RepairAssignment -> RepairProgress -> full health
+//! ```
+//!
+//! A structure hit within [`UNDER_ATTACK_TICKS`] is under active attack.
+//! Workers stay away from it unless its cell carries the owner's Defend
+//! paint: the projection drops the opportunity, and the lease layer
+//! pulls back any Worker already heading there. Repair resumes once the
+//! attack window passes or the player paints Defend over the structure.
+//!
+//! Completed structure sprites show [`StructureDamageStage`] tints so
+//! the player can read damage at a glance.
+
+use bevy::prelude::*;
+
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState};
+use crate::nanobot::components::{DirectMovementComponent, Nanobot};
+use crate::nanobot::defend::DEFEND_PRESSURE_BASELINE;
+use crate::nanobot::placement::BUILDING_FOOTPRINT_RADIUS;
+use crate::nanobot::structure::Structure;
+use crate::structure_sprites::{
+    StructureDamageStage, StructureSprites, StructureVisual, StructureVisualState,
+};
+
+/// Health a Worker restores per fixed tick of repair. Faster than
+/// maintenance, since repair is the response to combat.
+pub const REPAIR_HEALTH_PER_TICK: u32 = 4;
+
+/// Longest single repair shift before the Worker returns to the
+/// allocator. A structure that still needs repair is re-projected.
+pub const REPAIR_WORK_DURATION_TICKS: u32 = crate::SIMULATION_HZ as u32;
+
+/// Ticks after a hit during which a structure counts as under
+/// active attack.
+pub const UNDER_ATTACK_TICKS: u32 = crate::SIMULATION_HZ as u32 * 2;
+
+/// Ticks after the last hit before recent damage stops raising
+/// repair priority.
+pub const RECENT_DAMAGE_TICKS: u32 = crate::SIMULATION_HZ as u32 * 10;
+
+/// Priority added per point of recent combat damage, on top of the
+/// missing health it already caused.
+pub const REPAIR_RECENT_DAMAGE_WEIGHT: u32 = 2;
+
+/// Priority added per unit of Defend pressure above baseline.
+pub const REPAIR_PRESSURE_WEIGHT: f32 = 25.0;
+
+/// Combat damage a structure took recently. Inserted by
+/// [`crate::nanobot::defender_combat_system`] and removed once
+/// [`RECENT_DAMAGE_TICKS`] pass without another hit.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RecentDamage {
+    pub amount: u32,
+    pub ticks_since_hit: u32,
+}
+
+impl RecentDamage {
+    /// Add a fresh hit and restart the attack window.
+    pub fn record_hit(&mut self, amount: u32) {
+        self.amount = self.amount.saturating_add(amount);
+        self.ticks_since_hit = 0;
+    }
+
+    /// True while the last hit is inside [`UNDER_ATTACK_TICKS`].
+    pub fn under_attack(&self) -> bool {
+        self.ticks_since_hit < UNDER_ATTACK_TICKS
+    }
+}
+
+impl Structure {
+    /// True when the structure has lost health and a Worker can
+    /// restore it.
+    pub fn needs_repair(&self) -> bool {
+        self.health < self.max_health()
+    }
+}
+
+/// Repair ranking for a damaged structure. Missing health is the base;
+/// recent combat damage and Defend pressure on the structure's cell
+/// raise it so Workers go where the fighting is.
+pub fn repair_priority(structure: &Structure, recent: Option<&RecentDamage>, pressure: f32) -> u32 {
+    let missing = structure.max_health().saturating_sub(structure.health);
+    let recent = recent.map_or(0, |recent| {
+        recent.amount.saturating_mul(REPAIR_RECENT_DAMAGE_WEIGHT)
+    });
+    let threat = ((pressure - DEFEND_PRESSURE_BASELINE).max(0.0) * REPAIR_PRESSURE_WEIGHT) as u32;
+    missing.saturating_add(recent).saturating_add(threat)
+}
+
+/// True when Workers must stay away: the structure is under active
+/// attack and nothing defends its cell.
+pub fn repair_blocked(recent: Option<&RecentDamage>, defended: bool) -> bool {
+    !defended && recent.is_some_and(RecentDamage::under_attack)
+}
+
+/// Marks a Worker walking to a damaged structure.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct RepairAssignment {
+    pub cell: IVec2,
+    pub target: Entity,
+}
+
+/// A Worker at its target, restoring health each tick.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct RepairProgress {
+    pub cell: IVec2,
+    pub target: Entity,
+    pub ticks_worked: u32,
+}
+
+impl BehaviourState for RepairAssignment {
+    const ROLE: BehaviourRole = BehaviourRole::REPAIR;
+}
+
+impl BehaviourState for RepairProgress {
+    const ROLE: BehaviourRole = BehaviourRole::REPAIR;
+}
+
+/// Age every structure's recent damage and drop records older than
+/// [`RECENT_DAMAGE_TICKS`].
+pub fn recent_damage_decay_system(
+    mut commands: Commands,
+    mut damaged: Query<(Entity, &mut RecentDamage)>,
+) {
+    for (entity, mut recent) in &mut damaged {
+        recent.ticks_since_hit = recent.ticks_since_hit.saturating_add(1);
+        if recent.ticks_since_hit >= RECENT_DAMAGE_TICKS {
+            commands.entity(entity).try_remove::<RecentDamage>();
+        }
+    }
+}
+
+/// Start repair work once a Worker reaches its target, or re-issue
+/// movement when separation pushed it off the footprint.
+#[allow(clippy::type_complexity)]
+pub fn worker_repair_arrive_system(
+    mut commands: Commands,
+    workers: Query<
+        (Entity, &Transform, &RepairAssignment),
+        (
+            With<Nanobot>,
+            Without<DirectMovementComponent>,
+            Without<RepairProgress>,
+        ),
+    >,
+    structures: Query<&Transform, With<Structure>>,
+) {
+    for (entity, transform, assignment) in &workers {
+        let Ok(target_transform) = structures.get(assignment.target) else {
+            commands.entity(entity).remove::<RepairAssignment>();
+            continue;
+        };
+        let target = target_transform.translation.truncate();
+        if transform.translation.truncate().distance(target) <= BUILDING_FOOTPRINT_RADIUS {
+            commands.entity(entity).insert(RepairProgress {
+                cell: assignment.cell,
+                target: assignment.target,
+                ticks_worked: 0,
+            });
+        } else {
+            commands.entity(entity).insert(DirectMovementComponent {
+                xy: target,
+                stop_radius: BUILDING_FOOTPRINT_RADIUS,
+            });
+        }
+    }
+}
+
+/// Restore health on each repaired structure. The Worker is released
+/// at full health, after [`REPAIR_WORK_DURATION_TICKS`], or when the
+/// target is gone.
+pub fn worker_repair_work_system(
+    mut commands: Commands,
+    mut workers: Query<(Entity, &mut RepairProgress), With<Nanobot>>,
+    mut structures: Query<&mut Structure>,
+) {
+    for (entity, mut progress) in &mut workers {
+        let Ok(mut structure) = structures.get_mut(progress.target) else {
+            release_repair_worker(&mut commands, entity);
+            continue;
+        };
+        structure.health = (structure.health + REPAIR_HEALTH_PER_TICK).min(structure.max_health());
+        progress.ticks_worked += 1;
+        if !structure.needs_repair() || progress.ticks_worked >= REPAIR_WORK_DURATION_TICKS {
+            release_repair_worker(&mut commands, entity);
+        }
+    }
+}
+
+fn release_repair_worker(commands: &mut Commands, entity: Entity) {
+    commands
+        .entity(entity)
+        .remove::<RepairAssignment>()
+        .remove::<RepairProgress>();
+}
+
+/// Tint completed structure sprites by damage stage. The stage is
+/// cached on the entity so the sprite is only written when it changes,
+/// and a structure that never took damage keeps its completed colour.
+#[allow(clippy::type_complexity)]
+pub fn structure_damage_visual_system(
+    mut commands: Commands,
+    mut structures: Query<
+        (
+            Entity,
+            &Structure,
+            &StructureVisual,
+            &mut Sprite,
+            Option<&mut StructureDamageStage>,
+        ),
+        Changed<Structure>,
+    >,
+) {
+    for (entity, structure, visual, mut sprite, stage) in &mut structures {
+        if visual.state != StructureVisualState::Completed {
+            continue;
+        }
+        let next = StructureDamageStage::for_health(structure.health, structure.max_health());
+        match stage {
+            Some(stage) if *stage == next => continue,
+            Some(mut stage) => *stage = next,
+            None if next == StructureDamageStage::Intact => continue,
+            None => {
+                commands.entity(entity).insert(next);
+            }
+        }
+        sprite.color = StructureSprites::damage_color(next);
+    }
+}
+
+/// Plugin that wires the repair chain. The attack window ages during
+/// the threat phase, before combat records this tick's hits; repair
+/// work runs in the maintenance phase alongside upkeep.
+pub struct RepairPlugin;
+
+impl Plugin for RepairPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_behaviour_state::<RepairAssignment>()
+            .add_behaviour_state::<RepairProgress>()
+            .add_systems(
+                FixedUpdate,
+                recent_damage_decay_system.in_set(crate::nanobot::NanobotSimulationSet::Threat),
+            )
+            .add_systems(
+                FixedUpdate,
+                (worker_repair_arrive_system, worker_repair_work_system)
+                    .chain()
+                    .in_set(crate::nanobot::NanobotSimulationSet::Maintenance)
+                    .after(crate::nanobot::RegionalAllocationSet::Acquire),
+            )
+            .add_systems(Update, structure_damage_visual_system);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Priority and attack-window helpers. The combat -> repair flow
+    //! is covered by `tests/behavior/repair.rs`.
+
+    use super::*;
+    use crate::nanobot::structure::{STRUCTURE_MAX_HEALTH, StructureKind};
+
+    fn damaged(health: u32) -> Structure {
+        let mut structure = Structure::new(StructureKind::Basic);
+        structure.health = health;
+        structure
+    }
+
+    #[test]
+    fn recent_damage_and_pressure_raise_priority() {
+        let structure = damaged(STRUCTURE_MAX_HEALTH - 10);
+        let calm = repair_priority(&structure, None, DEFEND_PRESSURE_BASELINE);
+        assert_eq!(calm, 10);
+        let hit = RecentDamage {
+            amount: 10,
+            ticks_since_hit: 0,
+        };
+        let recent = repair_priority(&structure, Some(&hit), DEFEND_PRESSURE_BASELINE);
+        assert!(recent > calm);
+        let pressured = repair_priority(&structure, Some(&hit), DEFEND_PRESSURE_BASELINE + 2.0);
+        assert!(pressured > recent);
+    }
+
+    #[test]
+    fn attack_window_blocks_only_undefended_structures() {
+        let mut hit = RecentDamage::default();
+        hit.record_hit(5);
+        assert!(repair_blocked(Some(&hit), false));
+        assert!(!repair_blocked(Some(&hit), true));
+        hit.ticks_since_hit = UNDER_ATTACK_TICKS;
+        assert!(!repair_blocked(Some(&hit), false));
+        assert!(!repair_blocked(None, false));
+    }
+
+    #[test]
+    fn full_health_structure_needs_no_repair() {
+        assert!(!Structure::new(StructureKind::Basic).needs_repair());
+        assert!(damaged(STRUCTURE_MAX_HEALTH - 1).needs_repair());
+    }
+
+    #[test]
+    fn attack_window_closes_before_recent_damage_expires() {
+        const { assert!(UNDER_ATTACK_TICKS < RECENT_DAMAGE_TICKS) };
+    }
+}
@@ -0,0 +1,580 @@
+//! Per-swarm research: a small upgrade tree paid for in minerals.
+//!
+//! Research is a terminal consumer like production. Each swarm keeps its
+//! own [`SwarmResearch`] inside the [`ResearchState`] resource: a set of
+//! unlocked [`Upgrade`]s and an ordered queue the player edits through the
+//! research panel. Material reaches research the same way it reaches a
+//! Production Facility:
+//!
+//! ```text
+//!   Sink Stockpile -> (hauler, logistics leg 3) -> ResearchLab hopper
+//!   ResearchLab hopper -> funds the queue head (ledger debited)
+//!   funded head -> one progress tick per operational lab per fixed tick
+//!   progress >= Upgrade::ticks() -> unlocked, queue advances
+//! ```
+//!
+//! A swarm with queued research and no lab (planned or completed) plans a
+//! [`PlannedKind::ResearchLab`] in one of its free Build cells. The lab is
+//! built by a Worker through the shared planned-structure lifecycle.
+//!
+//! Unlocked upgrades are read by the systems they modify through the
+//! `ResearchState` helpers. Every consumer reads the resource as optional
+//! and falls back to the base constant, so apps without [`ResearchPlugin`]
+//! keep the original numbers.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+
+use crate::intent::{IntentGrid, IntentKind};
+use crate::nanobot::components::{Swarm, SwarmId};
+use crate::nanobot::gather::{EXTRACT_PER_TICK, world_to_cell};
+use crate::nanobot::haul::HAULER_CARRY_CAPACITY;
+use crate::nanobot::maintenance::{DEGRADATION_INTERVAL_TICKS, SupportCondition};
+use crate::nanobot::placement::{find_build_zone_placement, scaled_building_footprint_radius};
+use crate::nanobot::planned::{PlannedKind, PlannedStructure, planned_visual_components};
+use crate::nanobot::{Charger, OwnerSwarm, ProductionFacility};
+use crate::resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile};
+use crate::structure_sprites::StructureSprites;
+
+/// Capacity of a [`ResearchLab`]'s input hopper. One hopper covers the
+/// most expensive upgrade so a single delivery burst can fund it.
+pub const RESEARCH_LAB_INPUT_CAPACITY: u32 = 80;
+
+/// Hauler load once [`Upgrade::HaulerCarry`] is unlocked. Kept a
+/// multiple of [`crate::nanobot::HAULER_EXTRACT_PER_TICK`] so loading
+/// still finishes on a whole tick.
+pub const RESEARCHED_HAULER_CARRY_CAPACITY: u32 = 32;
+
+/// Worker extraction per tick once [`Upgrade::ExtractRate`] is unlocked.
+pub const RESEARCHED_EXTRACT_PER_TICK: u32 = 2;
+
+/// Defender attack multiplier once [`Upgrade::DefenderAttack`] is unlocked.
+pub const RESEARCHED_ATTACK_MULTIPLIER: f32 = 1.5;
+
+/// Degradation cadence once [`Upgrade::Durability`] is unlocked. Health
+/// still falls by `DEGRADATION_PER_STEP`, but half as often.
+pub const RESEARCHED_DEGRADATION_INTERVAL_TICKS: u32 = DEGRADATION_INTERVAL_TICKS * 2;
+
+/// One node of the research tree. Data-less so [`Upgrade::ALL`] can stay
+/// a `const` array for the UI and for table sizing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Upgrade {
+    /// Raises the hauler load to [`RESEARCHED_HAULER_CARRY_CAPACITY`].
+    HaulerCarry,
+    /// Raises worker extraction to [`RESEARCHED_EXTRACT_PER_TICK`].
+    ExtractRate,
+    /// Multiplies defender attack by [`RESEARCHED_ATTACK_MULTIPLIER`].
+    /// Requires [`Upgrade::ExtractRate`].
+    DefenderAttack,
+    /// Halves the structure degradation rate. Requires
+    /// [`Upgrade::HaulerCarry`].
+    Durability,
+}
+
+impl Upgrade {
+    pub const COUNT: usize = 4;
+
+    /// Every upgrade in stable display order. Roots come first so the
+    /// panel reads top-down along the tree.
+    pub const ALL: [Upgrade; Self::COUNT] = [
+        Upgrade::HaulerCarry,
+        Upgrade::ExtractRate,
+        Upgrade::DefenderAttack,
+        Upgrade::Durability,
+    ];
+
+    pub const fn index(self) -> usize {
+        match self {
+            Upgrade::HaulerCarry => 0,
+            Upgrade::ExtractRate => 1,
+            Upgrade::DefenderAttack => 2,
+            Upgrade::Durability => 3,
+        }
+    }
+
+    /// Minerals the queue head must draw from a lab hopper before
+    /// progress starts.
+    pub const fn cost(self) -> u32 {
+        match self {
+            Upgrade::HaulerCarry | Upgrade::ExtractRate => 40,
+            Upgrade::DefenderAttack | Upgrade::Durability => 80,
+        }
+    }
+
+    /// Lab-ticks of progress after funding. Two operational labs finish
+    /// in half the wall time.
+    pub const fn ticks(self) -> u32 {
+        match self {
+            Upgrade::HaulerCarry | Upgrade::ExtractRate => crate::SIMULATION_HZ as u32 * 5,
+            Upgrade::DefenderAttack | Upgrade::Durability => crate::SIMULATION_HZ as u32 * 10,
+        }
+    }
+
+    /// Upgrade that must be unlocked (or queued ahead) first.
+    pub const fn prerequisite(self) -> Option<Upgrade> {
+        match self {
+            Upgrade::HaulerCarry | Upgrade::ExtractRate => None,
+            Upgrade::DefenderAttack => Some(Upgrade::ExtractRate),
+            Upgrade::Durability => Some(Upgrade::HaulerCarry),
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Upgrade::HaulerCarry => "Hauler Carry",
+            Upgrade::ExtractRate => "Extraction",
+            Upgrade::DefenderAttack => "Defender Attack",
+            Upgrade::Durability => "Durability",
+        }
+    }
+}
+
+/// Research progress for a single swarm.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwarmResearch {
+    pub unlocked: HashSet<Upgrade>,
+    /// Pending upgrades, head first. Only the head is funded and
+    /// progressed.
+    pub queue: Vec<Upgrade>,
+    /// Minerals already drawn from lab hoppers for the queue head.
+    pub funded: u32,
+    /// Lab-ticks spent on the fully funded queue head.
+    pub progress: u32,
+}
+
+impl SwarmResearch {
+    pub fn is_unlocked(&self, upgrade: Upgrade) -> bool {
+        self.unlocked.contains(&upgrade)
+    }
+
+    pub fn is_queued(&self, upgrade: Upgrade) -> bool {
+        self.queue.contains(&upgrade)
+    }
+
+    /// True when `upgrade` is neither unlocked nor queued and its
+    /// prerequisite is unlocked or already in the queue.
+    pub fn can_enqueue(&self, upgrade: Upgrade) -> bool {
+        !self.is_unlocked(upgrade)
+            && !self.is_queued(upgrade)
+            && upgrade
+                .prerequisite()
+                .is_none_or(|required| self.is_unlocked(required) || self.is_queued(required))
+    }
+
+    /// Append `upgrade` to the queue. Returns `false` when
+    /// [`Self::can_enqueue`] rejects it.
+    pub fn enqueue(&mut self, upgrade: Upgrade) -> bool {
+        if !self.can_enqueue(upgrade) {
+            return false;
+        }
+        self.queue.push(upgrade);
+        true
+    }
+
+    /// Remove `upgrade` and every queued upgrade that depends on it.
+    /// Cancelling the head discards its funding: the minerals were
+    /// already consumed from the lab hopper.
+    pub fn cancel(&mut self, upgrade: Upgrade) -> bool {
+        let Some(position) = self.queue.iter().position(|queued| *queued == upgrade) else {
+            return false;
+        };
+        if position == 0 {
+            self.funded = 0;
+            self.progress = 0;
+        }
+        self.queue.remove(position);
+        let dependents: Vec<Upgrade> = self
+            .queue
+            .iter()
+            .copied()
+            .filter(|queued| queued.prerequisite() == Some(upgrade))
+            .collect();
+        for dependent in dependents {
+            self.cancel(dependent);
+        }
+        true
+    }
+
+    /// Minerals still needed before the queue head starts progressing.
+    pub fn funding_needed(&self) -> u32 {
+        self.queue
+            .first()
+            .map_or(0, |head| head.cost().saturating_sub(self.funded))
+    }
+
+    /// Head progress as a `0..=100` percentage; funding counts for
+    /// nothing until it is complete.
+    pub fn head_progress_percent(&self) -> u32 {
+        let Some(head) = self.queue.first() else {
+            return 0;
+        };
+        if self.funding_needed() > 0 {
+            return 0;
+        }
+        (self.progress.min(head.ticks()) * 100) / head.ticks().max(1)
+    }
+
+    /// Add one lab-tick of progress to a funded head, unlocking it when
+    /// done. Returns the upgrade that just completed.
+    fn advance(&mut self) -> Option<Upgrade> {
+        let head = *self.queue.first()?;
+        if self.funding_needed() > 0 {
+            return None;
+        }
+        self.progress = self.progress.saturating_add(1);
+        if self.progress < head.ticks() {
+            return None;
+        }
+        self.queue.remove(0);
+        self.unlocked.insert(head);
+        self.funded = 0;
+        self.progress = 0;
+        Some(head)
+    }
+}
+
+/// Research state for every swarm, keyed by [`SwarmId`].
+#[derive(Debug, Default, Resource)]
+pub struct ResearchState {
+    by_swarm: HashMap<SwarmId, SwarmResearch>,
+}
+
+impl ResearchState {
+    pub fn for_swarm(&self, swarm: SwarmId) -> Option<&SwarmResearch> {
+        self.by_swarm.get(&swarm)
+    }
+
+    pub fn for_swarm_mut(&mut self, swarm: SwarmId) -> &mut SwarmResearch {
+        self.by_swarm.entry(swarm).or_default()
+    }
+
+    pub fn is_unlocked(&self, swarm: SwarmId, upgrade: Upgrade) -> bool {
+        self.for_swarm(swarm)
+            .is_some_and(|research| research.is_unlocked(upgrade))
+    }
+
+    pub fn hauler_carry_capacity(&self, swarm: SwarmId) -> u32 {
+        if self.is_unlocked(swarm, Upgrade::HaulerCarry) {
+            RESEARCHED_HAULER_CARRY_CAPACITY
+        } else {
+            HAULER_CARRY_CAPACITY
+        }
+    }
+
+    pub fn extract_per_tick(&self, swarm: SwarmId) -> u32 {
+        if self.is_unlocked(swarm, Upgrade::ExtractRate) {
+            RESEARCHED_EXTRACT_PER_TICK
+        } else {
+            EXTRACT_PER_TICK
+        }
+    }
+
+    pub fn attack_multiplier(&self, swarm: SwarmId) -> f32 {
+        if self.is_unlocked(swarm, Upgrade::DefenderAttack) {
+            RESEARCHED_ATTACK_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    pub fn degradation_interval_ticks(&self, swarm: SwarmId) -> u32 {
+        if self.is_unlocked(swarm, Upgrade::Durability) {
+            RESEARCHED_DEGRADATION_INTERVAL_TICKS
+        } else {
+            DEGRADATION_INTERVAL_TICKS
+        }
+    }
+}
+
+/// Hauler load for `swarm`, or the base capacity when research is not
+/// running in this app.
+pub fn researched_hauler_carry_capacity(research: Option<&ResearchState>, swarm: SwarmId) -> u32 {
+    research.map_or(HAULER_CARRY_CAPACITY, |research| {
+        research.hauler_carry_capacity(swarm)
+    })
+}
+
+/// A completed Research Lab. Like [`ProductionFacility`] it is a terminal
+/// consumer with its own input hopper; haulers fill it from Sink
+/// Stockpiles and research drains it. It is deliberately not a
+/// `Stockpile`, so gather returns and hauler sources never see it.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct ResearchLab {
+    pub input_kind: ResourceKind,
+    pub input_amount: u32,
+    pub input_capacity: u32,
+}
+
+impl ResearchLab {
+    pub fn new() -> Self {
+        Self {
+            input_kind: ResourceKind::Minerals,
+            input_amount: 0,
+            input_capacity: RESEARCH_LAB_INPUT_CAPACITY,
+        }
+    }
+
+    /// Free hopper capacity for hauler delivery, mirroring
+    /// [`ProductionFacility::input_free_space`].
+    pub fn input_free_space(&self) -> u32 {
+        self.input_capacity.saturating_sub(self.input_amount)
+    }
+}
+
+impl Default for ResearchLab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Written once per unlocked upgrade.
+#[derive(Debug, Clone, Copy, Message)]
+pub struct ResearchCompleted {
+    pub swarm: SwarmId,
+    pub upgrade: Upgrade,
+}
+
+fn lab_swarm(owner: Option<&OwnerSwarm>, swarms: &Query<&SwarmId, With<Swarm>>) -> SwarmId {
+    owner
+        .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+        .unwrap_or(SwarmId::PLAYER)
+}
+
+/// Fund and progress each swarm's queue head from its operational labs.
+/// Funding drains lab hoppers (and the ledger) only up to the head's
+/// remaining cost; every operational lab adds one progress tick once the
+/// head is fully funded. Unowned labs serve the player, matching the
+/// production fallback.
+#[allow(clippy::type_complexity)]
+pub fn research_lab_work_system(
+    mut research: ResMut<ResearchState>,
+    mut ledger: ResMut<ResourceLedger>,
+    mut labs: Query<(
+        Entity,
+        &mut ResearchLab,
+        Option<&OwnerSwarm>,
+        Option<&SupportCondition>,
+    )>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut completed: MessageWriter<ResearchCompleted>,
+) {
+    let mut ordered: Vec<_> = labs.iter_mut().collect();
+    ordered.sort_by_key(|(entity, ..)| entity.to_bits());
+    for (_, mut lab, owner, condition) in ordered {
+        if condition.is_some_and(|condition| !condition.is_operational()) {
+            continue;
+        }
+        let swarm = lab_swarm(owner, &swarms);
+        let swarm_research = research.for_swarm_mut(swarm);
+        if swarm_research.queue.is_empty() {
+            continue;
+        }
+        let draw = swarm_research.funding_needed().min(lab.input_amount);
+        if draw > 0 {
+            lab.input_amount -= draw;
+            swarm_research.funded += draw;
+            ledger.remove_for(swarm, lab.input_kind, draw);
+        }
+        if let Some(upgrade) = swarm_research.advance() {
+            completed.write(ResearchCompleted { swarm, upgrade });
+        }
+    }
+}
+
+/// Plan a Research Lab for every swarm that has queued research but no
+/// lab, planned or completed. Placement follows the Production Facility
+/// rule: the swarm must own a free Build cell, and the lab must not
+/// overlap deposits or other support structures.
+#[allow(clippy::type_complexity)]
+pub fn research_lab_demand_system(
+    mut commands: Commands,
+    grid: Res<IntentGrid>,
+    structure_sprites: Res<StructureSprites>,
+    research: Res<ResearchState>,
+    labs: Query<Option<&OwnerSwarm>, With<ResearchLab>>,
+    planned: Query<(&PlannedStructure, Option<&OwnerSwarm>)>,
+    existing_targets: Query<
+        &Transform,
+        Or<(
+            With<PlannedStructure>,
+            With<ProductionFacility>,
+            With<Stockpile>,
+            With<Charger>,
+            With<ResearchLab>,
+        )>,
+    >,
+    deposits: Query<(&ResourceDeposit, &Transform)>,
+    swarms: Query<(Entity, &SwarmId), With<Swarm>>,
+) {
+    let mut cells_with_target: HashSet<IVec2> = HashSet::new();
+    let mut obstacles: Vec<(Vec2, f32)> = deposits
+        .iter()
+        .map(|(deposit, transform)| (transform.translation.truncate(), deposit.radius))
+        .collect();
+    for transform in &existing_targets {
+        let pos = transform.translation.truncate();
+        cells_with_target.insert(world_to_cell(pos));
+        obstacles.push((pos, scaled_building_footprint_radius(transform)));
+    }
+    let mut build_cells_by_swarm: HashMap<SwarmId, Vec<IVec2>> = HashMap::new();
+    for (cell, intent_cell) in grid.iter_active_cells() {
+        if !intent_cell.has(IntentKind::Build) || cells_with_target.contains(&cell) {
+            continue;
+        }
+        let Some(owner_id) = intent_cell.owner(IntentKind::Build) else {
+            continue;
+        };
+        build_cells_by_swarm.entry(owner_id).or_default().push(cell);
+    }
+    for (swarm_entity, swarm_id) in &swarms {
+        if research
+            .for_swarm(*swarm_id)
+            .is_none_or(|research| research.queue.is_empty())
+        {
+            continue;
+        }
+        let owns = |owner: Option<&OwnerSwarm>| match owner {
+            Some(OwnerSwarm(owner)) => *owner == swarm_entity,
+            None => *swarm_id == SwarmId::PLAYER,
+        };
+        let has_lab = labs.iter().any(owns)
+            || planned
+                .iter()
+                .any(|(planned, owner)| planned.kind == PlannedKind::ResearchLab && owns(owner));
+        if has_lab {
+            continue;
+        }
+        let Some((build_cell, placement_pos)) = build_cells_by_swarm
+            .get(swarm_id)
+            .and_then(|cells| find_build_zone_placement(cells, &obstacles, 29))
+        else {
+            continue;
+        };
+        commands.spawn((
+            PlannedStructure::new(PlannedKind::ResearchLab, build_cell),
+            OwnerSwarm(swarm_entity),
+            planned_visual_components(PlannedKind::ResearchLab, &structure_sprites, placement_pos),
+        ));
+    }
+}
+
+/// Plugin that wires research into the fixed schedule. Lab demand runs
+/// after Production Facility auto-creation and before Sink Stockpile
+/// demand, so production keeps first pick of a free Build cell and the
+/// lab still claims one before sink plans fill the rest.
+pub struct ResearchPlugin;
+
+impl Plugin for ResearchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ResearchState>()
+            .add_message::<ResearchCompleted>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    research_lab_work_system,
+                    research_lab_demand_system
+                        .after(crate::nanobot::production_facility_auto_creation_system)
+                        .before(crate::nanobot::planned::sink_stockpile_demand_system),
+                )
+                    .chain()
+                    .after(crate::nanobot::NanobotSimulationSet::Movement),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Pure queue and modifier tests. Lab delivery, funding and lab
+    //! planning are covered by `tests/behavior/research_lab.rs`.
+
+    use super::*;
+    use crate::nanobot::HAULER_EXTRACT_PER_TICK;
+
+    #[test]
+    fn researched_carry_capacity_keeps_whole_tick_loading() {
+        const {
+            assert!(RESEARCHED_HAULER_CARRY_CAPACITY > HAULER_CARRY_CAPACITY);
+            assert!(RESEARCHED_HAULER_CARRY_CAPACITY.is_multiple_of(HAULER_EXTRACT_PER_TICK));
+        };
+    }
+
+    #[test]
+    fn lab_hopper_covers_the_most_expensive_upgrade() {
+        for upgrade in Upgrade::ALL {
+            assert!(upgrade.cost() <= RESEARCH_LAB_INPUT_CAPACITY);
+        }
+    }
+
+    #[test]
+    fn upgrade_indexes_match_all_order() {
+        for (index, upgrade) in Upgrade::ALL.into_iter().enumerate() {
+            assert_eq!(upgrade.index(), index);
+        }
+    }
+
+    #[test]
+    fn enqueue_requires_prerequisite_unlocked_or_queued() {
+        let mut research = SwarmResearch::default();
+        assert!(!research.enqueue(Upgrade::DefenderAttack));
+        assert!(research.enqueue(Upgrade::ExtractRate));
+        assert!(research.enqueue(Upgrade::DefenderAttack));
+        assert!(!research.enqueue(Upgrade::ExtractRate), "no duplicates");
+        assert_eq!(
+            research.queue,
+            vec![Upgrade::ExtractRate, Upgrade::DefenderAttack]
+        );
+    }
+
+    #[test]
+    fn cancelling_a_prerequisite_drops_its_dependents() {
+        let mut research = SwarmResearch::default();
+        research.enqueue(Upgrade::HaulerCarry);
+        research.enqueue(Upgrade::ExtractRate);
+        research.enqueue(Upgrade::Durability);
+        research.funded = 10;
+
+        assert!(research.cancel(Upgrade::HaulerCarry));
+        assert_eq!(research.queue, vec![Upgrade::ExtractRate]);
+        assert_eq!(research.funded, 0, "head funding is discarded");
+    }
+
+    #[test]
+    fn funded_head_unlocks_after_its_ticks() {
+        let mut research = SwarmResearch::default();
+        research.enqueue(Upgrade::ExtractRate);
+        assert_eq!(research.advance(), None, "unfunded head does not progress");
+        research.funded = Upgrade::ExtractRate.cost();
+        for _ in 1..Upgrade::ExtractRate.ticks() {
+            assert_eq!(research.advance(), None);
+        }
+        assert_eq!(research.advance(), Some(Upgrade::ExtractRate));
+        assert!(research.is_unlocked(Upgrade::ExtractRate));
+        assert!(research.queue.is_empty());
+    }
+
+    #[test]
+    fn modifiers_fall_back_to_base_constants() {
+        let mut state = ResearchState::default();
+        assert_eq!(
+            state.hauler_carry_capacity(SwarmId::PLAYER),
+            HAULER_CARRY_CAPACITY
+        );
+        assert_eq!(state.extract_per_tick(SwarmId::PLAYER), EXTRACT_PER_TICK);
+        assert_eq!(state.attack_multiplier(SwarmId::PLAYER), 1.0);
+
+        state
+            .for_swarm_mut(SwarmId::PLAYER)
+            .unlocked
+            .insert(Upgrade::Durability);
+        assert_eq!(
+            state.degradation_interval_ticks(SwarmId::PLAYER),
+            RESEARCHED_DEGRADATION_INTERVAL_TICKS
+        );
+        assert_eq!(
+            state.degradation_interval_ticks(SwarmId(1)),
+            DEGRADATION_INTERVAL_TICKS,
+            "upgrades are per swarm"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Optional road segments built along Logistics Corridor paint.
+//!
+//! Without a [`CorridorRoads`] resource Corridor paint stays a hauler
+//! routing hint and nothing is built on it. With it, owned Corridor
+//! cells emerge into [`PlannedKind::Road`] plans that Workers build
+//! through the shared planned-structure lifecycle:
+//!
+//! ```text
+//!   owned Corridor cell, no road, no road plan
+//!     -> demand system plans a Road (capped per swarm)
+//!     -> a Worker builds it; promotion inserts Road { cell }
+//!     -> the grid records the road: every owned bot on the cell moves
+//!        ROAD_SPEED_MULTIPLIER faster, and routes through it cost
+//!        ROAD_COST_MULTIPLIER
+//!     -> MaintenancePlugin degrades it; a collapsed road is despawned
+//!        and the grid forgets it
+//! ```
+//!
+//! A built road outlives the paint that planned it: erasing Corridor
+//! stops new plans but leaves finished segments until they collapse.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::intent::{IntentGrid, IntentKind};
+use crate::nanobot::components::{Swarm, SwarmId};
+use crate::nanobot::gather::{get_world_from_zone, world_to_cell};
+use crate::nanobot::planned::{PlannedKind, PlannedStructure, planned_visual_components};
+use crate::nanobot::{OwnerSwarm, add_route_cost_cache};
+use crate::structure_sprites::StructureSprites;
+
+/// Top-speed multiplier for a nanobot standing on a road its swarm
+/// may use.
+pub const ROAD_SPEED_MULTIPLIER: f32 = 1.5;
+
+/// Road building on Corridor paint. While present, owned Corridor
+/// cells are planned as roads for Workers to build; without it
+/// Corridor paint only bends hauler routes.
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq)]
+pub struct CorridorRoads {
+    /// Most road plans one swarm keeps open at once, so a long
+    /// Corridor stroke does not bury every other build.
+    pub max_pending_plans: u32,
+}
+
+impl Default for CorridorRoads {
+    fn default() -> Self {
+        Self {
+            max_pending_plans: 4,
+        }
+    }
+}
+
+/// A built road segment covering one intent-grid cell.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct Road {
+    pub cell: IVec2,
+}
+
+/// Speed multiplier for a `swarm` nanobot at `pos`.
+pub fn road_speed_multiplier(grid: &IntentGrid, pos: Vec2, swarm: SwarmId) -> f32 {
+    if grid.road_visible_to(world_to_cell(pos), swarm) {
+        ROAD_SPEED_MULTIPLIER
+    } else {
+        1.0
+    }
+}
+
+/// Plan a road on every owned Corridor cell that has neither a road
+/// nor a road plan, up to [`CorridorRoads::max_pending_plans`] open
+/// plans per swarm. Cells are visited in the grid's row-major order,
+/// so a stroke is planned from one end.
+pub fn road_demand_system(
+    mut commands: Commands,
+    roads: Option<Res<CorridorRoads>>,
+    grid: Res<IntentGrid>,
+    structure_sprites: Res<StructureSprites>,
+    planned: Query<(&PlannedStructure, Option<&OwnerSwarm>)>,
+    swarms: Query<(Entity, &SwarmId), With<Swarm>>,
+) {
+    let Some(roads) = roads else {
+        return;
+    };
+    let swarm_by_id: HashMap<SwarmId, Entity> = swarms.iter().map(|(e, id)| (*id, e)).collect();
+    let mut pending: HashMap<Entity, u32> = HashMap::new();
+    let mut planned_cells = Vec::new();
+    for (planned, owner) in &planned {
+        if planned.kind != PlannedKind::Road {
+            continue;
+        }
+        planned_cells.push(planned.cell);
+        if let Some(OwnerSwarm(owner)) = owner {
+            *pending.entry(*owner).or_default() += 1;
+        }
+    }
+
+    for (cell, intent) in grid.iter_active_cells() {
+        if !intent.has(IntentKind::Corridor) || grid.has_road(cell) || planned_cells.contains(&cell)
+        {
+            continue;
+        }
+        let Some(owner) = intent
+            .owner(IntentKind::Corridor)
+            .and_then(|id| swarm_by_id.get(&id).copied())
+        else {
+            continue;
+        };
+        let open = pending.entry(owner).or_default();
+        if *open >= roads.max_pending_plans {
+            continue;
+        }
+        *open += 1;
+        commands.spawn((
+            PlannedStructure::new(PlannedKind::Road, cell),
+            OwnerSwarm(owner),
+            planned_visual_components(
+                PlannedKind::Road,
+                &structure_sprites,
+                get_world_from_zone(cell),
+            ),
+        ));
+    }
+}
+
+fn register_road(
+    added: On<Add, Road>,
+    mut grid: ResMut<IntentGrid>,
+    roads: Query<(&Road, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId>,
+) {
+    let Ok((road, owner)) = roads.get(added.entity) else {
+        return;
+    };
+    let owner = owner.and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied());
+    grid.set_road(road.cell, owner);
+}
+
+/// A collapsed or despawned road stops discounting routes
+/// and boosting speed on its cell.
+fn forget_road(removed: On<Remove, Road>, mut grid: ResMut<IntentGrid>, roads: Query<&Road>) {
+    if let Ok(road) = roads.get(removed.entity) {
+        grid.clear_road(road.cell);
+    }
+}
+
+/// Plans roads on Corridor paint and keeps the intent grid's road
+/// layer in step with built [`Road`] entities. Road demand runs with
+/// the other planned-structure demand, before Workers claim this
+/// tick's plans.
+pub struct RoadPlugin;
+
+impl Plugin for RoadPlugin {
+    fn build(&self, app: &mut App) {
+        add_route_cost_cache(app);
+        app.add_observer(register_road)
+            .add_observer(forget_road)
+            .add_systems(
+                FixedUpdate,
+                road_demand_system
+                    .before(crate::nanobot::planned::sink_stockpile_demand_system)
+                    .after(crate::nanobot::NanobotSimulationSet::Movement),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Speed lookup. Planning, building, routing, and collapse live in
+    //! `tests/behavior/road.rs`.
+
+    use super::*;
+
+    #[test]
+    fn only_usable_roads_raise_speed() {
+        let mut grid = IntentGrid::new(8, 8);
+        let on_road = get_world_from_zone(IVec2::new(1, 1));
+        assert_eq!(road_speed_multiplier(&grid, on_road, SwarmId::PLAYER), 1.0);
+
+        grid.set_road(IVec2::new(1, 1), Some(SwarmId::PLAYER));
+        assert_eq!(
+            road_speed_multiplier(&grid, on_road, SwarmId::PLAYER),
+            ROAD_SPEED_MULTIPLIER
+        );
+        assert_eq!(road_speed_multiplier(&grid, on_road, SwarmId(3)), 1.0);
+        assert_eq!(
+            road_speed_multiplier(&grid, get_world_from_zone(IVec2::ZERO), SwarmId::PLAYER),
+            1.0
+        );
+    }
+}
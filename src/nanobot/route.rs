@@ -3,28 +3,44 @@
 //! Logistics Corridor paint is a soft cost field for haulers. The
 //! planner keeps normal cells traversable, discounts visible owned
 //! corridor cells, and returns ordinary waypoints for the movement
-//! systems to follow.
+//! systems to follow. A built road (see [`crate::nanobot::Road`])
+//! discounts its cell further for the owning swarm, whatever the paint.
+//! Danger from hostile Defenders (see [`crate::nanobot::ThreatPlugin`])
+//! adds to a cell's cost for the threatened swarm only, so routes bend
+//! around a fight instead of refusing to cross it.
+//! Allocation and leg picking score routes through
+//! [`crate::nanobot::RouteCostCache`], which returns the same costs.
 
 use bevy::prelude::{IVec2, Vec2};
 use pathfinding::prelude::astar;
 
 use crate::{
     ZONE_BLOCK_SIZE,
-    ai::get_world_from_zone,
     intent::{IntentGrid, IntentKind},
-    nanobot::{SwarmId, gather::world_to_cell},
+    nanobot::{
+        SwarmId,
+        gather::{get_world_from_zone, world_to_cell},
+    },
 };
 
 const COST_SCALE: u32 = 1_000;
 const CARDINAL_STEP_COST: u32 = COST_SCALE;
 const DIAGONAL_STEP_COST: u32 = 1_414;
 const CORRIDOR_MIN_MULTIPLIER_SCALED: u32 = 350;
+const ROAD_MULTIPLIER_SCALED: u32 = 250;
 const NORMAL_MULTIPLIER_SCALED: u32 = COST_SCALE;
+const DANGER_STEP_SCALED: u32 = COST_SCALE;
 
 /// Cost multiplier for owned Logistics Corridor paint.
 pub const CORRIDOR_MIN_COST_MULTIPLIER: f32 =
     CORRIDOR_MIN_MULTIPLIER_SCALED as f32 / COST_SCALE as f32;
 
+/// Cost multiplier for a cell holding an owned built road.
+pub const ROAD_COST_MULTIPLIER: f32 = ROAD_MULTIPLIER_SCALED as f32 / COST_SCALE as f32;
+
+/// Cost multiplier added per danger level on a cell.
+pub const DANGER_COST_PER_LEVEL: f32 = DANGER_STEP_SCALED as f32 / COST_SCALE as f32;
+
 /// A planned route between two world positions.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PlannedRoute {
@@ -79,34 +95,51 @@ pub fn plan_hauler_route(
 
     Some(PlannedRoute {
         waypoints,
-        cost: scaled_cost as f32 / COST_SCALE as f32 * ZONE_BLOCK_SIZE,
+        cost: scaled_cost_to_world(scaled_cost),
     })
 }
 
+/// World-unit cost of a scaled planner cost.
+pub(super) fn scaled_cost_to_world(scaled_cost: u32) -> f32 {
+    scaled_cost as f32 / COST_SCALE as f32 * ZONE_BLOCK_SIZE
+}
+
+/// In-bounds 8-neighbours of `cell`.
+pub(super) fn route_neighbours(cell: IVec2, grid: &IntentGrid) -> impl Iterator<Item = IVec2> {
+    (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| IVec2::new(dx, dy)))
+        .filter(|offset| *offset != IVec2::ZERO)
+        .map(move |offset| cell + offset)
+        .filter(|next| grid.in_bounds(*next))
+}
+
+/// Scaled cost of one step from `from` into the neighbouring `to`.
+/// The entered cell's paint, road, and danger set the multiplier.
+pub(super) fn step_cost_scaled(from: IVec2, to: IVec2, grid: &IntentGrid, swarm: SwarmId) -> u32 {
+    let delta = to - from;
+    let step = if delta.x != 0 && delta.y != 0 {
+        DIAGONAL_STEP_COST
+    } else {
+        CARDINAL_STEP_COST
+    };
+    (step * traversal_multiplier_scaled(to, grid, swarm)).div_ceil(COST_SCALE)
+}
+
 fn route_successors(cell: IVec2, grid: &IntentGrid, swarm: SwarmId) -> Vec<(IVec2, u32)> {
-    let mut out = Vec::with_capacity(8);
-    for dy in -1..=1 {
-        for dx in -1..=1 {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
-            let next = cell + IVec2::new(dx, dy);
-            if !grid.in_bounds(next) {
-                continue;
-            }
-            let step = if dx != 0 && dy != 0 {
-                DIAGONAL_STEP_COST
-            } else {
-                CARDINAL_STEP_COST
-            };
-            let multiplier = traversal_multiplier_scaled(next, grid, swarm);
-            out.push((next, (step * multiplier).div_ceil(COST_SCALE)));
-        }
-    }
-    out
+    route_neighbours(cell, grid)
+        .map(|next| (next, step_cost_scaled(cell, next, grid, swarm)))
+        .collect()
 }
 
 fn traversal_multiplier_scaled(cell: IVec2, grid: &IntentGrid, swarm: SwarmId) -> u32 {
+    base_multiplier_scaled(cell, grid, swarm)
+        + u32::from(grid.danger_for(cell, swarm)) * DANGER_STEP_SCALED
+}
+
+fn base_multiplier_scaled(cell: IVec2, grid: &IntentGrid, swarm: SwarmId) -> u32 {
+    if grid.road_visible_to(cell, swarm) {
+        return ROAD_MULTIPLIER_SCALED;
+    }
     let Some(intent_cell) = grid.cell(cell) else {
         return NORMAL_MULTIPLIER_SCALED;
     };
@@ -116,12 +149,13 @@ fn traversal_multiplier_scaled(cell: IVec2, grid: &IntentGrid, swarm: SwarmId) -
     CORRIDOR_MIN_MULTIPLIER_SCALED
 }
 
-fn octile_heuristic_scaled(from: IVec2, to: IVec2) -> u32 {
+pub(super) fn octile_heuristic_scaled(from: IVec2, to: IVec2) -> u32 {
     let delta = (to - from).abs();
     let diagonal = delta.x.min(delta.y) as u32;
     let straight = delta.x.max(delta.y) as u32 - diagonal;
     let base = diagonal * DIAGONAL_STEP_COST + straight * CARDINAL_STEP_COST;
-    (base * CORRIDOR_MIN_MULTIPLIER_SCALED).div_ceil(COST_SCALE)
+    // The cheapest cell bounds the estimate so A* stays exact.
+    (base * ROAD_MULTIPLIER_SCALED).div_ceil(COST_SCALE)
 }
 
 #[cfg(test)]
@@ -201,4 +235,42 @@ mod tests {
 
         assert!(route.waypoints.iter().any(|p| world_to_cell(*p).y == 1));
     }
+
+    #[test]
+    fn owned_road_beats_corridor_paint_only_for_its_swarm() {
+        let start = Vec2::ZERO;
+        let end = Vec2::new(3.0 * ZONE_BLOCK_SIZE, 0.0);
+        let mut corridor = IntentGrid::new(8, 8);
+        corridor.paint(IVec2::new(1, 0), IntentKind::Corridor);
+        corridor.paint(IVec2::new(2, 0), IntentKind::Corridor);
+        let mut road = corridor.clone();
+        road.set_road(IVec2::new(1, 0), Some(SwarmId::PLAYER));
+        road.set_road(IVec2::new(2, 0), Some(SwarmId::PLAYER));
+
+        let painted = hauler_route_cost(start, end, &corridor, SwarmId::PLAYER);
+        assert!(hauler_route_cost(start, end, &road, SwarmId::PLAYER) < painted);
+        assert_eq!(hauler_route_cost(start, end, &road, SwarmId(5)), painted);
+    }
+
+    #[test]
+    fn danger_detours_only_the_threatened_swarm() {
+        let start = Vec2::new(0.0, 0.0);
+        let end = Vec2::new(4.0 * ZONE_BLOCK_SIZE, 0.0);
+        let mut grid = IntentGrid::new(12, 12);
+        let calm = plan_hauler_route(start, end, &grid, SwarmId::PLAYER).unwrap();
+        grid.replace_danger(std::collections::HashMap::from([(
+            (SwarmId::PLAYER, IVec2::new(2, 0)),
+            4,
+        )]));
+
+        let threatened = plan_hauler_route(start, end, &grid, SwarmId::PLAYER).unwrap();
+        assert!(threatened.cost > calm.cost);
+        assert!(
+            threatened
+                .waypoints
+                .iter()
+                .all(|p| world_to_cell(*p) != IVec2::new(2, 0))
+        );
+        assert_eq!(hauler_route_cost(start, end, &grid, SwarmId(5)), calm.cost);
+    }
 }
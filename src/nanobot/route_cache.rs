@@ -0,0 +1,467 @@
+//! Cached, hierarchical hauler route costs.
+//!
+//! [`hauler_route_cost`] runs a full 8-neighbour A* over intent cells
+//! on every call, and allocation and leg picking score every candidate
+//! pair with it. [`RouteCostCache`] answers the same question in two
+//! layers:
+//!
+//! ```text
+//!   (swarm, start cell, end cell) cached      -> cost
+//!   miss -> A* over cluster portals
+//!             clusters: AllocationRegion-sized blocks of cells
+//!             portals:  every cluster border cell
+//!             edges:    portal -> portal inside a cluster (table,
+//!                       built once per swarm and kept), and
+//!                       portal -> neighbouring cluster's portal
+//! ```
+//!
+//! Every border cell is a portal and each cluster table holds exact
+//! in-cluster shortest paths, so the abstract search returns the same
+//! cost as the flat planner. Corridor paint, built roads, and danger are
+//! the only inputs that change a cost: [`route_cost_cache_invalidate_system`]
+//! drains the grid's Corridor, road, and danger changes, drops the cluster
+//! tables they touch, and forgets the cached pairs a changed cell can
+//! reach. A route through cell `x` costs at least the octile bound
+//! `start -> x -> end` at road rates, so a pair whose cached cost is
+//! below that bound keeps it whichever way `x` changed. A hostile moving
+//! through a fight only clears the pairs around it. Waypoints for
+//! movement still come from [`crate::nanobot::plan_hauler_route`].
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+use bevy::prelude::*;
+use pathfinding::prelude::{astar, dijkstra_all};
+
+use crate::intent::IntentGrid;
+use crate::nanobot::allocation::{ALLOCATION_REGION_CELLS, AllocationRegion};
+use crate::nanobot::components::SwarmId;
+use crate::nanobot::gather::world_to_cell;
+use crate::nanobot::route::{
+    hauler_route_cost, octile_heuristic_scaled, route_neighbours, scaled_cost_to_world,
+    step_cost_scaled,
+};
+
+/// Cached `(swarm, start cell, end cell)` costs kept before the pair
+/// cache starts over.
+pub const ROUTE_CACHE_MAX_PAIRS: usize = 65_536;
+/// Cluster portal tables kept before the table cache starts over.
+pub const ROUTE_CACHE_MAX_CLUSTERS: usize = 2048;
+
+/// Shared hauler route-cost cache. Lookups take `&self`, so scoring
+/// closures can use it behind a plain `Res`.
+#[derive(Debug, Default, Resource)]
+pub struct RouteCostCache {
+    state: Mutex<RouteCacheState>,
+}
+
+#[derive(Debug, Default)]
+struct RouteCacheState {
+    grid_size: IVec2,
+    pairs: HashMap<(SwarmId, IVec2, IVec2), u32>,
+    clusters: HashMap<(SwarmId, AllocationRegion), ClusterTable>,
+}
+
+/// Portal-to-portal costs inside one cluster for one swarm.
+#[derive(Debug)]
+struct ClusterTable {
+    portals: Vec<IVec2>,
+    /// Row-major `portals.len()` squared costs, scaled like the flat
+    /// planner.
+    costs: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RouteNode {
+    Start,
+    Portal(IVec2),
+    Goal,
+}
+
+impl RouteCostCache {
+    /// Route cost between two world positions, equal to
+    /// [`hauler_route_cost`] for the same grid and swarm.
+    pub fn cost(&self, start: Vec2, end: Vec2, grid: &IntentGrid, swarm: SwarmId) -> f32 {
+        let start_cell = world_to_cell(start);
+        let end_cell = world_to_cell(end);
+        if start_cell == end_cell || !grid.in_bounds(start_cell) || !grid.in_bounds(end_cell) {
+            return hauler_route_cost(start, end, grid, swarm);
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let size = IVec2::new(grid.width(), grid.height());
+        if state.grid_size != size {
+            state.clear();
+            state.grid_size = size;
+        }
+        let key = (swarm, start_cell, end_cell);
+        if let Some(scaled) = state.pairs.get(&key) {
+            return scaled_cost_to_world(*scaled);
+        }
+        let Some(scaled) = state.search(start_cell, end_cell, grid, swarm) else {
+            return start.distance(end);
+        };
+        if state.pairs.len() >= ROUTE_CACHE_MAX_PAIRS {
+            state.pairs.clear();
+        }
+        state.pairs.insert(key, scaled);
+        scaled_cost_to_world(scaled)
+    }
+
+    /// Forget the cluster tables containing `cells` and every cached
+    /// pair whose cost a change to one of them could move.
+    pub fn invalidate(&mut self, cells: &[IVec2]) {
+        if cells.is_empty() {
+            return;
+        }
+        let state = self.state.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let regions: BTreeSet<AllocationRegion> = cells
+            .iter()
+            .map(|cell| AllocationRegion::for_cell(*cell))
+            .collect();
+        state.pairs.retain(|(_, start, end), cost| {
+            cells.iter().all(|cell| {
+                octile_heuristic_scaled(*start, *cell) + octile_heuristic_scaled(*cell, *end)
+                    > *cost
+            })
+        });
+        state
+            .clusters
+            .retain(|(_, region), _| !regions.contains(region));
+    }
+
+    /// Cached `(swarm, start cell, end cell)` costs.
+    pub fn cached_pairs(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pairs
+            .len()
+    }
+
+    /// Cluster portal tables built so far.
+    pub fn cached_clusters(&self) -> usize {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clusters
+            .len()
+    }
+}
+
+impl RouteCacheState {
+    fn clear(&mut self) {
+        self.pairs.clear();
+        self.clusters.clear();
+    }
+
+    /// Scaled cost from `start` to `end` over the portal graph.
+    fn search(
+        &mut self,
+        start: IVec2,
+        end: IVec2,
+        grid: &IntentGrid,
+        swarm: SwarmId,
+    ) -> Option<u32> {
+        let start_region = AllocationRegion::for_cell(start);
+        let end_region = AllocationRegion::for_cell(end);
+        let from_start = costs_from(start, start_region, grid, swarm);
+        let to_end = costs_to(end, end_region, grid, swarm);
+        let clusters = &mut self.clusters;
+
+        let successors = |node: &RouteNode| -> Vec<(RouteNode, u32)> {
+            let mut out = Vec::new();
+            match *node {
+                RouteNode::Start => {
+                    out.extend(
+                        from_start
+                            .iter()
+                            .filter(|(cell, _)| is_portal(**cell, start_region, grid))
+                            .map(|(cell, cost)| (RouteNode::Portal(*cell), *cost)),
+                    );
+                    if start_region == end_region
+                        && let Some(cost) = from_start.get(&end)
+                    {
+                        out.push((RouteNode::Goal, *cost));
+                    }
+                }
+                RouteNode::Portal(cell) => {
+                    let region = AllocationRegion::for_cell(cell);
+                    if !clusters.contains_key(&(swarm, region))
+                        && clusters.len() >= ROUTE_CACHE_MAX_CLUSTERS
+                    {
+                        clusters.clear();
+                    }
+                    let table = clusters
+                        .entry((swarm, region))
+                        .or_insert_with(|| ClusterTable::build(region, grid, swarm));
+                    out.extend(table.edges_from(cell));
+                    out.extend(
+                        route_neighbours(cell, grid)
+                            .filter(|next| AllocationRegion::for_cell(*next) != region)
+                            .map(|next| {
+                                (
+                                    RouteNode::Portal(next),
+                                    step_cost_scaled(cell, next, grid, swarm),
+                                )
+                            }),
+                    );
+                    if region == end_region
+                        && let Some(cost) = to_end.get(&cell)
+                    {
+                        out.push((RouteNode::Goal, *cost));
+                    }
+                }
+                RouteNode::Goal => {}
+            }
+            out
+        };
+        let heuristic = |node: &RouteNode| match *node {
+            RouteNode::Start => octile_heuristic_scaled(start, end),
+            RouteNode::Portal(cell) => octile_heuristic_scaled(cell, end),
+            RouteNode::Goal => 0,
+        };
+
+        astar(&RouteNode::Start, successors, heuristic, |node| {
+            *node == RouteNode::Goal
+        })
+        .map(|(_, cost)| cost)
+    }
+}
+
+impl ClusterTable {
+    fn build(region: AllocationRegion, grid: &IntentGrid, swarm: SwarmId) -> Self {
+        let portals: Vec<IVec2> = cluster_cells(region, grid)
+            .filter(|cell| is_portal(*cell, region, grid))
+            .collect();
+        let mut costs = Vec::with_capacity(portals.len() * portals.len());
+        for from in &portals {
+            let reached = costs_from(*from, region, grid, swarm);
+            costs.extend(
+                portals
+                    .iter()
+                    .map(|to| reached.get(to).copied().unwrap_or(u32::MAX)),
+            );
+        }
+        Self { portals, costs }
+    }
+
+    /// In-cluster edges from portal `from` to every other portal.
+    fn edges_from(&self, from: IVec2) -> impl Iterator<Item = (RouteNode, u32)> + '_ {
+        let row = self
+            .portals
+            .iter()
+            .position(|portal| *portal == from)
+            .map(|index| &self.costs[index * self.portals.len()..(index + 1) * self.portals.len()])
+            .unwrap_or_default();
+        self.portals
+            .iter()
+            .zip(row)
+            .filter(move |(to, cost)| **to != from && **cost != u32::MAX)
+            .map(|(to, cost)| (RouteNode::Portal(*to), *cost))
+    }
+}
+
+/// In-bounds cells of `region`.
+fn cluster_cells(region: AllocationRegion, grid: &IntentGrid) -> impl Iterator<Item = IVec2> + '_ {
+    let min = region.min_cell();
+    (0..ALLOCATION_REGION_CELLS)
+        .flat_map(|dy| (0..ALLOCATION_REGION_CELLS).map(move |dx| IVec2::new(dx, dy)))
+        .map(move |offset| min + offset)
+        .filter(|cell| grid.in_bounds(*cell))
+}
+
+/// True when `cell` of `region` borders an in-bounds cell of another
+/// cluster.
+fn is_portal(cell: IVec2, region: AllocationRegion, grid: &IntentGrid) -> bool {
+    route_neighbours(cell, grid).any(|next| AllocationRegion::for_cell(next) != region)
+}
+
+/// Scaled costs from `start` to every cell of `region`, moving only
+/// inside the region.
+fn costs_from(
+    start: IVec2,
+    region: AllocationRegion,
+    grid: &IntentGrid,
+    swarm: SwarmId,
+) -> HashMap<IVec2, u32> {
+    let mut costs: HashMap<IVec2, u32> = dijkstra_all(&start, |cell: &IVec2| {
+        let cell = *cell;
+        route_neighbours(cell, grid)
+            .filter(|next| AllocationRegion::for_cell(*next) == region)
+            .map(move |next| (next, step_cost_scaled(cell, next, grid, swarm)))
+            .collect::<Vec<_>>()
+    })
+    .into_iter()
+    .map(|(cell, (_, cost))| (cell, cost))
+    .collect();
+    costs.insert(start, 0);
+    costs
+}
+
+/// Scaled costs from every cell of `region` to `end`, moving only
+/// inside the region.
+fn costs_to(
+    end: IVec2,
+    region: AllocationRegion,
+    grid: &IntentGrid,
+    swarm: SwarmId,
+) -> HashMap<IVec2, u32> {
+    let mut costs: HashMap<IVec2, u32> = dijkstra_all(&end, |cell: &IVec2| {
+        let cell = *cell;
+        route_neighbours(cell, grid)
+            .filter(|previous| AllocationRegion::for_cell(*previous) == region)
+            .map(move |previous| (previous, step_cost_scaled(previous, cell, grid, swarm)))
+            .collect::<Vec<_>>()
+    })
+    .into_iter()
+    .map(|(cell, (_, cost))| (cell, cost))
+    .collect();
+    costs.insert(end, 0);
+    costs
+}
+
+/// Drop cached route costs whose Corridor paint, road, or danger changed since
+/// the last run.
+pub fn route_cost_cache_invalidate_system(
+    mut grid: ResMut<IntentGrid>,
+    mut cache: ResMut<RouteCostCache>,
+) {
+    if grid.route_dirty_count() == 0 {
+        return;
+    }
+    let cells = grid.drain_route_dirty();
+    cache.invalidate(&cells);
+}
+
+/// Owns the shared [`RouteCostCache`] and drains Corridor, road, and danger changes
+/// before allocation scores any route.
+pub struct RouteCostCachePlugin;
+
+impl Plugin for RouteCostCachePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RouteCostCache>().add_systems(
+            FixedUpdate,
+            route_cost_cache_invalidate_system
+                .before(crate::nanobot::RegionalAllocationSet::Acquire),
+        );
+    }
+}
+
+/// Add [`RouteCostCachePlugin`] unless the app already has it.
+pub fn add_route_cost_cache(app: &mut App) {
+    if !app.is_plugin_added::<RouteCostCachePlugin>() {
+        app.add_plugins(RouteCostCachePlugin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Hierarchical costs against the flat planner. Cache upkeep in
+    //! the running simulation lives in
+    //! `tests/behavior/route_cost_cache.rs`.
+
+    use super::*;
+    use crate::ZONE_BLOCK_SIZE;
+    use crate::intent::IntentKind;
+
+    fn at(cell: IVec2) -> Vec2 {
+        (cell.as_vec2() + 0.5) * ZONE_BLOCK_SIZE
+    }
+
+    /// A grid with a few corridor strokes crossing cluster borders.
+    fn painted_grid() -> IntentGrid {
+        let mut grid = IntentGrid::new(48, 48);
+        for x in -20..12 {
+            grid.paint(IVec2::new(x, 3), IntentKind::Corridor);
+        }
+        for y in -15..20 {
+            grid.paint_owned(IVec2::new(7, y), IntentKind::Corridor, Some(SwarmId(4)));
+        }
+        for step in 0..14 {
+            grid.paint(IVec2::new(-10 + step, -10 + step), IntentKind::Corridor);
+        }
+        grid
+    }
+
+    #[test]
+    fn hierarchical_costs_match_the_flat_planner() {
+        let grid = painted_grid();
+        let cache = RouteCostCache::default();
+        let cells = [
+            IVec2::new(-22, -20),
+            IVec2::new(-9, 2),
+            IVec2::new(0, 0),
+            IVec2::new(1, 1),
+            IVec2::new(7, 12),
+            IVec2::new(15, -18),
+            IVec2::new(21, 20),
+        ];
+        for swarm in [SwarmId::PLAYER, SwarmId(4)] {
+            for from in cells {
+                for to in cells {
+                    let flat = hauler_route_cost(at(from), at(to), &grid, swarm);
+                    let cached = cache.cost(at(from), at(to), &grid, swarm);
+                    assert!(
+                        (flat - cached).abs() < 1e-3,
+                        "{from} -> {to} for {swarm:?}: flat={flat} cached={cached}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_lookups_hit_the_pair_cache() {
+        let grid = painted_grid();
+        let cache = RouteCostCache::default();
+        let from = at(IVec2::new(-20, -20));
+        let to = at(IVec2::new(20, 20));
+
+        let first = cache.cost(from, to, &grid, SwarmId::PLAYER);
+        let clusters = cache.cached_clusters();
+        let second = cache.cost(from, to, &grid, SwarmId::PLAYER);
+
+        assert_eq!(first, second);
+        assert_eq!(cache.cached_pairs(), 1);
+        assert!(clusters > 0);
+        assert_eq!(cache.cached_clusters(), clusters);
+    }
+
+    #[test]
+    fn corridor_change_drops_reachable_pairs_and_touched_clusters_only() {
+        let mut grid = painted_grid();
+        let mut cache = RouteCostCache::default();
+        grid.drain_route_dirty();
+        let from = at(IVec2::new(-20, 0));
+        let to = at(IVec2::new(20, 0));
+        let before = cache.cost(from, to, &grid, SwarmId::PLAYER);
+        let far = cache.cost(
+            at(IVec2::new(-22, -22)),
+            at(IVec2::new(-20, -22)),
+            &grid,
+            SwarmId::PLAYER,
+        );
+        let clusters = cache.cached_clusters();
+
+        for x in -19..20 {
+            grid.paint(IVec2::new(x, 0), IntentKind::Corridor);
+        }
+        cache.invalidate(&grid.drain_route_dirty());
+
+        assert_eq!(cache.cached_pairs(), 1, "the far pair is out of reach");
+        assert_eq!(
+            cache.cost(
+                at(IVec2::new(-22, -22)),
+                at(IVec2::new(-20, -22)),
+                &grid,
+                SwarmId::PLAYER
+            ),
+            far
+        );
+        assert!(cache.cached_clusters() < clusters);
+        let after = cache.cost(from, to, &grid, SwarmId::PLAYER);
+        assert!(after < before);
+        assert!((after - hauler_route_cost(from, to, &grid, SwarmId::PLAYER)).abs() < 1e-3);
+    }
+}
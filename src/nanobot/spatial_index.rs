@@ -0,0 +1,553 @@
+//! Shared, incrementally updated spatial index for nanobots and
+//! support structures.
+//!
+//! Combat, separation, threat pressure, charger lookup and stockpile
+//! selection all ask "what is near this point?". Instead of each
+//! system bucketing every entity from scratch per tick, one
+//! [`SpatialIndex`] resource is kept current from `Changed<Transform>`,
+//! newly added structure markers, swarm changes, and removals.
+//!
+//! ```text
+//!   tick start        -> update (spawns, despawns, moved structures)
+//!   Movement set      -> separation reads last tick's positions
+//!   after velocity    -> update (this tick's bot movement)
+//!   Threat, Combat, role chains -> radius / nearest queries
+//! ```
+//!
+//! Entries carry their layer and resolved swarm so per-swarm filters
+//! never touch the ECS. Consumers that need more than position and
+//! swarm check each candidate against their own query; the index only
+//! orders candidates by distance, ties broken by entity bits.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::nanobot::charge::Charger;
+use crate::nanobot::components::{Nanobot, Swarm, SwarmId, SwarmMember};
+use crate::nanobot::production::{OwnerSwarm, ProductionFacility};
+use crate::nanobot::research::ResearchLab;
+use crate::nanobot::structure::Structure;
+use crate::resources::Stockpile;
+
+/// Bucket edge of the shared index in world units. One bucket spans
+/// four bot diameters, so separation and combat queries touch a
+/// handful of buckets.
+pub const SPATIAL_INDEX_BUCKET_SIZE: f32 = 64.0;
+
+/// What kind of entity an index entry stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpatialLayer {
+    Nanobot,
+    /// Stockpiles, Production Facilities, Chargers, Research Labs and
+    /// anything else carrying [`Structure`] condition.
+    Structure,
+}
+
+/// Which swarms a query accepts. Entries without a resolvable swarm
+/// only pass [`SwarmFilter::Any`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwarmFilter {
+    Any,
+    Only(SwarmId),
+    Except(SwarmId),
+}
+
+impl SwarmFilter {
+    pub fn accepts(self, swarm: Option<SwarmId>) -> bool {
+        match self {
+            SwarmFilter::Any => true,
+            SwarmFilter::Only(wanted) => swarm == Some(wanted),
+            SwarmFilter::Except(excluded) => swarm.is_some_and(|swarm| swarm != excluded),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub layer: SpatialLayer,
+    pub swarm: Option<SwarmId>,
+}
+
+type BucketKey = (SpatialLayer, i32, i32);
+
+#[derive(Debug, Resource)]
+pub struct SpatialIndex {
+    bucket_size: f32,
+    entries: HashMap<Entity, SpatialEntry>,
+    /// Entities per bucket, sorted by entity bits.
+    buckets: BTreeMap<BucketKey, Vec<Entity>>,
+    /// Smallest and largest bucket ever occupied. Only grows, which
+    /// keeps it a valid bound for the nearest-neighbour ring search.
+    bounds: Option<(IVec2, IVec2)>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(SPATIAL_INDEX_BUCKET_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(bucket_size: f32) -> Self {
+        assert!(bucket_size.is_finite() && bucket_size > 0.0);
+        Self {
+            bucket_size,
+            entries: HashMap::new(),
+            buckets: BTreeMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&SpatialEntry> {
+        self.entries.get(&entity)
+    }
+
+    fn bucket(&self, position: Vec2) -> IVec2 {
+        IVec2::new(
+            (position.x / self.bucket_size).floor() as i32,
+            (position.y / self.bucket_size).floor() as i32,
+        )
+    }
+
+    /// Insert `entry`, or move and update it if its entity is
+    /// already indexed.
+    pub fn insert(&mut self, entry: SpatialEntry) {
+        let bucket = self.bucket(entry.position);
+        let key = (entry.layer, bucket.x, bucket.y);
+        if let Some(previous) = self.entries.insert(entry.entity, entry) {
+            let old = self.bucket(previous.position);
+            let old_key = (previous.layer, old.x, old.y);
+            if old_key == key {
+                return;
+            }
+            self.unlink(old_key, entry.entity);
+        }
+        let members = self.buckets.entry(key).or_default();
+        if let Err(at) = members.binary_search_by_key(&entry.entity.to_bits(), |e| e.to_bits()) {
+            members.insert(at, entry.entity);
+        }
+        self.bounds = Some(match self.bounds {
+            None => (bucket, bucket),
+            Some((min, max)) => (min.min(bucket), max.max(bucket)),
+        });
+    }
+
+    /// Keep only entries whose entity passes `keep`.
+    pub fn retain(&mut self, mut keep: impl FnMut(Entity) -> bool) {
+        let dropped = self
+            .entries
+            .keys()
+            .copied()
+            .filter(|entity| !keep(*entity))
+            .collect::<Vec<_>>();
+        for entity in dropped {
+            self.remove(entity);
+        }
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<SpatialEntry> {
+        let entry = self.entries.remove(&entity)?;
+        let bucket = self.bucket(entry.position);
+        self.unlink((entry.layer, bucket.x, bucket.y), entity);
+        Some(entry)
+    }
+
+    fn unlink(&mut self, key: BucketKey, entity: Entity) {
+        if let Some(members) = self.buckets.get_mut(&key) {
+            members.retain(|member| *member != entity);
+            if members.is_empty() {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+
+    /// Every entry on `layer`, in bucket then entity order.
+    pub fn entries(&self, layer: SpatialLayer) -> impl Iterator<Item = &SpatialEntry> {
+        self.buckets
+            .range((layer, i32::MIN, i32::MIN)..=(layer, i32::MAX, i32::MAX))
+            .flat_map(|(_, members)| members.iter().map(|entity| &self.entries[entity]))
+    }
+
+    /// Entries of `layer` in the square ring of buckets exactly `ring`
+    /// buckets (Chebyshev) away from `center`.
+    fn ring(
+        &self,
+        layer: SpatialLayer,
+        center: IVec2,
+        ring: i32,
+    ) -> impl Iterator<Item = &SpatialEntry> {
+        (-ring..=ring)
+            .flat_map(move |dy| (-ring..=ring).map(move |dx| IVec2::new(dx, dy)))
+            .filter(move |offset| offset.x.abs() == ring || offset.y.abs() == ring)
+            .filter_map(move |offset| {
+                let coord = center + offset;
+                self.buckets.get(&(layer, coord.x, coord.y))
+            })
+            .flat_map(|members| members.iter().map(|entity| &self.entries[entity]))
+    }
+
+    /// Entries of `layer` accepted by `swarm` within `radius` of
+    /// `center`, nearest first.
+    pub fn within_radius(
+        &self,
+        center: Vec2,
+        radius: f32,
+        layer: SpatialLayer,
+        swarm: SwarmFilter,
+    ) -> Vec<SpatialEntry> {
+        let rings = (radius / self.bucket_size).ceil() as i32;
+        let center_bucket = self.bucket(center);
+        let mut found = (0..=rings.max(0))
+            .flat_map(|ring| self.ring(layer, center_bucket, ring))
+            .filter(|entry| swarm.accepts(entry.swarm))
+            .filter(|entry| entry.position.distance(center) <= radius)
+            .copied()
+            .collect::<Vec<_>>();
+        sort_by_distance(&mut found, center);
+        found
+    }
+
+    /// Up to `k` entries of `layer` accepted by `swarm` and
+    /// `accept`, nearest first. Rings of buckets are searched outward
+    /// until no unvisited bucket can hold anything closer.
+    pub fn nearest_k(
+        &self,
+        center: Vec2,
+        k: usize,
+        layer: SpatialLayer,
+        swarm: SwarmFilter,
+        mut accept: impl FnMut(&SpatialEntry) -> bool,
+    ) -> Vec<SpatialEntry> {
+        let Some((min, max)) = self.bounds else {
+            return Vec::new();
+        };
+        if k == 0 {
+            return Vec::new();
+        }
+        let center_bucket = self.bucket(center);
+        let reach = (center_bucket - min)
+            .abs()
+            .max((max - center_bucket).abs())
+            .max_element();
+        let mut found = Vec::new();
+        for ring in 0..=reach {
+            // Once the rings cover more bucket slots than are occupied,
+            // a plain scan of the layer is cheaper than more rings.
+            let slots = (2 * ring as usize + 1).pow(2);
+            if slots > self.buckets.len() {
+                found = self
+                    .entries(layer)
+                    .filter(|entry| swarm.accepts(entry.swarm))
+                    .filter(|entry| accept(entry))
+                    .copied()
+                    .collect();
+                break;
+            }
+            found.extend(
+                self.ring(layer, center_bucket, ring)
+                    .filter(|entry| swarm.accepts(entry.swarm))
+                    .filter(|entry| accept(entry))
+                    .copied(),
+            );
+            if found.len() < k {
+                continue;
+            }
+            sort_by_distance(&mut found, center);
+            // Anything past this ring is at least `ring` whole
+            // buckets away.
+            if found[k - 1].position.distance(center) <= ring as f32 * self.bucket_size {
+                break;
+            }
+        }
+        sort_by_distance(&mut found, center);
+        found.truncate(k);
+        found
+    }
+
+    /// The nearest entry of `layer` accepted by `swarm` and `accept`.
+    pub fn nearest(
+        &self,
+        center: Vec2,
+        layer: SpatialLayer,
+        swarm: SwarmFilter,
+        accept: impl FnMut(&SpatialEntry) -> bool,
+    ) -> Option<SpatialEntry> {
+        self.nearest_k(center, 1, layer, swarm, accept)
+            .into_iter()
+            .next()
+    }
+}
+
+fn sort_by_distance(entries: &mut [SpatialEntry], center: Vec2) {
+    entries.sort_by(|left, right| {
+        left.position
+            .distance(center)
+            .total_cmp(&right.position.distance(center))
+            .then_with(|| left.entity.to_bits().cmp(&right.entity.to_bits()))
+    });
+}
+
+pub(crate) type StructureMarkers = Or<(
+    With<Structure>,
+    With<Stockpile>,
+    With<Charger>,
+    With<ProductionFacility>,
+    With<ResearchLab>,
+)>;
+
+/// Removal messages for every component that puts an entity in the
+/// index.
+#[derive(SystemParam)]
+pub struct SpatialRemovals<'w, 's> {
+    nanobots: RemovedComponents<'w, 's, Nanobot>,
+    structures: RemovedComponents<'w, 's, Structure>,
+    stockpiles: RemovedComponents<'w, 's, Stockpile>,
+    chargers: RemovedComponents<'w, 's, Charger>,
+    facilities: RemovedComponents<'w, 's, ProductionFacility>,
+    labs: RemovedComponents<'w, 's, ResearchLab>,
+    owners: RemovedComponents<'w, 's, OwnerSwarm>,
+}
+
+/// Bring the index up to date with moved, spawned, re-owned and
+/// despawned nanobots and structures.
+#[allow(clippy::type_complexity)]
+pub fn spatial_index_update_system(
+    mut index: ResMut<SpatialIndex>,
+    nanobots: Query<
+        (Entity, &Transform, &SwarmMember),
+        (
+            With<Nanobot>,
+            Or<(Changed<Transform>, Changed<SwarmMember>)>,
+        ),
+    >,
+    structures: Query<
+        (Entity, &Transform, Option<&OwnerSwarm>),
+        (
+            StructureMarkers,
+            Without<Nanobot>,
+            Or<(
+                Changed<Transform>,
+                Changed<OwnerSwarm>,
+                Added<Structure>,
+                Added<Stockpile>,
+                Added<Charger>,
+                Added<ProductionFacility>,
+                Added<ResearchLab>,
+            )>,
+        ),
+    >,
+    indexed: Query<(), (With<Transform>, Or<(With<Nanobot>, StructureMarkers)>)>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut removed: SpatialRemovals,
+) {
+    let gone = removed
+        .nanobots
+        .read()
+        .chain(removed.structures.read())
+        .chain(removed.stockpiles.read())
+        .chain(removed.chargers.read())
+        .chain(removed.facilities.read())
+        .chain(removed.labs.read())
+        .collect::<Vec<_>>();
+    for entity in gone {
+        if indexed.get(entity).is_err() {
+            index.remove(entity);
+        }
+    }
+    // Removal messages only live for two frames; a frame run without
+    // a fixed tick can drop some. Compare against the entities indexed
+    // before this frame's spawns, so a missed despawn still shows up
+    // when something new took its place, and sweep on a surplus.
+    let spawned = nanobots
+        .iter()
+        .map(|(entity, ..)| entity)
+        .chain(structures.iter().map(|(entity, ..)| entity))
+        .filter(|entity| index.get(*entity).is_none())
+        .count();
+    if index.len() + spawned > indexed.iter().len() {
+        index.retain(|entity| indexed.get(entity).is_ok());
+    }
+
+    for (entity, transform, member) in &nanobots {
+        index.insert(SpatialEntry {
+            entity,
+            position: transform.translation.truncate(),
+            layer: SpatialLayer::Nanobot,
+            swarm: Some(member.0),
+        });
+    }
+    for (entity, transform, owner) in &structures {
+        index.insert(SpatialEntry {
+            entity,
+            position: transform.translation.truncate(),
+            layer: SpatialLayer::Structure,
+            swarm: owner.and_then(|owner| swarms.get(owner.0).ok().copied()),
+        });
+    }
+    let disowned = removed.owners.read().collect::<Vec<_>>();
+    for entity in disowned {
+        if structures.get(entity).is_ok() {
+            continue;
+        }
+        if let Some(entry) = index.get(entity).copied()
+            && entry.layer == SpatialLayer::Structure
+        {
+            index.insert(SpatialEntry {
+                swarm: None,
+                ..entry
+            });
+        }
+    }
+}
+
+/// Owns the [`SpatialIndex`] and its two per-tick updates. Plugins
+/// whose systems query the index add it through
+/// [`add_spatial_index`], so it is registered once however many of
+/// them an app uses.
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>().add_systems(
+            FixedUpdate,
+            (
+                spatial_index_update_system.before(crate::nanobot::NanobotSimulationSet::Movement),
+                spatial_index_update_system
+                    .in_set(crate::nanobot::NanobotSimulationSet::Movement)
+                    .after(crate::nanobot::velocity_system),
+            ),
+        );
+    }
+}
+
+/// Add [`SpatialIndexPlugin`] unless the app already has it.
+pub fn add_spatial_index(app: &mut App) {
+    if !app.is_plugin_added::<SpatialIndexPlugin>() {
+        app.add_plugins(SpatialIndexPlugin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Index bookkeeping and query ordering. The ECS updates and the
+    //! consumers live in `tests/behavior/spatial_index.rs`.
+
+    use super::*;
+
+    fn bot(bits: u32, position: Vec2, swarm: u32) -> SpatialEntry {
+        SpatialEntry {
+            entity: Entity::from_bits(u64::from(bits)),
+            position,
+            layer: SpatialLayer::Nanobot,
+            swarm: Some(SwarmId(swarm)),
+        }
+    }
+
+    #[test]
+    fn moving_an_entry_leaves_no_stale_bucket() {
+        let mut index = SpatialIndex::default();
+        let entry = bot(1, Vec2::ZERO, 0);
+        index.insert(entry);
+        index.insert(SpatialEntry {
+            position: Vec2::splat(500.0),
+            ..entry
+        });
+
+        assert_eq!(index.len(), 1);
+        assert!(
+            index
+                .within_radius(Vec2::ZERO, 10.0, SpatialLayer::Nanobot, SwarmFilter::Any)
+                .is_empty()
+        );
+        assert_eq!(index.entries(SpatialLayer::Nanobot).count(), 1);
+        index.remove(entry.entity);
+        assert!(index.is_empty());
+        assert_eq!(index.entries(SpatialLayer::Nanobot).count(), 0);
+    }
+
+    #[test]
+    fn radius_queries_are_sorted_and_swarm_filtered() {
+        let mut index = SpatialIndex::default();
+        index.insert(bot(1, Vec2::new(50.0, 0.0), 0));
+        index.insert(bot(2, Vec2::new(10.0, 0.0), 1));
+        index.insert(bot(3, Vec2::new(30.0, 0.0), 1));
+        index.insert(bot(4, Vec2::new(300.0, 0.0), 1));
+
+        let hostile = index.within_radius(
+            Vec2::ZERO,
+            100.0,
+            SpatialLayer::Nanobot,
+            SwarmFilter::Except(SwarmId(0)),
+        );
+        let bits = hostile
+            .iter()
+            .map(|entry| entry.entity.to_bits())
+            .collect::<Vec<_>>();
+        assert_eq!(bits, vec![2, 3]);
+        assert!(
+            index
+                .within_radius(Vec2::ZERO, 100.0, SpatialLayer::Structure, SwarmFilter::Any)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn nearest_k_matches_a_brute_force_scan() {
+        let mut index = SpatialIndex::default();
+        let points = (0..40)
+            .map(|i| {
+                let angle = i as f32 * 0.7;
+                Vec2::new(angle.cos(), angle.sin()) * (i as f32 * 37.0)
+            })
+            .collect::<Vec<_>>();
+        for (i, point) in points.iter().enumerate() {
+            index.insert(bot(i as u32 + 1, *point, i as u32 % 2));
+        }
+        let center = Vec2::new(120.0, -80.0);
+
+        let found = index.nearest_k(center, 5, SpatialLayer::Nanobot, SwarmFilter::Any, |_| true);
+
+        let mut brute = points.clone();
+        brute.sort_by(|a, b| a.distance(center).total_cmp(&b.distance(center)));
+        let found = found.iter().map(|entry| entry.position).collect::<Vec<_>>();
+        assert_eq!(found, brute[..5].to_vec());
+    }
+
+    #[test]
+    fn nearest_skips_rejected_candidates_and_reaches_far_buckets() {
+        let mut index = SpatialIndex::default();
+        index.insert(bot(1, Vec2::new(5.0, 0.0), 0));
+        index.insert(bot(2, Vec2::new(2000.0, 0.0), 0));
+
+        let far = index.nearest(
+            Vec2::ZERO,
+            SpatialLayer::Nanobot,
+            SwarmFilter::Only(SwarmId(0)),
+            |entry| entry.entity.to_bits() != 1,
+        );
+
+        assert_eq!(far.map(|entry| entry.entity.to_bits()), Some(2));
+        assert!(
+            index
+                .nearest(
+                    Vec2::ZERO,
+                    SpatialLayer::Nanobot,
+                    SwarmFilter::Only(SwarmId(7)),
+                    |_| true
+                )
+                .is_none()
+        );
+    }
+}
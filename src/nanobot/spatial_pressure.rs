@@ -30,9 +30,9 @@ pub fn crowding_factor(occupancy: u32, capacity: u32) -> f32 {
 ///
 /// The resource is a plain `HashMap` cloneable snapshot so the
 /// assignment system can read a consistent per-defender view
-/// without recomputing positions mid-loop. The future idle
-/// cosmetic spread issue can read the same density to de-clump
-/// idle nanobots without a second pass.
+/// without recomputing positions mid-loop. Idle spread keeps its own
+/// instance across ticks (it runs inside the movement chain, before
+/// this pass) and refills it with [`CellDensity::recount`].
 #[derive(Debug, Default, Clone, Resource)]
 pub struct CellDensity {
     counts: HashMap<IVec2, u32>,
@@ -55,6 +55,21 @@ impl CellDensity {
     pub fn is_empty(&self) -> bool {
         self.counts.is_empty()
     }
+
+    /// Every occupied cell with its count, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, u32)> + '_ {
+        self.counts.iter().map(|(cell, count)| (*cell, *count))
+    }
+
+    /// Replace the counts with one entry per position. The map's
+    /// allocation is kept, so a long-lived instance stops allocating
+    /// once the occupied-cell count settles.
+    pub fn recount(&mut self, positions: impl IntoIterator<Item = Vec2>) {
+        self.counts.clear();
+        for position in positions {
+            *self.counts.entry(world_to_cell(position)).or_insert(0) += 1;
+        }
+    }
 }
 
 /// Recompute [`CellDensity`] from every nanobot's world position.
@@ -68,11 +83,10 @@ pub fn cell_density_system(
     mut density: ResMut<CellDensity>,
     bots: Query<&Transform, With<Nanobot>>,
 ) {
-    density.counts.clear();
-    for transform in &bots {
-        let cell = world_to_cell(transform.translation.truncate());
-        *density.counts.entry(cell).or_insert(0) += 1;
-    }
+    density.recount(
+        bots.iter()
+            .map(|transform| transform.translation.truncate()),
+    );
 }
 
 /// World-space min (inclusive) and max (exclusive) corners of the
@@ -41,22 +41,30 @@
 //!    frame.
 //!
 //! The decision helpers are pure over plain Rust data so the contract
-//! can be unit-tested without Bevy. The system wires them up and
-//! rebuilds the per-type fit-cell list + density map each tick (per
-//! the issue's "rebuild both maps every tick" scope; cross-tick
-//! caching is explicitly out of scope).
+//! can be unit-tested without Bevy. The system wires them up over an
+//! [`IdleSpreadCache`] that survives across ticks:
+//!
+//! - the per-type fit-cell index is updated only for the cells the
+//!   [`IntentGrid`] reports through its spread-dirty set, and rebuilt
+//!   from the active cells only when the grid resource is replaced;
+//! - stranded lookups read a per-type nearest-fit transform, rebuilt
+//!   lazily on the first stranded query after that type's fit cells
+//!   change, so a stranded bot costs one table read instead of a scan
+//!   over every fit cell;
+//! - the density map reuses one [`CellDensity`] allocation.
 
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 use bevy::prelude::*;
 use rand::{RngExt, SeedableRng, rngs::StdRng};
 
-use crate::ai::get_world_from_zone;
 use crate::intent::{IntentCell, IntentGrid, IntentKind};
 use crate::nanobot::autonomy::{Commitment, NanobotType};
 use crate::nanobot::components::{DirectMovementComponent, Nanobot, VelocityComponent};
+use crate::nanobot::gather::get_world_from_zone;
 use crate::nanobot::gather::world_to_cell;
+use crate::nanobot::spatial_pressure::CellDensity;
 
 /// Per-tick velocity nudge magnitude applied to idle bots spreading
 /// across their type-fit region. Sized near [`BOT_SEPARATION_FORCE`]
@@ -202,12 +210,39 @@ impl FitCellIndex {
         )
     }
 
-    fn insert(&mut self, cell: IVec2) {
+    fn bucket_for_cell(cell: IVec2) -> (i32, i32) {
         let bucket = Self::bucket_for_position(get_world_from_zone(cell));
-        self.buckets
-            .entry((bucket.x, bucket.y))
-            .or_default()
-            .push(cell);
+        (bucket.x, bucket.y)
+    }
+
+    /// Add or remove `cell` so membership matches `fit`. Returns `true`
+    /// when membership changed. Emptied buckets are dropped so
+    /// [`Self::nearest`] only ever seeds from an occupied bucket.
+    fn set(&mut self, cell: IVec2, fit: bool) -> bool {
+        let bucket = Self::bucket_for_cell(cell);
+        if fit {
+            let cells = self.buckets.entry(bucket).or_default();
+            if cells.contains(&cell) {
+                return false;
+            }
+            cells.push(cell);
+            return true;
+        }
+        let Some(cells) = self.buckets.get_mut(&bucket) else {
+            return false;
+        };
+        let Some(position) = cells.iter().position(|candidate| *candidate == cell) else {
+            return false;
+        };
+        cells.swap_remove(position);
+        if cells.is_empty() {
+            self.buckets.remove(&bucket);
+        }
+        true
+    }
+
+    fn iter(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.buckets.values().flatten().copied()
     }
 
     fn bucket_distance_squared(from: Vec2, bucket: (i32, i32)) -> f32 {
@@ -265,8 +300,216 @@ impl FitCellIndex {
     }
 }
 
+/// Cells added around a type's fit-cell bounding box when building its
+/// nearest-fit transform. Stranded bots further out fall back to the
+/// bucketed [`FitCellIndex`] search.
+const NEAREST_FIT_MARGIN_CELLS: i32 = 32;
+
+/// Largest nearest-fit transform, in cells, built for one type. Sparse
+/// paint at opposite map corners would otherwise allocate a map-sized
+/// table; past this size the bucketed index answers alone.
+const NEAREST_FIT_MAX_CELLS: usize = 1 << 18;
+
+/// Exact Euclidean nearest-fit-cell table over a rectangle of cells,
+/// measured between cell centers. Built with the separable two-phase
+/// distance transform (a per-column scan, then a per-row lower envelope
+/// of parabolas), so construction is linear in the rectangle's area.
+struct NearestFitTransform {
+    min: IVec2,
+    width: i32,
+    height: i32,
+    nearest: Vec<IVec2>,
+}
+
+impl NearestFitTransform {
+    /// Build over the bounding box of `fit_cells` grown by `margin`.
+    /// `None` when there are no fit cells or the box exceeds
+    /// `max_cells`.
+    fn build(
+        fit_cells: impl Iterator<Item = IVec2> + Clone,
+        margin: i32,
+        max_cells: usize,
+    ) -> Option<Self> {
+        let (lo, hi) = fit_cells
+            .clone()
+            .fold(None, |bounds: Option<(IVec2, IVec2)>, cell| {
+                Some(bounds.map_or((cell, cell), |(lo, hi)| (lo.min(cell), hi.max(cell))))
+            })?;
+        let min = lo - IVec2::splat(margin);
+        let size = hi - lo + IVec2::splat(2 * margin + 1);
+        if size.x as usize * size.y as usize > max_cells {
+            return None;
+        }
+        let (width, height) = (size.x, size.y);
+        let index = |x: i32, y: i32| (y * width + x) as usize;
+
+        let mut fit = vec![false; (width * height) as usize];
+        for cell in fit_cells {
+            let local = cell - min;
+            fit[index(local.x, local.y)] = true;
+        }
+
+        // Phase 1: nearest fit row within each column.
+        let mut column_nearest: Vec<Option<i32>> = vec![None; fit.len()];
+        for x in 0..width {
+            let mut below = None;
+            for y in 0..height {
+                if fit[index(x, y)] {
+                    below = Some(y);
+                }
+                column_nearest[index(x, y)] = below;
+            }
+            let mut above = None;
+            for y in (0..height).rev() {
+                if fit[index(x, y)] {
+                    above = Some(y);
+                }
+                let slot = &mut column_nearest[index(x, y)];
+                if let Some(above) = above
+                    && slot.is_none_or(|below| above - y < y - below)
+                {
+                    *slot = Some(above);
+                }
+            }
+        }
+
+        // Phase 2: nearest column per row over the column results.
+        let mut nearest = Vec::with_capacity(fit.len());
+        for y in 0..height {
+            let heights: Vec<Option<i64>> = (0..width)
+                .map(|x| column_nearest[index(x, y)].map(|row| i64::from(y - row).pow(2)))
+                .collect();
+            for column in lower_envelope(&heights) {
+                let row = column_nearest[index(column as i32, y)]
+                    .expect("the lower envelope only picks columns with a fit cell");
+                nearest.push(min + IVec2::new(column as i32, row));
+            }
+        }
+        Some(Self {
+            min,
+            width,
+            height,
+            nearest,
+        })
+    }
+
+    /// Nearest fit cell to the center of `cell`, or `None` when `cell`
+    /// lies outside the table.
+    fn nearest(&self, cell: IVec2) -> Option<IVec2> {
+        let local = cell - self.min;
+        ((0..self.width).contains(&local.x) && (0..self.height).contains(&local.y))
+            .then(|| self.nearest[(local.y * self.width + local.x) as usize])
+    }
+}
+
+/// For every position `x`, the index `q` minimising
+/// `(x - q)^2 + heights[q]` over the present heights. `heights` must
+/// hold at least one value.
+fn lower_envelope(heights: &[Option<i64>]) -> Vec<usize> {
+    // (parabola, first x where it is lowest)
+    let mut hull: Vec<(usize, f64)> = Vec::new();
+    for (q, height) in heights.iter().enumerate() {
+        let Some(height) = *height else {
+            continue;
+        };
+        let offset = |i: usize, h: i64| h + (i as i64).pow(2);
+        let mut start = f64::NEG_INFINITY;
+        while let Some(&(p, p_start)) = hull.last() {
+            let p_height = heights[p].expect("the hull holds only present heights");
+            let crossing = (offset(q, height) - offset(p, p_height)) as f64
+                / (2 * (q as i64 - p as i64)) as f64;
+            if crossing <= p_start {
+                hull.pop();
+            } else {
+                start = crossing;
+                break;
+            }
+        }
+        hull.push((q, start));
+    }
+    let mut k = 0;
+    (0..heights.len())
+        .map(|x| {
+            while k + 1 < hull.len() && hull[k + 1].1 < x as f64 {
+                k += 1;
+            }
+            hull[k].0
+        })
+        .collect()
+}
+
+/// One type's fit cells plus its lazily rebuilt nearest-fit transform.
+#[derive(Default)]
+struct TypeFitCells {
+    index: FitCellIndex,
+    transform: Option<NearestFitTransform>,
+    transform_stale: bool,
+}
+
+impl TypeFitCells {
+    fn set(&mut self, cell: IVec2, fit: bool) {
+        if self.index.set(cell, fit) {
+            self.transform_stale = true;
+        }
+    }
+
+    /// Nearest fit cell for a stranded bot at `from` in `cell`. Inside
+    /// the transform the answer is exact for the cell's center; outside
+    /// it the bucketed index measures from the bot's own position.
+    fn nearest(&mut self, cell: IVec2, from: Vec2) -> Option<IVec2> {
+        if self.transform_stale {
+            self.transform = NearestFitTransform::build(
+                self.index.iter(),
+                NEAREST_FIT_MARGIN_CELLS,
+                NEAREST_FIT_MAX_CELLS,
+            );
+            self.transform_stale = false;
+        }
+        self.transform
+            .as_ref()
+            .and_then(|transform| transform.nearest(cell))
+            .or_else(|| self.index.nearest(from))
+    }
+}
+
+/// Idle-spread state kept across ticks by [`idle_spread_system`].
+#[derive(Default)]
+pub struct IdleSpreadCache {
+    fit: [TypeFitCells; NanobotType::COUNT],
+    density: CellDensity,
+    synced: bool,
+}
+
+impl IdleSpreadCache {
+    /// Bring the per-type fit cells in line with `grid`. Only the
+    /// spread-dirty cells are revisited, unless the grid resource was
+    /// replaced, in which case every active cell is re-indexed.
+    fn sync(&mut self, grid: &mut IntentGrid, replaced: bool, kind_sets: &[Vec<IntentKind>]) {
+        let dirty = grid.drain_spread_dirty();
+        if replaced || !self.synced {
+            self.fit = Default::default();
+            for (cell, _) in grid.iter_active_cells() {
+                self.refresh_cell(grid, cell, kind_sets);
+            }
+            self.synced = true;
+            return;
+        }
+        for cell in dirty {
+            self.refresh_cell(grid, cell, kind_sets);
+        }
+    }
+
+    fn refresh_cell(&mut self, grid: &IntentGrid, cell: IVec2, kind_sets: &[Vec<IntentKind>]) {
+        let intent = grid.cell(cell);
+        for (type_fit, kinds) in self.fit.iter_mut().zip(kind_sets) {
+            let fit = intent.is_some_and(|intent| kinds.iter().any(|k| intent.has(*k)));
+            type_fit.set(cell, fit);
+        }
+    }
+}
+
 /// Stable index of `ntype` inside [`NanobotType::ALL`]. Used to
-/// address the per-type fit-cell list built each tick.
+/// address the per-type fit cells in [`IdleSpreadCache`].
 fn type_index(ntype: NanobotType) -> usize {
     NanobotType::ALL
         .into_iter()
@@ -278,8 +521,9 @@ fn type_index(ntype: NanobotType) -> usize {
 /// nudge composes with `separation_system` and is consumed the same
 /// frame.
 ///
-/// `all_bots` reads every nanobot's transform once to build the
-/// density map (every bot counts, regardless of type, commitment, or
+/// The fit cells and density map live in an [`IdleSpreadCache`] local
+/// (see the module docs). `all_bots` reads every nanobot's transform
+/// once to recount the density map (every bot counts, regardless of type, commitment, or
 /// kind -- "occupied is occupied", matching the existing
 /// [`crate::nanobot::SoftWorkSlots`] model). `idle_bots` then nudges
 /// only `Commitment::Idle` bots that have no
@@ -289,7 +533,7 @@ fn type_index(ntype: NanobotType) -> usize {
 /// so they do not conflict.
 #[allow(clippy::type_complexity)]
 pub fn idle_spread_system(
-    grid: Res<IntentGrid>,
+    mut grid: ResMut<IntentGrid>,
     all_bots: Query<&Transform, With<Nanobot>>,
     mut idle_bots: Query<
         (
@@ -301,35 +545,23 @@ pub fn idle_spread_system(
         ),
         (With<Nanobot>, Without<DirectMovementComponent>),
     >,
+    mut cache: Local<IdleSpreadCache>,
     mut spread_tick: Local<u64>,
 ) {
-    // Per-type fit-cell list, rebuilt every tick (cross-tick caching
-    // is out of scope). Built in a single pass over painted cells so
-    // the per-bot loop never re-scans the grid.
     let kind_sets: [Vec<IntentKind>; NanobotType::COUNT] =
         std::array::from_fn(|i| fit_kinds(NanobotType::ALL[i]));
-    let mut fit_cells: [FitCellIndex; NanobotType::COUNT] =
-        std::array::from_fn(|_| FitCellIndex::default());
-    for (cell, intent_cell) in grid.iter_active_cells() {
-        if intent_cell.is_empty() {
-            continue;
-        }
-        for (type_idx, kinds) in kind_sets.iter().enumerate() {
-            if kinds.iter().any(|k| intent_cell.has(*k)) {
-                fit_cells[type_idx].insert(cell);
-            }
-        }
-    }
-
-    // Density map: cell -> count of every bot physically standing
-    // there this tick. Local to this system per the issue brief so
-    // spread stays self-contained (no cross-plugin ordering
-    // dependency on the defender density pass).
-    let mut density: HashMap<IVec2, u32> = HashMap::new();
-    for transform in &all_bots {
-        let cell = world_to_cell(transform.translation.truncate());
-        *density.entry(cell).or_insert(0) += 1;
-    }
+    let replaced = grid.is_added();
+    cache.sync(&mut grid, replaced, &kind_sets);
+    let IdleSpreadCache { fit, density, .. } = &mut *cache;
+
+    // Density: every bot physically standing in each cell this tick.
+    // Recounted here rather than read from the defend pass, which runs
+    // after movement.
+    density.recount(
+        all_bots
+            .iter()
+            .map(|transform| transform.translation.truncate()),
+    );
 
     let tick = *spread_tick;
     *spread_tick = spread_tick.wrapping_add(1);
@@ -348,17 +580,13 @@ pub fn idle_spread_system(
             // In-region gradient step. Exclude the bot's own body
             // from its own cell's count so a lone bot reads density 0
             // and falls through to random exploration.
-            let own_excl = density
-                .get(&own_cell)
-                .copied()
-                .unwrap_or(0)
-                .saturating_sub(1);
+            let own_excl = density.density(own_cell).saturating_sub(1);
             let neighbours: Vec<(IVec2, u32)> = king_neighbours(own_cell)
                 .into_iter()
                 .filter_map(|n| {
                     let neighbour = grid.cell(n)?;
                     let fit = kind_sets[type_idx].iter().any(|k| neighbour.has(*k));
-                    fit.then(|| (n, density.get(&n).copied().unwrap_or(0)))
+                    fit.then(|| (n, density.density(n)))
                 })
                 .collect();
             let seed = entity.to_bits().wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ tick.rotate_left(32);
@@ -366,7 +594,7 @@ pub fn idle_spread_system(
             gradient_step_target(own_excl, &neighbours, &mut rng)
         } else {
             // Stranded: drift toward the nearest type-fit cell.
-            fit_cells[type_idx].nearest(pos)
+            fit[type_idx].nearest(own_cell, pos)
         };
 
         if let Some(target_cell) = target {
@@ -389,7 +617,7 @@ mod tests {
     //! `tests/behavior/idle_spread.rs`.
 
     use super::*;
-    use crate::ai::get_world_from_zone;
+    use crate::nanobot::gather::get_world_from_zone;
     use rand::SeedableRng;
     use rand::rngs::StdRng;
 
@@ -593,7 +821,7 @@ mod tests {
         ];
         let mut index = FitCellIndex::default();
         for cell in candidates.iter().copied() {
-            index.insert(cell);
+            assert!(index.set(cell, true));
         }
         let probes = [
             Vec2::ZERO,
@@ -606,6 +834,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn nearest_fit_transform_matches_brute_force_distance() {
+        let fit_cells = [
+            IVec2::new(-6, 2),
+            IVec2::new(-5, 2),
+            IVec2::new(0, -4),
+            IVec2::new(3, 3),
+            IVec2::new(7, -1),
+        ];
+        let transform = NearestFitTransform::build(fit_cells.iter().copied(), 4, 1 << 12)
+            .expect("the box fits the cap");
+        let brute = |cell: IVec2| {
+            fit_cells
+                .iter()
+                .map(|fit| (*fit - cell).length_squared())
+                .min()
+                .unwrap()
+        };
+        for y in -8..=7 {
+            for x in -10..=11 {
+                let cell = IVec2::new(x, y);
+                let nearest = transform.nearest(cell).expect("inside the margin");
+                assert!(fit_cells.contains(&nearest));
+                assert_eq!((nearest - cell).length_squared(), brute(cell), "{cell}");
+            }
+        }
+        assert_eq!(transform.nearest(IVec2::new(12, 0)), None);
+        assert!(
+            NearestFitTransform::build(fit_cells.iter().copied(), 4, 16).is_none(),
+            "an oversized box falls back to the bucketed index"
+        );
+    }
+
+    #[test]
+    fn type_fit_cells_follow_incremental_paint_changes() {
+        let mut fit = TypeFitCells::default();
+        let from = get_world_from_zone(IVec2::ZERO);
+        fit.set(IVec2::new(2, 0), true);
+        fit.set(IVec2::new(-5, 0), true);
+        assert_eq!(fit.nearest(IVec2::ZERO, from), Some(IVec2::new(2, 0)));
+
+        fit.set(IVec2::new(2, 0), false);
+        assert_eq!(
+            fit.nearest(IVec2::ZERO, from),
+            Some(IVec2::new(-5, 0)),
+            "an erased cell invalidates the transform"
+        );
+        assert!(!fit.index.set(IVec2::new(2, 0), false));
+
+        fit.set(IVec2::new(-5, 0), false);
+        assert_eq!(fit.nearest(IVec2::ZERO, from), None);
+        assert!(fit.index.buckets.is_empty(), "emptied buckets are dropped");
+    }
+
     #[test]
     fn nearest_fit_cell_tie_keeps_first_in_iteration_order() {
         // Two equidistant candidates: min_by is stable and keeps the
@@ -0,0 +1,217 @@
+//! Optional per-type steering for nanobot movement.
+//!
+//! Without a [`NanobotSteering`] resource every nanobot moves at
+//! [`crate::game_settings::GameSettings::bot_speed`] straight at its
+//! destination, reaching full speed on the first tick. With it, each
+//! [`NanobotType`] has its own [`MovementProfile`]: a top speed, an
+//! acceleration, a turn rate, and an arrival radius inside which it
+//! slows down. A hauler also loses speed in proportion to how full its
+//! hold is, so long logistics routes cost more than their distance.
+//!
+//! ```text
+//!   DirectMovementComponent
+//!     -> desired speed: top speed, minus cargo weight,
+//!        ramped down inside the arrival radius
+//!     -> heading turns toward the destination by at most turn_rate
+//!     -> speed moves toward desired by at most acceleration
+//!     -> Steering keeps the result for the next tick
+//! ```
+//!
+//! [`Steering`] is the only movement state carried between ticks;
+//! [`crate::nanobot::VelocityComponent`] is still rebuilt every tick
+//! from it plus the separation nudges.
+
+use bevy::prelude::*;
+
+use crate::nanobot::autonomy::NanobotType;
+
+/// Movement limits for one nanobot type. Speeds are world units per
+/// fixed tick, turn rate is radians per fixed tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementProfile {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub turn_rate: f32,
+    pub arrival_radius: f32,
+}
+
+/// Per-type movement profiles. While present, bots accelerate, turn,
+/// and brake by [`MovementProfile`] and haulers slow with their load;
+/// without it every bot moves at `GameSettings::bot_speed`.
+#[derive(Debug, Resource, Clone, Copy, PartialEq)]
+pub struct NanobotSteering {
+    pub worker: MovementProfile,
+    pub hauler: MovementProfile,
+    pub defender: MovementProfile,
+    /// Share of a hauler's top speed lost with a full hold aboard.
+    pub cargo_slowdown: f32,
+}
+
+impl Default for NanobotSteering {
+    fn default() -> Self {
+        Self {
+            worker: MovementProfile {
+                max_speed: 5.0,
+                acceleration: 0.6,
+                turn_rate: 0.3,
+                arrival_radius: 48.0,
+            },
+            // Haulers are the fastest bots empty and the slowest full,
+            // so sink placement matters more than raw distance.
+            hauler: MovementProfile {
+                max_speed: 5.5,
+                acceleration: 0.4,
+                turn_rate: 0.15,
+                arrival_radius: 64.0,
+            },
+            defender: MovementProfile {
+                max_speed: 6.0,
+                acceleration: 1.0,
+                turn_rate: 0.45,
+                arrival_radius: 32.0,
+            },
+            cargo_slowdown: 0.4,
+        }
+    }
+}
+
+impl NanobotSteering {
+    pub fn profile(&self, kind: NanobotType) -> MovementProfile {
+        match kind {
+            NanobotType::Worker => self.worker,
+            NanobotType::Hauler => self.hauler,
+            NanobotType::Defender => self.defender,
+        }
+    }
+
+    /// Top speed for a `kind` bot carrying `cargo` units in a hold of
+    /// `carry_capacity`, its swarm's researched hauler capacity.
+    pub fn top_speed(&self, kind: NanobotType, cargo: u32, carry_capacity: u32) -> f32 {
+        let profile = self.profile(kind);
+        if kind != NanobotType::Hauler {
+            return profile.max_speed;
+        }
+        let load = (cargo as f32 / carry_capacity.max(1) as f32).min(1.0);
+        profile.max_speed * (1.0 - self.cargo_slowdown * load)
+    }
+}
+
+/// Velocity a steered nanobot kept from its last tick.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq)]
+pub struct Steering {
+    pub velocity: Vec2,
+}
+
+/// Next tick's velocity for a bot moving at `current` toward a
+/// destination `offset` away, limited by `profile` and `top_speed`.
+///
+/// A bot at or below its acceleration step pivots freely, so a bot
+/// that braked while facing away turns in place instead of orbiting
+/// its destination. The step never carries the bot past the
+/// destination.
+pub fn steer(profile: MovementProfile, top_speed: f32, current: Vec2, offset: Vec2) -> Vec2 {
+    let distance = offset.length();
+    if distance <= f32::EPSILON {
+        return Vec2::ZERO;
+    }
+    let wanted = offset / distance;
+    let speed = current.length();
+    let heading = if speed <= profile.acceleration {
+        wanted
+    } else {
+        let heading = current / speed;
+        let angle = heading.angle_to(wanted);
+        let turn = angle.clamp(-profile.turn_rate, profile.turn_rate);
+        Vec2::from_angle(turn).rotate(heading)
+    };
+
+    let arrival = (distance / profile.arrival_radius.max(f32::EPSILON)).min(1.0);
+    let alignment = heading.dot(wanted).max(0.0);
+    let desired = (top_speed * arrival * alignment).max(profile.acceleration);
+    let next_speed = if desired > speed {
+        (speed + profile.acceleration).min(desired)
+    } else {
+        (speed - profile.acceleration).max(desired)
+    };
+    heading * next_speed.min(distance)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Steering math. Per-type movement in the running simulation
+    //! lives in `tests/behavior/steering.rs`.
+
+    use super::*;
+    use crate::nanobot::haul::HAULER_CARRY_CAPACITY;
+    use crate::nanobot::research::RESEARCHED_HAULER_CARRY_CAPACITY;
+
+    fn worker() -> MovementProfile {
+        NanobotSteering::default().worker
+    }
+
+    #[test]
+    fn a_bot_at_rest_accelerates_toward_its_destination() {
+        let profile = worker();
+        let first = steer(profile, profile.max_speed, Vec2::ZERO, Vec2::X * 500.0);
+        assert!((first - Vec2::X * profile.acceleration).length() < 1e-5);
+        let mut velocity = first;
+        for _ in 0..100 {
+            velocity = steer(profile, profile.max_speed, velocity, Vec2::X * 500.0);
+        }
+        assert!((velocity.length() - profile.max_speed).abs() < 1e-4);
+    }
+
+    #[test]
+    fn heading_turns_by_at_most_the_turn_rate() {
+        let profile = worker();
+        let current = Vec2::X * profile.max_speed;
+        let next = steer(profile, profile.max_speed, current, Vec2::Y * 500.0);
+        let turned = Vec2::X.angle_to(next.normalize());
+        assert!((turned - profile.turn_rate).abs() < 1e-4);
+    }
+
+    #[test]
+    fn bots_slow_inside_the_arrival_radius_and_never_overshoot() {
+        let profile = worker();
+        let current = Vec2::X * profile.max_speed;
+        let near = steer(
+            profile,
+            profile.max_speed,
+            current,
+            Vec2::X * profile.arrival_radius * 0.25,
+        );
+        assert!(near.length() < profile.max_speed);
+        let last = steer(profile, profile.max_speed, current, Vec2::X * 0.1);
+        assert!(last.length() <= 0.1 + 1e-6);
+    }
+
+    #[test]
+    fn cargo_slows_only_haulers() {
+        let steering = NanobotSteering::default();
+        let capacity = HAULER_CARRY_CAPACITY;
+        let empty = steering.top_speed(NanobotType::Hauler, 0, capacity);
+        let full = steering.top_speed(NanobotType::Hauler, capacity, capacity);
+        assert!((full - empty * (1.0 - steering.cargo_slowdown)).abs() < 1e-5);
+        assert_eq!(
+            steering.top_speed(NanobotType::Hauler, capacity * 2, capacity),
+            full
+        );
+        assert_eq!(
+            steering.top_speed(NanobotType::Worker, capacity, capacity),
+            steering.worker.max_speed
+        );
+    }
+
+    #[test]
+    fn cargo_weight_is_measured_against_the_researched_hold() {
+        let steering = NanobotSteering::default();
+        let researched = RESEARCHED_HAULER_CARRY_CAPACITY;
+        let empty = steering.top_speed(NanobotType::Hauler, 0, researched);
+        let base_load = steering.top_speed(NanobotType::Hauler, HAULER_CARRY_CAPACITY, researched);
+        let full = steering.top_speed(NanobotType::Hauler, researched, researched);
+
+        let share = HAULER_CARRY_CAPACITY as f32 / researched as f32;
+        assert!((base_load - empty * (1.0 - steering.cargo_slowdown * share)).abs() < 1e-5);
+        assert!((full - empty * (1.0 - steering.cargo_slowdown)).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,108 @@
+//! Completed structure condition shared by every support building.
+//!
+//! Stockpiles, production facilities, chargers and research labs carry a
+//! [`Structure`] sidecar once their planned-structure work finishes. The
+//! sidecar holds health, the maintenance buffer counter, and the upgrade
+//! tier; the maintenance, collapse and upgrade chains all read it.
+
+use bevy::prelude::*;
+
+/// Maximum health a tier-0 `Structure` can have. Maintenance stops
+/// raising health at this cap. A structure starts at full health
+/// and degrades while it goes unmaintained.
+pub const STRUCTURE_MAX_HEALTH: u32 = 100;
+
+/// Distinct kinds of structures the swarm can build. The first
+/// implementation only models `Basic`; later issues (production
+/// facilities, chargers) extend this enum.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StructureKind {
+    #[default]
+    Basic,
+}
+
+/// A completed structure in the world. `health` is in
+/// `[0, max_health()]`. A new structure starts at full health;
+/// degradation (issue #12) lowers it and maintenance restores it.
+///
+/// `ticks_since_maintained` is the maintenance buffer counter
+/// from issue #12. The maintenance system resets it to 0 when a
+/// worker maintains the structure, and increments it every tick
+/// otherwise. When the counter exceeds the buffer, the structure
+/// starts losing health. The counter lives on the structure so
+/// the maintenance work system can reset it without searching
+/// for a separate state object.
+///
+/// `tier` is the in-place upgrade level (see
+/// [`crate::nanobot::structure_max_health`]); tier 0 is the
+/// freshly built structure.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct Structure {
+    pub kind: StructureKind,
+    pub health: u32,
+    pub ticks_since_maintained: u32,
+    pub tier: u32,
+}
+
+impl Structure {
+    /// Build a new structure at full health. Used by tests and
+    /// by the planned-structure completion path.
+    pub fn new(kind: StructureKind) -> Self {
+        Self {
+            kind,
+            health: STRUCTURE_MAX_HEALTH,
+            ticks_since_maintained: 0,
+            tier: 0,
+        }
+    }
+
+    /// Health cap for the structure's tier. Repair and
+    /// maintenance stop here.
+    pub fn max_health(&self) -> u32 {
+        crate::nanobot::structure_max_health(self.tier)
+    }
+
+    /// True when the structure is at or above max health.
+    pub fn is_full_health(&self) -> bool {
+        self.health >= self.max_health()
+    }
+
+    /// True when the structure has just collapsed (no health
+    /// left). The degradation system uses this to despawn
+    /// collapsed structures. A structure that has not been
+    /// collapsed has at least 1 health point.
+    pub fn is_collapsed(&self) -> bool {
+        self.health == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Pure-helper unit tests. The maintenance and collapse contracts
+    //! are covered by `tests/behavior/maintenance.rs`.
+
+    use super::*;
+
+    #[test]
+    fn structure_starts_at_full_health() {
+        let s = Structure::new(StructureKind::Basic);
+        assert_eq!(s.health, STRUCTURE_MAX_HEALTH);
+        assert!(s.is_full_health());
+    }
+
+    #[test]
+    fn structure_reports_full_health_correctly_after_damage() {
+        let mut s = Structure::new(StructureKind::Basic);
+        s.health = STRUCTURE_MAX_HEALTH - 1;
+        assert!(!s.is_full_health());
+        s.health = 0;
+        assert!(!s.is_full_health());
+        s.health = STRUCTURE_MAX_HEALTH;
+        assert!(s.is_full_health());
+    }
+
+    #[test]
+    fn structure_kind_default_is_basic() {
+        assert_eq!(StructureKind::default(), StructureKind::Basic);
+    }
+}
@@ -0,0 +1,328 @@
+//! In-place structure upgrade tiers.
+//!
+//! A completed support structure whose buffer stays full for
+//! [`UPGRADE_SATURATION_TICKS`] is re-planned for the next tier. The
+//! upgrade reuses the Planned Structure lifecycle: the completed entity
+//! gains a [`PlannedStructure`] of its own kind plus a [`PlannedUpgrade`]
+//! sidecar, and keeps working while a Worker builds the upgrade.
+//!
+//! ```text
+//!   Saturated (buffer full) for UPGRADE_SATURATION_TICKS
+//!     -> upgrade cost drawn from the structure's own delivered buffer
+//!     -> PlannedStructure + PlannedUpgrade on the same entity
+//!     -> a Worker spends worker time as for any plan
+//!     -> promotion applies the tier in place
+//! ```
+//!
+//! Each tier multiplies buffer capacity, grows the footprint, raises max
+//! health, speeds up charger refill, and shortens the maintenance buffer
+//! ([`crate::nanobot::maintenance_buffer_ticks`]), so bigger structures
+//! cost more upkeep.
+
+use bevy::prelude::*;
+
+use crate::nanobot::components::{Swarm, SwarmId};
+use crate::nanobot::gather::world_to_cell;
+use crate::nanobot::planned::{DEFAULT_PLANNED_WORK_TICKS, PlannedKind, PlannedStructure};
+use crate::nanobot::structure::{STRUCTURE_MAX_HEALTH, Structure};
+use crate::nanobot::{Charger, OwnerSwarm, ProductionFacility, ResearchLab};
+use crate::resources::{ResourceLedger, Stockpile, StockpileRole};
+
+/// Highest tier a structure can reach.
+pub const MAX_STRUCTURE_TIER: u32 = 2;
+
+/// Consecutive fixed ticks a structure's buffer must stay full before an
+/// upgrade is planned. Long enough that a single delivery burst does not
+/// trigger one.
+pub const UPGRADE_SATURATION_TICKS: u32 = crate::SIMULATION_HZ as u32 * 10;
+
+/// Minerals per tier step. Reaching tier `n` costs `n` times this, which
+/// never exceeds the smallest tier `n - 1` buffer (a facility hopper), so
+/// a saturated structure can always pay for its next tier.
+pub const STRUCTURE_UPGRADE_COST_PER_TIER: u32 = 40;
+
+/// Extra footprint scale per tier.
+pub const TIER_FOOTPRINT_SCALE_STEP: f32 = 0.25;
+
+/// Buffer capacity for a structure built with `base` capacity at `tier`.
+pub const fn tier_capacity(base: u32, tier: u32) -> u32 {
+    base * (1 + tier)
+}
+
+/// Transform scale for a structure at `tier`. Placement reads the scale
+/// through `scaled_building_footprint_radius`, so a bigger tier also
+/// keeps new plans further away.
+pub fn tier_footprint_scale(tier: u32) -> f32 {
+    1.0 + TIER_FOOTPRINT_SCALE_STEP * tier as f32
+}
+
+/// Max health for a structure at `tier`.
+pub const fn structure_max_health(tier: u32) -> u32 {
+    STRUCTURE_MAX_HEALTH * (1 + tier)
+}
+
+/// Mineral cost to upgrade to `tier`.
+pub const fn structure_upgrade_cost(tier: u32) -> u32 {
+    STRUCTURE_UPGRADE_COST_PER_TIER * tier
+}
+
+/// Worker-time budget to upgrade to `tier`.
+pub const fn structure_upgrade_work_ticks(tier: u32) -> u32 {
+    DEFAULT_PLANNED_WORK_TICKS * (1 + tier)
+}
+
+/// Sidecar on a completed structure that also carries a
+/// [`PlannedStructure`] for its next tier. Promotion reads `tier` and
+/// applies it in place instead of spawning a fresh structure.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedUpgrade {
+    pub tier: u32,
+}
+
+/// Consecutive fixed ticks the structure's buffer has been full.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct SaturationTicks(pub u32);
+
+/// Mutable view over the buffer an upgradable structure carries.
+enum TierBuffer<'a> {
+    Stockpile(Mut<'a, Stockpile>),
+    Charger(Mut<'a, Charger>),
+    Facility(Mut<'a, ProductionFacility>),
+    Lab(Mut<'a, ResearchLab>),
+}
+
+impl TierBuffer<'_> {
+    fn is_full(&self) -> bool {
+        match self {
+            TierBuffer::Stockpile(stockpile) => stockpile.free_space() == 0,
+            TierBuffer::Charger(charger) => charger.free_space() == 0,
+            TierBuffer::Facility(facility) => facility.input_free_space() == 0,
+            TierBuffer::Lab(lab) => lab.input_free_space() == 0,
+        }
+    }
+
+    /// Take `amount` from the buffer. Returns `false` (and takes nothing)
+    /// when the buffer holds less.
+    fn withdraw(&mut self, ledger: &mut ResourceLedger, swarm: SwarmId, amount: u32) -> bool {
+        let (kind, stored) = match self {
+            TierBuffer::Stockpile(stockpile) => (stockpile.kind, &mut stockpile.amount),
+            TierBuffer::Charger(charger) => (charger.kind, &mut charger.amount),
+            TierBuffer::Facility(facility) => (facility.input_kind, &mut facility.input_amount),
+            TierBuffer::Lab(lab) => (lab.input_kind, &mut lab.input_amount),
+        };
+        if *stored < amount {
+            return false;
+        }
+        *stored -= amount;
+        ledger.remove_for(swarm, kind, amount);
+        true
+    }
+
+    fn planned_kind(&self, role: Option<&StockpileRole>) -> PlannedKind {
+        match self {
+            TierBuffer::Stockpile(_) if role == Some(&StockpileRole::Sink) => {
+                PlannedKind::SinkStockpile
+            }
+            TierBuffer::Stockpile(_) => PlannedKind::SourceStockpile,
+            TierBuffer::Charger(_) => PlannedKind::Charger,
+            TierBuffer::Facility(_) => PlannedKind::ProductionFacility,
+            TierBuffer::Lab(_) => PlannedKind::ResearchLab,
+        }
+    }
+}
+
+/// Track how long each operational support structure has had a full
+/// buffer. Once that lasts [`UPGRADE_SATURATION_TICKS`], pay the next
+/// tier's cost out of the buffer and plan the upgrade on the same
+/// entity. Structures already under a plan are skipped, so the counter
+/// restarts after each upgrade.
+#[allow(clippy::type_complexity)]
+pub fn structure_saturation_upgrade_system(
+    mut commands: Commands,
+    mut structures: Query<
+        (
+            Entity,
+            &Structure,
+            &Transform,
+            Option<&StockpileRole>,
+            Option<&OwnerSwarm>,
+            Option<&mut SaturationTicks>,
+            (
+                Option<&mut Stockpile>,
+                Option<&mut Charger>,
+                Option<&mut ProductionFacility>,
+                Option<&mut ResearchLab>,
+            ),
+        ),
+        Without<PlannedStructure>,
+    >,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut ledger: ResMut<ResourceLedger>,
+) {
+    for (entity, structure, transform, role, owner, saturation, buffers) in &mut structures {
+        let mut buffer = match buffers {
+            (Some(stockpile), ..) => TierBuffer::Stockpile(stockpile),
+            (_, Some(charger), ..) => TierBuffer::Charger(charger),
+            (_, _, Some(facility), _) => TierBuffer::Facility(facility),
+            (_, _, _, Some(lab)) => TierBuffer::Lab(lab),
+            _ => continue,
+        };
+        if structure.tier >= MAX_STRUCTURE_TIER || !structure.is_operational() || !buffer.is_full()
+        {
+            if let Some(mut saturation) = saturation
+                && saturation.0 != 0
+            {
+                saturation.0 = 0;
+            }
+            continue;
+        }
+        let ticks = saturation.as_ref().map_or(0, |saturation| saturation.0) + 1;
+        if ticks < UPGRADE_SATURATION_TICKS {
+            match saturation {
+                Some(mut saturation) => saturation.0 = ticks,
+                None => {
+                    commands.entity(entity).insert(SaturationTicks(ticks));
+                }
+            }
+            continue;
+        }
+        let tier = structure.tier + 1;
+        let swarm = owner
+            .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+            .unwrap_or(SwarmId::PLAYER);
+        if !buffer.withdraw(&mut ledger, swarm, structure_upgrade_cost(tier)) {
+            continue;
+        }
+        let mut planned = PlannedStructure::new(
+            buffer.planned_kind(role),
+            world_to_cell(transform.translation.truncate()),
+        );
+        planned.work_remaining = structure_upgrade_work_ticks(tier);
+        commands
+            .entity(entity)
+            .insert((planned, PlannedUpgrade { tier }, SaturationTicks(0)));
+    }
+}
+
+/// Apply `tier` to a completed structure in place: scale buffer capacity,
+/// footprint, and max health, and grant the added health. Queued by
+/// planned-structure promotion when the plan carries a
+/// [`PlannedUpgrade`].
+pub fn apply_structure_tier(mut entity: EntityWorldMut, tier: u32) {
+    let Some(mut structure) = entity.get_mut::<Structure>() else {
+        return;
+    };
+    let previous = structure.tier;
+    if tier <= previous {
+        return;
+    }
+    let gained = structure_max_health(tier) - structure_max_health(previous);
+    structure.tier = tier;
+    structure.health = (structure.health + gained).min(structure.max_health());
+    let scale = |capacity: u32| tier_capacity(capacity / (1 + previous), tier);
+    if let Some(mut stockpile) = entity.get_mut::<Stockpile>() {
+        stockpile.capacity = scale(stockpile.capacity);
+    }
+    if let Some(mut charger) = entity.get_mut::<Charger>() {
+        charger.capacity = scale(charger.capacity);
+    }
+    if let Some(mut facility) = entity.get_mut::<ProductionFacility>() {
+        facility.input_capacity = scale(facility.input_capacity);
+    }
+    if let Some(mut lab) = entity.get_mut::<ResearchLab>() {
+        lab.input_capacity = scale(lab.input_capacity);
+    }
+    if let Some(mut transform) = entity.get_mut::<Transform>() {
+        transform.scale = Vec3::new(tier_footprint_scale(tier), tier_footprint_scale(tier), 1.0);
+    }
+}
+
+/// Plugin that wires saturation tracking into the fixed schedule. It
+/// runs before regional allocation projects work, so a freshly planned
+/// upgrade is claimable on the same tick.
+pub struct StructureTierPlugin;
+
+impl Plugin for StructureTierPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            structure_saturation_upgrade_system
+                .before(crate::nanobot::RegionalAllocationSet::Project),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Pure-helper unit tests. The saturation -> plan -> build flow is
+    //! covered by `tests/behavior/structure_upgrade.rs`.
+
+    use super::*;
+    use crate::resources::ResourceKind;
+
+    #[test]
+    fn tiers_scale_capacity_health_and_footprint() {
+        assert_eq!(tier_capacity(200, 0), 200);
+        assert_eq!(tier_capacity(200, 2), 600);
+        assert_eq!(structure_max_health(0), STRUCTURE_MAX_HEALTH);
+        assert_eq!(structure_max_health(1), STRUCTURE_MAX_HEALTH * 2);
+        assert_eq!(tier_footprint_scale(0), 1.0);
+        assert!(tier_footprint_scale(MAX_STRUCTURE_TIER) > tier_footprint_scale(1));
+    }
+
+    #[test]
+    fn higher_tiers_cost_more_material_and_work() {
+        assert!(structure_upgrade_cost(2) > structure_upgrade_cost(1));
+        assert!(structure_upgrade_work_ticks(2) > structure_upgrade_work_ticks(1));
+        assert!(
+            structure_upgrade_cost(MAX_STRUCTURE_TIER)
+                <= tier_capacity(
+                    crate::nanobot::PRODUCTION_INPUT_CAPACITY,
+                    MAX_STRUCTURE_TIER - 1
+                ),
+            "the smallest saturated buffer can pay for its next tier"
+        );
+    }
+
+    #[test]
+    fn higher_tiers_shorten_maintenance_buffer() {
+        let mut structure = Structure::new(crate::nanobot::StructureKind::Basic);
+        let base = structure.maintenance_buffer_ticks();
+        structure.tier = 1;
+        assert!(structure.maintenance_buffer_ticks() < base);
+        assert!(structure.maintenance_needs_threshold() < structure.maintenance_buffer_ticks());
+    }
+
+    #[test]
+    fn applying_a_tier_scales_buffers_in_place() {
+        let mut world = World::new();
+        let mut structure = Structure::new(crate::nanobot::StructureKind::Basic);
+        structure.health = 60;
+        let entity = world
+            .spawn((
+                structure,
+                Stockpile {
+                    kind: ResourceKind::Minerals,
+                    amount: 200,
+                    capacity: 200,
+                    radius: 32.0,
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        apply_structure_tier(world.entity_mut(entity), 1);
+        apply_structure_tier(world.entity_mut(entity), 1);
+
+        let structure = world.get::<Structure>(entity).unwrap();
+        assert_eq!(structure.tier, 1);
+        assert_eq!(structure.health, 60 + STRUCTURE_MAX_HEALTH);
+        let stockpile = world.get::<Stockpile>(entity).unwrap();
+        assert_eq!(stockpile.capacity, 400, "re-applying a tier is a no-op");
+        assert_eq!(stockpile.amount, 200);
+        assert_eq!(
+            world.get::<Transform>(entity).unwrap().scale.x,
+            tier_footprint_scale(1)
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Per-swarm statistics sampled into a time series.
+//!
+//! Flow counters (minerals extracted, hauled, and consumed by
+//! production, facility upgrades, chargers, and field resupply; bots
+//! produced) are recorded by the
+//! simulation systems that move the material, into a pending
+//! [`TelemetryCounters`] per [`SwarmId`]. Lifecycle counters (bots
+//! lost; structures planned, completed, and collapsed) come from
+//! observers, so every path that spawns or despawns is covered.
+//!
+//! ```text
+//!   systems / observers -> pending counters (per swarm)
+//!   every TELEMETRY_SAMPLE_TICKS:
+//!     pending + population + idle ratio -> TelemetrySample
+//!     -> history (bounded, oldest dropped)
+//! ```
+//!
+//! Samples hold the interval's flows, not running totals, so a graph
+//! reads rates directly. [`SwarmTelemetry::to_csv`] exports every
+//! swarm's history. Recording is optional: systems take the resource
+//! as `Option`, so apps without [`TelemetryPlugin`] pay nothing.
+//!
+//! [`LogisticsFlowHistory`] keeps the delivered amount per source and
+//! sink pair over the last minute, for the logistics overlay.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Write as _;
+
+use bevy::prelude::*;
+
+use crate::nanobot::autonomy::NanobotType;
+use crate::nanobot::behaviour::Behaviour;
+use crate::nanobot::components::{Nanobot, Swarm, SwarmId, SwarmMember};
+use crate::nanobot::planned::PlannedStructure;
+use crate::nanobot::production::OwnerSwarm;
+use crate::nanobot::reclaim::PlannedReclaim;
+use crate::nanobot::structure::Structure;
+use crate::nanobot::structure_tier::PlannedUpgrade;
+
+/// Fixed ticks between samples: one per second.
+pub const TELEMETRY_SAMPLE_TICKS: u64 = crate::SIMULATION_HZ as u64;
+
+/// Samples kept per swarm: ten minutes at one sample per second.
+pub const TELEMETRY_HISTORY_SAMPLES: usize = 600;
+
+/// Column header written by [`SwarmTelemetry::to_csv`].
+pub const TELEMETRY_CSV_HEADER: &str = "swarm,tick,minerals_extracted,minerals_hauled,\
+minerals_to_production,minerals_to_chargers,minerals_to_upgrades,minerals_to_resupply,\
+workers_produced,haulers_produced,defenders_produced,workers_lost,haulers_lost,defenders_lost,\
+structures_planned,structures_completed,structures_collapsed,workers,haulers,defenders,idle_ratio";
+
+/// One-second buckets in the [`LogisticsFlowHistory`] window.
+pub const LOGISTICS_FLOW_WINDOW_BUCKETS: usize = 60;
+
+/// Flow counts for one swarm over one sample interval. Minerals are
+/// the only [`crate::resources::ResourceKind`], so amounts are not
+/// split by kind. Per-type arrays follow [`NanobotType::ALL`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TelemetryCounters {
+    pub minerals_extracted: u32,
+    pub minerals_hauled: u32,
+    /// Minerals spent on new nanobots.
+    pub minerals_to_production: u32,
+    pub minerals_to_chargers: u32,
+    /// Minerals spent on production facility speed upgrades.
+    pub minerals_to_upgrades: u32,
+    /// Minerals haulers turned into charge on Defenders in the field.
+    pub minerals_to_resupply: u32,
+    pub produced: [u32; NanobotType::COUNT],
+    pub lost: [u32; NanobotType::COUNT],
+    pub structures_planned: u32,
+    pub structures_completed: u32,
+    pub structures_collapsed: u32,
+}
+
+impl TelemetryCounters {
+    pub fn record_produced(&mut self, kind: NanobotType) {
+        self.produced[type_index(kind)] += 1;
+    }
+
+    pub fn record_lost(&mut self, kind: NanobotType) {
+        self.lost[type_index(kind)] += 1;
+    }
+}
+
+/// One sample: the interval's flows plus the population snapshot at
+/// the sample tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TelemetrySample {
+    pub tick: u64,
+    pub flows: TelemetryCounters,
+    pub population: [u32; NanobotType::COUNT],
+    /// Share of the swarm's nanobots holding no behaviour state.
+    /// Zero for an empty swarm.
+    pub idle_ratio: f32,
+}
+
+/// Telemetry for every swarm, keyed by [`SwarmId`].
+#[derive(Debug, Default, Resource)]
+pub struct SwarmTelemetry {
+    tick: u64,
+    pending: HashMap<SwarmId, TelemetryCounters>,
+    history: HashMap<SwarmId, VecDeque<TelemetrySample>>,
+}
+
+impl SwarmTelemetry {
+    /// Counters for the interval in progress.
+    pub fn counters_mut(&mut self, swarm: SwarmId) -> &mut TelemetryCounters {
+        self.pending.entry(swarm).or_default()
+    }
+
+    /// Samples for `swarm`, oldest first.
+    pub fn history(&self, swarm: SwarmId) -> impl Iterator<Item = &TelemetrySample> {
+        self.history.get(&swarm).into_iter().flatten()
+    }
+
+    pub fn latest(&self, swarm: SwarmId) -> Option<&TelemetrySample> {
+        self.history.get(&swarm).and_then(VecDeque::back)
+    }
+
+    /// Close the interval for `swarm` and append its sample.
+    pub fn push_sample(
+        &mut self,
+        swarm: SwarmId,
+        population: [u32; NanobotType::COUNT],
+        idle_ratio: f32,
+    ) {
+        let sample = TelemetrySample {
+            tick: self.tick,
+            flows: self.pending.remove(&swarm).unwrap_or_default(),
+            population,
+            idle_ratio,
+        };
+        let history = self.history.entry(swarm).or_default();
+        if history.len() == TELEMETRY_HISTORY_SAMPLES {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+
+    /// Every swarm's history as CSV, one row per sample, swarms in id
+    /// order.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(TELEMETRY_CSV_HEADER);
+        csv.push('\n');
+        let mut swarms = self.history.keys().copied().collect::<Vec<_>>();
+        swarms.sort_by_key(|swarm| swarm.0);
+        for swarm in swarms {
+            for sample in self.history(swarm) {
+                let flows = &sample.flows;
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{:.3}",
+                    swarm.0,
+                    sample.tick,
+                    flows.minerals_extracted,
+                    flows.minerals_hauled,
+                    flows.minerals_to_production,
+                    flows.minerals_to_chargers,
+                    flows.minerals_to_upgrades,
+                    flows.minerals_to_resupply,
+                    flows.produced[0],
+                    flows.produced[1],
+                    flows.produced[2],
+                    flows.lost[0],
+                    flows.lost[1],
+                    flows.lost[2],
+                    flows.structures_planned,
+                    flows.structures_completed,
+                    flows.structures_collapsed,
+                    sample.population[0],
+                    sample.population[1],
+                    sample.population[2],
+                    sample.idle_ratio,
+                );
+            }
+        }
+        csv
+    }
+}
+
+/// Minerals delivered per `(source, sink)` pair over the last
+/// [`LOGISTICS_FLOW_WINDOW_BUCKETS`] seconds. Haulers record each
+/// delivery; the window rolls one bucket per
+/// [`TELEMETRY_SAMPLE_TICKS`].
+#[derive(Debug, Resource)]
+pub struct LogisticsFlowHistory {
+    tick: u64,
+    buckets: VecDeque<BTreeMap<(Entity, Entity), u32>>,
+}
+
+impl Default for LogisticsFlowHistory {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            buckets: VecDeque::from([BTreeMap::new()]),
+        }
+    }
+}
+
+impl LogisticsFlowHistory {
+    pub fn record(&mut self, source: Entity, sink: Entity, amount: u32) {
+        if let Some(bucket) = self.buckets.back_mut() {
+            *bucket.entry((source, sink)).or_default() += amount;
+        }
+    }
+
+    /// Advance one fixed tick, opening a new bucket on each second
+    /// boundary and dropping the oldest past the window.
+    pub fn advance(&mut self) {
+        self.tick += 1;
+        if !self.tick.is_multiple_of(TELEMETRY_SAMPLE_TICKS) {
+            return;
+        }
+        self.buckets.push_back(BTreeMap::new());
+        while self.buckets.len() > LOGISTICS_FLOW_WINDOW_BUCKETS {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Total delivered per pair across the window, in pair order.
+    pub fn totals(&self) -> BTreeMap<(Entity, Entity), u32> {
+        let mut totals = BTreeMap::new();
+        for bucket in &self.buckets {
+            for (&pair, &amount) in bucket {
+                *totals.entry(pair).or_default() += amount;
+            }
+        }
+        totals
+    }
+}
+
+pub fn logistics_flow_advance_system(mut flows: ResMut<LogisticsFlowHistory>) {
+    flows.advance();
+}
+
+fn type_index(kind: NanobotType) -> usize {
+    NanobotType::ALL
+        .iter()
+        .position(|candidate| *candidate == kind)
+        .expect("NanobotType::ALL lists every type")
+}
+
+fn owner_swarm(owner: Option<&OwnerSwarm>, swarms: &Query<&SwarmId>) -> SwarmId {
+    owner
+        .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+        .unwrap_or(SwarmId::PLAYER)
+}
+
+/// Advance the telemetry clock and, every [`TELEMETRY_SAMPLE_TICKS`],
+/// close the interval for every swarm with a population snapshot.
+pub fn telemetry_sample_system(
+    mut telemetry: ResMut<SwarmTelemetry>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    nanobots: Query<(&NanobotType, &SwarmMember, Option<&Behaviour>), With<Nanobot>>,
+) {
+    telemetry.tick += 1;
+    if !telemetry.tick.is_multiple_of(TELEMETRY_SAMPLE_TICKS) {
+        return;
+    }
+    let mut population = HashMap::<SwarmId, ([u32; NanobotType::COUNT], u32)>::new();
+    for swarm in &swarms {
+        population.entry(*swarm).or_default();
+    }
+    for (kind, member, behaviour) in &nanobots {
+        let (counts, idle) = population.entry(member.0).or_default();
+        counts[type_index(*kind)] += 1;
+        if behaviour.is_none_or(Behaviour::is_idle) {
+            *idle += 1;
+        }
+    }
+    let pending = telemetry.pending.keys().copied().collect::<Vec<_>>();
+    for swarm in pending {
+        population.entry(swarm).or_default();
+    }
+    for (swarm, (counts, idle)) in population {
+        let total = counts.iter().sum::<u32>();
+        let idle_ratio = if total == 0 {
+            0.0
+        } else {
+            idle as f32 / total as f32
+        };
+        telemetry.push_sample(swarm, counts, idle_ratio);
+    }
+}
+
+/// A despawned nanobot counts as lost for its swarm.
+pub fn record_nanobot_lost(
+    removed: On<Remove, Nanobot>,
+    telemetry: Option<ResMut<SwarmTelemetry>>,
+    nanobots: Query<(&NanobotType, &SwarmMember)>,
+) {
+    let Some(mut telemetry) = telemetry else {
+        return;
+    };
+    if let Ok((kind, member)) = nanobots.get(removed.entity) {
+        telemetry.counters_mut(member.0).record_lost(*kind);
+    }
+}
+
+/// A new structure plan. Upgrade and reclaim plans reuse the
+/// lifecycle on an existing structure and are not counted.
+#[allow(clippy::type_complexity)]
+pub fn record_structure_planned(
+    added: On<Add, PlannedStructure>,
+    telemetry: Option<ResMut<SwarmTelemetry>>,
+    planned: Query<Option<&OwnerSwarm>, (Without<PlannedUpgrade>, Without<PlannedReclaim>)>,
+    swarms: Query<&SwarmId>,
+) {
+    let Some(mut telemetry) = telemetry else {
+        return;
+    };
+    if let Ok(owner) = planned.get(added.entity) {
+        telemetry
+            .counters_mut(owner_swarm(owner, &swarms))
+            .structures_planned += 1;
+    }
+}
+
+/// A plan removed with its work done was promoted to a structure;
+/// one removed early was cancelled and is not counted.
+#[allow(clippy::type_complexity)]
+pub fn record_structure_completed(
+    removed: On<Remove, PlannedStructure>,
+    telemetry: Option<ResMut<SwarmTelemetry>>,
+    planned: Query<
+        (&PlannedStructure, Option<&OwnerSwarm>),
+        (Without<PlannedUpgrade>, Without<PlannedReclaim>),
+    >,
+    swarms: Query<&SwarmId>,
+) {
+    let Some(mut telemetry) = telemetry else {
+        return;
+    };
+    if let Ok((planned, owner)) = planned.get(removed.entity)
+        && planned.is_complete()
+    {
+        telemetry
+            .counters_mut(owner_swarm(owner, &swarms))
+            .structures_completed += 1;
+    }
+}
+
+/// A structure removed at zero health collapsed or was destroyed.
+/// Reclaim removes a healthy structure and is not counted.
+pub fn record_structure_collapsed(
+    removed: On<Remove, Structure>,
+    telemetry: Option<ResMut<SwarmTelemetry>>,
+    structures: Query<(&Structure, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId>,
+) {
+    let Some(mut telemetry) = telemetry else {
+        return;
+    };
+    if let Ok((structure, owner)) = structures.get(removed.entity)
+        && structure.health == 0
+    {
+        telemetry
+            .counters_mut(owner_swarm(owner, &swarms))
+            .structures_collapsed += 1;
+    }
+}
+
+/// Plugin that owns [`SwarmTelemetry`] and [`LogisticsFlowHistory`].
+/// Sampling runs after the maintenance phase so a sample sees the
+/// whole tick's flows.
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SwarmTelemetry>()
+            .init_resource::<LogisticsFlowHistory>()
+            .add_observer(record_nanobot_lost)
+            .add_observer(record_structure_planned)
+            .add_observer(record_structure_completed)
+            .add_observer(record_structure_collapsed)
+            .add_systems(
+                FixedUpdate,
+                (telemetry_sample_system, logistics_flow_advance_system)
+                    .after(crate::nanobot::NanobotSimulationSet::Maintenance),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! History and CSV shape. Recording from the simulation is covered
+    //! by `tests/behavior/telemetry.rs`.
+
+    use super::*;
+
+    #[test]
+    fn sample_drains_pending_counters() {
+        let mut telemetry = SwarmTelemetry::default();
+        telemetry.counters_mut(SwarmId::PLAYER).minerals_extracted += 5;
+        telemetry.push_sample(SwarmId::PLAYER, [1, 0, 0], 1.0);
+        telemetry.push_sample(SwarmId::PLAYER, [1, 0, 0], 0.0);
+
+        let samples = telemetry.history(SwarmId::PLAYER).collect::<Vec<_>>();
+        assert_eq!(samples[0].flows.minerals_extracted, 5);
+        assert_eq!(samples[1].flows, TelemetryCounters::default());
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut telemetry = SwarmTelemetry::default();
+        for _ in 0..TELEMETRY_HISTORY_SAMPLES + 5 {
+            telemetry.push_sample(SwarmId::PLAYER, [0; NanobotType::COUNT], 0.0);
+        }
+        assert_eq!(
+            telemetry.history(SwarmId::PLAYER).count(),
+            TELEMETRY_HISTORY_SAMPLES
+        );
+    }
+
+    #[test]
+    fn csv_has_one_row_per_sample_in_swarm_order() {
+        let mut telemetry = SwarmTelemetry::default();
+        telemetry
+            .counters_mut(SwarmId(2))
+            .record_lost(NanobotType::Hauler);
+        telemetry.push_sample(SwarmId(2), [0, 1, 0], 0.0);
+        telemetry.counters_mut(SwarmId::PLAYER).minerals_hauled += 7;
+        telemetry.push_sample(SwarmId::PLAYER, [2, 0, 0], 0.5);
+
+        let csv = telemetry.to_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], TELEMETRY_CSV_HEADER);
+        assert_eq!(lines[1], "0,0,0,7,0,0,0,0,0,0,0,0,0,0,0,0,0,2,0,0,0.500");
+        assert_eq!(lines[2], "2,0,0,0,0,0,0,0,0,0,0,0,1,0,0,0,0,0,1,0,0.000");
+        assert_eq!(
+            lines[0].split(',').count(),
+            lines[1].split(',').count(),
+            "header and rows agree"
+        );
+    }
+
+    #[test]
+    fn logistics_flow_window_drops_deliveries_older_than_a_minute() {
+        let source = Entity::from_raw_u32(1).expect("test entity");
+        let sink = Entity::from_raw_u32(2).expect("test entity");
+        let mut flows = LogisticsFlowHistory::default();
+        flows.record(source, sink, 3);
+        for _ in 0..TELEMETRY_SAMPLE_TICKS {
+            flows.advance();
+        }
+        flows.record(source, sink, 4);
+        assert_eq!(flows.totals().get(&(source, sink)), Some(&7));
+
+        for _ in 1..LOGISTICS_FLOW_WINDOW_BUCKETS as u64 * TELEMETRY_SAMPLE_TICKS {
+            flows.advance();
+        }
+        assert_eq!(flows.totals().get(&(source, sink)), Some(&4));
+    }
+}
@@ -0,0 +1,354 @@
+//! Per-swarm danger from hostile Defenders, and the flee state it
+//! drives for Workers and Haulers.
+//!
+//! ```text
+//!   hostile Defender        -> DANGER_PER_DEFENDER on every cell its
+//!                              attack circle touches, for every other swarm
+//!   structure under attack  -> DANGER_PER_RECENT_DAMAGE on its cell,
+//!                              for its owner
+//!     -> IntentGrid danger layer (capped at DANGER_MAX_LEVEL)
+//!     -> route costs rise by DANGER_COST_PER_LEVEL per level
+//!     -> a Worker or Hauler standing on FLEE_DANGER_LEVEL or more:
+//!          Commitment::Fleeing, lease suspended, task roles exited,
+//!          walks to the safest nearby cell
+//!     -> FLEE_CALM_TICKS below the flee level: Commitment::Idle and
+//!        the lease is handed back to the allocator to resume
+//! ```
+//!
+//! Cargo stays on a fleeing bot; a Worker's load is delivered again
+//! once it calms down. A loaded Hauler sets its leg aside as a
+//! [`SuspendedHaul`] and takes it back on recovery, so the carry and
+//! reroute systems see it as an ordinary loaded hauler again.
+
+use std::collections::{BTreeSet, HashMap};
+
+use bevy::prelude::*;
+
+use crate::intent::IntentGrid;
+use crate::nanobot::behaviour::{BehaviourAppExt, BehaviourRole, BehaviourState, exit_role};
+use crate::nanobot::{
+    Cargo, Commitment, DEFENDER_ATTACK_RANGE, DirectMovementComponent, HaulerAssignment,
+    LogisticsReservation, Nanobot, NanobotType, RecentDamage, RegionalLease, SpatialIndex,
+    SpatialLayer, Swarm, SwarmId, SwarmMember, add_route_cost_cache, add_spatial_index,
+    cell_overlaps_circle, get_world_from_zone, route_cost_cache_invalidate_system, world_to_cell,
+};
+
+/// Danger one hostile Defender adds to each cell in its reach.
+pub const DANGER_PER_DEFENDER: u8 = 2;
+/// Danger a structure under attack adds to its owner's cell.
+pub const DANGER_PER_RECENT_DAMAGE: u8 = 1;
+/// Cap on one cell's danger for one swarm.
+pub const DANGER_MAX_LEVEL: u8 = 8;
+/// Danger at which a Worker or Hauler drops its task and backs away.
+pub const FLEE_DANGER_LEVEL: u8 = DANGER_PER_DEFENDER;
+/// Ticks below [`FLEE_DANGER_LEVEL`] before a fleeing bot resumes work.
+pub const FLEE_CALM_TICKS: u32 = crate::SIMULATION_HZ as u32;
+/// Cells searched around a fleeing bot for somewhere safer.
+const FLEE_SEARCH_RADIUS: i32 = 2;
+
+/// Task roles a fleeing bot abandons.
+const ABANDONED_ROLES: [BehaviourRole; 6] = [
+    BehaviourRole::GATHER,
+    BehaviourRole::DELIVER,
+    BehaviourRole::BUILD,
+    BehaviourRole::MAINTAIN,
+    BehaviourRole::REPAIR,
+    BehaviourRole::HAUL,
+];
+
+/// A Worker or Hauler backing away from hostile Defenders.
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Fleeing {
+    /// Consecutive ticks spent below [`FLEE_DANGER_LEVEL`].
+    pub calm_ticks: u32,
+}
+
+impl BehaviourState for Fleeing {
+    const ROLE: BehaviourRole = BehaviourRole::FLEE;
+
+    /// Fleeing abandons the current task. Reservations go too, so
+    /// another bot can take over the logistics the fleeing one held.
+    fn on_enter(nanobot: &mut EntityCommands) {
+        nanobot.queue(suspend_haul);
+        for role in ABANDONED_ROLES {
+            nanobot.queue(exit_role(role));
+        }
+        nanobot.remove::<LogisticsReservation>();
+    }
+}
+
+/// A loaded Hauler's leg, set aside while it flees. The cargo stays
+/// aboard; recovery hands the leg back and the hauler delivers or
+/// reroutes the load as usual.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct SuspendedHaul {
+    pub assignment: HaulerAssignment,
+    pub reservation: Option<LogisticsReservation>,
+}
+
+/// Set a Hauler's leg aside before its HAUL role is exited. A hauler
+/// caught with an empty hold drops it instead, so the assignment
+/// system can give it a fresh leg once it calms down.
+fn suspend_haul(mut nanobot: EntityWorldMut) {
+    let Some(assignment) = nanobot.get::<HaulerAssignment>().copied() else {
+        return;
+    };
+    match nanobot.get::<Cargo>().map(|cargo| cargo.amount) {
+        Some(0) => {
+            nanobot.remove::<Cargo>();
+        }
+        Some(_) => {
+            let reservation = nanobot.get::<LogisticsReservation>().copied();
+            nanobot.insert(SuspendedHaul {
+                assignment,
+                reservation,
+            });
+        }
+        None => {}
+    }
+}
+
+/// Rebuild the grid's danger layer from hostile Defender positions and
+/// structures under attack. The grid only marks cells whose level
+/// changed, so a quiet map leaves the route-cost cache alone.
+pub fn danger_field_system(
+    mut grid: ResMut<IntentGrid>,
+    index: Res<SpatialIndex>,
+    kinds: Query<&NanobotType, With<Nanobot>>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    damaged: Query<(Entity, &RecentDamage)>,
+) {
+    let mut known = swarms.iter().copied().collect::<BTreeSet<_>>();
+    known.extend(
+        index
+            .entries(SpatialLayer::Nanobot)
+            .filter_map(|entry| entry.swarm),
+    );
+
+    let mut danger = HashMap::<(SwarmId, IVec2), u8>::new();
+    let mut add = |swarm: SwarmId, cell: IVec2, level: u8| {
+        let entry = danger.entry((swarm, cell)).or_default();
+        *entry = entry.saturating_add(level).min(DANGER_MAX_LEVEL);
+    };
+    for entry in index.entries(SpatialLayer::Nanobot) {
+        let Some(attacker) = entry.swarm else {
+            continue;
+        };
+        if !kinds
+            .get(entry.entity)
+            .is_ok_and(|kind| *kind == NanobotType::Defender)
+        {
+            continue;
+        }
+        let center = world_to_cell(entry.position);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let cell = center + IVec2::new(dx, dy);
+                if !cell_overlaps_circle(cell, entry.position, DEFENDER_ATTACK_RANGE) {
+                    continue;
+                }
+                for swarm in known.iter().copied().filter(|swarm| *swarm != attacker) {
+                    add(swarm, cell, DANGER_PER_DEFENDER);
+                }
+            }
+        }
+    }
+    for (entity, recent) in &damaged {
+        if !recent.under_attack() {
+            continue;
+        }
+        let Some(entry) = index.get(entity) else {
+            continue;
+        };
+        if let Some(owner) = entry.swarm {
+            add(
+                owner,
+                world_to_cell(entry.position),
+                DANGER_PER_RECENT_DAMAGE,
+            );
+        }
+    }
+    grid.replace_danger(danger);
+}
+
+/// Pull threatened Workers and Haulers off their tasks. The lease is
+/// suspended rather than released so the allocator can hand the bot
+/// work again once it calms down.
+#[allow(clippy::type_complexity)]
+pub fn flee_threat_system(
+    mut commands: Commands,
+    grid: Res<IntentGrid>,
+    mut bots: Query<
+        (
+            Entity,
+            &Transform,
+            &NanobotType,
+            &SwarmMember,
+            Option<&mut Commitment>,
+            Option<&mut RegionalLease>,
+        ),
+        (With<Nanobot>, Without<Fleeing>),
+    >,
+) {
+    for (entity, transform, kind, member, commitment, lease) in &mut bots {
+        if !matches!(kind, NanobotType::Worker | NanobotType::Hauler) {
+            continue;
+        }
+        let pos = transform.translation.truncate();
+        if grid.danger_for(world_to_cell(pos), member.0) < FLEE_DANGER_LEVEL {
+            continue;
+        }
+        if let Some(mut commitment) = commitment {
+            *commitment = Commitment::Fleeing;
+        }
+        if let Some(mut lease) = lease {
+            lease.suspend_for_threat();
+        }
+        let mut nanobot = commands.entity(entity);
+        nanobot.insert(Fleeing::default());
+        match retreat_point(&grid, pos, member.0) {
+            Some(xy) => nanobot.insert(DirectMovementComponent {
+                xy,
+                stop_radius: 0.0,
+            }),
+            None => nanobot.remove::<DirectMovementComponent>(),
+        };
+    }
+}
+
+/// Count calm ticks for fleeing bots, keep backing away while the
+/// danger holds, and hand the bot back to the allocator once calm. A
+/// suspended Hauler leg is restored at the same time.
+#[allow(clippy::type_complexity)]
+pub fn flee_recovery_system(
+    mut commands: Commands,
+    grid: Res<IntentGrid>,
+    mut bots: Query<(
+        Entity,
+        &Transform,
+        &SwarmMember,
+        &mut Fleeing,
+        Option<&mut Commitment>,
+        Option<&mut RegionalLease>,
+        Option<&SuspendedHaul>,
+        Has<DirectMovementComponent>,
+    )>,
+) {
+    for (entity, transform, member, mut fleeing, commitment, lease, suspended, moving) in &mut bots
+    {
+        let pos = transform.translation.truncate();
+        if grid.danger_for(world_to_cell(pos), member.0) >= FLEE_DANGER_LEVEL {
+            fleeing.calm_ticks = 0;
+            if !moving && let Some(xy) = retreat_point(&grid, pos, member.0) {
+                commands.entity(entity).insert(DirectMovementComponent {
+                    xy,
+                    stop_radius: 0.0,
+                });
+            }
+            continue;
+        }
+        fleeing.calm_ticks += 1;
+        if fleeing.calm_ticks < FLEE_CALM_TICKS {
+            continue;
+        }
+        if let Some(mut commitment) = commitment {
+            *commitment = Commitment::Idle;
+        }
+        if let Some(mut lease) = lease {
+            lease.request_resume();
+        }
+        let mut nanobot = commands.entity(entity);
+        nanobot
+            .remove::<Fleeing>()
+            .remove::<DirectMovementComponent>();
+        if let Some(haul) = suspended {
+            nanobot.insert(haul.assignment).remove::<SuspendedHaul>();
+            if let Some(reservation) = haul.reservation {
+                nanobot.insert(reservation);
+            }
+        }
+    }
+}
+
+/// Centre of the least dangerous cell within [`FLEE_SEARCH_RADIUS`] of
+/// `pos`, nearest first. `None` when nothing nearby is safer than the
+/// bot's own cell.
+pub fn retreat_point(grid: &IntentGrid, pos: Vec2, swarm: SwarmId) -> Option<Vec2> {
+    let here = world_to_cell(pos);
+    let current = grid.danger_for(here, swarm);
+    let mut best: Option<(u8, f32, Vec2)> = None;
+    for dy in -FLEE_SEARCH_RADIUS..=FLEE_SEARCH_RADIUS {
+        for dx in -FLEE_SEARCH_RADIUS..=FLEE_SEARCH_RADIUS {
+            let cell = here + IVec2::new(dx, dy);
+            if cell == here || !grid.in_bounds(cell) {
+                continue;
+            }
+            let level = grid.danger_for(cell, swarm);
+            let center = get_world_from_zone(cell);
+            let distance = pos.distance_squared(center);
+            if best.is_none_or(|(best_level, best_distance, _)| {
+                (level, distance) < (best_level, best_distance)
+            }) {
+                best = Some((level, distance, center));
+            }
+        }
+    }
+    best.filter(|(level, _, _)| *level < current)
+        .map(|(_, _, center)| center)
+}
+
+/// Derives per-swarm danger from hostile Defenders and recent structure
+/// damage, and moves threatened Workers and Haulers out of it. Danger
+/// is refreshed after movement and before allocation and the route-cost
+/// cache read it.
+pub struct ThreatPlugin;
+
+impl Plugin for ThreatPlugin {
+    fn build(&self, app: &mut App) {
+        add_spatial_index(app);
+        add_route_cost_cache(app);
+        app.add_behaviour_state::<Fleeing>().add_systems(
+            FixedUpdate,
+            (
+                danger_field_system,
+                flee_threat_system,
+                flee_recovery_system,
+            )
+                .chain()
+                .in_set(crate::nanobot::NanobotSimulationSet::Threat)
+                .before(crate::nanobot::RegionalAllocationSet::Project)
+                .before(route_cost_cache_invalidate_system),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Retreat choice. Danger upkeep and the flee / resume cycle live in
+    //! `tests/behavior/threat.rs`.
+
+    use super::*;
+
+    #[test]
+    fn retreat_picks_the_nearest_safer_cell() {
+        let mut grid = IntentGrid::new(8, 8);
+        let here = IVec2::new(3, 3);
+        let pos = get_world_from_zone(here);
+        assert_eq!(retreat_point(&grid, pos, SwarmId::PLAYER), None);
+
+        let mut danger = HashMap::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                danger.insert((SwarmId::PLAYER, here + IVec2::new(dx, dy)), 4);
+            }
+        }
+        danger.insert((SwarmId::PLAYER, IVec2::new(4, 4)), 1);
+        grid.replace_danger(danger);
+
+        assert_eq!(
+            retreat_point(&grid, pos, SwarmId::PLAYER),
+            Some(get_world_from_zone(IVec2::new(3, 1)))
+        );
+        assert_eq!(retreat_point(&grid, pos, SwarmId(2)), None);
+    }
+}
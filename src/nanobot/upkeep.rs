@@ -0,0 +1,179 @@
+//! Optional mineral upkeep for structure maintenance.
+//!
+//! Without a [`MaintenanceUpkeep`] resource maintenance costs only
+//! worker time. With it, every owned completed structure gets an
+//! upkeep depot: a separate inflow-only terminal entity at the
+//! structure's position holding an [`UpkeepStock`]. Haulers fill the
+//! depot from Sink Stockpiles like any other Terminal Consumer, and
+//! each maintenance shift spends [`MaintenanceUpkeep::per_shift`] from
+//! it before the worker starts.
+//!
+//! ```text
+//!   Structure (owned, completed)
+//!     -> attach system spawns depot (UpkeepStock + UpkeepFor)
+//!     -> haulers deliver minerals into the depot
+//!     -> maintenance shift pays per_shift, then resets the buffer
+//!     -> empty depot: no maintenance is projected, the structure decays
+//! ```
+//!
+//! The depot is its own entity so a hauler leg names exactly one
+//! sink: a Production Facility's input hopper and its upkeep stock
+//! never share an entity. Unowned structures get no depot and keep
+//! the worker-time-only model, since no swarm's haulers serve them.
+
+use bevy::prelude::*;
+
+use crate::nanobot::OwnerSwarm;
+use crate::nanobot::components::{Swarm, SwarmId};
+use crate::nanobot::structure::Structure;
+use crate::resources::{ResourceKind, ResourceLedger};
+
+/// Minerals a single maintenance shift consumes.
+pub const DEFAULT_UPKEEP_PER_SHIFT: u32 = 2;
+
+/// Depot capacity: enough for a handful of shifts so one hauler
+/// trip keeps a structure stable for several buffer periods.
+pub const DEFAULT_UPKEEP_CAPACITY: u32 = 10;
+
+/// Mineral upkeep for maintenance. While present, every owned
+/// completed structure gets an [`UpkeepStock`] depot that each
+/// maintenance shift draws `per_shift` from; without it maintenance
+/// costs only worker time.
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceUpkeep {
+    pub kind: ResourceKind,
+    pub per_shift: u32,
+    pub capacity: u32,
+}
+
+impl Default for MaintenanceUpkeep {
+    fn default() -> Self {
+        Self {
+            kind: ResourceKind::Minerals,
+            per_shift: DEFAULT_UPKEEP_PER_SHIFT,
+            capacity: DEFAULT_UPKEEP_CAPACITY,
+        }
+    }
+}
+
+impl MaintenanceUpkeep {
+    /// True when a structure whose depot holds `stock` can pay for
+    /// one shift. A structure without a depot is exempt.
+    pub fn covers(&self, stock: Option<&UpkeepStock>) -> bool {
+        stock.is_none_or(|stock| stock.amount >= self.per_shift)
+    }
+}
+
+/// True when maintenance on a structure with depot `stock` may run
+/// under the optional `upkeep` model.
+pub fn upkeep_covers(upkeep: Option<&MaintenanceUpkeep>, stock: Option<&UpkeepStock>) -> bool {
+    upkeep.is_none_or(|upkeep| upkeep.covers(stock))
+}
+
+/// Mineral stock held by an upkeep depot.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct UpkeepStock {
+    pub kind: ResourceKind,
+    pub amount: u32,
+    pub capacity: u32,
+}
+
+impl UpkeepStock {
+    pub fn new(kind: ResourceKind, capacity: u32) -> Self {
+        Self {
+            kind,
+            amount: 0,
+            capacity,
+        }
+    }
+
+    pub fn free_space(&self) -> u32 {
+        self.capacity.saturating_sub(self.amount)
+    }
+}
+
+/// On a structure: its upkeep depot entity.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct UpkeepDepot(pub Entity);
+
+/// On an upkeep depot: the structure it supplies.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub struct UpkeepFor(pub Entity);
+
+/// Give every owned structure without a depot a fresh, empty one.
+#[allow(clippy::type_complexity)]
+pub fn attach_upkeep_depots_system(
+    mut commands: Commands,
+    upkeep: Option<Res<MaintenanceUpkeep>>,
+    structures: Query<(Entity, &Transform, &OwnerSwarm), (With<Structure>, Without<UpkeepDepot>)>,
+) {
+    let Some(upkeep) = upkeep else {
+        return;
+    };
+    for (structure, transform, owner) in &structures {
+        let depot = commands
+            .spawn((
+                UpkeepStock::new(upkeep.kind, upkeep.capacity),
+                UpkeepFor(structure),
+                *owner,
+                Transform::from_translation(transform.translation),
+            ))
+            .id();
+        commands.entity(structure).insert(UpkeepDepot(depot));
+    }
+}
+
+/// A structure that collapses, despawns, or is reclaimed takes its
+/// depot with it. Remaining stock is lost and leaves the owner's
+/// ledger.
+pub fn despawn_upkeep_depot(
+    removed: On<Remove, Structure>,
+    mut commands: Commands,
+    depots: Query<&UpkeepDepot>,
+    stocks: Query<(&UpkeepStock, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut ledger: ResMut<ResourceLedger>,
+) {
+    let Ok(UpkeepDepot(depot)) = depots.get(removed.entity) else {
+        return;
+    };
+    if let Ok((stock, owner)) = stocks.get(*depot) {
+        let swarm = owner
+            .and_then(|owner| swarms.get(owner.0).ok())
+            .copied()
+            .unwrap_or(SwarmId::PLAYER);
+        ledger.remove_for(swarm, stock.kind, stock.amount);
+    }
+    commands.entity(*depot).try_despawn();
+    commands.entity(removed.entity).try_remove::<UpkeepDepot>();
+}
+
+#[cfg(test)]
+mod tests {
+    //! Cost checks. Depot lifecycle and the haul / maintenance
+    //! contract live in `tests/behavior/maintenance_upkeep.rs`.
+
+    use super::*;
+
+    #[test]
+    fn disabled_upkeep_covers_everything() {
+        let empty = UpkeepStock::new(ResourceKind::Minerals, DEFAULT_UPKEEP_CAPACITY);
+        assert!(upkeep_covers(None, Some(&empty)));
+    }
+
+    #[test]
+    fn enabled_upkeep_requires_a_full_shift_of_stock() {
+        let upkeep = MaintenanceUpkeep::default();
+        let mut stock = UpkeepStock::new(ResourceKind::Minerals, upkeep.capacity);
+        stock.amount = upkeep.per_shift - 1;
+        assert!(!upkeep.covers(Some(&stock)));
+        stock.amount = upkeep.per_shift;
+        assert!(upkeep.covers(Some(&stock)));
+        assert!(upkeep.covers(None), "structures without a depot are exempt");
+    }
+
+    #[test]
+    fn depot_capacity_holds_several_shifts() {
+        const { assert!(DEFAULT_UPKEEP_CAPACITY >= DEFAULT_UPKEEP_PER_SHIFT * 3) };
+    }
+}
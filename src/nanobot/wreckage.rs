@@ -0,0 +1,201 @@
+//! Salvageable wreckage left behind by destroyed structures and
+//! nanobots.
+//!
+//! ```text
+//!   structure collapses (neglect or combat) / nanobot dies
+//!     -> buffered minerals and cargo leave the owner's custody
+//!     -> a neutral Wreckage deposit holds them plus a Salvage share
+//!        of the minerals the structure or bot cost
+//!     -> a dead bot near an existing wreck adds to that pile instead
+//!     -> Workers of any swarm gather it inside their Gather paint
+//!     -> the emptied wreck despawns
+//! ```
+//!
+//! A wreck is an ordinary unowned [`ResourceDeposit`], so the gather
+//! projection, extraction, and depletion chain handle it unchanged and
+//! a fight leaves contested loot for whichever side paints over it.
+//! Without a [`Salvage`] resource destruction leaves nothing behind.
+
+use bevy::prelude::*;
+
+use crate::GAMEPLAY_SPRITE_Z;
+use crate::building::Minerals;
+use crate::nanobot::deposit_dynamics::{DepositSprite, deposit_depletion_events_system};
+use crate::nanobot::reclaim::{
+    RECLAIMED_CACHE_RADIUS, buffered_minerals, paid_material_kind, structure_build_cost,
+    structure_swarm,
+};
+use crate::nanobot::structure::Structure;
+use crate::resources::{ResourceDeposit, ResourceKind, ResourceLedger};
+
+/// Resource a wreck holds. Every structure buffer and cargo load is
+/// minerals in the first implementation.
+pub const WRECKAGE_KIND: ResourceKind = ResourceKind::Minerals;
+
+/// Worker reach radius of a wreck. Matches a reclaimed cache, the other
+/// pile a structure leaves behind.
+pub const WRECKAGE_RADIUS: f32 = RECLAIMED_CACHE_RADIUS;
+
+/// Distance within which a dead nanobot's wreckage joins an existing
+/// wreck instead of starting a pile of its own.
+pub const WRECKAGE_MERGE_RADIUS: f32 = WRECKAGE_RADIUS * 2.0;
+
+/// Tint applied to the deposit sprite so wrecks read apart from natural
+/// deposits.
+const WRECKAGE_TINT: Color = Color::srgb(0.55, 0.55, 0.6);
+
+/// Salvage rate for wreckage. While present, a collapsed structure or
+/// a dead nanobot leaves a neutral wreck; without it destruction
+/// leaves nothing behind.
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq)]
+pub struct Salvage {
+    /// Share of a destroyed structure's or nanobot's mineral cost
+    /// recovered on top of what it was holding. Below
+    /// [`crate::nanobot::RECLAIM_REFUND_PERCENT`], so an orderly
+    /// reclaim still beats letting a structure fall.
+    pub percent: u32,
+}
+
+impl Default for Salvage {
+    fn default() -> Self {
+        Self { percent: 25 }
+    }
+}
+
+impl Salvage {
+    /// Minerals a wreck holds: everything `buffered` plus the salvage
+    /// share of `cost`.
+    pub fn wreckage(&self, buffered: u32, cost: u32) -> u32 {
+        buffered.saturating_add(cost.saturating_mul(self.percent) / 100)
+    }
+}
+
+/// Marks a deposit left by destruction. Wrecks never regrow and are
+/// despawned once emptied.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct Wreckage;
+
+/// Spawn a wreck holding `amount` minerals at `pos`. Nothing is spawned
+/// for an empty wreck.
+pub fn spawn_wreckage(world: &mut World, pos: Vec2, amount: u32) -> Option<Entity> {
+    if amount == 0 {
+        return None;
+    }
+    let sprite = world.get_resource::<DepositSprite>().map(|sprite| Sprite {
+        color: WRECKAGE_TINT,
+        ..Sprite::from_image(sprite.0.clone())
+    });
+    let mut entity = world.spawn((
+        Wreckage,
+        Minerals {},
+        ResourceDeposit {
+            kind: WRECKAGE_KIND,
+            amount,
+            capacity: amount,
+            radius: WRECKAGE_RADIUS,
+        },
+        Transform::from_translation(pos.extend(GAMEPLAY_SPRITE_Z)),
+    ));
+    if let Some(sprite) = sprite {
+        entity.insert(sprite);
+    }
+    Some(entity.id())
+}
+
+/// Add `amount` minerals to the nearest wreck within
+/// [`WRECKAGE_MERGE_RADIUS`] of `pos`, or spawn a new wreck when none is
+/// close. A fight then leaves a few piles rather than one per fallen
+/// bot.
+pub fn merge_wreckage(world: &mut World, pos: Vec2, amount: u32) -> Option<Entity> {
+    if amount == 0 {
+        return None;
+    }
+    let nearest = world
+        .query_filtered::<(Entity, &Transform), (With<Wreckage>, With<ResourceDeposit>)>()
+        .iter(world)
+        .map(|(entity, transform)| (entity, transform.translation.truncate().distance(pos)))
+        .filter(|&(_, distance)| distance <= WRECKAGE_MERGE_RADIUS)
+        .min_by(|left, right| left.1.total_cmp(&right.1).then(left.0.cmp(&right.0)))
+        .map(|(entity, _)| entity);
+    if let Some(wreck) = nearest
+        && let Some(mut deposit) = world.get_mut::<ResourceDeposit>(wreck)
+    {
+        deposit.amount = deposit.amount.saturating_add(amount);
+        deposit.capacity = deposit.capacity.max(deposit.amount);
+        return Some(wreck);
+    }
+    spawn_wreckage(world, pos, amount)
+}
+
+/// Despawn a structure whose health reached zero. Queued by the
+/// degradation and combat systems. With [`Salvage`] present its
+/// buffered minerals leave the owner's ledger and land in a wreck
+/// together with the salvage share of what the structure cost.
+pub fn collapse_structure(mut entity: EntityWorldMut) {
+    let salvage = entity.world().get_resource::<Salvage>().copied();
+    if let Some(salvage) = salvage
+        && let Some(structure) = entity.get::<Structure>().copied()
+    {
+        let buffered = buffered_minerals(entity.as_readonly());
+        let cost = structure_build_cost(paid_material_kind(&entity), structure.tier);
+        let amount = salvage.wreckage(buffered, cost);
+        let id = entity.id();
+        let pos = entity
+            .get::<Transform>()
+            .map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+        entity.world_scope(|world| {
+            let swarm = structure_swarm(world, id);
+            if let Some(mut ledger) = world.get_resource_mut::<ResourceLedger>() {
+                ledger.remove_for(swarm, WRECKAGE_KIND, buffered);
+            }
+            spawn_wreckage(world, pos, amount);
+        });
+    }
+    entity.despawn();
+}
+
+/// Despawn wrecks that Workers have emptied. Runs after the depletion
+/// messages so the gather chain has released Workers walking to them.
+pub fn wreckage_cleanup_system(
+    mut commands: Commands,
+    wrecks: Query<(Entity, &ResourceDeposit), With<Wreckage>>,
+) {
+    for (entity, deposit) in &wrecks {
+        if !deposit.has_work() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Plugin that clears emptied wrecks. Spawning happens wherever a
+/// structure or nanobot is destroyed.
+pub struct WreckagePlugin;
+
+impl Plugin for WreckagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            wreckage_cleanup_system
+                .after(crate::nanobot::NanobotSimulationSet::Movement)
+                .after(deposit_depletion_events_system),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Salvage math. Destruction leaving gatherable wrecks lives in
+    //! `tests/behavior/wreckage.rs`.
+
+    use super::*;
+
+    #[test]
+    fn wreckage_keeps_the_buffer_and_salvages_a_share_of_the_cost() {
+        let salvage = Salvage::default();
+        assert_eq!(salvage.wreckage(0, 0), 0);
+        assert_eq!(salvage.wreckage(12, 0), 12);
+        assert_eq!(salvage.wreckage(0, 20), 5);
+        assert_eq!(salvage.wreckage(7, 20), 12);
+        assert_eq!(Salvage { percent: 0 }.wreckage(7, 20), 7);
+    }
+}
@@ -51,9 +51,11 @@ pub struct ResourceDeposit {
     pub kind: ResourceKind,
     /// How much of `kind` is currently sitting in this deposit.
     pub amount: u32,
-    /// Maximum amount this deposit can hold. The simulation does
-    /// not auto-respawn deposits in the first implementation; the
-    /// `amount` is allowed to go to zero and stay there.
+    /// Maximum amount this deposit can hold. A plain deposit does
+    /// not respawn: `amount` is allowed to go to zero and stay
+    /// there. Deposits carrying a
+    /// [`crate::nanobot::DepositRegeneration`] regrow toward this
+    /// ceiling.
     pub capacity: u32,
     /// Worker reach radius in world units. A worker within
     /// `radius` of the deposit's `Transform` may extract.
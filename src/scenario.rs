@@ -10,13 +10,13 @@ use bevy::{math::vec3, prelude::*};
 
 use crate::{
     GAMEPLAY_SPRITE_Z,
-    ai::{AiStateComponent, get_world_from_zone},
     building::{Minerals, ProcessingFacility},
     intent::{IntentGrid, IntentKind},
     nanobot::{
-        Commitment, Health, Nanobot, NanobotBundle, NanobotSprites, NanobotType, OpponentSwarm,
-        OwnerSwarm, ProductionFacility, ProductionPriority, Swarm, SwarmBundle, SwarmId,
-        SwarmMember, SwarmProduction, VelocityComponent,
+        Commitment, DepositRegeneration, Health, Nanobot, NanobotBundle, NanobotSprites,
+        NanobotType, OpponentSwarm, OwnerSwarm, ProductionFacility, ProductionPriority,
+        RegenerationCurve, Swarm, SwarmBundle, SwarmId, SwarmMember, SwarmProduction,
+        VelocityComponent, get_world_from_zone,
     },
     resources::{ResourceDeposit, ResourceKind},
 };
@@ -99,6 +99,7 @@ pub fn spawn_default_player_scenario(
     commands: &mut Commands<'_, '_>,
     asset_server: &Res<'_, AssetServer>,
     grid: &mut IntentGrid,
+    deposit_regeneration: Option<RegenerationCurve>,
 ) {
     paint_default_player_intent(grid);
 
@@ -141,7 +142,13 @@ pub fn spawn_default_player_scenario(
         ],
     );
 
-    spawn_deposit(commands, swarm, deposit_pos, &deposit_texture);
+    spawn_deposit(
+        commands,
+        swarm,
+        deposit_pos,
+        &deposit_texture,
+        deposit_regeneration,
+    );
     spawn_production_facility(commands, swarm, facility_pos, &facility_texture);
 }
 
@@ -150,6 +157,7 @@ pub fn spawn_default_opponent_scenario(
     asset_server: &Res<'_, AssetServer>,
     grid: &mut IntentGrid,
     mut id_alloc: ResMut<crate::nanobot::OpponentSwarmIdAlloc>,
+    deposit_regeneration: Option<RegenerationCurve>,
 ) {
     // The opponent id is allocated from the world's
     // `OpponentSwarmIdAlloc` resource so the swarm entity, the
@@ -197,7 +205,13 @@ pub fn spawn_default_opponent_scenario(
         ],
     );
 
-    spawn_deposit(commands, opponent, deposit_pos, &deposit_texture);
+    spawn_deposit(
+        commands,
+        opponent,
+        deposit_pos,
+        &deposit_texture,
+        deposit_regeneration,
+    );
     spawn_production_facility(commands, opponent, facility_pos, &facility_texture);
 }
 
@@ -223,7 +237,6 @@ fn spawn_seed_nanobots(
                     nanobot: Nanobot {},
                     nanobot_type: *kind,
                     velocity: VelocityComponent::default(),
-                    ai_state: AiStateComponent::new(),
                     health: Health::default(),
                     swarm_member: SwarmMember::new(swarm_id),
                 },
@@ -240,8 +253,9 @@ fn spawn_deposit(
     owner: Entity,
     world_pos: Vec2,
     texture: &Handle<Image>,
+    regeneration: Option<RegenerationCurve>,
 ) {
-    commands.spawn((
+    let mut deposit = commands.spawn((
         Minerals {},
         ResourceDeposit {
             kind: ResourceKind::Minerals,
@@ -256,6 +270,9 @@ fn spawn_deposit(
                 .with_scale(vec3(2., 2., 1.)),
         ),
     ));
+    if let Some(curve) = regeneration {
+        deposit.insert(DepositRegeneration::new(curve));
+    }
 }
 
 fn spawn_production_facility(
@@ -3,19 +3,25 @@
 //! Structures show physical buffer amounts plus reservation state. Cargo bars
 //! appear above Workers and Haulers only while cargo exists or a source transfer
 //! is active. Completed support structures also show maintenance reserve and
-//! health, while active maintenance Workers show shift progress. Every segment
-//! reads live ECS state each update.
+//! health, plus the upkeep depot's mineral stock when material upkeep is
+//! enabled, while active maintenance Workers show shift progress. Chargers
+//! add a queue bar that fills as defenders wait for a slot. Planned
+//! structures with a construction cost show delivered against required
+//! material under their build progress. Every segment reads live ECS state
+//! each update.
 
 use bevy::{ecs::query::QueryFilter, prelude::*};
 
 use crate::GAMEPLAY_SPRITE_Z;
 use crate::fly_camera::CameraZoom2d;
 use crate::nanobot::{
-    BOT_RADIUS, Cargo, Charger, DEFAULT_PLANNED_WORK_TICKS, ExtractProgress, HAULER_CARRY_CAPACITY,
-    HaulerLoading, LogisticsReservation, MAINTENANCE_BUFFER_TICKS, MAINTENANCE_NEEDS_THRESHOLD,
-    MAINTENANCE_WORK_DURATION_TICKS, MaintenanceProgress, Nanobot, NanobotType,
-    PLANNED_STRUCTURE_FOOTPRINT, PlannedStructure, ProductionFacility, STRUCTURE_MAX_HEALTH,
-    SUPPORT_OPERATIONAL_HEALTH_THRESHOLD, Structure, WORKER_CARRY_CAPACITY,
+    BOT_RADIUS, Cargo, Charger, ChargerQueue, DEFAULT_UPKEEP_PER_SHIFT, ExtractProgress,
+    HAULER_CARRY_CAPACITY, HaulerLoading, LogisticsReservation, MAINTENANCE_BUFFER_TICKS,
+    MAINTENANCE_NEEDS_THRESHOLD, MAINTENANCE_WORK_DURATION_TICKS, MAX_DEFENDERS_PER_CHARGER,
+    MaintenanceProgress, Nanobot, NanobotType, PLANNED_STRUCTURE_FOOTPRINT, PlannedMaterial,
+    PlannedReclaim, PlannedStructure, PlannedUpgrade, ProductionFacility, STRUCTURE_MAX_HEALTH,
+    SUPPORT_OPERATIONAL_HEALTH_THRESHOLD, Structure, UpkeepDepot, UpkeepStock,
+    WORKER_CARRY_CAPACITY,
 };
 use crate::resources::{ResourceDeposit, Stockpile};
 
@@ -105,7 +111,8 @@ pub struct StructureOverlayBackground;
 #[derive(Debug, Component, Clone, Copy)]
 pub struct StructureOverlayFill;
 
-/// Maintenance, health, or active Worker progress bar.
+/// Maintenance, health, upkeep stock, charger queue, construction
+/// material, or active Worker progress bar.
 #[derive(Debug, Component, Clone, Copy)]
 pub struct ConditionOverlay {
     pub target: Entity,
@@ -117,6 +124,9 @@ pub struct ConditionOverlay {
 pub enum ConditionOverlayKind {
     Maintenance,
     Health,
+    Upkeep,
+    ChargerQueue,
+    Material,
     WorkerProgress,
 }
 
@@ -172,10 +182,8 @@ pub fn fill_fraction(amount: u32, capacity: u32) -> f32 {
 
 /// Planned-structure build progress as a `0.0..=1.0` fraction.
 pub fn planned_fill_fraction(planned: &PlannedStructure) -> f32 {
-    fill_fraction(
-        DEFAULT_PLANNED_WORK_TICKS.saturating_sub(planned.work_remaining),
-        DEFAULT_PLANNED_WORK_TICKS,
-    )
+    let total = planned.kind.build_work_ticks();
+    fill_fraction(total.saturating_sub(planned.work_remaining), total)
 }
 
 pub fn maintenance_fill_fraction(ticks_since_maintained: u32) -> f32 {
@@ -202,6 +210,15 @@ pub fn condition_fill_color(kind: ConditionOverlayKind, value: u32) -> Color {
             Color::srgb(1.0, 0.68, 0.20)
         }
         ConditionOverlayKind::Health => Color::srgb(0.25, 0.85, 0.35),
+        ConditionOverlayKind::Upkeep if value < DEFAULT_UPKEEP_PER_SHIFT => {
+            Color::srgb(1.0, 0.22, 0.18)
+        }
+        ConditionOverlayKind::Upkeep => Color::srgb(0.25, 0.55, 1.0),
+        ConditionOverlayKind::ChargerQueue if value >= MAX_DEFENDERS_PER_CHARGER => {
+            Color::srgb(1.0, 0.22, 0.18)
+        }
+        ConditionOverlayKind::ChargerQueue => Color::srgb(1.0, 0.68, 0.20),
+        ConditionOverlayKind::Material => Color::srgb(1.0, 0.68, 0.20),
         ConditionOverlayKind::WorkerProgress => Color::WHITE,
     }
 }
@@ -298,7 +315,14 @@ pub fn structure_overlay_spawn_system(
     deposits: Query<Entity, With<ResourceDeposit>>,
     stockpiles: Query<Entity, With<Stockpile>>,
     facilities: Query<Entity, With<ProductionFacility>>,
-    planned: Query<Entity, With<PlannedStructure>>,
+    planned: Query<
+        Entity,
+        (
+            With<PlannedStructure>,
+            Without<PlannedUpgrade>,
+            Without<PlannedReclaim>,
+        ),
+    >,
     chargers: Query<Entity, With<Charger>>,
     cargo_bots: Query<
         (
@@ -384,7 +408,14 @@ fn actual_overlay_kind(
     deposits: &Query<Entity, With<ResourceDeposit>>,
     stockpiles: &Query<Entity, With<Stockpile>>,
     facilities: &Query<Entity, With<ProductionFacility>>,
-    planned: &Query<Entity, With<PlannedStructure>>,
+    planned: &Query<
+        Entity,
+        (
+            With<PlannedStructure>,
+            Without<PlannedUpgrade>,
+            Without<PlannedReclaim>,
+        ),
+    >,
     chargers: &Query<Entity, With<Charger>>,
     cargo_bots: &Query<
         (
@@ -488,12 +519,14 @@ fn spawn_overlay_for(commands: &mut Commands, target: Entity, kind: StructureOve
 pub fn condition_overlay_spawn_system(
     mut commands: Commands,
     support_targets: Query<
-        Entity,
+        (Entity, Has<UpkeepDepot>),
         (
             With<Structure>,
             Or<(With<Stockpile>, With<ProductionFacility>, With<Charger>)>,
         ),
     >,
+    chargers: Query<Entity, (With<Structure>, With<ChargerQueue>)>,
+    planned_sites: Query<Entity, With<PlannedMaterial>>,
     maintenance_workers: Query<
         Entity,
         (
@@ -508,16 +541,32 @@ pub fn condition_overlay_spawn_system(
         .iter()
         .map(|overlay| (overlay.target, overlay.kind))
         .collect();
-    for target in &support_targets {
+    for (target, has_depot) in &support_targets {
         for kind in [
             ConditionOverlayKind::Maintenance,
             ConditionOverlayKind::Health,
+            ConditionOverlayKind::Upkeep,
         ] {
+            if kind == ConditionOverlayKind::Upkeep && !has_depot {
+                continue;
+            }
             if !covered.contains(&(target, kind)) {
                 spawn_condition_overlay(&mut commands, target, kind);
             }
         }
     }
+    for target in &chargers {
+        let kind = ConditionOverlayKind::ChargerQueue;
+        if !covered.contains(&(target, kind)) {
+            spawn_condition_overlay(&mut commands, target, kind);
+        }
+    }
+    for target in &planned_sites {
+        let kind = ConditionOverlayKind::Material;
+        if !covered.contains(&(target, kind)) {
+            spawn_condition_overlay(&mut commands, target, kind);
+        }
+    }
     for target in &maintenance_workers {
         let kind = ConditionOverlayKind::WorkerProgress;
         if !covered.contains(&(target, kind)) {
@@ -558,7 +607,11 @@ fn spawn_condition_overlay(commands: &mut Commands, target: Entity, kind: Condit
 
 fn condition_bar_size(kind: ConditionOverlayKind) -> Vec2 {
     match kind {
-        ConditionOverlayKind::Maintenance | ConditionOverlayKind::Health => CONDITION_BAR_SIZE,
+        ConditionOverlayKind::Maintenance
+        | ConditionOverlayKind::Health
+        | ConditionOverlayKind::Upkeep
+        | ConditionOverlayKind::ChargerQueue
+        | ConditionOverlayKind::Material => CONDITION_BAR_SIZE,
         ConditionOverlayKind::WorkerProgress => CARGO_BAR_SIZE,
     }
 }
@@ -567,9 +620,15 @@ fn condition_overlay_offset_y(kind: ConditionOverlayKind) -> f32 {
     let resource_y = PLANNED_STRUCTURE_FOOTPRINT / 2.0 + STRUCTURE_FOOTPRINT_LABEL_GAP;
     let maintenance_y =
         resource_y + STRUCTURE_BAR_SIZE.y / 2.0 + CONDITION_BAR_GAP + CONDITION_BAR_SIZE.y / 2.0;
+    let health_y = maintenance_y + CONDITION_BAR_SIZE.y + CONDITION_BAR_GAP;
+    let upkeep_y = health_y + CONDITION_BAR_SIZE.y + CONDITION_BAR_GAP;
     match kind {
-        ConditionOverlayKind::Maintenance => maintenance_y,
-        ConditionOverlayKind::Health => maintenance_y + CONDITION_BAR_SIZE.y + CONDITION_BAR_GAP,
+        // Planned sites have no maintenance bar, so material takes
+        // the first slot under build progress.
+        ConditionOverlayKind::Maintenance | ConditionOverlayKind::Material => maintenance_y,
+        ConditionOverlayKind::Health => health_y,
+        ConditionOverlayKind::Upkeep => upkeep_y,
+        ConditionOverlayKind::ChargerQueue => upkeep_y + CONDITION_BAR_SIZE.y + CONDITION_BAR_GAP,
         ConditionOverlayKind::WorkerProgress => BOT_RADIUS + HAULER_OVERLAY_GAP,
     }
 }
@@ -582,6 +641,10 @@ pub fn condition_overlay_update_system(
         (With<ConditionOverlayFill>, Without<ConditionOverlay>),
     >,
     conditions: Query<&Structure, Without<ConditionOverlay>>,
+    upkeep_depots: Query<&UpkeepDepot, Without<ConditionOverlay>>,
+    upkeep_stocks: Query<&UpkeepStock, Without<ConditionOverlay>>,
+    charger_queues: Query<&ChargerQueue, Without<ConditionOverlay>>,
+    planned_materials: Query<&PlannedMaterial, Without<ConditionOverlay>>,
     maintenance_workers: Query<&MaintenanceProgress, Without<ConditionOverlay>>,
     target_transforms: Query<
         &Transform,
@@ -600,14 +663,46 @@ pub fn condition_overlay_update_system(
                 .get(overlay.target)
                 .map(|condition| {
                     (
-                        maintenance_fill_fraction(condition.ticks_since_maintained),
+                        1.0 - fill_fraction(
+                            condition.ticks_since_maintained,
+                            condition.maintenance_buffer_ticks(),
+                        ),
                         condition.ticks_since_maintained,
                     )
                 })
                 .unwrap_or_default(),
             ConditionOverlayKind::Health => conditions
                 .get(overlay.target)
-                .map(|condition| (health_fill_fraction(condition.health), condition.health))
+                .map(|condition| {
+                    (
+                        fill_fraction(condition.health, condition.max_health()),
+                        condition.health,
+                    )
+                })
+                .unwrap_or_default(),
+            ConditionOverlayKind::Upkeep => upkeep_depots
+                .get(overlay.target)
+                .ok()
+                .and_then(|depot| upkeep_stocks.get(depot.0).ok())
+                .map(|stock| (fill_fraction(stock.amount, stock.capacity), stock.amount))
+                .unwrap_or_default(),
+            ConditionOverlayKind::ChargerQueue => charger_queues
+                .get(overlay.target)
+                .map(|queue| {
+                    (
+                        fill_fraction(queue.waiting, MAX_DEFENDERS_PER_CHARGER),
+                        queue.waiting,
+                    )
+                })
+                .unwrap_or_default(),
+            ConditionOverlayKind::Material => planned_materials
+                .get(overlay.target)
+                .map(|material| {
+                    (
+                        fill_fraction(material.delivered, material.required),
+                        material.delivered,
+                    )
+                })
                 .unwrap_or_default(),
             ConditionOverlayKind::WorkerProgress => maintenance_workers
                 .get(overlay.target)
@@ -713,10 +808,8 @@ fn compute_overlay_amounts(
         StructureOverlayKind::Planned => planned
             .get(target)
             .map(|value| {
-                (
-                    DEFAULT_PLANNED_WORK_TICKS.saturating_sub(value.work_remaining),
-                    DEFAULT_PLANNED_WORK_TICKS,
-                )
+                let total = value.kind.build_work_ticks();
+                (total.saturating_sub(value.work_remaining), total)
             })
             .unwrap_or_default(),
         StructureOverlayKind::Charger => chargers
@@ -872,12 +965,21 @@ pub fn condition_overlay_cleanup_system(
         ),
     >,
     maintenance_workers: Query<(), (With<Nanobot>, With<MaintenanceProgress>)>,
+    upkeep_depots: Query<(), With<UpkeepDepot>>,
+    chargers: Query<(), (With<Structure>, With<ChargerQueue>)>,
+    planned_sites: Query<(), With<PlannedMaterial>>,
 ) {
     for (entity, overlay) in &overlays {
         let valid = match overlay.kind {
             ConditionOverlayKind::Maintenance | ConditionOverlayKind::Health => {
                 support_targets.get(overlay.target).is_ok()
             }
+            ConditionOverlayKind::Upkeep => {
+                support_targets.get(overlay.target).is_ok()
+                    && upkeep_depots.get(overlay.target).is_ok()
+            }
+            ConditionOverlayKind::ChargerQueue => chargers.get(overlay.target).is_ok(),
+            ConditionOverlayKind::Material => planned_sites.get(overlay.target).is_ok(),
             ConditionOverlayKind::WorkerProgress => maintenance_workers.get(overlay.target).is_ok(),
         };
         if !valid {
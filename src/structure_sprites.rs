@@ -1,6 +1,6 @@
-use bevy::prelude::{AssetServer, Component, Handle, Image, Resource, Sprite};
+use bevy::prelude::{AssetServer, Color, Component, Handle, Image, Resource, Sprite};
 
-use crate::nanobot::PlannedKind;
+use crate::nanobot::{PlannedKind, completed_visual_color};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StructureVisualState {
@@ -30,6 +30,30 @@ impl StructureVisual {
     }
 }
 
+/// Health band a completed structure's sprite shows. Cached on the
+/// entity by [`crate::nanobot::structure_damage_visual_system`].
+#[derive(Debug, Clone, Copy, Component, Default, PartialEq, Eq)]
+pub enum StructureDamageStage {
+    #[default]
+    Intact,
+    Damaged,
+    Critical,
+}
+
+impl StructureDamageStage {
+    /// Intact above three quarters of `max`, critical at or below one
+    /// quarter, damaged in between.
+    pub fn for_health(health: u32, max: u32) -> Self {
+        if health.saturating_mul(4) > max.saturating_mul(3) {
+            Self::Intact
+        } else if health.saturating_mul(4) > max {
+            Self::Damaged
+        } else {
+            Self::Critical
+        }
+    }
+}
+
 #[derive(Debug, Clone, Resource)]
 pub struct StructureSprites {
     pub planned_source_stockpile: Handle<Image>,
@@ -108,16 +132,34 @@ impl StructureSprites {
             }
             (PlannedKind::Charger, StructureVisualState::Planned) => self.planned_charger.clone(),
             (PlannedKind::Charger, StructureVisualState::Completed) => self.charger.clone(),
-            (PlannedKind::ProductionFacility, StructureVisualState::Planned) => {
-                self.planned_production_facility.clone()
-            }
-            (PlannedKind::ProductionFacility, StructureVisualState::Completed) => {
-                self.production_facility.clone()
-            }
+            // Research Labs have no art of their own yet and share the
+            // Production Facility sprites.
+            (
+                PlannedKind::ProductionFacility | PlannedKind::ResearchLab,
+                StructureVisualState::Planned,
+            ) => self.planned_production_facility.clone(),
+            (
+                PlannedKind::ProductionFacility | PlannedKind::ResearchLab,
+                StructureVisualState::Completed,
+            ) => self.production_facility.clone(),
+            // Roads have no art yet; the default image draws a plain
+            // tile under the planned and completed tints.
+            (PlannedKind::Road, _) => Handle::default(),
         }
     }
 
     pub fn sprite(&self, kind: PlannedKind, state: StructureVisualState) -> Sprite {
         Sprite::from_image(self.handle(kind, state))
     }
+
+    /// Completed-sprite tint for a damage stage. The structures have no
+    /// damaged art yet, so stages darken and redden the completed
+    /// colour instead.
+    pub fn damage_color(stage: StructureDamageStage) -> Color {
+        match stage {
+            StructureDamageStage::Intact => completed_visual_color(),
+            StructureDamageStage::Damaged => Color::srgba(0.55, 0.5, 0.2, 1.0),
+            StructureDamageStage::Critical => Color::srgba(0.6, 0.18, 0.12, 1.0),
+        }
+    }
 }
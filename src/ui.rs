@@ -1,9 +1,13 @@
 pub mod button_bg_interaction;
 pub mod consts;
+pub mod facility_panel;
 mod fps_count;
+pub mod heatmap_panel;
 pub mod intent_layer_panel;
 pub mod production_priority_panel;
+pub mod research_panel;
 mod status_panel;
+pub mod telemetry_panel;
 mod ui_interaction_system;
 mod ui_setup;
 
@@ -19,7 +23,12 @@ use crate::zones::zone_brush_system;
 
 use self::{
     button_bg_interaction::button_background_system,
+    facility_panel::{
+        FacilityPanelSelection, facility_panel_click_system, setup_facility_panel,
+        update_facility_panel,
+    },
     fps_count::fps_ui_system,
+    heatmap_panel::{heatmap_panel_click_system, setup_heatmap_panel, update_heatmap_panel},
     intent_layer_panel::{
         intent_layer_button_click_system, setup_intent_layer_panel,
         update_intent_layer_panel_highlight,
@@ -28,7 +37,12 @@ use self::{
         ProductionPriorityDragState, production_priority_drag_system,
         setup_production_priority_panel, update_production_priority_panel,
     },
+    research_panel::{research_button_click_system, setup_research_panel, update_research_panel},
     status_panel::{setup_status_panel, update_status_panel_system},
+    telemetry_panel::{
+        TelemetryPanelState, setup_telemetry_panel, telemetry_panel_click_system,
+        update_telemetry_panel,
+    },
 };
 
 #[derive(Debug, Default)]
@@ -38,6 +52,8 @@ impl Plugin for NanoswarmUiSetupPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(UiHandling::default())
             .init_resource::<ProductionPriorityDragState>()
+            .init_resource::<FacilityPanelSelection>()
+            .init_resource::<TelemetryPanelState>()
             .add_plugins(FrameTimeDiagnosticsPlugin::default())
             .add_systems(
                 Startup,
@@ -46,6 +62,10 @@ impl Plugin for NanoswarmUiSetupPlugin {
                     setup_status_panel,
                     setup_intent_layer_panel,
                     setup_production_priority_panel,
+                    setup_research_panel,
+                    setup_facility_panel,
+                    setup_telemetry_panel,
+                    setup_heatmap_panel,
                 )
                     .chain(),
             )
@@ -66,6 +86,22 @@ impl Plugin for NanoswarmUiSetupPlugin {
                     update_production_priority_panel,
                 )
                     .chain(),
+            )
+            .add_systems(
+                Update,
+                (research_button_click_system, update_research_panel).chain(),
+            )
+            .add_systems(
+                Update,
+                (facility_panel_click_system, update_facility_panel).chain(),
+            )
+            .add_systems(
+                Update,
+                (telemetry_panel_click_system, update_telemetry_panel).chain(),
+            )
+            .add_systems(
+                Update,
+                (heatmap_panel_click_system, update_heatmap_panel).chain(),
             );
     }
 }
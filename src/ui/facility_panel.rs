@@ -0,0 +1,348 @@
+//! Right-side Production Facility inspector.
+//!
+//! Steps through the player's facilities one at a time. The status text
+//! shows the current target, queue, specialization, pin, and speed level;
+//! the button rows write [`ProductionFacilityCommand`]s for the selected
+//! facility. Facilities are ordered by entity id so the selection index
+//! stays stable while nothing is built or destroyed.
+
+use bevy::prelude::*;
+use bevy::ui::{
+    AlignItems, BorderRadius, FlexDirection, JustifyContent, PositionType, RelativeCursorPosition,
+};
+
+use crate::nanobot::{
+    FacilityAction, NanobotType, OwnerSwarm, PRODUCTION_FACILITY_MAX_LEVEL, ProductionFacility,
+    ProductionFacilityCommand, Swarm, SwarmId, production_progress_percent,
+};
+
+use super::button_bg_interaction::ButtonBgInteractiveComponent;
+use super::consts::NORMAL_BUTTON;
+use super::ui_setup::FontsResource;
+
+#[derive(Debug, Component)]
+pub struct FacilityPanelRoot;
+
+#[derive(Debug, Component)]
+pub struct FacilityPanelText;
+
+/// What a facility panel button does when pressed.
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub enum FacilityPanelButton {
+    Previous,
+    Next,
+    /// Pin the current target, or release an existing pin.
+    TogglePin,
+    Command(FacilityAction),
+}
+
+/// Index into the player's facilities, ordered by entity id. Wraps when
+/// facilities disappear.
+#[derive(Debug, Default, Resource)]
+pub struct FacilityPanelSelection {
+    pub index: usize,
+}
+
+pub const PANEL_TOP: f32 = 292.0;
+pub const PANEL_RIGHT: f32 = 8.0;
+pub const PANEL_WIDTH: f32 = 240.0;
+pub const PANEL_PADDING: f32 = 10.0;
+pub const PANEL_FONT_SIZE: f32 = 14.0;
+pub const PANEL_TITLE_FONT_SIZE: f32 = 18.0;
+pub const PANEL_GAP: f32 = 5.0;
+pub const BUTTON_PADDING: f32 = 4.0;
+
+fn type_label(kind: NanobotType) -> &'static str {
+    match kind {
+        NanobotType::Worker => "Worker",
+        NanobotType::Hauler => "Hauler",
+        NanobotType::Defender => "Defender",
+    }
+}
+
+fn type_initial(kind: NanobotType) -> &'static str {
+    match kind {
+        NanobotType::Worker => "W",
+        NanobotType::Hauler => "H",
+        NanobotType::Defender => "D",
+    }
+}
+
+/// Status text for the selected facility. Pure so the wording can be
+/// tested without a Bevy `App`.
+pub fn facility_panel_text(
+    facility: Option<&ProductionFacility>,
+    index: usize,
+    count: usize,
+) -> String {
+    let Some(facility) = facility else {
+        return "No facilities".to_string();
+    };
+    let current = match facility.current_target {
+        Some(kind) => format!(
+            "{} {}%",
+            type_label(kind),
+            production_progress_percent(facility)
+        ),
+        None => "Idle".to_string(),
+    };
+    let queue = if facility.queue.is_empty() {
+        "-".to_string()
+    } else {
+        facility
+            .queue
+            .iter()
+            .map(|kind| type_initial(*kind))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let specialization = facility.specialization.map_or("Any", type_label);
+    let pinned = facility.pinned.map_or("-", type_label);
+    let upgrade = if facility.upgrade_pending {
+        " (upgrading)"
+    } else {
+        ""
+    };
+    format!(
+        "Facility {}/{count}: {current}\nQueue: {queue}\nOnly: {specialization}  Pin: {pinned}\nLevel {}/{PRODUCTION_FACILITY_MAX_LEVEL}{upgrade}",
+        index + 1,
+        facility.level,
+    )
+}
+
+fn spawn_button(
+    row: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &str,
+    action: FacilityPanelButton,
+) {
+    row.spawn((
+        Button,
+        ButtonBgInteractiveComponent,
+        action,
+        BackgroundColor(NORMAL_BUTTON),
+        Node {
+            padding: UiRect::all(Val::Px(BUTTON_PADDING)),
+            border_radius: BorderRadius::all(Val::Px(3.0)),
+            ..default()
+        },
+    ))
+    .with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont {
+                font: font.clone(),
+                font_size: PANEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+fn row_node() -> Node {
+    Node {
+        flex_direction: FlexDirection::Row,
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(PANEL_GAP),
+        ..default()
+    }
+}
+
+pub fn setup_facility_panel(mut commands: Commands, fonts: Res<FontsResource>) {
+    let font = fonts.font.clone();
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(PANEL_TOP),
+                right: Val::Px(PANEL_RIGHT),
+                width: Val::Px(PANEL_WIDTH),
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANEL_GAP),
+                align_items: AlignItems::Stretch,
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.03, 0.04, 0.05, 0.78)),
+            RelativeCursorPosition::default(),
+            FacilityPanelRoot,
+        ))
+        .with_children(|panel| {
+            panel.spawn(row_node()).with_children(|row| {
+                spawn_button(row, &font, "<", FacilityPanelButton::Previous);
+                row.spawn((
+                    Text::new("Facilities"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: PANEL_TITLE_FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
+                spawn_button(row, &font, ">", FacilityPanelButton::Next);
+            });
+            panel.spawn((
+                FacilityPanelText,
+                Text::new(facility_panel_text(None, 0, 0)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: PANEL_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            panel.spawn(row_node()).with_children(|row| {
+                spawn_button(
+                    row,
+                    &font,
+                    "Any",
+                    FacilityPanelButton::Command(FacilityAction::Specialize(None)),
+                );
+                for kind in NanobotType::ALL {
+                    spawn_button(
+                        row,
+                        &font,
+                        type_initial(kind),
+                        FacilityPanelButton::Command(FacilityAction::Specialize(Some(kind))),
+                    );
+                }
+            });
+            panel.spawn(row_node()).with_children(|row| {
+                for kind in NanobotType::ALL {
+                    spawn_button(
+                        row,
+                        &font,
+                        &format!("+{}", type_initial(kind)),
+                        FacilityPanelButton::Command(FacilityAction::Enqueue(kind)),
+                    );
+                }
+                spawn_button(
+                    row,
+                    &font,
+                    "Clear",
+                    FacilityPanelButton::Command(FacilityAction::ClearQueue),
+                );
+            });
+            panel.spawn(row_node()).with_children(|row| {
+                spawn_button(row, &font, "Pin", FacilityPanelButton::TogglePin);
+                spawn_button(
+                    row,
+                    &font,
+                    "Cancel",
+                    FacilityPanelButton::Command(FacilityAction::CancelCurrent),
+                );
+                spawn_button(
+                    row,
+                    &font,
+                    "Upgrade",
+                    FacilityPanelButton::Command(FacilityAction::Upgrade),
+                );
+            });
+        });
+}
+
+/// Player facilities in stable entity order. Unowned facilities count as
+/// the player's, matching the production fallback.
+fn player_facilities<'a>(
+    facilities: impl Iterator<Item = (Entity, &'a ProductionFacility, Option<&'a OwnerSwarm>)>,
+    swarms: &Query<&SwarmId, With<Swarm>>,
+) -> Vec<(Entity, &'a ProductionFacility)> {
+    let mut owned: Vec<_> = facilities
+        .filter(|(_, _, owner)| {
+            owner
+                .and_then(|OwnerSwarm(owner)| swarms.get(*owner).ok().copied())
+                .unwrap_or(SwarmId::PLAYER)
+                == SwarmId::PLAYER
+        })
+        .map(|(entity, facility, _)| (entity, facility))
+        .collect();
+    owned.sort_by_key(|(entity, _)| entity.to_bits());
+    owned
+}
+
+#[allow(clippy::type_complexity)]
+pub fn facility_panel_click_system(
+    mut selection: ResMut<FacilityPanelSelection>,
+    buttons: Query<(&Interaction, &FacilityPanelButton), (Changed<Interaction>, With<Button>)>,
+    facilities: Query<(Entity, &ProductionFacility, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut commands: MessageWriter<ProductionFacilityCommand>,
+) {
+    let owned = player_facilities(facilities.iter(), &swarms);
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed || owned.is_empty() {
+            continue;
+        }
+        let index = selection.index % owned.len();
+        let (entity, facility) = owned[index];
+        let action = match *button {
+            FacilityPanelButton::Previous => {
+                selection.index = (index + owned.len() - 1) % owned.len();
+                continue;
+            }
+            FacilityPanelButton::Next => {
+                selection.index = (index + 1) % owned.len();
+                continue;
+            }
+            FacilityPanelButton::TogglePin if facility.pinned.is_some() => FacilityAction::Unpin,
+            FacilityPanelButton::TogglePin => FacilityAction::PinCurrent,
+            FacilityPanelButton::Command(action) => action,
+        };
+        commands.write(ProductionFacilityCommand {
+            facility: entity,
+            action,
+        });
+    }
+}
+
+pub fn update_facility_panel(
+    selection: Res<FacilityPanelSelection>,
+    facilities: Query<(Entity, &ProductionFacility, Option<&OwnerSwarm>)>,
+    swarms: Query<&SwarmId, With<Swarm>>,
+    mut text: Query<&mut Text, With<FacilityPanelText>>,
+) {
+    let Ok(mut text) = text.single_mut() else {
+        return;
+    };
+    let owned = player_facilities(facilities.iter(), &swarms);
+    let label = if owned.is_empty() {
+        facility_panel_text(None, 0, 0)
+    } else {
+        let index = selection.index % owned.len();
+        facility_panel_text(Some(owned[index].1), index, owned.len())
+    };
+    if text.0 != label {
+        text.0 = label;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_reports_missing_facility() {
+        assert_eq!(facility_panel_text(None, 0, 0), "No facilities");
+    }
+
+    #[test]
+    fn text_lists_target_queue_and_settings() {
+        let mut facility = ProductionFacility::new();
+        facility.current_target = Some(NanobotType::Hauler);
+        facility.progress = facility.ticks_per_bot() / 4;
+        facility.enqueue(NanobotType::Worker);
+        facility.enqueue(NanobotType::Defender);
+        facility.pinned = Some(NanobotType::Hauler);
+        facility.level = 1;
+        facility.upgrade_pending = true;
+
+        assert_eq!(
+            facility_panel_text(Some(&facility), 1, 3),
+            "Facility 2/3: Hauler 25%\nQueue: W D\nOnly: Any  Pin: Hauler\nLevel 1/2 (upgrading)"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Bottom-right heatmap layer picker and legend.
+//!
+//! The `<` / `>` buttons cycle [`HeatmapOverlaySettings::layer`]
+//! through off and every [`HeatmapLayer`]. While a layer is shown the
+//! legend draws the colour ramp from zero to the field's current
+//! maximum.
+
+use bevy::prelude::*;
+use bevy::ui::{
+    AlignItems, BorderRadius, FlexDirection, JustifyContent, PositionType, RelativeCursorPosition,
+};
+
+use crate::heatmap_overlay::{HeatmapField, HeatmapLayer, HeatmapOverlaySettings, heatmap_color};
+
+use super::button_bg_interaction::ButtonBgInteractiveComponent;
+use super::consts::NORMAL_BUTTON;
+use super::ui_setup::FontsResource;
+
+#[derive(Debug, Component)]
+pub struct HeatmapPanelRoot;
+
+#[derive(Debug, Component)]
+pub struct HeatmapPanelText;
+
+/// Legend row: ramp swatches plus the maximum label.
+#[derive(Debug, Component)]
+pub struct HeatmapLegend;
+
+#[derive(Debug, Component)]
+pub struct HeatmapLegendMaxText;
+
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapPanelButton {
+    Previous,
+    Next,
+}
+
+pub const PANEL_BOTTOM: f32 = 8.0;
+pub const PANEL_RIGHT: f32 = 8.0;
+pub const PANEL_WIDTH: f32 = 240.0;
+pub const PANEL_PADDING: f32 = 8.0;
+pub const PANEL_FONT_SIZE: f32 = 14.0;
+pub const PANEL_GAP: f32 = 5.0;
+pub const BUTTON_PADDING: f32 = 4.0;
+pub const LEGEND_HEIGHT: f32 = 10.0;
+/// Swatches in the legend ramp, coldest first.
+pub const LEGEND_STEPS: usize = 10;
+
+pub fn heatmap_panel_text(layer: Option<HeatmapLayer>) -> String {
+    match layer {
+        Some(layer) => format!("Heatmap: {}", layer.label()),
+        None => "Heatmap: off".to_string(),
+    }
+}
+
+/// Legend maximum, e.g. `12 bots`. Fractional fields keep one decimal.
+pub fn heatmap_legend_max_text(layer: HeatmapLayer, max: f32) -> String {
+    if max.fract() == 0.0 {
+        format!("{max:.0} {}", layer.unit())
+    } else {
+        format!("{max:.1} {}", layer.unit())
+    }
+}
+
+/// Opaque swatch colour for legend step `index`.
+pub fn legend_swatch_color(index: usize) -> Color {
+    let t = (index + 1) as f32 / LEGEND_STEPS as f32;
+    heatmap_color(t).with_alpha(1.0)
+}
+
+fn spawn_button(
+    row: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &str,
+    action: HeatmapPanelButton,
+) {
+    row.spawn((
+        Button,
+        ButtonBgInteractiveComponent,
+        action,
+        BackgroundColor(NORMAL_BUTTON),
+        Node {
+            padding: UiRect::all(Val::Px(BUTTON_PADDING)),
+            border_radius: BorderRadius::all(Val::Px(3.0)),
+            ..default()
+        },
+    ))
+    .with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont {
+                font: font.clone(),
+                font_size: PANEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+pub fn setup_heatmap_panel(mut commands: Commands, fonts: Res<FontsResource>) {
+    let font = fonts.font.clone();
+    let text_font = TextFont {
+        font: font.clone(),
+        font_size: PANEL_FONT_SIZE,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(PANEL_BOTTOM),
+                right: Val::Px(PANEL_RIGHT),
+                width: Val::Px(PANEL_WIDTH),
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANEL_GAP),
+                align_items: AlignItems::Stretch,
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.03, 0.04, 0.05, 0.78)),
+            RelativeCursorPosition::default(),
+            HeatmapPanelRoot,
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(PANEL_GAP),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_button(row, &font, "<", HeatmapPanelButton::Previous);
+                    row.spawn((
+                        HeatmapPanelText,
+                        Text::new(heatmap_panel_text(None)),
+                        text_font.clone(),
+                        TextColor(Color::WHITE),
+                    ));
+                    spawn_button(row, &font, ">", HeatmapPanelButton::Next);
+                });
+            panel
+                .spawn((
+                    HeatmapLegend,
+                    Node {
+                        display: Display::None,
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(PANEL_GAP),
+                        ..default()
+                    },
+                ))
+                .with_children(|legend| {
+                    legend.spawn((Text::new("0"), text_font.clone(), TextColor(Color::WHITE)));
+                    legend
+                        .spawn(Node {
+                            flex_grow: 1.0,
+                            height: Val::Px(LEGEND_HEIGHT),
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        })
+                        .with_children(|ramp| {
+                            for index in 0..LEGEND_STEPS {
+                                ramp.spawn((
+                                    Node {
+                                        flex_grow: 1.0,
+                                        ..default()
+                                    },
+                                    BackgroundColor(legend_swatch_color(index)),
+                                ));
+                            }
+                        });
+                    legend.spawn((
+                        HeatmapLegendMaxText,
+                        Text::new(""),
+                        text_font.clone(),
+                        TextColor(Color::WHITE),
+                    ));
+                });
+        });
+}
+
+pub fn heatmap_panel_click_system(
+    settings: Option<ResMut<HeatmapOverlaySettings>>,
+    buttons: Query<(&Interaction, &HeatmapPanelButton), (Changed<Interaction>, With<Button>)>,
+) {
+    let Some(mut settings) = settings else {
+        return;
+    };
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let offset = match button {
+            HeatmapPanelButton::Previous => -1,
+            HeatmapPanelButton::Next => 1,
+        };
+        settings.layer = HeatmapLayer::step(settings.layer, offset);
+    }
+}
+
+pub fn update_heatmap_panel(
+    field: Option<Res<HeatmapField>>,
+    mut text: Query<&mut Text, (With<HeatmapPanelText>, Without<HeatmapLegendMaxText>)>,
+    mut legend: Query<&mut Node, With<HeatmapLegend>>,
+    mut max_text: Query<&mut Text, (With<HeatmapLegendMaxText>, Without<HeatmapPanelText>)>,
+) {
+    let Some(field) = field else {
+        return;
+    };
+    if !field.is_changed() {
+        return;
+    }
+    let layer = field.layer();
+    if let Ok(mut text) = text.single_mut() {
+        *text = Text::new(heatmap_panel_text(layer));
+    }
+    if let Ok(mut node) = legend.single_mut() {
+        node.display = if layer.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+    if let (Some(layer), Ok(mut text)) = (layer, max_text.single_mut()) {
+        *text = Text::new(heatmap_legend_max_text(layer, field.max()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panel_text_names_the_layer() {
+        assert_eq!(heatmap_panel_text(None), "Heatmap: off");
+        assert_eq!(
+            heatmap_panel_text(Some(HeatmapLayer::Starvation)),
+            "Heatmap: Starvation"
+        );
+    }
+
+    #[test]
+    fn legend_max_keeps_fractions_only_when_present() {
+        assert_eq!(
+            heatmap_legend_max_text(HeatmapLayer::Crowding, 12.0),
+            "12 bots"
+        );
+        assert_eq!(heatmap_legend_max_text(HeatmapLayer::Threat, 1.5), "1.5 x");
+    }
+
+    #[test]
+    fn legend_ramp_ends_on_the_hottest_colour() {
+        assert_eq!(
+            legend_swatch_color(LEGEND_STEPS - 1),
+            heatmap_color(1.0).with_alpha(1.0)
+        );
+        assert_ne!(
+            legend_swatch_color(0),
+            legend_swatch_color(LEGEND_STEPS - 1)
+        );
+    }
+}
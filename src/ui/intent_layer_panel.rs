@@ -50,6 +50,7 @@ const LAYER_COLORS: [(IntentKind, Color); IntentKind::COUNT] = [
     (IntentKind::Build, Color::srgb(0.85, 0.20, 0.85)),
     (IntentKind::Defend, Color::srgb(0.20, 0.30, 0.90)),
     (IntentKind::Corridor, Color::srgb(0.85, 0.80, 0.10)),
+    (IntentKind::Reclaim, Color::srgb(0.85, 0.45, 0.10)),
 ];
 
 const ACTIVE_BORDER_THICKNESS: f32 = 3.0;
@@ -164,6 +165,7 @@ fn layer_key_label(kind: IntentKind) -> &'static str {
         IntentKind::Build => "2",
         IntentKind::Defend => "3",
         IntentKind::Corridor => "4",
+        IntentKind::Reclaim => "5",
     }
 }
 
@@ -173,6 +175,7 @@ fn kind_name(kind: IntentKind) -> &'static str {
         IntentKind::Build => "Build",
         IntentKind::Defend => "Defend",
         IntentKind::Corridor => "Corridor",
+        IntentKind::Reclaim => "Reclaim",
     }
 }
 
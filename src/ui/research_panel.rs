@@ -0,0 +1,244 @@
+//! Right-side research queue UI.
+//!
+//! One button per [`Upgrade`] sits below the production-priority panel.
+//! Clicking an available upgrade appends it to the player's research queue;
+//! clicking a queued upgrade cancels it (and anything queued that depends on
+//! it). Button labels show cost, queue position, funding, and progress.
+
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy::ui::{AlignItems, BorderRadius, FlexDirection, PositionType, RelativeCursorPosition};
+
+use crate::nanobot::{ResearchState, SwarmId, SwarmResearch, Upgrade};
+
+use super::button_bg_interaction::ButtonBgInteractiveComponent;
+use super::consts::NORMAL_BUTTON;
+use super::ui_setup::FontsResource;
+
+#[derive(Debug, Component)]
+pub struct ResearchPanelRoot;
+
+/// A queue/cancel button for one upgrade.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct ResearchButton(pub Upgrade);
+
+/// Label inside a [`ResearchButton`].
+#[derive(Debug, Component, Clone, Copy)]
+pub struct ResearchButtonText(pub Upgrade);
+
+pub const PANEL_TOP: f32 = 116.0;
+pub const PANEL_RIGHT: f32 = 8.0;
+pub const PANEL_WIDTH: f32 = 240.0;
+pub const PANEL_PADDING: f32 = 10.0;
+pub const PANEL_FONT_SIZE: f32 = 14.0;
+pub const PANEL_TITLE_FONT_SIZE: f32 = 18.0;
+pub const PANEL_GAP: f32 = 5.0;
+pub const BUTTON_PADDING: f32 = 5.0;
+
+const LOCKED_TEXT: Color = Color::srgb(0.50, 0.50, 0.50);
+const DONE_TEXT: Color = Color::srgb(0.40, 0.85, 0.45);
+
+/// Button label for `upgrade` given the player's research. Pure so the
+/// wording can be tested without a Bevy `App`.
+pub fn research_button_label(research: Option<&SwarmResearch>, upgrade: Upgrade) -> String {
+    let default = SwarmResearch::default();
+    let research = research.unwrap_or(&default);
+    let name = upgrade.label();
+    if research.is_unlocked(upgrade) {
+        return format!("{name}: done");
+    }
+    match research.queue.iter().position(|queued| *queued == upgrade) {
+        Some(0) if research.funding_needed() > 0 => {
+            format!("{name}: funding {}/{}", research.funded, upgrade.cost())
+        }
+        Some(0) => format!("{name}: {}%", research.head_progress_percent()),
+        Some(position) => format!("{name}: queued #{}", position + 1),
+        None if research.can_enqueue(upgrade) => format!("{name}: {} min", upgrade.cost()),
+        None => format!("{name}: locked"),
+    }
+}
+
+fn research_button_text_color(research: Option<&SwarmResearch>, upgrade: Upgrade) -> Color {
+    let default = SwarmResearch::default();
+    let research = research.unwrap_or(&default);
+    if research.is_unlocked(upgrade) {
+        DONE_TEXT
+    } else if research.is_queued(upgrade) || research.can_enqueue(upgrade) {
+        Color::WHITE
+    } else {
+        LOCKED_TEXT
+    }
+}
+
+/// Queue or cancel `upgrade` for the player. Returns whether the queue
+/// changed.
+pub fn toggle_research(research: &mut ResearchState, upgrade: Upgrade) -> bool {
+    let player = research.for_swarm_mut(SwarmId::PLAYER);
+    if player.is_queued(upgrade) {
+        player.cancel(upgrade)
+    } else {
+        player.enqueue(upgrade)
+    }
+}
+
+pub fn setup_research_panel(mut commands: Commands, fonts: Res<FontsResource>) {
+    let font = fonts.font.clone();
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(PANEL_TOP),
+                right: Val::Px(PANEL_RIGHT),
+                width: Val::Px(PANEL_WIDTH),
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANEL_GAP),
+                align_items: AlignItems::Stretch,
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.03, 0.04, 0.05, 0.78)),
+            RelativeCursorPosition::default(),
+            ResearchPanelRoot,
+        ))
+        .with_children(|panel| {
+            panel.spawn((
+                Text::new("Research"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: PANEL_TITLE_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+            for upgrade in Upgrade::ALL {
+                panel
+                    .spawn((
+                        Button,
+                        ButtonBgInteractiveComponent,
+                        ResearchButton(upgrade),
+                        BackgroundColor(NORMAL_BUTTON),
+                        Node {
+                            padding: UiRect::all(Val::Px(BUTTON_PADDING)),
+                            border_radius: BorderRadius::all(Val::Px(3.0)),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            ResearchButtonText(upgrade),
+                            Text::new(research_button_label(None, upgrade)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: PANEL_FONT_SIZE,
+                                ..default()
+                            },
+                            TextColor(research_button_text_color(None, upgrade)),
+                        ));
+                    });
+            }
+        });
+}
+
+/// Toggle the pressed upgrade in the player's queue. Only buttons under
+/// the panel root count, matching the intent-layer panel.
+#[allow(clippy::type_complexity)]
+pub fn research_button_click_system(
+    research: Option<ResMut<ResearchState>>,
+    panel_root: Query<Entity, With<ResearchPanelRoot>>,
+    buttons: Query<(Entity, &Interaction, &ResearchButton), (Changed<Interaction>, With<Button>)>,
+    children_query: Query<&Children>,
+) {
+    let Some(mut research) = research else {
+        return;
+    };
+    let Ok(panel_root) = panel_root.single() else {
+        return;
+    };
+    let panel_children: HashSet<Entity> = children_query.iter_descendants(panel_root).collect();
+    for (entity, interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed || !panel_children.contains(&entity) {
+            continue;
+        }
+        toggle_research(&mut research, button.0);
+    }
+}
+
+pub fn update_research_panel(
+    research: Option<Res<ResearchState>>,
+    mut labels: Query<(&ResearchButtonText, &mut Text, &mut TextColor)>,
+) {
+    let Some(research) = research else {
+        return;
+    };
+    if !research.is_changed() {
+        return;
+    }
+    let player = research.for_swarm(SwarmId::PLAYER);
+    for (label, mut text, mut color) in &mut labels {
+        *text = Text::new(research_button_label(player, label.0));
+        *color = TextColor(research_button_text_color(player, label.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_follow_the_research_lifecycle() {
+        let mut research = SwarmResearch::default();
+        assert_eq!(
+            research_button_label(Some(&research), Upgrade::ExtractRate),
+            "Extraction: 40 min"
+        );
+        assert_eq!(
+            research_button_label(Some(&research), Upgrade::DefenderAttack),
+            "Defender Attack: locked"
+        );
+
+        research.enqueue(Upgrade::ExtractRate);
+        research.enqueue(Upgrade::DefenderAttack);
+        research.funded = 10;
+        assert_eq!(
+            research_button_label(Some(&research), Upgrade::ExtractRate),
+            "Extraction: funding 10/40"
+        );
+        assert_eq!(
+            research_button_label(Some(&research), Upgrade::DefenderAttack),
+            "Defender Attack: queued #2"
+        );
+
+        research.funded = 40;
+        research.progress = Upgrade::ExtractRate.ticks() / 2;
+        assert_eq!(
+            research_button_label(Some(&research), Upgrade::ExtractRate),
+            "Extraction: 50%"
+        );
+
+        research.unlocked.insert(Upgrade::HaulerCarry);
+        assert_eq!(
+            research_button_label(Some(&research), Upgrade::HaulerCarry),
+            "Hauler Carry: done"
+        );
+    }
+
+    #[test]
+    fn toggle_queues_then_cancels_for_the_player() {
+        let mut state = ResearchState::default();
+        assert!(toggle_research(&mut state, Upgrade::HaulerCarry));
+        assert!(
+            state
+                .for_swarm(SwarmId::PLAYER)
+                .is_some_and(|research| research.is_queued(Upgrade::HaulerCarry))
+        );
+        assert!(toggle_research(&mut state, Upgrade::HaulerCarry));
+        assert!(
+            state
+                .for_swarm(SwarmId::PLAYER)
+                .is_some_and(|research| research.queue.is_empty())
+        );
+    }
+}
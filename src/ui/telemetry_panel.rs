@@ -0,0 +1,369 @@
+//! Bottom-left telemetry graph.
+//!
+//! Plots one player metric from [`SwarmTelemetry`] as a bar per sample,
+//! newest on the right. The `<` / `>` buttons cycle the metric; `CSV`
+//! writes every swarm's history to [`TELEMETRY_CSV_PATH`].
+
+use bevy::prelude::*;
+use bevy::ui::{
+    AlignItems, BorderRadius, FlexDirection, JustifyContent, PositionType, RelativeCursorPosition,
+};
+
+use crate::nanobot::{SwarmId, SwarmTelemetry, TelemetrySample};
+
+use super::button_bg_interaction::ButtonBgInteractiveComponent;
+use super::consts::NORMAL_BUTTON;
+use super::ui_setup::FontsResource;
+
+#[derive(Debug, Component)]
+pub struct TelemetryPanelRoot;
+
+#[derive(Debug, Component)]
+pub struct TelemetryPanelText;
+
+/// One graph column. Index 0 is the oldest shown sample.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct TelemetryGraphBar(pub usize);
+
+#[derive(Debug, Component, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryPanelButton {
+    Previous,
+    Next,
+    ExportCsv,
+}
+
+/// Metric the graph plots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TelemetryMetric {
+    #[default]
+    MineralsExtracted,
+    MineralsHauled,
+    MineralsConsumed,
+    BotsProduced,
+    BotsLost,
+    StructuresCompleted,
+    IdleRatio,
+}
+
+impl TelemetryMetric {
+    pub const ALL: [TelemetryMetric; 7] = [
+        TelemetryMetric::MineralsExtracted,
+        TelemetryMetric::MineralsHauled,
+        TelemetryMetric::MineralsConsumed,
+        TelemetryMetric::BotsProduced,
+        TelemetryMetric::BotsLost,
+        TelemetryMetric::StructuresCompleted,
+        TelemetryMetric::IdleRatio,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TelemetryMetric::MineralsExtracted => "Extracted",
+            TelemetryMetric::MineralsHauled => "Hauled",
+            TelemetryMetric::MineralsConsumed => "Consumed",
+            TelemetryMetric::BotsProduced => "Bots produced",
+            TelemetryMetric::BotsLost => "Bots lost",
+            TelemetryMetric::StructuresCompleted => "Structures built",
+            TelemetryMetric::IdleRatio => "Idle",
+        }
+    }
+
+    /// The metric's value in one sample. Consumption sums production,
+    /// upgrades, chargers, and field resupply; bot counts sum every
+    /// type.
+    pub fn value(self, sample: &TelemetrySample) -> f32 {
+        let flows = &sample.flows;
+        match self {
+            TelemetryMetric::MineralsExtracted => flows.minerals_extracted as f32,
+            TelemetryMetric::MineralsHauled => flows.minerals_hauled as f32,
+            TelemetryMetric::MineralsConsumed => {
+                (flows.minerals_to_production
+                    + flows.minerals_to_upgrades
+                    + flows.minerals_to_chargers
+                    + flows.minerals_to_resupply) as f32
+            }
+            TelemetryMetric::BotsProduced => flows.produced.iter().sum::<u32>() as f32,
+            TelemetryMetric::BotsLost => flows.lost.iter().sum::<u32>() as f32,
+            TelemetryMetric::StructuresCompleted => flows.structures_completed as f32,
+            TelemetryMetric::IdleRatio => sample.idle_ratio,
+        }
+    }
+
+    fn format(self, value: f32) -> String {
+        match self {
+            TelemetryMetric::IdleRatio => format!("{:.0}%", value * 100.0),
+            _ => format!("{value:.0}/s"),
+        }
+    }
+
+    fn step(self, offset: isize) -> Self {
+        let len = Self::ALL.len() as isize;
+        let index = Self::ALL
+            .iter()
+            .position(|metric| *metric == self)
+            .unwrap_or_default() as isize;
+        Self::ALL[(index + offset).rem_euclid(len) as usize]
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct TelemetryPanelState {
+    pub metric: TelemetryMetric,
+}
+
+/// Where the `CSV` button writes, relative to the working directory.
+pub const TELEMETRY_CSV_PATH: &str = "telemetry.csv";
+
+pub const PANEL_BOTTOM: f32 = 8.0;
+pub const PANEL_LEFT: f32 = 5.0;
+pub const PANEL_WIDTH: f32 = 260.0;
+pub const PANEL_PADDING: f32 = 8.0;
+pub const PANEL_FONT_SIZE: f32 = 14.0;
+pub const PANEL_GAP: f32 = 5.0;
+pub const BUTTON_PADDING: f32 = 4.0;
+pub const GRAPH_HEIGHT: f32 = 64.0;
+/// Samples shown: the last minute at one sample per second.
+pub const GRAPH_COLUMNS: usize = 60;
+
+const BAR_COLOR: Color = Color::srgb(0.35, 0.75, 0.95);
+
+/// Bar heights in percent for the last `columns` values, right-aligned
+/// so the newest sample is the rightmost bar. Counts scale to the
+/// largest shown value; the idle ratio is already a fraction.
+pub fn graph_heights(metric: TelemetryMetric, values: &[f32], columns: usize) -> Vec<f32> {
+    let shown = &values[values.len().saturating_sub(columns)..];
+    let scale = match metric {
+        TelemetryMetric::IdleRatio => 1.0,
+        _ => shown.iter().copied().fold(1.0, f32::max),
+    };
+    let mut heights = vec![0.0; columns - shown.len()];
+    heights.extend(
+        shown
+            .iter()
+            .map(|value| (value / scale * 100.0).clamp(0.0, 100.0)),
+    );
+    heights
+}
+
+pub fn telemetry_panel_text(metric: TelemetryMetric, latest: Option<&TelemetrySample>) -> String {
+    match latest {
+        Some(sample) => format!(
+            "{}: {}",
+            metric.label(),
+            metric.format(metric.value(sample))
+        ),
+        None => format!("{}: --", metric.label()),
+    }
+}
+
+fn spawn_button(
+    row: &mut ChildSpawnerCommands,
+    font: &Handle<Font>,
+    label: &str,
+    action: TelemetryPanelButton,
+) {
+    row.spawn((
+        Button,
+        ButtonBgInteractiveComponent,
+        action,
+        BackgroundColor(NORMAL_BUTTON),
+        Node {
+            padding: UiRect::all(Val::Px(BUTTON_PADDING)),
+            border_radius: BorderRadius::all(Val::Px(3.0)),
+            ..default()
+        },
+    ))
+    .with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont {
+                font: font.clone(),
+                font_size: PANEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+pub fn setup_telemetry_panel(mut commands: Commands, fonts: Res<FontsResource>) {
+    let font = fonts.font.clone();
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(PANEL_BOTTOM),
+                left: Val::Px(PANEL_LEFT),
+                width: Val::Px(PANEL_WIDTH),
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(PANEL_GAP),
+                align_items: AlignItems::Stretch,
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.03, 0.04, 0.05, 0.78)),
+            RelativeCursorPosition::default(),
+            TelemetryPanelRoot,
+        ))
+        .with_children(|panel| {
+            panel
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(PANEL_GAP),
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_button(row, &font, "<", TelemetryPanelButton::Previous);
+                    row.spawn((
+                        TelemetryPanelText,
+                        Text::new(telemetry_panel_text(TelemetryMetric::default(), None)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: PANEL_FONT_SIZE,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                    spawn_button(row, &font, ">", TelemetryPanelButton::Next);
+                    spawn_button(row, &font, "CSV", TelemetryPanelButton::ExportCsv);
+                });
+            panel
+                .spawn(Node {
+                    height: Val::Px(GRAPH_HEIGHT),
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::FlexEnd,
+                    column_gap: Val::Px(1.0),
+                    ..default()
+                })
+                .with_children(|graph| {
+                    for index in 0..GRAPH_COLUMNS {
+                        graph.spawn((
+                            TelemetryGraphBar(index),
+                            Node {
+                                flex_grow: 1.0,
+                                height: Val::Percent(0.0),
+                                ..default()
+                            },
+                            BackgroundColor(BAR_COLOR),
+                        ));
+                    }
+                });
+        });
+}
+
+pub fn telemetry_panel_click_system(
+    mut state: ResMut<TelemetryPanelState>,
+    telemetry: Option<Res<SwarmTelemetry>>,
+    buttons: Query<(&Interaction, &TelemetryPanelButton), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            TelemetryPanelButton::Previous => state.metric = state.metric.step(-1),
+            TelemetryPanelButton::Next => state.metric = state.metric.step(1),
+            TelemetryPanelButton::ExportCsv => {
+                let Some(telemetry) = telemetry.as_deref() else {
+                    continue;
+                };
+                match std::fs::write(TELEMETRY_CSV_PATH, telemetry.to_csv()) {
+                    Ok(()) => info!("telemetry exported to {TELEMETRY_CSV_PATH}"),
+                    Err(error) => warn!("telemetry export failed: {error}"),
+                }
+            }
+        }
+    }
+}
+
+pub fn update_telemetry_panel(
+    state: Res<TelemetryPanelState>,
+    telemetry: Option<Res<SwarmTelemetry>>,
+    mut text: Query<&mut Text, With<TelemetryPanelText>>,
+    mut bars: Query<(&TelemetryGraphBar, &mut Node)>,
+) {
+    let Some(telemetry) = telemetry else {
+        return;
+    };
+    if !telemetry.is_changed() && !state.is_changed() {
+        return;
+    }
+    let metric = state.metric;
+    if let Ok(mut text) = text.single_mut() {
+        *text = Text::new(telemetry_panel_text(
+            metric,
+            telemetry.latest(SwarmId::PLAYER),
+        ));
+    }
+    let values = telemetry
+        .history(SwarmId::PLAYER)
+        .map(|sample| metric.value(sample))
+        .collect::<Vec<_>>();
+    let heights = graph_heights(metric, &values, GRAPH_COLUMNS);
+    for (bar, mut node) in &mut bars {
+        node.height = Val::Percent(heights[bar.0]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_right_aligns_and_scales_to_the_shown_peak() {
+        let heights = graph_heights(TelemetryMetric::MineralsHauled, &[2.0, 4.0], 4);
+        assert_eq!(heights, vec![0.0, 0.0, 50.0, 100.0]);
+
+        let long = (0..10).map(|value| value as f32).collect::<Vec<_>>();
+        let heights = graph_heights(TelemetryMetric::MineralsHauled, &long, 3);
+        assert_eq!(heights.len(), 3);
+        assert_eq!(
+            heights[2], 100.0,
+            "older peaks outside the window are ignored"
+        );
+    }
+
+    #[test]
+    fn idle_ratio_plots_as_a_fraction() {
+        let heights = graph_heights(TelemetryMetric::IdleRatio, &[0.25], 1);
+        assert_eq!(heights, vec![25.0]);
+    }
+
+    #[test]
+    fn metric_cycle_wraps_both_ways() {
+        assert_eq!(
+            TelemetryMetric::MineralsExtracted.step(-1),
+            TelemetryMetric::IdleRatio
+        );
+        assert_eq!(
+            TelemetryMetric::IdleRatio.step(1),
+            TelemetryMetric::MineralsExtracted
+        );
+    }
+
+    #[test]
+    fn panel_text_shows_the_latest_value() {
+        let mut sample = TelemetrySample::default();
+        sample.flows.minerals_to_production = 5;
+        sample.flows.minerals_to_chargers = 2;
+        sample.flows.minerals_to_upgrades = 10;
+        sample.flows.minerals_to_resupply = 1;
+        assert_eq!(
+            telemetry_panel_text(TelemetryMetric::MineralsConsumed, Some(&sample)),
+            "Consumed: 18/s"
+        );
+        sample.idle_ratio = 0.5;
+        assert_eq!(
+            telemetry_panel_text(TelemetryMetric::IdleRatio, Some(&sample)),
+            "Idle: 50%"
+        );
+        assert_eq!(
+            telemetry_panel_text(TelemetryMetric::BotsLost, None),
+            "Bots lost: --"
+        );
+    }
+}
@@ -205,7 +205,10 @@ pub fn mirror_intent_to_zone_material_system(
         .set_data(packed_presence);
 }
 
-fn zone_buffer_index_from_grid_point(point: IVec2, width: i32, height: i32) -> Option<IVec2> {
+/// Buffer coordinates for an intent-grid `point` on a centred
+/// `width` x `height` map, rows flipped to match the mesh UVs. `None`
+/// outside the map. Shared with the heatmap overlay.
+pub fn zone_buffer_index_from_grid_point(point: IVec2, width: i32, height: i32) -> Option<IVec2> {
     let half = ivec2(width / 2, height / 2);
     let mut idx = point + half;
     idx.y = height - idx.y - 1;
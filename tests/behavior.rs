@@ -8,10 +8,18 @@ mod automatic_construction_issue34;
 mod charger;
 #[path = "behavior/charger_planned.rs"]
 mod charger_planned;
+#[path = "behavior/charger_queue.rs"]
+mod charger_queue;
 #[path = "behavior/combat.rs"]
 mod combat;
+#[path = "behavior/cooperative_construction.rs"]
+mod cooperative_construction;
 #[path = "behavior/defend_zone.rs"]
 mod defend_zone;
+#[path = "behavior/deposit_regeneration.rs"]
+mod deposit_regeneration;
+#[path = "behavior/field_resupply.rs"]
+mod field_resupply;
 #[path = "behavior/fixed_simulation.rs"]
 mod fixed_simulation;
 #[path = "behavior/full_source_stockpile.rs"]
@@ -26,12 +34,20 @@ mod gather_zone;
 mod gradual_hauler_pickup;
 #[path = "behavior/hauler_corridor.rs"]
 mod hauler_corridor;
+#[path = "behavior/hauler_multi_stop.rs"]
+mod hauler_multi_stop;
+#[path = "behavior/heatmap_overlay.rs"]
+mod heatmap_overlay;
 #[path = "behavior/idle_spread.rs"]
 mod idle_spread;
 #[path = "behavior/intent_brush.rs"]
 mod intent_brush;
+#[path = "behavior/logistics_overlay.rs"]
+mod logistics_overlay;
 #[path = "behavior/maintenance.rs"]
 mod maintenance;
+#[path = "behavior/maintenance_upkeep.rs"]
+mod maintenance_upkeep;
 #[path = "behavior/nanobot_autonomy.rs"]
 mod nanobot_autonomy;
 #[path = "behavior/no_instant_spawning.rs"]
@@ -42,6 +58,8 @@ mod opponent_swarm;
 mod per_swarm_intent_ownership;
 #[path = "behavior/physical_worker_gather.rs"]
 mod physical_worker_gather;
+#[path = "behavior/planned_material.rs"]
+mod planned_material;
 #[path = "behavior/planned_structure.rs"]
 mod planned_structure;
 #[path = "behavior/population_demand.rs"]
@@ -50,28 +68,52 @@ mod population_demand;
 mod production_collapse;
 #[path = "behavior/production_facility.rs"]
 mod production_facility;
+#[path = "behavior/production_facility_control.rs"]
+mod production_facility_control;
 #[path = "behavior/production_facility_planned.rs"]
 mod production_facility_planned;
 #[path = "behavior/production_priority_panel.rs"]
 mod production_priority_panel;
+#[path = "behavior/reclaim.rs"]
+mod reclaim;
 #[path = "behavior/regional_allocation.rs"]
 mod regional_allocation;
+#[path = "behavior/repair.rs"]
+mod repair;
+#[path = "behavior/research_lab.rs"]
+mod research_lab;
+#[path = "behavior/road.rs"]
+mod road;
+#[path = "behavior/route_cost_cache.rs"]
+mod route_cost_cache;
 #[path = "behavior/sink_stockpile.rs"]
 mod sink_stockpile;
 #[path = "behavior/source_stockpile_flow.rs"]
 mod source_stockpile_flow;
 #[path = "behavior/source_stockpile_placement.rs"]
 mod source_stockpile_placement;
+#[path = "behavior/spatial_index.rs"]
+mod spatial_index;
+#[path = "behavior/steering.rs"]
+mod steering;
 #[path = "behavior/stockpile_and_haul.rs"]
 mod stockpile_and_haul;
 #[path = "behavior/structure_overlay.rs"]
 mod structure_overlay;
+#[path = "behavior/structure_upgrade.rs"]
+mod structure_upgrade;
 #[path = "behavior/tactical_overlay.rs"]
 mod tactical_overlay;
+#[path = "behavior/telemetry.rs"]
+mod telemetry;
 #[path = "behavior/terminal_logistics_priority.rs"]
 mod terminal_logistics_priority;
+#[path = "behavior/threat.rs"]
+mod threat;
 #[path = "behavior/world_space_nanobots.rs"]
 mod world_space_nanobots;
+#[path = "behavior/wreckage.rs"]
+mod wreckage;
 #[path = "behavior/zone_brush_ui_capture.rs"]
 mod zone_brush_ui_capture;
 #[path = "behavior/zone_overlay_draw_order.rs"]
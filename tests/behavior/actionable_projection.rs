@@ -2,10 +2,10 @@ use bevy::prelude::*;
 use top_down_2d_rts_prototype_nano_swarm::{
     intent::{IntentGrid, IntentKind},
     nanobot::{
-        ActionableOpportunity, ActionableProjection, AllocationRegion, DefendPressure,
-        MAINTENANCE_NEEDS_THRESHOLD, OpportunityCategory, OwnerSwarm, PlannedKind,
+        ActionableOpportunity, ActionableProjection, AllocationRegion, DEFEND_PRESSURE_BASELINE,
+        DefendPressure, MAINTENANCE_NEEDS_THRESHOLD, OpportunityCategory, OwnerSwarm, PlannedKind,
         PlannedStructure, SUPPORT_OPERATIONAL_HEALTH_THRESHOLD, Structure, StructureKind, SwarmId,
-        project_actionable_opportunities_system,
+        project_actionable_opportunities_system, repair_priority,
     },
     resources::{ResourceDeposit, ResourceKind, Stockpile, StockpileRole},
 };
@@ -57,7 +57,10 @@ fn unclaimed_planned_structure_projects_remaining_build_work() {
     let opportunities = projection.opportunities(AllocationRegion::for_cell(IVec2::new(9, 1)));
     assert_eq!(opportunities.len(), 1);
     assert_eq!(opportunities[0].category, OpportunityCategory::PlannedBuild);
-    assert_eq!(opportunities[0].available_work, 5);
+    assert_eq!(
+        opportunities[0].available_work,
+        PlannedKind::ProductionFacility.build_work_ticks()
+    );
 }
 
 #[test]
@@ -115,6 +118,31 @@ fn defend_pressure_changes_projected_defender_capacity() {
     assert_eq!(defend.available_work, 3);
 }
 
+#[test]
+fn damaged_structure_projects_one_repair_slot_ranked_by_priority() {
+    let mut app = projection_app();
+    let mut structure = Structure::new(StructureKind::Basic);
+    structure.health -= 40;
+    app.world_mut()
+        .spawn((structure, Transform::from_xyz(32.0, 32.0, 0.0)));
+
+    app.update();
+
+    let repair = app
+        .world()
+        .resource::<ActionableProjection>()
+        .opportunities(AllocationRegion::for_cell(IVec2::ZERO))
+        .iter()
+        .find(|opportunity| opportunity.category == OpportunityCategory::Repair)
+        .copied()
+        .expect("Repair opportunity projected");
+    assert_eq!(repair.available_work, 1, "one Worker repairs a structure");
+    assert_eq!(
+        repair.priority,
+        repair_priority(&structure, None, DEFEND_PRESSURE_BASELINE)
+    );
+}
+
 #[test]
 fn haul_opportunity_is_indexed_by_source_region() {
     let mut app = projection_app();
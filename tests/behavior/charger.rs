@@ -14,17 +14,16 @@
 
 use bevy::{math::Vec2, prelude::*};
 use top_down_2d_rts_prototype_nano_swarm::{
-    ai::AiPlugin,
     intent::{IntentGrid, IntentKind},
     nanobot::{
-        CHARGE_DRAIN_PER_TICK, CHARGE_REFILL_PER_TICK, CHARGER_MATERIAL_DRAIN_PER_TICK, Cargo,
-        Charge, Charger, ChargerAssignment, ChargerProgress, DEFENDER_BASE_ATTACK,
-        DEFENDER_BASE_DEFENSE, DefendAssignment, DefendHold, EMPTY_CHARGE_HEALTH_LOSS_PER_TICK,
-        Health, LOW_CHARGE_THRESHOLD, LogisticsReservation, MAX_CHARGE, NANOBOT_DEFAULT_MAX_HEALTH,
-        Nanobot, NanobotBundle, NanobotPlugin, NanobotType, OwnerSwarm, PlannedKind,
-        PlannedStructure, SUPPORT_OPERATIONAL_HEALTH_THRESHOLD, SoftWorkSlots, Structure,
-        StructureKind, Swarm, SwarmBundle, SwarmId, SwarmMember, WEAKENED_CHARGE_THRESHOLD,
-        defender_charger_work_system, nanobot_death_cleanup_system,
+        BehaviourAppExt, CHARGE_DRAIN_PER_TICK, CHARGE_REFILL_PER_TICK,
+        CHARGER_MATERIAL_DRAIN_PER_TICK, Cargo, Charge, Charger, ChargerAssignment,
+        ChargerProgress, DEFENDER_BASE_ATTACK, DEFENDER_BASE_DEFENSE, DefendAssignment, DefendHold,
+        EMPTY_CHARGE_HEALTH_LOSS_PER_TICK, Health, LOW_CHARGE_THRESHOLD, LogisticsReservation,
+        MAX_CHARGE, NANOBOT_DEFAULT_MAX_HEALTH, Nanobot, NanobotBundle, NanobotPlugin, NanobotType,
+        OwnerSwarm, PlannedKind, PlannedStructure, SUPPORT_OPERATIONAL_HEALTH_THRESHOLD,
+        SoftWorkSlots, Structure, StructureKind, Swarm, SwarmBundle, SwarmId, SwarmMember,
+        WEAKENED_CHARGE_THRESHOLD, defender_charger_work_system, nanobot_death_cleanup_system,
     },
     resources::{ResourceKind, ResourceLedger},
 };
@@ -1122,9 +1121,11 @@ fn loaded_nanobot_death_loses_exact_cargo_and_releases_reservation() {
 }
 
 #[test]
-fn nanobot_plugin_cleans_dead_bot_after_ai_deferred_commands() {
+fn nanobot_plugin_cleans_dead_bot_after_behaviour_hook_commands() {
     let mut app = common::minimal_app();
-    app.add_plugins((NanobotPlugin::default(), AiPlugin));
+    app.add_plugins(NanobotPlugin::default())
+        .add_behaviour_state::<ChargerAssignment>()
+        .add_behaviour_state::<DefendHold>();
     let cargo_amount = 7;
     app.world_mut().resource_mut::<ResourceLedger>().add_for(
         SwarmId::PLAYER,
@@ -1142,6 +1143,12 @@ fn nanobot_plugin_cleans_dead_bot_after_ai_deferred_commands() {
                 amount: cargo_amount,
             },
             Transform::default(),
+            // Behaviour exit observers fire while the death
+            // cleanup despawns the bot.
+            ChargerAssignment {
+                charger: Entity::PLACEHOLDER,
+            },
+            DefendHold { cell: IVec2::ZERO },
         ))
         .id();
 
@@ -0,0 +1,200 @@
+//! Charger slot reservations and queueing: a `ChargerAssignment`
+//! reserves a place at a charger, rotating defenders weigh nearby
+//! chargers by predicted wait at their own top speed, defenders that
+//! find every slot taken wait in a numbered queue, and `ChargerQueue`
+//! summarises each charger for the overlay.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        Charge, ChargerAssignment, ChargerProgress, ChargerQueue, ChargerWaiting, DefendHold,
+        MAX_DEFENDERS_PER_CHARGER, NanobotSteering,
+    },
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn low_defender(app: &mut App, world_pos: Vec2) -> Entity {
+    let defender = common::spawn_defender_at(app, world_pos);
+    app.world_mut()
+        .entity_mut(defender)
+        .get_mut::<Charge>()
+        .unwrap()
+        .current = 0.2;
+    defender
+}
+
+fn queue_of(app: &App, charger: Entity) -> ChargerQueue {
+    app.world()
+        .get::<ChargerQueue>(charger)
+        .copied()
+        .expect("every charger carries a queue summary")
+}
+
+#[test]
+fn defenders_beyond_the_slot_count_wait_in_order() {
+    let mut app = common::sim_app_with_charge();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let charger = common::spawn_charger_at(&mut app, IVec2::ZERO, 200);
+    let center = common::cell_world_center(IVec2::ZERO);
+    let defenders: Vec<Entity> = (0..MAX_DEFENDERS_PER_CHARGER + 2)
+        .map(|_| {
+            let defender = low_defender(&mut app, center);
+            app.world_mut()
+                .entity_mut(defender)
+                .insert(ChargerAssignment { charger });
+            defender
+        })
+        .collect();
+
+    app.update();
+
+    let slots = MAX_DEFENDERS_PER_CHARGER as usize;
+    for &defender in &defenders[..slots] {
+        assert!(app.world().get::<ChargerProgress>(defender).is_some());
+    }
+    for (position, &defender) in defenders[slots..].iter().enumerate() {
+        assert!(app.world().get::<ChargerProgress>(defender).is_none());
+        assert_eq!(
+            app.world().get::<ChargerWaiting>(defender),
+            Some(&ChargerWaiting {
+                position: position as u32
+            })
+        );
+    }
+    assert_eq!(
+        queue_of(&app, charger),
+        ChargerQueue {
+            reserved: MAX_DEFENDERS_PER_CHARGER + 2,
+            charging: MAX_DEFENDERS_PER_CHARGER,
+            waiting: 2,
+        }
+    );
+
+    // Free one slot: the head of the queue moves in and the rest
+    // move up.
+    app.world_mut()
+        .entity_mut(defenders[0])
+        .remove::<(ChargerAssignment, ChargerProgress)>();
+    app.update();
+
+    assert!(
+        app.world()
+            .get::<ChargerProgress>(defenders[slots])
+            .is_some()
+    );
+    assert!(
+        app.world()
+            .get::<ChargerWaiting>(defenders[slots])
+            .is_none()
+    );
+    assert_eq!(
+        app.world().get::<ChargerWaiting>(defenders[slots + 1]),
+        Some(&ChargerWaiting { position: 0 })
+    );
+    assert_eq!(queue_of(&app, charger).waiting, 1);
+}
+
+#[test]
+fn rotation_sends_overflow_to_the_charger_that_frees_up_sooner() {
+    let mut app = common::sim_app_with_charge();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cell = IVec2::ZERO;
+    app.world_mut()
+        .resource_mut::<IntentGrid>()
+        .paint(cell, IntentKind::Defend);
+    let center = common::cell_world_center(cell);
+    let near = common::spawn_charger_at(&mut app, cell, 200);
+    let far = common::spawn_charger_at(&mut app, cell, 200);
+    app.world_mut()
+        .entity_mut(far)
+        .insert(Transform::from_translation(
+            (center + Vec2::new(100.0, 0.0)).extend(0.0),
+        ));
+    let defenders: Vec<Entity> = (0..MAX_DEFENDERS_PER_CHARGER + 1)
+        .map(|_| {
+            let defender = low_defender(&mut app, center + Vec2::new(25.0, 0.0));
+            app.world_mut()
+                .entity_mut(defender)
+                .insert(DefendHold { cell });
+            defender
+        })
+        .collect();
+
+    app.update();
+
+    let picks: Vec<Entity> = defenders
+        .iter()
+        .map(|&defender| {
+            app.world()
+                .get::<ChargerAssignment>(defender)
+                .expect("every low holding defender rotates")
+                .charger
+        })
+        .collect();
+    let at_near = picks.iter().filter(|&&charger| charger == near).count();
+    assert_eq!(at_near, MAX_DEFENDERS_PER_CHARGER as usize);
+    assert_eq!(picks.iter().filter(|&&charger| charger == far).count(), 1);
+    assert_eq!(
+        queue_of(&app, near).reserved,
+        MAX_DEFENDERS_PER_CHARGER,
+        "a full charger sheds the next defender instead of queueing it"
+    );
+    assert_eq!(queue_of(&app, far).reserved, 1);
+}
+
+/// Charger picked by one low holding defender standing on a full
+/// charger, with a free one 200 units away.
+fn rotation_pick(steering: Option<NanobotSteering>) -> (Entity, Entity, Entity) {
+    let mut app = common::sim_app_with_charge();
+    if let Some(steering) = steering {
+        app.insert_resource(steering);
+    }
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cell = IVec2::ZERO;
+    app.world_mut()
+        .resource_mut::<IntentGrid>()
+        .paint(cell, IntentKind::Defend);
+    let center = common::cell_world_center(cell);
+    let full = common::spawn_charger_at(&mut app, cell, 200);
+    let free = common::spawn_charger_at(&mut app, cell, 200);
+    app.world_mut()
+        .entity_mut(free)
+        .insert(Transform::from_translation(
+            (center + Vec2::new(200.0, 0.0)).extend(0.0),
+        ));
+    for _ in 0..MAX_DEFENDERS_PER_CHARGER {
+        let queued = low_defender(&mut app, center + Vec2::new(0.0, 300.0));
+        app.world_mut()
+            .entity_mut(queued)
+            .insert(ChargerAssignment { charger: full });
+    }
+    let defender = low_defender(&mut app, center);
+    app.world_mut()
+        .entity_mut(defender)
+        .insert(DefendHold { cell });
+
+    app.update();
+
+    let pick = app
+        .world()
+        .get::<ChargerAssignment>(defender)
+        .expect("a low holding defender rotates")
+        .charger;
+    (pick, full, free)
+}
+
+#[test]
+fn rotation_weighs_travel_at_the_defenders_own_top_speed() {
+    // A full session at the full charger takes longer than a fast
+    // defender needs to reach the free one, but not a slow one.
+    let (pick, full, _) = rotation_pick(None);
+    assert_eq!(pick, full, "at the 5-unit default speed the queue wins");
+
+    let mut steering = NanobotSteering::default();
+    steering.defender.max_speed = 20.0;
+    let (pick, _, free) = rotation_pick(Some(steering));
+    assert_eq!(pick, free, "a fast defender walks to the free charger");
+}
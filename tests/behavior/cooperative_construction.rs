@@ -0,0 +1,113 @@
+//! Cooperative construction: a build with enough work left opens
+//! extra work slots, the allocator fills them with helper Workers,
+//! and the crew finishes sooner with diminishing returns per helper.
+//! Every plan here is built through `PlannedStructure::new`, so the
+//! crews come from each kind's own work budget.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::nanobot::{
+    PlannedKind, PlannedStructure, PlannedStructureClaim, ProductionFacility,
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn facility_plan(app: &mut App) -> Entity {
+    common::spawn_planned_structure_of_kind_at_cell(
+        app,
+        IVec2::ZERO,
+        PlannedKind::ProductionFacility,
+    )
+}
+
+fn claimants(app: &mut App, planned: Entity) -> usize {
+    app.world_mut()
+        .query::<&PlannedStructureClaim>()
+        .iter(app.world())
+        .filter(|claim| claim.target == planned)
+        .count()
+}
+
+/// Ticks until the facility plan completes with `workers`
+/// idle Workers standing on the site.
+fn ticks_to_build(workers: usize) -> usize {
+    let mut app = common::sim_app_with_planned();
+    let center = common::cell_world_center(IVec2::ZERO);
+    common::spawn_swarm_at(&mut app, center);
+    let planned = facility_plan(&mut app);
+    for _ in 0..workers {
+        common::spawn_worker_at(&mut app, center);
+    }
+    let budget = PlannedKind::ProductionFacility.build_work_ticks() as usize;
+    for tick in 1..=budget * 2 {
+        app.update();
+        if app.world().get::<ProductionFacility>(planned).is_some() {
+            return tick;
+        }
+    }
+    panic!("{workers} worker(s) never finished the facility");
+}
+
+#[test]
+fn facility_plan_takes_a_crew_up_to_its_work_slots() {
+    let mut app = common::sim_app_with_planned();
+    let center = common::cell_world_center(IVec2::ZERO);
+    common::spawn_swarm_at(&mut app, center);
+    let planned = facility_plan(&mut app);
+    for _ in 0..5 {
+        common::spawn_worker_at(&mut app, center);
+    }
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert_eq!(
+        claimants(&mut app, planned) as u32,
+        PlannedKind::ProductionFacility.build_work_slots(),
+        "extra workers fill the slots and no more"
+    );
+    assert!(
+        app.world()
+            .get::<PlannedStructure>(planned)
+            .unwrap()
+            .active_worker
+            .is_some(),
+        "one of the crew leads the build"
+    );
+}
+
+#[test]
+fn stockpile_plan_stays_a_one_worker_job() {
+    let mut app = common::sim_app_with_planned();
+    let center = common::cell_world_center(IVec2::ZERO);
+    common::spawn_swarm_at(&mut app, center);
+    let planned = common::spawn_planned_structure_of_kind_at_cell(
+        &mut app,
+        IVec2::ZERO,
+        PlannedKind::SinkStockpile,
+    );
+    for _ in 0..3 {
+        common::spawn_worker_at(&mut app, center);
+    }
+
+    app.update();
+
+    assert_eq!(claimants(&mut app, planned), 1);
+}
+
+#[test]
+fn busy_crew_finishes_a_facility_faster_with_diminishing_returns() {
+    let solo = ticks_to_build(1);
+    let crew = ticks_to_build(PlannedKind::ProductionFacility.build_work_slots() as usize);
+
+    // A full crew of three adds 1 + 1/2 + 1/3 worker-ticks per tick.
+    assert!(
+        crew * 3 < solo * 2,
+        "a crew must cut the build time well down; solo={solo} crew={crew}"
+    );
+    assert!(
+        crew * 3 > solo,
+        "helpers add less than a full worker each; solo={solo} crew={crew}"
+    );
+}
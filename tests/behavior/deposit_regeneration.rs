@@ -0,0 +1,291 @@
+//! Integration tests for deposit regeneration and map-wide resource
+//! dynamics.
+//!
+//! Covers the contracts the regeneration feature adds on top of the
+//! gather zone suite:
+//!   1. a drained deposit with a regeneration curve refills and the
+//!      painted Gather Zone reactivates an idle Worker on it,
+//!   2. depletion / replenishment messages fire once per zero crossing,
+//!   3. scripted deposits appear on their authored tick,
+//!   4. random deposits stay inside their rectangle and respect
+//!      `max_active`,
+//!   5. spawns never land on an occupied cell, and scripted deposits
+//!      do not count against the random quota.
+
+use bevy::{math::Vec2, prelude::*};
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        DepletedDeposit, DepositDepleted, DepositDynamics, DepositDynamicsConfig,
+        DepositDynamicsPlugin, DepositRegeneration, DepositReplenished, RandomDepositSpawns,
+        RegenerationCurve, ScriptedDeposit, ScriptedDepositSpawn, SpawnedDeposit, SwarmId,
+        deposit_depletion_events_system, world_to_cell,
+    },
+    resources::{ResourceDeposit, ResourceKind, ResourceLedger},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn build_app() -> App {
+    let mut app = common::sim_app_with_gather();
+    app.add_plugins(DepositDynamicsPlugin);
+    app
+}
+
+#[derive(Debug, Default, Resource)]
+struct CrossingCounts {
+    depleted: u32,
+    replenished: u32,
+}
+
+fn count_crossings(
+    mut counts: ResMut<CrossingCounts>,
+    mut depleted: MessageReader<DepositDepleted>,
+    mut replenished: MessageReader<DepositReplenished>,
+) {
+    counts.depleted += depleted.read().count() as u32;
+    counts.replenished += replenished.read().count() as u32;
+}
+
+#[test]
+fn regenerating_deposit_reactivates_gather_after_depletion() {
+    // A drained deposit with a burst curve sits at zero until the
+    // first burst. The Gather Zone was never cleared, so the refill
+    // alone must bring the idle Worker back to extraction.
+    let mut app = build_app();
+    let deposit_pos = Vec2::new(100.0, 0.0);
+    let deposit = common::spawn_deposit(&mut app, deposit_pos, 0);
+    app.world_mut()
+        .entity_mut(deposit)
+        .insert(DepositRegeneration::new(RegenerationCurve::Burst {
+            interval_ticks: 4,
+            amount: 8,
+        }));
+    let _stockpile = common::spawn_stockpile(&mut app, Vec2::new(150.0, 0.0), 0, 1000);
+    let _worker = common::spawn_worker_at(&mut app, deposit_pos);
+    {
+        let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+        assert!(grid.paint(IVec2::new(0, 0), IntentKind::Gather));
+    }
+
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(
+        app.world()
+            .resource::<ResourceLedger>()
+            .total_for(SwarmId::PLAYER, ResourceKind::Minerals),
+        0,
+        "nothing can be extracted before the first burst"
+    );
+
+    for _ in 0..8 {
+        app.update();
+    }
+    assert!(
+        app.world()
+            .resource::<ResourceLedger>()
+            .total_for(SwarmId::PLAYER, ResourceKind::Minerals)
+            > 0,
+        "idle worker must re-engage once the deposit regrows"
+    );
+}
+
+#[test]
+fn depletion_messages_fire_once_per_zero_crossing() {
+    let mut app = build_app();
+    app.init_resource::<CrossingCounts>();
+    app.add_systems(
+        FixedUpdate,
+        count_crossings.after(deposit_depletion_events_system),
+    );
+    let deposit = common::spawn_deposit(&mut app, Vec2::new(100.0, 0.0), 5);
+    app.update();
+
+    app.world_mut()
+        .get_mut::<ResourceDeposit>(deposit)
+        .unwrap()
+        .amount = 0;
+    for _ in 0..3 {
+        app.update();
+    }
+    assert!(app.world().entity(deposit).contains::<DepletedDeposit>());
+    assert_eq!(app.world().resource::<CrossingCounts>().depleted, 1);
+    assert_eq!(app.world().resource::<CrossingCounts>().replenished, 0);
+
+    app.world_mut()
+        .get_mut::<ResourceDeposit>(deposit)
+        .unwrap()
+        .amount = 3;
+    for _ in 0..3 {
+        app.update();
+    }
+    assert!(!app.world().entity(deposit).contains::<DepletedDeposit>());
+    assert_eq!(app.world().resource::<CrossingCounts>().depleted, 1);
+    assert_eq!(app.world().resource::<CrossingCounts>().replenished, 1);
+}
+
+#[test]
+fn scripted_deposit_appears_on_its_tick() {
+    let mut app = build_app();
+    let cell = IVec2::new(3, 2);
+    app.insert_resource(DepositDynamics::new(DepositDynamicsConfig {
+        scripted_spawns: vec![ScriptedDepositSpawn {
+            at_tick: 3,
+            cell: (cell.x, cell.y),
+            amount: 50,
+            capacity: 80,
+            radius: 40.0,
+            regeneration: Some(RegenerationCurve::Linear { per_tick: 1.0 }),
+        }],
+        ..Default::default()
+    }));
+
+    app.update();
+    app.update();
+    let mut deposits = app.world_mut().query::<&ResourceDeposit>();
+    assert_eq!(deposits.iter(app.world()).count(), 0);
+
+    app.update();
+    let mut deposits = app
+        .world_mut()
+        .query::<(&ResourceDeposit, &Transform, &DepositRegeneration)>();
+    let spawned: Vec<_> = deposits.iter(app.world()).collect();
+    assert_eq!(spawned.len(), 1, "scripted deposit spawns exactly once");
+    let (deposit, transform, _) = spawned[0];
+    assert_eq!(deposit.amount, 50);
+    assert_eq!(deposit.capacity, 80);
+    assert_eq!(
+        transform.translation.truncate(),
+        common::cell_world_center(cell)
+    );
+}
+
+#[test]
+fn random_deposits_respect_rectangle_and_max_active() {
+    let mut app = build_app();
+    app.insert_resource(DepositDynamics::new(DepositDynamicsConfig {
+        random_spawns: Some(RandomDepositSpawns {
+            seed: 42,
+            interval_ticks: 1,
+            max_active: 2,
+            min_cell: (2, -1),
+            max_cell: (4, 1),
+            amount: 30,
+            radius: 32.0,
+            regeneration: None,
+        }),
+        ..Default::default()
+    }));
+
+    for _ in 0..6 {
+        app.update();
+    }
+    let mut deposits = app
+        .world_mut()
+        .query_filtered::<&Transform, (With<ResourceDeposit>, With<SpawnedDeposit>)>();
+    let cells: Vec<IVec2> = deposits
+        .iter(app.world())
+        .map(|transform| world_to_cell(transform.translation.truncate()))
+        .collect();
+    assert_eq!(
+        cells.len(),
+        2,
+        "live random spawns are capped at max_active"
+    );
+    for cell in cells {
+        assert!(
+            (2..=4).contains(&cell.x) && (-1..=1).contains(&cell.y),
+            "random deposit at {cell:?} is outside the authored rectangle"
+        );
+    }
+}
+
+#[test]
+fn blocked_scripted_spawn_is_skipped() {
+    let mut app = build_app();
+    let blocked = IVec2::new(1, 1);
+    let open = IVec2::new(-2, 1);
+    common::spawn_sink_stockpile(&mut app, common::cell_world_center(blocked), 0, 100);
+    let spawn = |cell: IVec2| ScriptedDepositSpawn {
+        at_tick: 1,
+        cell: (cell.x, cell.y),
+        amount: 50,
+        capacity: 50,
+        radius: 40.0,
+        regeneration: None,
+    };
+    app.insert_resource(DepositDynamics::new(DepositDynamicsConfig {
+        scripted_spawns: vec![spawn(blocked), spawn(open), spawn(open)],
+        ..Default::default()
+    }));
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let mut deposits = app
+        .world_mut()
+        .query_filtered::<&Transform, With<ScriptedDeposit>>();
+    let cells: Vec<IVec2> = deposits
+        .iter(app.world())
+        .map(|transform| world_to_cell(transform.translation.truncate()))
+        .collect();
+    assert_eq!(
+        cells,
+        vec![open],
+        "the stockpile's cell and the already-filled cell are both skipped"
+    );
+}
+
+#[test]
+fn random_spawns_avoid_occupied_cells_and_ignore_scripted_deposits() {
+    let mut app = build_app();
+    let scripted = IVec2::new(2, 0);
+    let blocked = IVec2::new(2, 1);
+    common::spawn_deposit(&mut app, common::cell_world_center(blocked), 10);
+    app.insert_resource(DepositDynamics::new(DepositDynamicsConfig {
+        scripted_spawns: vec![ScriptedDepositSpawn {
+            at_tick: 1,
+            cell: (scripted.x, scripted.y),
+            amount: 50,
+            capacity: 50,
+            radius: 40.0,
+            regeneration: None,
+        }],
+        random_spawns: Some(RandomDepositSpawns {
+            seed: 3,
+            interval_ticks: 2,
+            max_active: 1,
+            min_cell: (2, 0),
+            max_cell: (3, 1),
+            amount: 30,
+            radius: 32.0,
+            regeneration: None,
+        }),
+        ..Default::default()
+    }));
+
+    for _ in 0..8 {
+        app.update();
+    }
+
+    let mut random = app
+        .world_mut()
+        .query_filtered::<&Transform, With<SpawnedDeposit>>();
+    let cells: Vec<IVec2> = random
+        .iter(app.world())
+        .map(|transform| world_to_cell(transform.translation.truncate()))
+        .collect();
+    assert_eq!(
+        cells.len(),
+        1,
+        "the scripted deposit leaves the random quota free"
+    );
+    assert!(
+        cells[0] != scripted && cells[0] != blocked,
+        "random deposit landed on occupied cell {:?}",
+        cells[0]
+    );
+}
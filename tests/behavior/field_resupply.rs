@@ -0,0 +1,138 @@
+//! Field resupply: with a `FieldResupply` resource, Haulers carry
+//! minerals from a sink Stockpile to a Defender holding its Defend
+//! cell and restore its Charge in place, ranked against charger
+//! deliveries on the same urgency tiers. The minerals spent on charge
+//! leave the ledger.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::nanobot::{
+    Charge, ChargerAssignment, DefendHold, FieldResupply, HaulerAssignment, LogisticsReservation,
+    OwnerSwarm, SwarmId, world_to_cell,
+};
+use top_down_2d_rts_prototype_nano_swarm::resources::{ResourceKind, ResourceLedger, Stockpile};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn spawn_holding_defender(app: &mut App, world_pos: Vec2, current: f32) -> Entity {
+    let defender = common::spawn_defender_at(app, world_pos);
+    app.world_mut().entity_mut(defender).insert((
+        Charge { current, max: 1.0 },
+        DefendHold {
+            cell: world_to_cell(world_pos),
+        },
+    ));
+    defender
+}
+
+fn supplied_app() -> (App, Entity, Entity) {
+    let mut app = common::sim_app_with_gather_haul();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let source = common::spawn_sink_stockpile(&mut app, Vec2::new(10.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        100,
+    );
+    (app, swarm, source)
+}
+
+#[test]
+fn hauler_tops_up_a_holding_defender_in_place() {
+    let (mut app, _, source) = supplied_app();
+    app.insert_resource(FieldResupply::default());
+    let start = Vec2::new(200.0, 0.0);
+    let defender = spawn_holding_defender(&mut app, start, 0.4);
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    app.update();
+
+    let reservation = app
+        .world()
+        .get::<LogisticsReservation>(hauler)
+        .expect("the defender's need is reservable");
+    assert_eq!(reservation.source, source);
+    assert_eq!(reservation.destination, defender);
+    assert_eq!(reservation.amount, 12, "only what tops the defender up");
+
+    for _ in 0..200 {
+        app.update();
+        if app.world().get::<Charge>(defender).unwrap().is_full() {
+            break;
+        }
+    }
+
+    assert!(app.world().get::<Charge>(defender).unwrap().is_full());
+    assert_eq!(app.world().get::<Stockpile>(source).unwrap().amount, 88);
+    assert_eq!(
+        app.world()
+            .resource::<ResourceLedger>()
+            .total_for(SwarmId::PLAYER, ResourceKind::Minerals),
+        88,
+        "minerals turned into charge leave the ledger"
+    );
+    assert!(app.world().get::<HaulerAssignment>(hauler).is_none());
+    let pos = app
+        .world()
+        .get::<Transform>(defender)
+        .unwrap()
+        .translation
+        .truncate();
+    assert_eq!(pos, start, "the defender never left its hold");
+}
+
+#[test]
+fn defenders_are_not_haul_terminals_without_field_resupply() {
+    let (mut app, _, _) = supplied_app();
+    spawn_holding_defender(&mut app, Vec2::new(200.0, 0.0), 0.0);
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert!(app.world().get::<HaulerAssignment>(hauler).is_none());
+}
+
+#[test]
+fn resupply_ranks_with_charger_deliveries_by_charge_urgency() {
+    // An empty holding defender outranks a charger nobody is waiting on.
+    let (mut app, swarm, _) = supplied_app();
+    app.insert_resource(FieldResupply::default());
+    let charger = common::spawn_charger_at(&mut app, IVec2::ZERO, 0);
+    app.world_mut()
+        .entity_mut(charger)
+        .insert(OwnerSwarm(swarm));
+    let empty = spawn_holding_defender(&mut app, Vec2::new(20.0, 0.0), 0.0);
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+    app.update();
+    assert_eq!(
+        app.world().get::<HaulerAssignment>(hauler).unwrap().sink,
+        empty
+    );
+
+    // A defender waiting at an empty charger outranks one that is
+    // still holding above the rotation threshold.
+    let (mut app, swarm, _) = supplied_app();
+    app.insert_resource(FieldResupply::default());
+    let charger = common::spawn_charger_at(&mut app, IVec2::ZERO, 0);
+    app.world_mut()
+        .entity_mut(charger)
+        .insert(OwnerSwarm(swarm));
+    let waiting = common::spawn_defender_at(&mut app, Vec2::new(20.0, 0.0));
+    app.world_mut().entity_mut(waiting).insert((
+        Charge {
+            current: 0.0,
+            max: 1.0,
+        },
+        ChargerAssignment { charger },
+    ));
+    spawn_holding_defender(&mut app, Vec2::new(20.0, 0.0), 0.7);
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+    app.update();
+    assert_eq!(
+        app.world().get::<HaulerAssignment>(hauler).unwrap().sink,
+        charger
+    );
+}
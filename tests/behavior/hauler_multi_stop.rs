@@ -0,0 +1,173 @@
+//! Multi-stop hauler trips: topping a short load up at a second
+//! source, and splitting a load across two terminals.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        Cargo, Charge, Charger, ChargerAssignment, HaulerTrip, HaulerTripStop,
+        LogisticsReservation, OwnerSwarm, ProductionFacility,
+    },
+    resources::Stockpile,
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn owned_sink_stockpile(app: &mut App, swarm: Entity, pos: Vec2, amount: u32) -> Entity {
+    let stockpile = common::spawn_sink_stockpile(app, pos, amount, 100);
+    app.world_mut()
+        .entity_mut(stockpile)
+        .insert(OwnerSwarm(swarm));
+    stockpile
+}
+
+fn owned_facility(app: &mut App, swarm: Entity, pos: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            ProductionFacility::new(),
+            OwnerSwarm(swarm),
+            Transform::from_translation(pos.extend(0.0)),
+        ))
+        .id()
+}
+
+fn trip_stops(app: &mut App) -> Vec<(Entity, LogisticsReservation)> {
+    app.world_mut()
+        .query_filtered::<(Entity, &LogisticsReservation), With<HaulerTripStop>>()
+        .iter(app.world())
+        .map(|(entity, reservation)| (entity, *reservation))
+        .collect()
+}
+
+fn input_amount(app: &App, facility: Entity) -> u32 {
+    app.world()
+        .get::<ProductionFacility>(facility)
+        .expect("facility")
+        .input_amount
+}
+
+fn stockpile_amount(app: &App, stockpile: Entity) -> u32 {
+    app.world()
+        .get::<Stockpile>(stockpile)
+        .expect("stockpile")
+        .amount
+}
+
+#[test]
+fn short_source_is_topped_up_at_a_nearby_source_before_delivery() {
+    let mut app = common::sim_app_with_gather_haul();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let near = owned_sink_stockpile(&mut app, swarm, Vec2::new(10.0, 0.0), 8);
+    let second = owned_sink_stockpile(&mut app, swarm, Vec2::new(10.0, 80.0), 10);
+    let facility = owned_facility(&mut app, swarm, Vec2::new(200.0, 0.0));
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    app.update();
+
+    let reservation = *app
+        .world()
+        .get::<LogisticsReservation>(hauler)
+        .expect("hauler takes the short leg");
+    assert_eq!(
+        (reservation.source, reservation.destination),
+        (near, facility)
+    );
+    assert_eq!(reservation.amount, 8);
+    let stops = trip_stops(&mut app);
+    assert_eq!(stops.len(), 1);
+    let (stop, pickup) = stops[0];
+    assert_eq!((pickup.source, pickup.destination), (second, facility));
+    assert_eq!(pickup.amount, 10);
+    assert_eq!(
+        app.world().get::<HaulerTrip>(hauler).expect("trip").pickups,
+        vec![stop]
+    );
+
+    let mut first_delivery = None;
+    for _ in 0..300 {
+        app.update();
+        let delivered = input_amount(&app, facility);
+        if delivered > 0 {
+            let carried = app
+                .world()
+                .get::<Cargo>(hauler)
+                .map_or(0, |cargo| cargo.amount);
+            first_delivery = Some(delivered + carried);
+            break;
+        }
+    }
+    assert_eq!(
+        first_delivery,
+        Some(18),
+        "both pickups arrive in one delivery"
+    );
+    assert_eq!(stockpile_amount(&app, near), 0);
+    assert_eq!(stockpile_amount(&app, second), 0);
+    assert!(
+        trip_stops(&mut app).is_empty(),
+        "reached stops are released"
+    );
+}
+
+#[test]
+fn load_is_split_between_a_charger_and_a_nearby_facility() {
+    let mut app = common::sim_app_with_gather_haul();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let source = owned_sink_stockpile(&mut app, swarm, Vec2::new(10.0, 0.0), 100);
+    let charger = common::spawn_charger_at(&mut app, IVec2::ZERO, 8);
+    app.world_mut()
+        .entity_mut(charger)
+        .insert(OwnerSwarm(swarm));
+    let charger_pos = common::cell_world_center(IVec2::ZERO);
+    let facility = owned_facility(&mut app, swarm, charger_pos + Vec2::new(120.0, 0.0));
+    // A half-charged defender waiting on the charger makes it the
+    // urgent terminal, but it only needs 4 more minerals.
+    let defender = common::spawn_defender_at(&mut app, charger_pos);
+    app.world_mut().entity_mut(defender).insert((
+        Charge {
+            current: 0.5,
+            max: 1.0,
+        },
+        ChargerAssignment { charger },
+    ));
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    app.update();
+
+    let reservation = *app
+        .world()
+        .get::<LogisticsReservation>(hauler)
+        .expect("hauler serves the charger first");
+    assert_eq!(reservation.destination, charger);
+    assert_eq!(reservation.amount, 20, "the whole trip is loaded up front");
+    assert_eq!(reservation.destination_remaining, 4);
+    let stops = trip_stops(&mut app);
+    assert_eq!(stops.len(), 1);
+    let (_, dropoff) = stops[0];
+    assert_eq!((dropoff.source, dropoff.destination), (source, facility));
+    assert_eq!(
+        dropoff.source_remaining, 0,
+        "the first leg claims the source"
+    );
+    assert_eq!(dropoff.destination_remaining, 16);
+
+    let mut facility_share = None;
+    for _ in 0..300 {
+        app.update();
+        if input_amount(&app, facility) > 0 {
+            let carried = app
+                .world()
+                .get::<Cargo>(hauler)
+                .map_or(0, |cargo| cargo.amount);
+            facility_share = Some(input_amount(&app, facility) + carried);
+            break;
+        }
+    }
+    assert_eq!(facility_share, Some(16));
+    assert_eq!(
+        app.world().get::<Charger>(charger).expect("charger").amount,
+        12,
+        "the charger received only its emergency share"
+    );
+    assert_eq!(stockpile_amount(&app, source), 80);
+}
@@ -0,0 +1,133 @@
+//! Heatmap overlay sampling from the running simulation.
+//!
+//! Each test selects one layer and reads [`HeatmapField`], the CPU side
+//! of the overlay.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    heatmap_overlay::{HeatmapField, HeatmapLayer, HeatmapOverlayPlugin, HeatmapOverlaySettings},
+    intent::{IntentGrid, IntentKind},
+    nanobot::{ALLOCATION_REGION_CELLS, DefendPressure, OpportunityCategory},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn select(app: &mut App, layer: Option<HeatmapLayer>) {
+    app.world_mut()
+        .resource_mut::<HeatmapOverlaySettings>()
+        .layer = layer;
+}
+
+fn field(app: &App) -> &HeatmapField {
+    app.world().resource::<HeatmapField>()
+}
+
+/// A painted Gather cell at the origin with a deposit and somewhere
+/// to deliver, but no Worker to take the work.
+fn unclaimed_gather_work(app: &mut App) {
+    let center = common::cell_world_center(IVec2::ZERO);
+    assert!(
+        app.world_mut()
+            .resource_mut::<IntentGrid>()
+            .paint(IVec2::ZERO, IntentKind::Gather)
+    );
+    common::spawn_deposit(app, center, 50);
+    common::spawn_stockpile(app, center, 0, 100);
+}
+
+#[test]
+fn crowding_counts_nanobots_per_cell() {
+    let mut app = common::sim_app_with_defend();
+    app.add_plugins(HeatmapOverlayPlugin);
+    let cell = IVec2::new(2, 1);
+    for _ in 0..3 {
+        common::spawn_worker_at(&mut app, common::cell_world_center(cell));
+    }
+    select(&mut app, Some(HeatmapLayer::Crowding));
+
+    app.update();
+    app.update();
+
+    let field = field(&app);
+    assert_eq!(field.layer(), Some(HeatmapLayer::Crowding));
+    assert_eq!(field.value(cell), 3.0);
+    assert_eq!(field.max(), 3.0);
+    assert_eq!(field.value(IVec2::ZERO), 0.0);
+}
+
+#[test]
+fn threat_shows_defend_pressure_above_baseline() {
+    let mut app = common::sim_app();
+    app.add_plugins(HeatmapOverlayPlugin);
+    let mut pressure = DefendPressure::default();
+    pressure.set(IVec2::new(4, 4), 3.0);
+    app.insert_resource(pressure);
+    select(&mut app, Some(HeatmapLayer::Threat));
+
+    app.update();
+
+    assert_eq!(field(&app).value(IVec2::new(4, 4)), 2.0);
+}
+
+#[test]
+fn category_pressure_fills_the_whole_region() {
+    let mut app = common::sim_app_with_gather();
+    app.add_plugins(HeatmapOverlayPlugin);
+    unclaimed_gather_work(&mut app);
+    select(
+        &mut app,
+        Some(HeatmapLayer::Pressure(OpportunityCategory::Gather)),
+    );
+
+    app.update();
+    app.update();
+
+    let field = field(&app);
+    let far_corner = IVec2::splat(ALLOCATION_REGION_CELLS - 1);
+    assert!(field.value(IVec2::ZERO) > 0.0);
+    assert_eq!(field.value(far_corner), field.value(IVec2::ZERO));
+    assert_eq!(
+        field.value(IVec2::new(ALLOCATION_REGION_CELLS, 0)),
+        0.0,
+        "the neighbouring region has no gather work"
+    );
+}
+
+#[test]
+fn starvation_grows_while_work_waits_for_a_suitable_bot() {
+    let mut app = common::sim_app_with_gather();
+    app.add_plugins(HeatmapOverlayPlugin);
+    unclaimed_gather_work(&mut app);
+    // An idle Defender keeps the swarm allocating without being able
+    // to take the Gather work.
+    common::spawn_defender_at(&mut app, Vec2::ZERO);
+    select(&mut app, Some(HeatmapLayer::Starvation));
+
+    app.update();
+    let early = field(&app).value(IVec2::ZERO);
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let late = field(&app).value(IVec2::ZERO);
+    assert!(late > early, "age grew from {early} to {late}");
+}
+
+#[test]
+fn turning_the_overlay_off_clears_the_field() {
+    let mut app = common::sim_app_with_defend();
+    app.add_plugins(HeatmapOverlayPlugin);
+    common::spawn_worker_at(&mut app, Vec2::ZERO);
+    select(&mut app, Some(HeatmapLayer::Crowding));
+    app.update();
+    app.update();
+    assert!(field(&app).max() > 0.0);
+
+    select(&mut app, None);
+    app.update();
+
+    let field = field(&app);
+    assert_eq!(field.layer(), None);
+    assert_eq!(field.iter().count(), 0);
+}
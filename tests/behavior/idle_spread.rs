@@ -15,7 +15,8 @@
 //! - A Worker spreads across the merged Gather+Build region; a
 //!   Defender ignores Gather paint (Defend-only); the region
 //!   boundary follows `fit_for == 1.0`.
-//! - A stranded idle bot drifts toward the nearest type-fit cell.
+//! - A stranded idle bot drifts toward the nearest type-fit cell, and
+//!   the cached fit cells follow later repaints.
 //!
 //! The isolated-nudge tests register `idle_spread_system` alone on a
 //! [`common::minimal_app`] so the observed `VelocityComponent` is
@@ -34,7 +35,7 @@ use top_down_2d_rts_prototype_nano_swarm::{
 #[path = "../common/mod.rs"]
 mod common;
 
-/// World center of `cell`, matching `ai::get_world_from_zone`.
+/// World center of `cell`, matching `nanobot::get_world_from_zone`.
 fn center(cell: IVec2) -> Vec2 {
     common::cell_world_center(cell)
 }
@@ -306,6 +307,37 @@ fn stranded_idle_bot_drifts_toward_nearest_fit_cell() {
     );
 }
 
+#[test]
+fn stranded_bot_follows_repainted_fit_cells_across_ticks() {
+    // The fit-cell cache is updated from the grid's dirty cells, so
+    // erasing the nearest fit cell and painting another on the far
+    // side must flip the stranded drift on the next tick.
+    let mut app = spread_only_app();
+    paint(&mut app, IVec2::new(2, 0), IntentKind::Gather);
+    let bot = common::spawn_worker_at(&mut app, center(IVec2::new(0, 0)));
+
+    app.update();
+    let velocity = app.world().get::<VelocityComponent>(bot).unwrap().value;
+    assert!(velocity.x > 0.0, "drift east first; got {velocity:?}");
+
+    {
+        let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+        grid.erase(IVec2::new(2, 0), IntentKind::Gather);
+        grid.add(IVec2::new(-3, 0), IntentKind::Gather);
+    }
+    app.world_mut()
+        .get_mut::<VelocityComponent>(bot)
+        .unwrap()
+        .value = Vec2::ZERO;
+    app.update();
+
+    let velocity = app.world().get::<VelocityComponent>(bot).unwrap().value;
+    assert!(
+        velocity.x < 0.0,
+        "drift west after the repaint; got {velocity:?}"
+    );
+}
+
 #[test]
 fn stranded_bot_with_no_fit_paint_anywhere_stays_put() {
     // If no type-fit paint exists anywhere on the grid, the stranded
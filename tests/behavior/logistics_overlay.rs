@@ -0,0 +1,229 @@
+//! Logistics network overlay: reservation lines, last-minute flow
+//! lines, and starved terminal highlights.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    logistics_overlay::{
+        LOGISTICS_OVERLAY_TOGGLE_KEY, LogisticsOverlayMarker, LogisticsOverlayPlugin,
+        LogisticsOverlaySettings,
+    },
+    nanobot::{
+        LogisticsFlowHistory, LogisticsReservation, OwnerSwarm, PlannedKind, PlannedMaterial,
+        PlannedStructure, ProductionFacility, TelemetryPlugin, world_to_cell,
+    },
+    resources::ResourceKind,
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn overlay_app() -> App {
+    let mut app = common::sim_app_with_gather_haul();
+    app.add_plugins((TelemetryPlugin, LogisticsOverlayPlugin));
+    app
+}
+
+fn show_overlay(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<LogisticsOverlaySettings>()
+        .visible = true;
+}
+
+/// Press the toggle key for exactly one update.
+fn press_toggle(app: &mut App) {
+    let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    input.reset_all();
+    input.press(LOGISTICS_OVERLAY_TOGGLE_KEY);
+    app.update();
+}
+
+fn markers(app: &mut App) -> Vec<(LogisticsOverlayMarker, Transform)> {
+    app.world_mut()
+        .query::<(&LogisticsOverlayMarker, &Transform)>()
+        .iter(app.world())
+        .map(|(marker, transform)| (*marker, *transform))
+        .collect()
+}
+
+fn markers_of(
+    app: &mut App,
+    filter: impl Fn(&LogisticsOverlayMarker) -> bool,
+) -> Vec<LogisticsOverlayMarker> {
+    markers(app)
+        .into_iter()
+        .map(|(marker, _)| marker)
+        .filter(|marker| filter(marker))
+        .collect()
+}
+
+/// A source stockpile and a facility owned by a fresh swarm.
+fn source_and_facility(app: &mut App) -> (Entity, Entity) {
+    let swarm = common::spawn_swarm_at(app, Vec2::ZERO);
+    let source = common::spawn_sink_stockpile(app, Vec2::new(10.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    let facility = app
+        .world_mut()
+        .spawn((
+            ProductionFacility::new(),
+            OwnerSwarm(swarm),
+            Transform::from_xyz(60.0, 0.0, 0.0),
+        ))
+        .id();
+    (source, facility)
+}
+
+#[test]
+fn overlay_is_hidden_until_toggled_by_key() {
+    let mut app = overlay_app();
+    let source = common::spawn_stockpile(&mut app, Vec2::ZERO, 10, 10);
+    let sink = common::spawn_sink_stockpile(&mut app, Vec2::new(100.0, 0.0), 0, 10);
+    app.world_mut().spawn(LogisticsReservation::new(
+        source,
+        sink,
+        ResourceKind::Minerals,
+        5,
+    ));
+
+    app.update();
+    assert!(markers(&mut app).is_empty(), "hidden by default");
+
+    app.init_resource::<ButtonInput<KeyCode>>();
+    press_toggle(&mut app);
+    assert!(app.world().resource::<LogisticsOverlaySettings>().visible);
+    assert_eq!(markers(&mut app).len(), 1);
+
+    press_toggle(&mut app);
+    assert!(markers(&mut app).is_empty(), "toggling off clears markers");
+}
+
+#[test]
+fn reservation_is_drawn_from_source_to_destination() {
+    let mut app = overlay_app();
+    show_overlay(&mut app);
+    let source = common::spawn_stockpile(&mut app, Vec2::ZERO, 10, 10);
+    let sink = common::spawn_sink_stockpile(&mut app, Vec2::new(100.0, 0.0), 0, 10);
+    let carrier = app
+        .world_mut()
+        .spawn(LogisticsReservation::new(
+            source,
+            sink,
+            ResourceKind::Minerals,
+            5,
+        ))
+        .id();
+
+    app.update();
+    app.update();
+
+    let markers = markers(&mut app);
+    assert_eq!(markers.len(), 1);
+    let (marker, transform) = markers[0];
+    assert_eq!(marker, LogisticsOverlayMarker::Reservation { carrier });
+    assert_eq!(transform.translation.truncate(), Vec2::new(50.0, 0.0));
+    assert_eq!(transform.scale.x, 100.0);
+
+    app.world_mut()
+        .entity_mut(carrier)
+        .remove::<LogisticsReservation>();
+    app.update();
+    app.update();
+    assert!(
+        markers_of(&mut app, |marker| matches!(
+            marker,
+            LogisticsOverlayMarker::Reservation { .. }
+        ))
+        .is_empty(),
+        "released reservations are no longer drawn"
+    );
+}
+
+#[test]
+fn hauler_deliveries_draw_a_flow_line_for_the_pair() {
+    let mut app = overlay_app();
+    show_overlay(&mut app);
+    let (source, facility) = source_and_facility(&mut app);
+    common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    let mut delivered = false;
+    for _ in 0..200 {
+        app.update();
+        if app
+            .world()
+            .resource::<LogisticsFlowHistory>()
+            .totals()
+            .contains_key(&(source, facility))
+        {
+            delivered = true;
+            break;
+        }
+    }
+    assert!(delivered, "hauler delivered to the facility");
+    app.update();
+
+    assert_eq!(
+        markers_of(&mut app, |marker| matches!(
+            marker,
+            LogisticsOverlayMarker::Flow { .. }
+        )),
+        vec![LogisticsOverlayMarker::Flow {
+            source,
+            sink: facility,
+        }]
+    );
+}
+
+#[test]
+fn terminal_waiting_past_threshold_is_highlighted() {
+    let mut app = overlay_app();
+    show_overlay(&mut app);
+    app.world_mut()
+        .resource_mut::<LogisticsOverlaySettings>()
+        .starved_waiting_ticks = 3;
+    let (_, facility) = source_and_facility(&mut app);
+
+    for _ in 0..6 {
+        app.update();
+    }
+
+    assert_eq!(
+        markers_of(&mut app, |marker| matches!(
+            marker,
+            LogisticsOverlayMarker::StarvedTerminal { .. }
+        )),
+        vec![LogisticsOverlayMarker::StarvedTerminal { terminal: facility }]
+    );
+}
+
+#[test]
+fn planned_site_waiting_on_material_is_highlighted() {
+    let mut app = overlay_app();
+    show_overlay(&mut app);
+    app.world_mut()
+        .resource_mut::<LogisticsOverlaySettings>()
+        .starved_waiting_ticks = 3;
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let sink = common::spawn_sink_stockpile(&mut app, Vec2::new(10.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(sink).insert(OwnerSwarm(swarm));
+    let site_pos = Vec2::new(60.0, 0.0);
+    let site = app
+        .world_mut()
+        .spawn((
+            PlannedStructure::new(PlannedKind::Charger, world_to_cell(site_pos)),
+            PlannedMaterial::new(ResourceKind::Minerals, PlannedKind::Charger.mineral_cost()),
+            OwnerSwarm(swarm),
+            Transform::from_translation(site_pos.extend(0.0)),
+        ))
+        .id();
+
+    for _ in 0..6 {
+        app.update();
+    }
+
+    assert_eq!(
+        markers_of(&mut app, |marker| matches!(
+            marker,
+            LogisticsOverlayMarker::StarvedTerminal { .. }
+        )),
+        vec![LogisticsOverlayMarker::StarvedTerminal { terminal: site }]
+    );
+}
@@ -0,0 +1,237 @@
+//! Maintenance as a consumable-material activity.
+//!
+//! With [`MaintenanceUpkeep`] present, owned structures get an upkeep
+//! depot, each maintenance shift spends minerals from it, haulers
+//! refill it from Sink Stockpiles, and an empty depot leaves the
+//! structure to decay. Spent and lost upkeep leaves the owner's ledger.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        HaulPlugin, HaulerAssignment, MAINTENANCE_NEEDS_THRESHOLD, MaintenanceUpkeep, OwnerSwarm,
+        Structure, SwarmId, UpkeepDepot, UpkeepFor, UpkeepStock,
+    },
+    resources::{ResourceKind, ResourceLedger},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn upkeep_app() -> App {
+    let mut app = common::sim_app_with_maintenance();
+    app.insert_resource(MaintenanceUpkeep::default());
+    app
+}
+
+fn spawn_owned_structure(app: &mut App, swarm: Entity, pos: Vec2) -> Entity {
+    let structure = common::spawn_structure_at(app, pos);
+    app.world_mut()
+        .entity_mut(structure)
+        .insert(OwnerSwarm(swarm));
+    structure
+}
+
+fn depot_of(app: &App, structure: Entity) -> Entity {
+    app.world()
+        .entity(structure)
+        .get::<UpkeepDepot>()
+        .expect("owned structure has an upkeep depot")
+        .0
+}
+
+fn stock(app: &App, depot: Entity) -> UpkeepStock {
+    *app.world().entity(depot).get::<UpkeepStock>().unwrap()
+}
+
+fn make_stale(app: &mut App, structure: Entity) {
+    app.world_mut()
+        .entity_mut(structure)
+        .get_mut::<Structure>()
+        .unwrap()
+        .ticks_since_maintained = MAINTENANCE_NEEDS_THRESHOLD;
+}
+
+#[test]
+fn owned_structure_gets_an_empty_depot_and_unowned_does_not() {
+    let mut app = upkeep_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let owned = spawn_owned_structure(&mut app, swarm, Vec2::ZERO);
+    let unowned = common::spawn_structure_at(&mut app, Vec2::new(200.0, 0.0));
+
+    app.update();
+
+    let depot = depot_of(&app, owned);
+    assert_eq!(stock(&app, depot).amount, 0, "depots start empty");
+    assert_eq!(
+        app.world().entity(depot).get::<UpkeepFor>(),
+        Some(&UpkeepFor(owned))
+    );
+    assert!(app.world().entity(unowned).get::<UpkeepDepot>().is_none());
+}
+
+#[test]
+fn no_depots_without_the_upkeep_resource() {
+    let mut app = common::sim_app_with_maintenance();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let structure = spawn_owned_structure(&mut app, swarm, Vec2::ZERO);
+
+    app.update();
+
+    assert!(app.world().entity(structure).get::<UpkeepDepot>().is_none());
+}
+
+#[test]
+fn empty_depot_blocks_maintenance() {
+    let mut app = upkeep_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let structure = spawn_owned_structure(&mut app, swarm, Vec2::ZERO);
+    app.update();
+    make_stale(&mut app, structure);
+    common::spawn_worker_at(&mut app, Vec2::ZERO);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let buffer = app
+        .world()
+        .entity(structure)
+        .get::<Structure>()
+        .unwrap()
+        .ticks_since_maintained;
+    assert!(
+        buffer > MAINTENANCE_NEEDS_THRESHOLD,
+        "an unfunded structure is not maintained; buffer {buffer}"
+    );
+}
+
+#[test]
+fn funded_shift_spends_upkeep_and_resets_the_buffer() {
+    let mut app = upkeep_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let structure = spawn_owned_structure(&mut app, swarm, Vec2::ZERO);
+    app.update();
+    let depot = depot_of(&app, structure);
+    app.world_mut()
+        .entity_mut(depot)
+        .get_mut::<UpkeepStock>()
+        .unwrap()
+        .amount = 5;
+    make_stale(&mut app, structure);
+    common::spawn_worker_at(&mut app, Vec2::ZERO);
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let upkeep = MaintenanceUpkeep::default();
+    assert_eq!(stock(&app, depot).amount, 5 - upkeep.per_shift);
+    let buffer = app
+        .world()
+        .entity(structure)
+        .get::<Structure>()
+        .unwrap()
+        .ticks_since_maintained;
+    assert!(buffer < MAINTENANCE_NEEDS_THRESHOLD);
+}
+
+#[test]
+fn hauler_supplies_an_empty_depot_from_a_sink_stockpile() {
+    let mut app = upkeep_app();
+    app.add_plugins(HaulPlugin);
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let source = common::spawn_sink_stockpile(&mut app, Vec2::new(10.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    let structure = spawn_owned_structure(&mut app, swarm, Vec2::new(40.0, 0.0));
+    app.update();
+    let depot = depot_of(&app, structure);
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    // Acquisition runs on its own cadence; wait for the first pass.
+    let mut assignment = None;
+    for _ in 0..20 {
+        app.update();
+        assignment = app
+            .world()
+            .entity(hauler)
+            .get::<HaulerAssignment>()
+            .copied();
+        if assignment.is_some() {
+            break;
+        }
+    }
+
+    let assignment = assignment.expect("an empty depot is terminal demand");
+    assert_eq!(assignment.source, source);
+    assert_eq!(assignment.sink, depot);
+
+    for _ in 0..200 {
+        app.update();
+    }
+    let stocked = stock(&app, depot);
+    assert_eq!(stocked.kind, ResourceKind::Minerals);
+    assert!(stocked.amount > 0, "hauler delivered into the depot");
+    assert!(stocked.amount <= stocked.capacity);
+}
+
+#[test]
+fn depot_despawns_with_its_structure() {
+    let mut app = upkeep_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let structure = spawn_owned_structure(&mut app, swarm, Vec2::ZERO);
+    app.update();
+    let depot = depot_of(&app, structure);
+
+    app.world_mut().entity_mut(structure).despawn();
+    app.update();
+
+    assert!(app.world().get_entity(depot).is_err());
+}
+
+#[test]
+fn ledger_tracks_depot_stock_through_shifts_and_despawn() {
+    let mut app = upkeep_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let structure = spawn_owned_structure(&mut app, swarm, Vec2::ZERO);
+    app.update();
+    let depot = depot_of(&app, structure);
+    app.world_mut()
+        .entity_mut(depot)
+        .get_mut::<UpkeepStock>()
+        .unwrap()
+        .amount = 7;
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        7,
+    );
+    common::spawn_worker_at(&mut app, Vec2::ZERO);
+
+    let upkeep = MaintenanceUpkeep::default();
+    for shift in 1..=2 {
+        make_stale(&mut app, structure);
+        for _ in 0..20 {
+            app.update();
+        }
+        let stocked = stock(&app, depot).amount;
+        assert_eq!(stocked, 7 - shift * upkeep.per_shift);
+        assert_eq!(
+            app.world()
+                .resource::<ResourceLedger>()
+                .total_for(SwarmId::PLAYER, ResourceKind::Minerals),
+            stocked,
+            "shift {shift} spent upkeep out of the ledger"
+        );
+    }
+
+    app.world_mut().entity_mut(structure).despawn();
+    app.update();
+
+    assert_eq!(
+        app.world()
+            .resource::<ResourceLedger>()
+            .total(ResourceKind::Minerals),
+        0,
+        "a despawned depot's stock leaves the ledger"
+    );
+}
@@ -7,7 +7,7 @@
 //! Planned Structure lifecycle. Scenario seed structures may
 //! still spawn completed at startup, but paint and demand alone
 //! must never produce a completed structure (Stockpile,
-//! ProductionFacility, Charger, or the legacy `BuildSite`).
+//! ProductionFacility, or Charger).
 //!
 //! Each test isolates one demand source -- Gather paint, Build
 //! paint, production demand, and Defend demand -- and asserts
@@ -100,8 +100,8 @@ fn gather_paint_does_not_instant_spawn_any_support_structure() {
     // for a Worker to build the plan.
     //
     // The test also asserts that no other kind of completed
-    // support structure (ProductionFacility, Charger,
-    // legacy BuildSite) appears as a side effect of Gather
+    // support structure (ProductionFacility, Charger)
+    // appears as a side effect of Gather
     // paint, so a regression that re-introduces a stray
     // auto-spawner would be caught here.
     let mut app = common::sim_app_with_gather_planned();
@@ -160,8 +160,8 @@ fn build_paint_does_not_instant_spawn_any_support_structure() {
     // nothing, when no swarm exists to plan for).
     //
     // The test also pins the absence of any other completed
-    // support structure (ProductionFacility, Charger,
-    // legacy `BuildSite`) so a regression that re-adds a
+    // support structure (ProductionFacility, Charger) so
+    // a regression that re-adds a
     // spontaneous auto-spawner for any kind fails here.
     let mut app = common::sim_app_with_planned();
     let cell = IVec2::new(0, 0);
@@ -204,26 +204,12 @@ fn build_paint_does_not_instant_spawn_any_support_structure() {
 }
 
 #[test]
-fn build_paint_with_swarm_does_not_spawn_legacy_buildsite() {
+fn build_paint_with_swarm_spawns_no_structure_or_plan() {
     // Acceptance: "demand-driven support structures should
-    // always pass through Planned Structure first." The
-    // legacy `BuildSite`/`Structure` system (issue #10) was
-    // the previous "instant Build-paint spawn" path. It is
-    // not part of the PRD-named kind set (Source / Sink /
-    // Production / Charger) and must not appear as a side
-    // effect of Build paint any more. The new Sink Stockpile
-    // plan covers the demand; the legacy system has been
-    // disabled.
-    //
-    // The helper intentionally does NOT include the legacy
-    // `BuildPlugin`, so a regression that re-adds the
-    // `BuildPlugin` to the production app would not be
-    // visible here -- the regression suite covers the
-    // completed-structure contract (no instant Stockpile,
-    // Facility, or Charger) in the other tests in this
-    // file. This test pins the related contract that the
-    // planned-structure system does not produce any
-    // `BuildSite` entity of its own.
+    // always pass through Planned Structure first." Build
+    // paint used to spawn a structure site directly; that
+    // path is gone, and the planned-structure lifecycle is
+    // the only way a support structure reaches the world.
     let mut app = common::sim_app_with_build_planned();
     let cell = IVec2::new(0, 0);
     let center = common::cell_world_center(cell);
@@ -234,16 +220,13 @@ fn build_paint_with_swarm_does_not_spawn_legacy_buildsite() {
         app.update();
     }
 
-    // The legacy `BuildSite` component, if it still
-    // auto-spawned from Build paint, would be visible here.
-    let build_sites = {
+    let stockpiles = {
         let world = app.world_mut();
-        let mut q = world.query::<&top_down_2d_rts_prototype_nano_swarm::nanobot::BuildSite>();
-        q.iter(world).count()
+        completed_stockpile_count(world)
     };
     assert_eq!(
-        build_sites, 0,
-        "Build paint must not spawn a legacy BuildSite; only PlannedStructures are allowed"
+        stockpiles, 0,
+        "Build paint must not spawn a completed Stockpile"
     );
     // Issue #34: no plan emerges from Build paint alone.
     let plans = {
@@ -366,19 +349,10 @@ fn all_demand_sources_share_zero_completed_structures() {
     // and assert the world has zero completed support
     // structures of any kind. A future regression that adds
     // a new "spontaneous spawn" path for any of the four
-    // PRD kinds (or the legacy BuildSite) will trip this
-    // test in one place. The test name in the failure
-    // message makes the offending demand source obvious
-    // because the assertion runs after each tick of every
-    // source.
-    //
-    // The builder does NOT include the legacy `BuildPlugin`;
-    // the regression that this test catches is "any demand
-    // source produces a completed support structure of any
-    // kind without going through the planned-structure
-    // chain". A regression that re-adds `BuildPlugin` to
-    // the production app would have to be caught by code
-    // review, not by this test.
+    // PRD kinds will trip this test in one place. The test
+    // name in the failure message makes the offending demand
+    // source obvious because the assertion runs after each
+    // tick of every source.
     let mut app = common::sim_app_with_build_planned();
     // Add the production + charge + defend plugins on top
     // so every demand source is exercised. The
@@ -427,10 +401,6 @@ fn all_demand_sources_share_zero_completed_structures() {
         let stockpiles = completed_stockpile_count(world);
         let facilities = completed_facility_count(world);
         let chargers = completed_charger_count(world);
-        let legacy_sites = {
-            let mut q = world.query::<&top_down_2d_rts_prototype_nano_swarm::nanobot::BuildSite>();
-            q.iter(world).count()
-        };
         assert_eq!(
             stockpiles, 0,
             "any demand tick must not spawn a completed Stockpile; got {stockpiles}"
@@ -443,10 +413,6 @@ fn all_demand_sources_share_zero_completed_structures() {
             chargers, 0,
             "any demand tick must not spawn a completed Charger; got {chargers}"
         );
-        assert_eq!(
-            legacy_sites, 0,
-            "any demand tick must not spawn a legacy BuildSite; got {legacy_sites}"
-        );
     }
 }
 
@@ -198,7 +198,6 @@ fn opponent_swarm_uses_own_fixed_priority_through_same_production_systems() {
                 nanobot: Nanobot {},
                 nanobot_type: NanobotType::Worker,
                 velocity: VelocityComponent::default(),
-                ai_state: Default::default(),
                 health: Health::default(),
                 swarm_member: top_down_2d_rts_prototype_nano_swarm::nanobot::SwarmMember::new(
                     top_down_2d_rts_prototype_nano_swarm::nanobot::SwarmId::PLAYER,
@@ -0,0 +1,220 @@
+//! Construction material delivered to planned sites.
+//!
+//! With [`ConstructionCost`] present, owned planned structures carry a
+//! [`PlannedMaterial`] stock sized by their kind's mineral cost.
+//! Haulers deliver into the site from Sink Stockpiles, and worker time
+//! only advances as far as the delivered material pays for. A finished
+//! build spends its material out of the owner's ledger; an erased plan
+//! drops it as a cache.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        Charger, ConstructionCost, OwnerSwarm, PlannedKind, PlannedMaterial, PlannedStructure,
+        PlannedStructurePlugin, PlannedStructureProgress, ReclaimedCache, SwarmId,
+    },
+    resources::{ResourceKind, ResourceLedger, Stockpile},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn material_app() -> App {
+    let mut app = common::sim_app_with_gather_haul();
+    app.add_plugins(PlannedStructurePlugin);
+    app.insert_resource(ConstructionCost::default());
+    app
+}
+
+fn owned_planned_charger(app: &mut App, swarm: Entity, cell: IVec2) -> Entity {
+    let planned = common::spawn_planned_structure_of_kind_at_cell(app, cell, PlannedKind::Charger);
+    app.world_mut()
+        .entity_mut(planned)
+        .insert(OwnerSwarm(swarm));
+    planned
+}
+
+fn material(app: &App, planned: Entity) -> Option<PlannedMaterial> {
+    app.world().get::<PlannedMaterial>(planned).copied()
+}
+
+fn work_remaining(app: &App, planned: Entity) -> u32 {
+    app.world()
+        .get::<PlannedStructure>(planned)
+        .expect("still planned")
+        .work_remaining
+}
+
+#[test]
+fn owned_plan_gets_an_empty_stock_and_unowned_does_not() {
+    let mut app = material_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let owned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+    let unowned = common::spawn_planned_structure_at_cell(&mut app, IVec2::new(2, 0));
+
+    app.update();
+
+    assert_eq!(
+        material(&app, owned),
+        Some(PlannedMaterial::new(
+            ResourceKind::Minerals,
+            PlannedKind::Charger.mineral_cost()
+        ))
+    );
+    assert_eq!(material(&app, unowned), None);
+}
+
+#[test]
+fn no_stock_without_the_cost_resource() {
+    let mut app = common::sim_app_with_planned();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let planned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+
+    app.update();
+
+    assert_eq!(material(&app, planned), None);
+}
+
+#[test]
+fn unfunded_plan_gets_no_worker_time() {
+    let mut app = material_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let planned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+    common::spawn_worker_at(&mut app, common::cell_world_center(IVec2::ZERO));
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    assert_eq!(
+        work_remaining(&app, planned),
+        PlannedKind::Charger.build_work_ticks()
+    );
+}
+
+#[test]
+fn partial_material_pays_for_part_of_the_build() {
+    let mut app = material_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let planned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+    let cost = PlannedKind::Charger.mineral_cost();
+    app.world_mut().entity_mut(planned).insert(PlannedMaterial {
+        delivered: cost / 2,
+        ..PlannedMaterial::new(ResourceKind::Minerals, cost)
+    });
+    let worker = common::spawn_worker_at(&mut app, common::cell_world_center(IVec2::ZERO));
+
+    for _ in 0..40 {
+        app.update();
+    }
+
+    let budget = PlannedKind::Charger.build_work_ticks();
+    assert_eq!(work_remaining(&app, planned), budget - budget / 2);
+    assert!(
+        app.world()
+            .get::<PlannedStructureProgress>(worker)
+            .is_none(),
+        "the worker is released while the site waits on material"
+    );
+    assert_eq!(
+        app.world()
+            .get::<PlannedStructure>(planned)
+            .unwrap()
+            .active_worker,
+        None
+    );
+}
+
+#[test]
+fn hauled_material_lets_the_worker_finish_the_build() {
+    let mut app = material_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let center = common::cell_world_center(IVec2::ZERO);
+    let source = common::spawn_sink_stockpile(&mut app, center + Vec2::new(150.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    let planned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+    common::spawn_hauler_at(&mut app, center + Vec2::new(150.0, 40.0));
+    common::spawn_worker_at(&mut app, center);
+
+    let mut completed = false;
+    for _ in 0..300 {
+        app.update();
+        if app.world().get::<Charger>(planned).is_some() {
+            completed = true;
+            break;
+        }
+    }
+
+    assert!(completed, "the planned charger is built once paid for");
+    assert_eq!(material(&app, planned), None, "the material is spent");
+    let cost = PlannedKind::Charger.mineral_cost();
+    assert_eq!(
+        app.world().get::<Stockpile>(source).unwrap().amount,
+        100 - cost,
+        "exactly the construction cost left the stockpile"
+    );
+}
+
+/// Stock `planned` with `delivered` minerals already in the player's
+/// custody, as a hauler delivery would leave it.
+fn deliver(app: &mut App, planned: Entity, delivered: u32) {
+    let cost = PlannedKind::Charger.mineral_cost();
+    app.world_mut().entity_mut(planned).insert(PlannedMaterial {
+        delivered,
+        ..PlannedMaterial::new(ResourceKind::Minerals, cost)
+    });
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        delivered,
+    );
+}
+
+fn ledger_total(app: &App) -> u32 {
+    app.world()
+        .resource::<ResourceLedger>()
+        .total(ResourceKind::Minerals)
+}
+
+#[test]
+fn completed_build_spends_its_material_out_of_the_ledger() {
+    let mut app = material_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let planned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+    deliver(&mut app, planned, PlannedKind::Charger.mineral_cost());
+    common::spawn_worker_at(&mut app, common::cell_world_center(IVec2::ZERO));
+
+    for _ in 0..40 {
+        app.update();
+    }
+
+    assert!(app.world().get::<Charger>(planned).is_some());
+    assert_eq!(ledger_total(&app), 0, "the build consumed the material");
+}
+
+#[test]
+fn erased_plan_drops_its_material_as_a_cache() {
+    let mut app = material_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let planned = owned_planned_charger(&mut app, swarm, IVec2::ZERO);
+    let delivered = PlannedKind::Charger.mineral_cost() / 2;
+    deliver(&mut app, planned, delivered);
+
+    app.world_mut().entity_mut(planned).despawn();
+    app.update();
+
+    let world = app.world_mut();
+    let caches: Vec<(Stockpile, Option<Entity>)> = world
+        .query_filtered::<(&Stockpile, Option<&OwnerSwarm>), With<ReclaimedCache>>()
+        .iter(world)
+        .map(|(stockpile, owner)| (*stockpile, owner.map(|owner| owner.0)))
+        .collect();
+    assert_eq!(caches.len(), 1);
+    assert_eq!(caches[0].0.amount, delivered);
+    assert_eq!(caches[0].1, Some(swarm), "the cache stays with the owner");
+    assert_eq!(
+        ledger_total(&app),
+        delivered,
+        "the dropped material stays in custody"
+    );
+}
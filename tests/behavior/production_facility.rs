@@ -583,9 +583,8 @@ fn additional_facility_plans_when_existing_busy_and_build_zone_free() {
     use top_down_2d_rts_prototype_nano_swarm::{
         intent::{IntentGrid, IntentKind},
         nanobot::{
-            DEFAULT_PLANNED_WORK_TICKS, OwnerSwarm, PRODUCTION_PRESSURE_TICKS, PlannedKind,
-            PlannedProductionTarget, PlannedStructure, SwarmId, completed_visual_color,
-            planned_visual_color,
+            OwnerSwarm, PRODUCTION_PRESSURE_TICKS, PlannedKind, PlannedProductionTarget,
+            PlannedStructure, SwarmId, completed_visual_color, planned_visual_color,
         },
     };
     let mut app = common::sim_app_with_production_planned();
@@ -681,17 +680,17 @@ fn additional_facility_plans_when_existing_busy_and_build_zone_free() {
     // The Worker must be placed AT the planned cell
     // center so the claim + arrive + work chain can
     // fire without a long walk. The build then
-    // completes in `DEFAULT_PLANNED_WORK_TICKS` ticks
+    // completes in `build_work_ticks()` ticks
     // of worker time.
     let worker = common::spawn_worker_at(&mut app, center);
 
     // 1 tick for claim + arrive (worker is already at
     // the cell, so the arrive system fires on the same
-    // tick as the claim), then `DEFAULT_PLANNED_WORK_TICKS`
+    // tick as the claim), then `build_work_ticks()`
     // ticks of work. The build completes on the
-    // `DEFAULT_PLANNED_WORK_TICKS + 1`-th tick. We do
+    // `build_work_ticks() + 1`-th tick. We do
     // Completion must remain idle until logistics pays a full cycle.
-    let build_ticks = 3 + DEFAULT_PLANNED_WORK_TICKS as usize;
+    let build_ticks = 3 + PlannedKind::ProductionFacility.build_work_ticks() as usize;
     for _ in 0..(build_ticks + 200) {
         app.update();
         if app
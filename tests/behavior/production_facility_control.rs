@@ -0,0 +1,242 @@
+//! Integration tests for per-facility production control.
+//!
+//! Covers the overrides layered on the intent-first default:
+//!   1. a specialized facility only builds its type from demand,
+//!   2. queued orders start ahead of demand,
+//!   3. a pinned type restarts after each cycle,
+//!   4. cancelling refunds the cycle cost to hopper and ledger,
+//!   5. a paid upgrade shortens the next cycle,
+//!   6. the facility panel writes commands for the selected facility.
+//!
+//! The facility helper unit tests live in `src/nanobot/production.rs`.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        FacilityAction, Nanobot, NanobotType, PRODUCTION_COST_PER_BOT, PRODUCTION_INPUT_CAPACITY,
+        PRODUCTION_UPGRADE_COST, ProductionFacility, ProductionFacilityCommand, ProductionPriority,
+        SwarmId, production_ticks_per_bot,
+    },
+    resources::{ResourceKind, ResourceLedger},
+    ui::{
+        FontsResource,
+        facility_panel::{
+            FacilityPanelButton, FacilityPanelSelection, facility_panel_click_system,
+            setup_facility_panel,
+        },
+    },
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn build_app() -> App {
+    let mut app = common::sim_app_with_production();
+    let mut priority = ProductionPriority::new();
+    priority.set_weight(NanobotType::Worker, 3);
+    app.insert_resource(priority);
+    app
+}
+
+fn facility(app: &App, entity: Entity) -> &ProductionFacility {
+    app.world().get::<ProductionFacility>(entity).unwrap()
+}
+
+fn send(app: &mut App, facility: Entity, action: FacilityAction) {
+    app.world_mut()
+        .write_message(ProductionFacilityCommand { facility, action });
+}
+
+fn count_type(app: &mut App, kind: NanobotType) -> usize {
+    let mut q = app
+        .world_mut()
+        .query_filtered::<&NanobotType, With<Nanobot>>();
+    q.iter(app.world()).filter(|t| **t == kind).count()
+}
+
+#[test]
+fn specialized_facility_ignores_other_demand() {
+    let mut app = build_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let entity = common::spawn_idle_facility_at(&mut app, Vec2::ZERO);
+    send(
+        &mut app,
+        entity,
+        FacilityAction::Specialize(Some(NanobotType::Hauler)),
+    );
+    app.world_mut()
+        .resource_mut::<ProductionPriority>()
+        .set_weight(NanobotType::Hauler, 1);
+
+    app.update();
+
+    assert_eq!(
+        facility(&app, entity).current_target,
+        Some(NanobotType::Hauler),
+        "Worker leads priority but the facility only builds Haulers"
+    );
+}
+
+#[test]
+fn queued_order_starts_before_demand() {
+    let mut app = build_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let entity = common::spawn_idle_facility_at(&mut app, Vec2::ZERO);
+    send(
+        &mut app,
+        entity,
+        FacilityAction::Enqueue(NanobotType::Defender),
+    );
+    send(
+        &mut app,
+        entity,
+        FacilityAction::Enqueue(NanobotType::Hauler),
+    );
+
+    app.update();
+
+    let f = facility(&app, entity);
+    assert_eq!(f.current_target, Some(NanobotType::Defender));
+    assert_eq!(
+        f.queue.iter().copied().collect::<Vec<_>>(),
+        vec![NanobotType::Hauler]
+    );
+}
+
+#[test]
+fn pinned_type_restarts_after_cycle() {
+    let mut app = build_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let entity = common::spawn_idle_facility_at(&mut app, Vec2::ZERO);
+    send(
+        &mut app,
+        entity,
+        FacilityAction::Enqueue(NanobotType::Defender),
+    );
+    app.update();
+    send(&mut app, entity, FacilityAction::PinCurrent);
+
+    for _ in 0..production_ticks_per_bot(0) + 1 {
+        app.update();
+    }
+
+    assert_eq!(count_type(&mut app, NanobotType::Defender), 1);
+    let f = facility(&app, entity);
+    assert_eq!(f.pinned, Some(NanobotType::Defender));
+    assert_eq!(
+        f.current_target,
+        Some(NanobotType::Defender),
+        "pin overrides Worker demand for the next cycle"
+    );
+}
+
+#[test]
+fn cancel_refunds_cost_to_hopper_and_ledger() {
+    let mut app = build_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let entity = common::spawn_idle_facility_at(&mut app, Vec2::ZERO);
+    app.update();
+    assert!(facility(&app, entity).is_busy());
+    let ledger = |app: &App| {
+        app.world()
+            .resource::<ResourceLedger>()
+            .total_for(SwarmId::PLAYER, ResourceKind::Minerals)
+    };
+    assert_eq!(
+        ledger(&app),
+        PRODUCTION_INPUT_CAPACITY - PRODUCTION_COST_PER_BOT
+    );
+
+    // Clear demand so the refunded hopper is not spent again.
+    app.insert_resource(ProductionPriority::new());
+    send(&mut app, entity, FacilityAction::CancelCurrent);
+    app.update();
+
+    let f = facility(&app, entity);
+    assert!(!f.is_busy());
+    assert_eq!(f.input_amount, PRODUCTION_INPUT_CAPACITY);
+    assert_eq!(ledger(&app), PRODUCTION_INPUT_CAPACITY);
+}
+
+#[test]
+fn paid_upgrade_shortens_next_cycle() {
+    let mut app = build_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let entity = common::spawn_idle_facility_at(&mut app, Vec2::ZERO);
+    app.world_mut()
+        .get_mut::<ProductionFacility>(entity)
+        .unwrap()
+        .input_amount = PRODUCTION_UPGRADE_COST - 1;
+    send(&mut app, entity, FacilityAction::Upgrade);
+    app.update();
+    let f = facility(&app, entity);
+    assert!(f.upgrade_pending);
+    assert_eq!(f.level, 0);
+    assert!(
+        !f.is_busy(),
+        "a pending upgrade holds the hopper instead of starting a cycle"
+    );
+
+    common::fill_facility_input(&mut app, entity);
+    app.update();
+    let f = facility(&app, entity);
+    assert_eq!(f.level, 1);
+    assert!(!f.upgrade_pending);
+    assert!(!f.is_busy(), "the upgrade spent the whole hopper");
+
+    common::fill_facility_input(&mut app, entity);
+    for _ in 0..production_ticks_per_bot(1) {
+        app.update();
+    }
+    assert_eq!(
+        count_type(&mut app, NanobotType::Worker),
+        1,
+        "a level 1 cycle finishes before the base cycle would"
+    );
+}
+
+#[test]
+fn panel_buttons_command_the_selected_facility() {
+    let mut app = build_app();
+    app.insert_resource(FontsResource {
+        font: Handle::default(),
+    })
+    .init_resource::<FacilityPanelSelection>()
+    .add_systems(Startup, setup_facility_panel)
+    .add_systems(Update, facility_panel_click_system);
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let first = common::spawn_idle_facility_at(&mut app, Vec2::ZERO);
+    let second = common::spawn_idle_facility_at(&mut app, Vec2::new(200.0, 0.0));
+    app.update();
+
+    let button = |app: &mut App, target: FacilityPanelButton| {
+        let mut q = app.world_mut().query::<(Entity, &FacilityPanelButton)>();
+        q.iter(app.world())
+            .find(|(_, button)| **button == target)
+            .map(|(entity, _)| entity)
+            .expect("panel button exists")
+    };
+    let next = button(&mut app, FacilityPanelButton::Next);
+    let only_defenders = button(
+        &mut app,
+        FacilityPanelButton::Command(FacilityAction::Specialize(Some(NanobotType::Defender))),
+    );
+
+    common::press_button(&mut app, next);
+    common::press_button(&mut app, only_defenders);
+
+    let (first_spec, second_spec) = (
+        facility(&app, first).specialization,
+        facility(&app, second).specialization,
+    );
+    let selected = if first.to_bits() < second.to_bits() {
+        second_spec
+    } else {
+        first_spec
+    };
+    assert_eq!(selected, Some(NanobotType::Defender));
+    assert!(
+        first_spec.is_none() || second_spec.is_none(),
+        "only the selected facility is specialized"
+    );
+}
@@ -0,0 +1,187 @@
+//! Integration tests for Reclaim intent.
+//!
+//! Covers the deconstruction lifecycle:
+//!   1. Reclaim paint over an owned structure plans a reclaim on the
+//!      same entity,
+//!   2. erasing the paint before the work finishes cancels the plan,
+//!   3. a Worker deconstructs the structure into a Reclaimed Cache
+//!      holding its buffer plus the build-cost refund, which counts the
+//!      construction material when construction costs are on,
+//!   4. a Hauler empties the cache into a Sink Stockpile and the empty
+//!      cache despawns.
+//!
+//! The refund helper unit tests live in `src/nanobot/reclaim.rs`.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        ConstructionCost, OwnerSwarm, PlannedKind, PlannedReclaim, PlannedStructure,
+        PlannedStructurePlugin, RECLAIM_WORK_TICKS, ReclaimPlugin, ReclaimedCache, Structure,
+        StructureKind, SwarmId, reclaim_refund,
+    },
+    resources::{ResourceKind, ResourceLedger, Stockpile, StockpileRole},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+const CELL: IVec2 = IVec2::ZERO;
+
+fn build_app() -> App {
+    let mut app = common::sim_app_with_gather_haul();
+    app.add_plugins((PlannedStructurePlugin, ReclaimPlugin));
+    app
+}
+
+fn spawn_owned_sink(app: &mut App, swarm: Entity, amount: u32, tier: u32) -> Entity {
+    let center = common::cell_world_center(CELL);
+    let stockpile = common::spawn_sink_stockpile(app, center, amount, 200);
+    let mut structure = Structure::new(StructureKind::Basic);
+    structure.tier = tier;
+    app.world_mut()
+        .entity_mut(stockpile)
+        .insert((structure, OwnerSwarm(swarm)));
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        amount,
+    );
+    stockpile
+}
+
+fn paint_reclaim(app: &mut App, cell: IVec2) {
+    let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+    assert!(grid.paint_owned(cell, IntentKind::Reclaim, Some(SwarmId::PLAYER)));
+}
+
+fn ledger(app: &App) -> u32 {
+    app.world()
+        .resource::<ResourceLedger>()
+        .total_for(SwarmId::PLAYER, ResourceKind::Minerals)
+}
+
+#[test]
+fn reclaim_paint_plans_deconstruction_in_place() {
+    let mut app = build_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let sink = spawn_owned_sink(&mut app, swarm, 50, 0);
+
+    app.update();
+    assert!(
+        app.world().get::<PlannedReclaim>(sink).is_none(),
+        "an unpainted structure is never reclaimed"
+    );
+
+    paint_reclaim(&mut app, CELL);
+    app.update();
+
+    let world = app.world();
+    assert!(world.get::<PlannedReclaim>(sink).is_some());
+    let planned = world.get::<PlannedStructure>(sink).unwrap();
+    assert_eq!(planned.kind, PlannedKind::SinkStockpile);
+    assert_eq!(planned.work_remaining, RECLAIM_WORK_TICKS);
+    assert!(
+        world.get::<Structure>(sink).is_some(),
+        "the structure keeps operating until the work finishes"
+    );
+}
+
+#[test]
+fn erasing_reclaim_paint_cancels_pending_plan() {
+    let mut app = build_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let sink = spawn_owned_sink(&mut app, swarm, 50, 0);
+    paint_reclaim(&mut app, CELL);
+    app.update();
+    assert!(app.world().get::<PlannedReclaim>(sink).is_some());
+
+    app.world_mut().resource_mut::<IntentGrid>().erase_owned(
+        CELL,
+        IntentKind::Reclaim,
+        Some(SwarmId::PLAYER),
+    );
+    app.update();
+
+    let world = app.world();
+    assert!(world.get::<PlannedReclaim>(sink).is_none());
+    assert!(world.get::<PlannedStructure>(sink).is_none());
+    assert_eq!(world.get::<Stockpile>(sink).unwrap().amount, 50);
+}
+
+#[test]
+fn worker_deconstructs_structure_into_refunded_cache() {
+    let mut app = build_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let sink = spawn_owned_sink(&mut app, swarm, 50, 1);
+    paint_reclaim(&mut app, CELL);
+    common::spawn_worker_at(&mut app, common::cell_world_center(CELL));
+
+    for _ in 0..2 + RECLAIM_WORK_TICKS + 5 {
+        app.update();
+    }
+
+    let world = app.world();
+    assert!(world.get::<Structure>(sink).is_none());
+    assert!(world.get::<PlannedStructure>(sink).is_none());
+    assert!(world.get::<ReclaimedCache>(sink).is_some());
+    assert_eq!(
+        world.get::<StockpileRole>(sink),
+        Some(&StockpileRole::Source)
+    );
+    let cache = world.get::<Stockpile>(sink).unwrap();
+    let expected = 50 + reclaim_refund(None, 1);
+    assert_eq!(cache.amount, expected);
+    assert_eq!(cache.capacity, expected, "caches never accept deliveries");
+    assert_eq!(ledger(&app), expected);
+}
+
+#[test]
+fn reclaim_refunds_a_share_of_the_construction_material() {
+    let mut app = build_app();
+    app.insert_resource(ConstructionCost::default());
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let sink = spawn_owned_sink(&mut app, swarm, 50, 0);
+    paint_reclaim(&mut app, CELL);
+    common::spawn_worker_at(&mut app, common::cell_world_center(CELL));
+
+    for _ in 0..2 + RECLAIM_WORK_TICKS + 5 {
+        app.update();
+    }
+
+    let refund = reclaim_refund(Some(PlannedKind::SinkStockpile), 0);
+    assert!(refund > 0, "a tier-0 build paid for its material");
+    let expected = 50 + refund;
+    assert_eq!(app.world().get::<Stockpile>(sink).unwrap().amount, expected);
+    assert_eq!(ledger(&app), expected);
+}
+
+#[test]
+fn hauler_empties_reclaimed_cache_into_sink() {
+    let mut app = build_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let reclaimed = spawn_owned_sink(&mut app, swarm, 20, 0);
+    paint_reclaim(&mut app, CELL);
+    common::spawn_worker_at(&mut app, common::cell_world_center(CELL));
+    for _ in 0..2 + RECLAIM_WORK_TICKS + 5 {
+        app.update();
+    }
+    assert!(app.world().get::<ReclaimedCache>(reclaimed).is_some());
+
+    let sink_pos = common::cell_world_center(IVec2::new(4, 0));
+    let sink = common::spawn_sink_stockpile(&mut app, sink_pos, 0, 200);
+    app.world_mut().entity_mut(sink).insert(OwnerSwarm(swarm));
+    common::spawn_hauler_at(&mut app, common::cell_world_center(CELL));
+
+    for _ in 0..300 {
+        app.update();
+    }
+
+    let world = app.world();
+    assert!(
+        world.get_entity(reclaimed).is_err(),
+        "the emptied cache despawns"
+    );
+    assert_eq!(world.get::<Stockpile>(sink).unwrap().amount, 20);
+    assert_eq!(ledger(&app), 20, "hauling changes custody, not quantity");
+}
@@ -31,6 +31,7 @@ fn defend_opportunity(
         cell,
         owner: None,
         available_work,
+        priority: available_work,
     }
 }
 
@@ -48,16 +49,17 @@ fn allocation_clock_advances_at_exact_deterministic_ten_hertz_boundaries() {
 }
 
 #[test]
-fn minimum_activation_covers_all_five_categories_before_weighted_pressure() {
-    let pressure = CategoryValues::new([100, 1, 1, 1, 1]);
+fn minimum_activation_covers_all_six_categories_before_weighted_pressure() {
+    let pressure = CategoryValues::new([100, 1, 1, 1, 1, 1]);
 
-    let budget = allocate_category_budget(9, pressure);
+    let budget = allocate_category_budget(10, pressure);
 
-    assert_eq!(budget.total(), 9);
+    assert_eq!(budget.total(), 10);
     assert_eq!(budget.get(OpportunityCategory::Gather), 5);
     for category in [
         OpportunityCategory::PlannedBuild,
         OpportunityCategory::Maintenance,
+        OpportunityCategory::Repair,
         OpportunityCategory::Defend,
         OpportunityCategory::Haul,
     ] {
@@ -67,7 +69,7 @@ fn minimum_activation_covers_all_five_categories_before_weighted_pressure() {
 
 #[test]
 fn one_worker_activates_planned_build_before_gather_pressure() {
-    let pressure = CategoryValues::new([100, 1, 0, 0, 0]);
+    let pressure = CategoryValues::new([100, 1, 0, 0, 0, 0]);
 
     let budget = allocate_category_budget(1, pressure);
 
@@ -80,7 +82,7 @@ fn one_worker_activates_planned_build_before_gather_pressure() {
 fn distant_valid_work_pulls_capacity_outward_within_the_bound() {
     let pressure = RegionalPressure {
         region: region(2, 0),
-        categories: CategoryValues::new([0, 0, 0, 0, 1]),
+        categories: CategoryValues::new([0, 0, 0, 0, 1, 0]),
     };
 
     let in_range = outward_pull_budget(region(0, 0), 3, &[pressure], 2);
@@ -99,7 +101,7 @@ fn local_choice_obeys_region_candidate_owner_and_category_bounds() {
         1,
         &[RegionalPressure {
             region: region(0, 0),
-            categories: CategoryValues::new([0, 0, 0, 1, 0]),
+            categories: CategoryValues::new([0, 0, 0, 1, 0, 0]),
         }],
         0,
     );
@@ -138,7 +140,7 @@ fn defend_threat_pressure_beats_distance_after_claims() {
         1,
         &[RegionalPressure {
             region: region(0, 0),
-            categories: CategoryValues::new([0, 0, 0, 1, 0]),
+            categories: CategoryValues::new([0, 0, 0, 1, 0, 0]),
         }],
         1,
     );
@@ -177,7 +179,7 @@ fn bounded_choice_falls_back_from_full_exact_claims_within_runtime_limit() {
         1,
         &[RegionalPressure {
             region: region(0, 0),
-            categories: CategoryValues::new([0, 0, 0, 1, 0]),
+            categories: CategoryValues::new([0, 0, 0, 1, 0, 0]),
         }],
         0,
     );
@@ -246,7 +248,7 @@ fn regional_pass_uses_stable_bot_order_and_reassignment_limit() {
         2,
         &[RegionalPressure {
             region: region(0, 0),
-            categories: CategoryValues::new([0, 0, 0, 2, 0]),
+            categories: CategoryValues::new([0, 0, 0, 2, 0, 0]),
         }],
         0,
     );
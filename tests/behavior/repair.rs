@@ -0,0 +1,193 @@
+//! Structure repair as a threat-ranked Worker activity.
+//!
+//! Damage projects Repair work separately from maintenance, Workers
+//! restore health, recent combat damage ranks a structure first, and an
+//! undefended structure under active attack is left alone.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        CombatPlugin, DefendHold, MaintenanceAssignment, MaintenanceProgress, OwnerSwarm,
+        PlannedKind, RecentDamage, RepairAssignment, RepairProgress, STRUCTURE_MAX_HEALTH,
+        Structure, Swarm, SwarmId, UNDER_ATTACK_TICKS,
+    },
+    structure_sprites::{StructureDamageStage, StructureSprites, StructureVisual},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn damage(app: &mut App, structure: Entity, health: u32) {
+    app.world_mut()
+        .entity_mut(structure)
+        .get_mut::<Structure>()
+        .unwrap()
+        .health = health;
+}
+
+fn health(app: &App, structure: Entity) -> u32 {
+    app.world()
+        .entity(structure)
+        .get::<Structure>()
+        .expect("structure still exists")
+        .health
+}
+
+fn repair_target(app: &App, worker: Entity) -> Option<Entity> {
+    let worker = app.world().entity(worker);
+    worker
+        .get::<RepairAssignment>()
+        .map(|assignment| assignment.target)
+        .or_else(|| {
+            worker
+                .get::<RepairProgress>()
+                .map(|progress| progress.target)
+        })
+}
+
+/// Tick until `worker` holds a repair marker, up to `ticks`.
+fn wait_for_repair(app: &mut App, worker: Entity, ticks: usize) -> Option<Entity> {
+    for _ in 0..ticks {
+        app.update();
+        if let Some(target) = repair_target(app, worker) {
+            return Some(target);
+        }
+    }
+    None
+}
+
+#[test]
+fn worker_repairs_damaged_structure_to_full_health() {
+    let mut app = common::sim_app_with_repair();
+    let center = common::cell_world_center(IVec2::ZERO);
+    let structure = common::spawn_structure_at(&mut app, center);
+    damage(&mut app, structure, STRUCTURE_MAX_HEALTH - 20);
+    let worker = common::spawn_worker_at(&mut app, center);
+
+    assert_eq!(wait_for_repair(&mut app, worker, 20), Some(structure));
+    assert!(
+        app.world()
+            .entity(worker)
+            .get::<MaintenanceAssignment>()
+            .is_none()
+            && app
+                .world()
+                .entity(worker)
+                .get::<MaintenanceProgress>()
+                .is_none(),
+        "damage on a fresh structure is repair, not maintenance",
+    );
+
+    for _ in 0..30 {
+        app.update();
+    }
+    assert_eq!(health(&app, structure), STRUCTURE_MAX_HEALTH);
+    assert_eq!(repair_target(&app, worker), None, "worker released");
+}
+
+#[test]
+fn recently_hit_structure_is_repaired_before_a_closer_calm_one() {
+    let mut app = common::sim_app_with_repair();
+    let worker_pos = common::cell_world_center(IVec2::ZERO);
+    let calm = common::spawn_structure_at(&mut app, common::cell_world_center(IVec2::new(1, 0)));
+    let hit = common::spawn_structure_at(&mut app, common::cell_world_center(IVec2::new(3, 0)));
+    damage(&mut app, calm, STRUCTURE_MAX_HEALTH - 20);
+    damage(&mut app, hit, STRUCTURE_MAX_HEALTH - 20);
+    // Past the attack window, so Workers may approach, but still
+    // recent enough to raise the structure's priority.
+    app.world_mut().entity_mut(hit).insert(RecentDamage {
+        amount: 20,
+        ticks_since_hit: UNDER_ATTACK_TICKS,
+    });
+    let worker = common::spawn_worker_at(&mut app, worker_pos);
+
+    assert_eq!(wait_for_repair(&mut app, worker, 20), Some(hit));
+}
+
+#[test]
+fn undefended_structure_under_attack_is_left_alone() {
+    let mut app = common::sim_app_with_repair();
+    let center = common::cell_world_center(IVec2::ZERO);
+    let structure = common::spawn_structure_at(&mut app, center);
+    damage(&mut app, structure, STRUCTURE_MAX_HEALTH - 20);
+    app.world_mut().entity_mut(structure).insert(RecentDamage {
+        amount: 20,
+        ticks_since_hit: 0,
+    });
+    let worker = common::spawn_worker_at(&mut app, center);
+
+    assert_eq!(wait_for_repair(&mut app, worker, 30), None);
+    assert_eq!(health(&app, structure), STRUCTURE_MAX_HEALTH - 20);
+}
+
+#[test]
+fn defend_paint_lets_workers_repair_under_attack() {
+    let mut app = common::sim_app_with_repair();
+    let cell = IVec2::ZERO;
+    let center = common::cell_world_center(cell);
+    app.world_mut()
+        .resource_mut::<IntentGrid>()
+        .paint(cell, IntentKind::Defend);
+    let structure = common::spawn_structure_at(&mut app, center);
+    damage(&mut app, structure, STRUCTURE_MAX_HEALTH - 20);
+    app.world_mut().entity_mut(structure).insert(RecentDamage {
+        amount: 20,
+        ticks_since_hit: 0,
+    });
+    let worker = common::spawn_worker_at(&mut app, center);
+
+    assert_eq!(wait_for_repair(&mut app, worker, 30), Some(structure));
+}
+
+#[test]
+fn combat_hit_records_recent_damage_on_the_structure() {
+    let mut app = common::sim_app_with_defend();
+    app.add_plugins(CombatPlugin);
+    let cell = IVec2::ZERO;
+    let center = common::cell_world_center(cell);
+    let defender = common::spawn_defender_at(&mut app, center);
+    app.world_mut()
+        .entity_mut(defender)
+        .insert(DefendHold { cell });
+    let enemy_swarm = app.world_mut().spawn((Swarm {}, SwarmId(11))).id();
+    let structure = common::spawn_structure_at(&mut app, center + Vec2::new(16.0, 0.0));
+    app.world_mut()
+        .entity_mut(structure)
+        .insert(OwnerSwarm(enemy_swarm));
+
+    app.update();
+
+    let lost = STRUCTURE_MAX_HEALTH - health(&app, structure);
+    assert!(lost > 0, "defender damaged the hostile structure");
+    assert_eq!(
+        app.world().entity(structure).get::<RecentDamage>(),
+        Some(&RecentDamage {
+            amount: lost,
+            ticks_since_hit: 0,
+        })
+    );
+}
+
+#[test]
+fn completed_sprite_is_tinted_by_damage_stage() {
+    let mut app = common::sim_app_with_repair();
+    let structure = common::spawn_structure_at(&mut app, Vec2::ZERO);
+    app.world_mut().entity_mut(structure).insert((
+        StructureVisual::completed(PlannedKind::SourceStockpile),
+        Sprite::default(),
+    ));
+    damage(&mut app, structure, 1);
+
+    app.update();
+
+    let entity = app.world().entity(structure);
+    assert_eq!(
+        entity.get::<StructureDamageStage>(),
+        Some(&StructureDamageStage::Critical)
+    );
+    assert_eq!(
+        entity.get::<Sprite>().unwrap().color,
+        StructureSprites::damage_color(StructureDamageStage::Critical)
+    );
+}
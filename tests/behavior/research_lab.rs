@@ -0,0 +1,170 @@
+//! Integration tests for Research Labs and the per-swarm upgrade tree.
+//!
+//! Covers the contracts research adds on top of terminal logistics:
+//!   1. a Research Lab is a terminal haulers feed from owned Sink
+//!      Stockpiles,
+//!   2. a funded queue head drains the lab hopper and the ledger, then
+//!      unlocks after its research time,
+//!   3. an unlocked Hauler Carry upgrade raises reservation size for the
+//!      researching swarm only,
+//!   4. queued research with no lab plans one on an owned Build cell.
+//!
+//! The queue and modifier unit tests live in `src/nanobot/research.rs`.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        HAULER_CARRY_CAPACITY, HaulerAssignment, LogisticsReservation, OwnerSwarm, PlannedKind,
+        PlannedStructure, RESEARCHED_HAULER_CARRY_CAPACITY, ResearchLab, ResearchPlugin,
+        ResearchState, SwarmId, Upgrade,
+    },
+    resources::{ResourceKind, ResourceLedger},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn build_app() -> App {
+    let mut app = common::sim_app_with_gather_haul();
+    app.add_plugins(ResearchPlugin);
+    app
+}
+
+fn spawn_lab(app: &mut App, owner: Entity, pos: Vec2, input_amount: u32) -> Entity {
+    app.world_mut()
+        .spawn((
+            ResearchLab {
+                input_amount,
+                ..ResearchLab::new()
+            },
+            OwnerSwarm(owner),
+            Transform::from_translation(pos.extend(0.0)),
+        ))
+        .id()
+}
+
+#[test]
+fn hauler_feeds_research_lab_from_owned_sink() {
+    let mut app = build_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let source = common::spawn_sink_stockpile(&mut app, Vec2::new(10.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    let lab = spawn_lab(&mut app, swarm, Vec2::new(40.0, 0.0), 0);
+    let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    app.update();
+
+    let assignment = app
+        .world()
+        .entity(hauler)
+        .get::<HaulerAssignment>()
+        .expect("an empty lab hopper is terminal demand");
+    assert_eq!(assignment.source, source);
+    assert_eq!(assignment.sink, lab);
+}
+
+#[test]
+fn funded_head_drains_lab_and_unlocks_after_research_time() {
+    let mut app = build_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cost = Upgrade::HaulerCarry.cost();
+    let lab = spawn_lab(&mut app, swarm, Vec2::new(40.0, 0.0), cost + 5);
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        cost + 5,
+    );
+    app.world_mut()
+        .resource_mut::<ResearchState>()
+        .for_swarm_mut(SwarmId::PLAYER)
+        .enqueue(Upgrade::HaulerCarry);
+
+    app.update();
+    assert_eq!(
+        app.world().get::<ResearchLab>(lab).unwrap().input_amount,
+        5,
+        "funding draws only the head's cost from the hopper"
+    );
+    assert_eq!(
+        app.world()
+            .resource::<ResourceLedger>()
+            .total_for(SwarmId::PLAYER, ResourceKind::Minerals),
+        5
+    );
+
+    for _ in 0..Upgrade::HaulerCarry.ticks() {
+        app.update();
+    }
+    let research = app.world().resource::<ResearchState>();
+    assert!(research.is_unlocked(SwarmId::PLAYER, Upgrade::HaulerCarry));
+    assert!(
+        research
+            .for_swarm(SwarmId::PLAYER)
+            .is_some_and(|research| research.queue.is_empty())
+    );
+}
+
+#[test]
+fn hauler_carry_upgrade_raises_reservation_for_its_swarm() {
+    let run = |unlocked: bool| {
+        let mut app = build_app();
+        if unlocked {
+            app.world_mut()
+                .resource_mut::<ResearchState>()
+                .for_swarm_mut(SwarmId::PLAYER)
+                .unlocked
+                .insert(Upgrade::HaulerCarry);
+        }
+        let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+        let source = common::spawn_sink_stockpile(&mut app, Vec2::new(10.0, 0.0), 100, 100);
+        app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+        spawn_lab(&mut app, swarm, Vec2::new(40.0, 0.0), 0);
+        let hauler = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+        app.update();
+        app.world()
+            .entity(hauler)
+            .get::<LogisticsReservation>()
+            .expect("hauler reserves lab delivery")
+            .amount
+    };
+
+    assert_eq!(run(false), HAULER_CARRY_CAPACITY);
+    assert_eq!(run(true), RESEARCHED_HAULER_CARRY_CAPACITY);
+}
+
+#[test]
+fn queued_research_plans_lab_on_owned_build_cell() {
+    let mut app = build_app();
+    let _swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    {
+        let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+        assert!(grid.paint_owned(IVec2::new(0, 0), IntentKind::Build, Some(SwarmId::PLAYER)));
+    }
+
+    app.update();
+    let mut planned = app.world_mut().query::<&PlannedStructure>();
+    assert_eq!(
+        planned
+            .iter(app.world())
+            .filter(|plan| plan.kind == PlannedKind::ResearchLab)
+            .count(),
+        0,
+        "an empty queue needs no lab"
+    );
+
+    app.world_mut()
+        .resource_mut::<ResearchState>()
+        .for_swarm_mut(SwarmId::PLAYER)
+        .enqueue(Upgrade::ExtractRate);
+    app.update();
+    app.update();
+    let mut planned = app.world_mut().query::<&PlannedStructure>();
+    assert_eq!(
+        planned
+            .iter(app.world())
+            .filter(|plan| plan.kind == PlannedKind::ResearchLab)
+            .count(),
+        1
+    );
+}
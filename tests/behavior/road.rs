@@ -0,0 +1,181 @@
+//! Corridor roads: with [`CorridorRoads`] enabled, owned Corridor paint
+//! emerges into Road plans that Workers build. A built road speeds up
+//! every bot of its swarm standing on it, discounts routes through it,
+//! and collapses under neglect like any other maintained structure.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        CorridorRoads, DEFAULT_PLANNED_WORK_TICKS, DirectMovementComponent, OwnerSwarm,
+        PlannedKind, PlannedStructure, ROAD_SPEED_MULTIPLIER, Road, RoadPlugin, Structure, SwarmId,
+        SwarmMember, hauler_route_cost,
+    },
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn paint_corridor(app: &mut App, cells: impl IntoIterator<Item = IVec2>) {
+    let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+    for cell in cells {
+        grid.paint_owned(cell, IntentKind::Corridor, Some(SwarmId::PLAYER));
+    }
+}
+
+fn road_plans(app: &mut App) -> Vec<IVec2> {
+    let mut plans = app
+        .world_mut()
+        .query_filtered::<&PlannedStructure, With<OwnerSwarm>>();
+    let mut cells: Vec<IVec2> = plans
+        .iter(app.world())
+        .filter(|planned| planned.kind == PlannedKind::Road)
+        .map(|planned| planned.cell)
+        .collect();
+    cells.sort_by_key(|cell| (cell.y, cell.x));
+    cells
+}
+
+fn spawn_road(app: &mut App, owner: Entity, cell: IVec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Road { cell },
+            OwnerSwarm(owner),
+            Transform::from_translation(common::cell_world_center(cell).extend(0.0)),
+        ))
+        .id()
+}
+
+/// Distance `bot` covers in one tick heading east.
+fn one_tick_step(app: &mut App, bot: Entity) -> f32 {
+    let start = app.world().get::<Transform>(bot).unwrap().translation.x;
+    app.world_mut()
+        .entity_mut(bot)
+        .insert(DirectMovementComponent {
+            xy: Vec2::new(start + 400.0, 0.0),
+            stop_radius: 0.0,
+        });
+    app.update();
+    app.world().get::<Transform>(bot).unwrap().translation.x - start
+}
+
+#[test]
+fn corridor_paint_plans_capped_roads_only_when_enabled() {
+    let mut app = common::sim_app_with_planned();
+    app.add_plugins(RoadPlugin);
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    paint_corridor(&mut app, (0..6).map(|x| IVec2::new(x, 0)));
+
+    app.update();
+    assert!(road_plans(&mut app).is_empty(), "roads are opt-in");
+
+    app.insert_resource(CorridorRoads {
+        max_pending_plans: 2,
+    });
+    app.update();
+    app.update();
+    assert_eq!(
+        road_plans(&mut app),
+        vec![IVec2::new(0, 0), IVec2::new(1, 0)],
+        "plans follow the stroke up to the per-swarm cap"
+    );
+}
+
+#[test]
+fn worker_builds_a_road_that_discounts_routes() {
+    let mut app = common::sim_app_with_planned();
+    app.add_plugins(RoadPlugin);
+    app.insert_resource(CorridorRoads::default());
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cell = IVec2::new(1, 0);
+    paint_corridor(&mut app, [cell]);
+    common::spawn_worker_at(&mut app, common::cell_world_center(cell));
+    let route_cost = |app: &App| {
+        hauler_route_cost(
+            common::cell_world_center(IVec2::new(0, 0)),
+            common::cell_world_center(IVec2::new(2, 0)),
+            app.world().resource::<IntentGrid>(),
+            SwarmId::PLAYER,
+        )
+    };
+    let painted = route_cost(&app);
+
+    for _ in 0..DEFAULT_PLANNED_WORK_TICKS + 20 {
+        app.update();
+    }
+
+    let mut roads = app.world_mut().query::<&Road>();
+    assert_eq!(
+        roads
+            .iter(app.world())
+            .map(|road| road.cell)
+            .collect::<Vec<_>>(),
+        vec![cell]
+    );
+    assert!(
+        road_plans(&mut app).is_empty(),
+        "a built cell plans no more"
+    );
+    assert!(
+        app.world()
+            .resource::<IntentGrid>()
+            .road_visible_to(cell, SwarmId::PLAYER)
+    );
+    assert!(route_cost(&app) < painted);
+}
+
+#[test]
+fn roads_speed_up_only_their_own_swarm() {
+    let mut app = common::sim_app();
+    app.add_plugins(RoadPlugin);
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let worker = common::spawn_worker_at(&mut app, Vec2::new(8.0, 8.0));
+    let base = one_tick_step(&mut app, worker);
+
+    app.world_mut()
+        .entity_mut(worker)
+        .get_mut::<Transform>()
+        .unwrap()
+        .translation = Vec3::new(8.0, 8.0, 0.0);
+    spawn_road(&mut app, swarm, IVec2::ZERO);
+    let boosted = one_tick_step(&mut app, worker);
+    assert!((boosted - base * ROAD_SPEED_MULTIPLIER).abs() < 1e-3);
+
+    app.world_mut().despawn(worker);
+    let stranger = common::spawn_worker_at(&mut app, Vec2::new(8.0, 8.0));
+    app.world_mut()
+        .entity_mut(stranger)
+        .insert(SwarmMember::new(SwarmId(5)));
+    assert!((one_tick_step(&mut app, stranger) - base).abs() < 1e-3);
+}
+
+#[test]
+fn neglected_road_collapses_and_leaves_the_grid() {
+    let mut app = common::sim_app_with_maintenance();
+    app.add_plugins(RoadPlugin);
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cell = IVec2::new(3, 0);
+    let road = spawn_road(&mut app, swarm, cell);
+    app.update();
+    assert!(app.world().resource::<IntentGrid>().has_road(cell));
+
+    {
+        let mut structure = app
+            .world_mut()
+            .get_mut::<Structure>(road)
+            .expect("a built road carries a maintenance condition");
+        structure.health = 1;
+        structure.ticks_since_maintained = u32::MAX / 2;
+    }
+    let mut collapsed = false;
+    for _ in 0..200 {
+        app.update();
+        if app.world().get_entity(road).is_err() {
+            collapsed = true;
+            break;
+        }
+    }
+
+    assert!(collapsed, "an unmaintained road collapses");
+    assert!(!app.world().resource::<IntentGrid>().has_road(cell));
+}
@@ -0,0 +1,134 @@
+//! Route-cost cache upkeep: allocation and leg picking share one
+//! [`RouteCostCache`], and painting or erasing Corridor drops the
+//! cached costs it can reach before the next acquisition pass scores
+//! routes. Danger from a hostile on the move only clears pairs near it.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{RouteCostCache, SwarmId, SwarmMember, ThreatPlugin, hauler_route_cost},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+const FROM: IVec2 = IVec2::new(-3, 0);
+const TO: IVec2 = IVec2::new(3, 0);
+
+fn cached_cost(app: &App) -> f32 {
+    let grid = app.world().resource::<IntentGrid>();
+    app.world().resource::<RouteCostCache>().cost(
+        common::cell_world_center(FROM),
+        common::cell_world_center(TO),
+        grid,
+        SwarmId::PLAYER,
+    )
+}
+
+fn flat_cost(app: &App) -> f32 {
+    hauler_route_cost(
+        common::cell_world_center(FROM),
+        common::cell_world_center(TO),
+        app.world().resource::<IntentGrid>(),
+        SwarmId::PLAYER,
+    )
+}
+
+#[test]
+fn corridor_paint_refreshes_cached_route_costs() {
+    let mut app = common::sim_app();
+    app.update();
+    let before = cached_cost(&app);
+    assert_eq!(app.world().resource::<RouteCostCache>().cached_pairs(), 1);
+
+    {
+        let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+        for x in FROM.x + 1..TO.x {
+            grid.paint(IVec2::new(x, 0), IntentKind::Corridor);
+        }
+    }
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<RouteCostCache>().cached_pairs(),
+        0,
+        "Corridor paint on the route drops its cached pair"
+    );
+    assert_eq!(app.world().resource::<IntentGrid>().route_dirty_count(), 0);
+    let painted = cached_cost(&app);
+    assert!(painted < before);
+    assert!((painted - flat_cost(&app)).abs() < 1e-3);
+
+    {
+        let mut grid = app.world_mut().resource_mut::<IntentGrid>();
+        for x in FROM.x + 1..TO.x {
+            grid.erase(IVec2::new(x, 0), IntentKind::Corridor);
+        }
+    }
+    app.update();
+
+    assert!((cached_cost(&app) - before).abs() < 1e-3);
+}
+
+#[test]
+fn other_paint_keeps_cached_route_costs() {
+    let mut app = common::sim_app();
+    app.update();
+    cached_cost(&app);
+
+    app.world_mut()
+        .resource_mut::<IntentGrid>()
+        .paint(IVec2::new(0, 0), IntentKind::Gather);
+    app.update();
+
+    assert_eq!(app.world().resource::<RouteCostCache>().cached_pairs(), 1);
+}
+
+#[test]
+fn moving_hostile_keeps_unrelated_cached_pairs() {
+    let mut app = common::sim_app();
+    app.insert_resource(IntentGrid::new(64, 64));
+    app.add_plugins(ThreatPlugin);
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    app.update();
+
+    let far_from = common::cell_world_center(IVec2::new(-3, 20));
+    let far_to = common::cell_world_center(IVec2::new(3, 20));
+    let far_cost = |app: &App| {
+        app.world().resource::<RouteCostCache>().cost(
+            far_from,
+            far_to,
+            app.world().resource::<IntentGrid>(),
+            SwarmId::PLAYER,
+        )
+    };
+    let far = far_cost(&app);
+    cached_cost(&app);
+    assert_eq!(app.world().resource::<RouteCostCache>().cached_pairs(), 2);
+
+    let hostile = common::spawn_defender_at(&mut app, common::cell_world_center(IVec2::ZERO));
+    app.world_mut()
+        .entity_mut(hostile)
+        .insert(SwarmMember::new(SwarmId(3)));
+    app.update();
+    assert_eq!(
+        app.world().resource::<RouteCostCache>().cached_pairs(),
+        1,
+        "danger on the route drops the near pair"
+    );
+
+    for x in 1..4 {
+        cached_cost(&app);
+        app.world_mut()
+            .get_mut::<Transform>(hostile)
+            .unwrap()
+            .translation = common::cell_world_center(IVec2::new(x, 0)).extend(0.0);
+        app.update();
+        assert_eq!(
+            app.world().resource::<RouteCostCache>().cached_pairs(),
+            1,
+            "only the far pair survives the hostile's step to cell {x}"
+        );
+    }
+    assert_eq!(far_cost(&app), far);
+}
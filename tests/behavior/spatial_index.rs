@@ -0,0 +1,155 @@
+//! Shared spatial index: one [`SpatialIndex`] resource follows
+//! nanobot movement, structure spawns and despawns, and swarm
+//! ownership, and the nearest-charger lookup reads it instead of
+//! scanning every charger.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        Charge, ChargerAssignment, DefendHold, DirectMovementComponent, LOW_CHARGE_THRESHOLD,
+        OwnerSwarm, SpatialIndex, SpatialLayer, SwarmFilter, SwarmId,
+    },
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn indexed_position(app: &App, entity: Entity) -> Option<Vec2> {
+    app.world()
+        .resource::<SpatialIndex>()
+        .get(entity)
+        .map(|entry| entry.position)
+}
+
+#[test]
+fn index_follows_a_nanobot_from_spawn_to_despawn() {
+    let mut app = common::sim_app();
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+
+    app.update();
+    assert_eq!(indexed_position(&app, worker), Some(Vec2::ZERO));
+
+    app.world_mut()
+        .entity_mut(worker)
+        .insert(DirectMovementComponent {
+            xy: Vec2::new(400.0, 0.0),
+            stop_radius: 0.0,
+        });
+    app.update();
+    let moved = app
+        .world()
+        .get::<Transform>(worker)
+        .unwrap()
+        .translation
+        .truncate();
+    assert_ne!(moved, Vec2::ZERO);
+    assert_eq!(
+        indexed_position(&app, worker),
+        Some(moved),
+        "the index sees this tick's movement"
+    );
+
+    app.world_mut().despawn(worker);
+    app.update();
+    assert_eq!(indexed_position(&app, worker), None);
+    assert!(app.world().resource::<SpatialIndex>().is_empty());
+}
+
+#[test]
+fn missed_despawn_is_swept_when_a_spawn_lands_the_same_frame() {
+    let mut app = common::sim_app();
+    let gone = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    app.update();
+    assert!(indexed_position(&app, gone).is_some());
+
+    // Age the removal message out, as frames without a fixed tick do.
+    app.world_mut().despawn(gone);
+    app.world_mut().clear_trackers();
+    app.world_mut().clear_trackers();
+    let spawned = common::spawn_worker_at(&mut app, Vec2::new(200.0, 0.0));
+    app.update();
+
+    assert_eq!(indexed_position(&app, gone), None);
+    assert!(indexed_position(&app, spawned).is_some());
+    assert_eq!(app.world().resource::<SpatialIndex>().len(), 1);
+}
+
+#[test]
+fn structures_are_indexed_with_their_owner_swarm() {
+    let mut app = common::sim_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let owned = common::spawn_stockpile(&mut app, Vec2::new(100.0, 0.0), 0, 100);
+    app.world_mut().entity_mut(owned).insert(OwnerSwarm(swarm));
+    let legacy = common::spawn_stockpile(&mut app, Vec2::new(50.0, 0.0), 0, 100);
+
+    app.update();
+
+    let index = app.world().resource::<SpatialIndex>();
+    let nearest_owned = index.nearest(
+        Vec2::ZERO,
+        SpatialLayer::Structure,
+        SwarmFilter::Only(SwarmId::PLAYER),
+        |_| true,
+    );
+    assert_eq!(nearest_owned.map(|entry| entry.entity), Some(owned));
+    let nearest_any = index.nearest(
+        Vec2::ZERO,
+        SpatialLayer::Structure,
+        SwarmFilter::Any,
+        |_| true,
+    );
+    assert_eq!(nearest_any.map(|entry| entry.entity), Some(legacy));
+    assert!(
+        index
+            .nearest(Vec2::ZERO, SpatialLayer::Nanobot, SwarmFilter::Any, |_| {
+                true
+            })
+            .is_none(),
+        "structures never show up on the nanobot layer"
+    );
+
+    app.world_mut().entity_mut(owned).remove::<OwnerSwarm>();
+    app.update();
+    assert_eq!(
+        app.world()
+            .resource::<SpatialIndex>()
+            .get(owned)
+            .map(|entry| entry.swarm),
+        Some(None),
+        "a disowned structure loses its swarm tag"
+    );
+}
+
+#[test]
+fn defender_skips_a_despawned_charger_and_takes_the_next_nearest() {
+    let mut app = common::sim_app_with_charge_planned();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cell = IVec2::ZERO;
+    app.world_mut()
+        .resource_mut::<IntentGrid>()
+        .paint(cell, IntentKind::Defend);
+    let near = common::spawn_charger_at(&mut app, cell, 100);
+    let far = common::spawn_charger_at(&mut app, IVec2::new(2, 0), 100);
+
+    app.update();
+    app.world_mut().despawn(near);
+
+    let defender = common::spawn_defender_at(&mut app, common::cell_world_center(cell));
+    app.world_mut()
+        .entity_mut(defender)
+        .insert(DefendHold { cell })
+        .get_mut::<Charge>()
+        .unwrap()
+        .current = LOW_CHARGE_THRESHOLD;
+
+    app.update();
+
+    assert_eq!(
+        app.world()
+            .get::<ChargerAssignment>(defender)
+            .expect("the low-charge defender rotates to a charger")
+            .charger,
+        far
+    );
+}
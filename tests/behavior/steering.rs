@@ -0,0 +1,172 @@
+//! Per-type steering: acceleration from rest, per-type top speeds,
+//! arrival slowing, and cargo weight on haulers measured against
+//! their swarm's researched hold.
+//!
+//! Without [`NanobotSteering`] every bot keeps the constant `bot_speed`
+//! movement the rest of the suite times against.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        Cargo, DirectMovementComponent, HAULER_CARRY_CAPACITY, NanobotSteering, ResearchState,
+        SwarmId, SwarmMember, Upgrade,
+    },
+    resources::ResourceKind,
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+const TRIP: f32 = 400.0;
+
+fn steered_app() -> App {
+    let mut app = common::sim_app();
+    app.insert_resource(NanobotSteering::default());
+    app
+}
+
+fn send(app: &mut App, bot: Entity, to: Vec2) {
+    app.world_mut()
+        .entity_mut(bot)
+        .insert(DirectMovementComponent {
+            xy: to,
+            stop_radius: 0.0,
+        });
+}
+
+fn x(app: &App, bot: Entity) -> f32 {
+    app.world().get::<Transform>(bot).unwrap().translation.x
+}
+
+/// Ticks until `bot` drops its destination, with the x position
+/// after every tick.
+fn run_trip(app: &mut App, bot: Entity) -> Vec<f32> {
+    let mut positions = Vec::new();
+    for _ in 0..1000 {
+        app.update();
+        positions.push(x(app, bot));
+        if app.world().get::<DirectMovementComponent>(bot).is_none() {
+            return positions;
+        }
+    }
+    panic!("bot never arrived");
+}
+
+fn steps(positions: &[f32]) -> Vec<f32> {
+    positions.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+#[test]
+fn bots_accelerate_from_rest_instead_of_jumping_to_full_speed() {
+    let mut app = steered_app();
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    send(&mut app, worker, Vec2::new(TRIP, 0.0));
+
+    let positions = run_trip(&mut app, worker);
+
+    let profile = NanobotSteering::default().worker;
+    assert!((positions[0] - profile.acceleration).abs() < 1e-3);
+    let fastest = steps(&positions).into_iter().fold(0.0, f32::max);
+    assert!((fastest - profile.max_speed).abs() < 1e-3);
+}
+
+#[test]
+fn bots_slow_down_before_they_arrive() {
+    let mut app = steered_app();
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    send(&mut app, worker, Vec2::new(TRIP, 0.0));
+
+    let positions = run_trip(&mut app, worker);
+
+    // The final step is the stop tick itself; the one before it is
+    // the last approach step.
+    let steps = steps(&positions);
+    let approach = steps[steps.len() - 2];
+    assert!(
+        approach < NanobotSteering::default().worker.acceleration * 2.0,
+        "the final approach is slower than cruising speed"
+    );
+    assert!(
+        positions.iter().all(|x| *x <= TRIP),
+        "the bot never overshoots its destination"
+    );
+}
+
+#[test]
+fn defenders_outrun_workers_over_the_same_trip() {
+    let mut app = steered_app();
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    let defender = common::spawn_defender_at(&mut app, Vec2::new(0.0, 200.0));
+    send(&mut app, worker, Vec2::new(TRIP, 0.0));
+    send(&mut app, defender, Vec2::new(TRIP, 200.0));
+
+    for _ in 0..40 {
+        app.update();
+    }
+
+    assert!(x(&app, defender) > x(&app, worker));
+}
+
+#[test]
+fn a_loaded_hauler_is_slower_than_an_empty_one() {
+    let mut app = steered_app();
+    let empty = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+    let loaded = common::spawn_hauler_at(&mut app, Vec2::new(0.0, 200.0));
+    app.world_mut().entity_mut(loaded).insert(Cargo {
+        kind: ResourceKind::Minerals,
+        amount: HAULER_CARRY_CAPACITY,
+    });
+    send(&mut app, empty, Vec2::new(TRIP, 0.0));
+    send(&mut app, loaded, Vec2::new(TRIP, 200.0));
+
+    let empty_ticks = run_trip(&mut app, empty).len();
+    let loaded_ticks = empty_ticks + run_trip(&mut app, loaded).len();
+
+    assert!(
+        loaded_ticks > empty_ticks * 5 / 4,
+        "a full hold costs a large share of a hauler's speed; empty={empty_ticks} loaded={loaded_ticks}"
+    );
+}
+
+#[test]
+fn researched_hold_carries_the_same_load_faster() {
+    let mut app = steered_app();
+    app.init_resource::<ResearchState>();
+    app.world_mut()
+        .resource_mut::<ResearchState>()
+        .for_swarm_mut(SwarmId::PLAYER)
+        .unlocked
+        .insert(Upgrade::HaulerCarry);
+    let researched = common::spawn_hauler_at(&mut app, Vec2::ZERO);
+    let base = common::spawn_hauler_at(&mut app, Vec2::new(0.0, 200.0));
+    app.world_mut()
+        .entity_mut(base)
+        .insert(SwarmMember::new(SwarmId(2)));
+    for hauler in [researched, base] {
+        app.world_mut().entity_mut(hauler).insert(Cargo {
+            kind: ResourceKind::Minerals,
+            amount: HAULER_CARRY_CAPACITY,
+        });
+    }
+    send(&mut app, researched, Vec2::new(TRIP, 0.0));
+    send(&mut app, base, Vec2::new(TRIP, 200.0));
+
+    let researched_ticks = run_trip(&mut app, researched).len();
+    let base_ticks = researched_ticks + run_trip(&mut app, base).len();
+
+    assert!(
+        base_ticks > researched_ticks,
+        "a base load only partly fills a researched hold; researched={researched_ticks} base={base_ticks}"
+    );
+}
+
+#[test]
+fn without_steering_bots_keep_the_constant_bot_speed() {
+    let mut app = common::sim_app();
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    send(&mut app, worker, Vec2::new(TRIP, 0.0));
+
+    app.update();
+
+    assert_eq!(x(&app, worker), common::default_game_settings().bot_speed);
+}
@@ -5,11 +5,12 @@ use top_down_2d_rts_prototype_nano_swarm::{
     GAMEPLAY_SPRITE_Z, MAP_HEIGHT, MAP_WIDTH, ZONE_BLOCK_SIZE,
     fly_camera::CameraZoom2d,
     nanobot::{
-        Cargo, Charger, ExtractProgress, HAULER_CARRY_CAPACITY, HaulerLoad, LogisticsReservation,
-        MAINTENANCE_BUFFER_TICKS, MAINTENANCE_NEEDS_THRESHOLD, MAINTENANCE_WORK_DURATION_TICKS,
-        MaintenanceAssignment, MaintenanceProgress, PlannedKind, PlannedStructure,
-        ProductionFacility, STRUCTURE_MAX_HEALTH, SUPPORT_OPERATIONAL_HEALTH_THRESHOLD, Structure,
-        StructureKind, WORKER_CARRY_CAPACITY,
+        Cargo, Charger, ChargerQueue, ExtractProgress, HAULER_CARRY_CAPACITY, HaulerLoad,
+        LogisticsReservation, MAINTENANCE_BUFFER_TICKS, MAINTENANCE_NEEDS_THRESHOLD,
+        MAINTENANCE_WORK_DURATION_TICKS, MAX_DEFENDERS_PER_CHARGER, MaintenanceAssignment,
+        MaintenanceProgress, PlannedKind, PlannedStructure, ProductionFacility,
+        STRUCTURE_MAX_HEALTH, SUPPORT_OPERATIONAL_HEALTH_THRESHOLD, Structure, StructureKind,
+        WORKER_CARRY_CAPACITY,
     },
     resources::{ResourceDeposit, ResourceKind, Stockpile, StockpileRole},
     structure_overlay::{
@@ -123,6 +124,39 @@ fn charger_overlay_bar_uses_amount_over_capacity() {
     assert_fill_fraction(&app, overlay, StructureOverlayKind::Charger, 0.25);
 }
 
+#[test]
+fn charger_queue_bar_fills_with_waiting_defenders() {
+    let mut app = build_app();
+    let charger = common::spawn_charger_at(&mut app, IVec2::ZERO, 25);
+    app.world_mut().entity_mut(charger).insert((
+        Structure::new(StructureKind::Basic),
+        ChargerQueue {
+            reserved: 5,
+            charging: 3,
+            waiting: 2,
+        },
+    ));
+
+    app.update();
+
+    let queue = find_condition_overlay_for(&mut app, charger, ConditionOverlayKind::ChargerQueue);
+    assert_condition_fill(
+        &app,
+        queue,
+        fill_fraction(2, MAX_DEFENDERS_PER_CHARGER),
+        condition_fill_color(ConditionOverlayKind::ChargerQueue, 2),
+    );
+
+    app.world_mut().entity_mut(charger).remove::<ChargerQueue>();
+    app.update();
+
+    let world = app.world_mut();
+    let mut overlays = world.query::<&ConditionOverlay>();
+    assert!(overlays.iter(world).all(|overlay| {
+        overlay.target != charger || overlay.kind != ConditionOverlayKind::ChargerQueue
+    }));
+}
+
 #[test]
 fn completed_support_structure_gets_stacked_condition_bars() {
     let mut app = build_app();
@@ -0,0 +1,130 @@
+//! Integration tests for in-place structure upgrade tiers.
+//!
+//! Covers the saturation-driven upgrade lifecycle:
+//!   1. a buffer that stays full plans a paid upgrade on the same
+//!      entity,
+//!   2. a buffer with any free space never plans one,
+//!   3. a Worker builds the upgrade in place, keeping the stored
+//!      material and raising capacity, health, and footprint.
+//!
+//! The tier helper unit tests live in `src/nanobot/structure_tier.rs`.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        OwnerSwarm, PlannedKind, PlannedStructure, PlannedUpgrade, STRUCTURE_MAX_HEALTH, Structure,
+        StructureKind, StructureTierPlugin, SwarmId, UPGRADE_SATURATION_TICKS,
+        structure_upgrade_cost, structure_upgrade_work_ticks, tier_footprint_scale,
+    },
+    resources::{ResourceKind, ResourceLedger, Stockpile, StockpileRole},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+const CAPACITY: u32 = 200;
+
+fn build_app() -> App {
+    let mut app = common::sim_app_with_planned();
+    app.add_plugins(StructureTierPlugin);
+    app
+}
+
+fn spawn_stockpile(app: &mut App, amount: u32) -> Entity {
+    let swarm = common::spawn_swarm_at(app, Vec2::ZERO);
+    let center = common::cell_world_center(IVec2::ZERO);
+    let stockpile = common::spawn_sink_stockpile(app, center, amount, CAPACITY);
+    app.world_mut()
+        .entity_mut(stockpile)
+        .insert((Structure::new(StructureKind::Basic), OwnerSwarm(swarm)));
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        amount,
+    );
+    stockpile
+}
+
+fn ledger(app: &App) -> u32 {
+    app.world()
+        .resource::<ResourceLedger>()
+        .total_for(SwarmId::PLAYER, ResourceKind::Minerals)
+}
+
+#[test]
+fn saturated_stockpile_plans_paid_upgrade() {
+    let mut app = build_app();
+    let stockpile = spawn_stockpile(&mut app, CAPACITY);
+
+    for _ in 0..UPGRADE_SATURATION_TICKS - 1 {
+        app.update();
+    }
+    assert!(
+        app.world().get::<PlannedUpgrade>(stockpile).is_none(),
+        "saturation must last the full window"
+    );
+
+    app.update();
+    let world = app.world();
+    assert_eq!(
+        world.get::<PlannedUpgrade>(stockpile),
+        Some(&PlannedUpgrade { tier: 1 })
+    );
+    let planned = world.get::<PlannedStructure>(stockpile).unwrap();
+    assert_eq!(planned.kind, PlannedKind::SinkStockpile);
+    assert_eq!(planned.work_remaining, structure_upgrade_work_ticks(1));
+    assert_eq!(world.get::<Structure>(stockpile).unwrap().tier, 0);
+    let paid = CAPACITY - structure_upgrade_cost(1);
+    assert_eq!(world.get::<Stockpile>(stockpile).unwrap().amount, paid);
+    assert_eq!(ledger(&app), paid);
+}
+
+#[test]
+fn stockpile_with_free_space_never_plans_upgrade() {
+    let mut app = build_app();
+    let stockpile = spawn_stockpile(&mut app, CAPACITY - 1);
+
+    for _ in 0..UPGRADE_SATURATION_TICKS + 5 {
+        app.update();
+    }
+
+    assert!(app.world().get::<PlannedUpgrade>(stockpile).is_none());
+    assert_eq!(ledger(&app), CAPACITY - 1);
+}
+
+#[test]
+fn worker_builds_upgrade_in_place() {
+    let mut app = build_app();
+    let stockpile = spawn_stockpile(&mut app, CAPACITY);
+    for _ in 0..UPGRADE_SATURATION_TICKS {
+        app.update();
+    }
+    assert!(app.world().get::<PlannedUpgrade>(stockpile).is_some());
+
+    common::spawn_worker_at(&mut app, common::cell_world_center(IVec2::ZERO));
+    for _ in 0..1 + structure_upgrade_work_ticks(1) + 5 {
+        app.update();
+    }
+
+    let world = app.world();
+    assert!(world.get::<PlannedStructure>(stockpile).is_none());
+    assert!(world.get::<PlannedUpgrade>(stockpile).is_none());
+    let structure = world.get::<Structure>(stockpile).unwrap();
+    assert_eq!(structure.tier, 1);
+    assert_eq!(structure.health, STRUCTURE_MAX_HEALTH * 2);
+    let buffer = world.get::<Stockpile>(stockpile).unwrap();
+    assert_eq!(buffer.capacity, CAPACITY * 2);
+    assert_eq!(
+        buffer.amount,
+        CAPACITY - structure_upgrade_cost(1),
+        "the upgrade keeps the stored material"
+    );
+    assert_eq!(
+        world.get::<StockpileRole>(stockpile),
+        Some(&StockpileRole::Sink)
+    );
+    assert_eq!(
+        world.get::<Transform>(stockpile).unwrap().scale.x,
+        tier_footprint_scale(1)
+    );
+}
@@ -0,0 +1,184 @@
+//! Per-swarm telemetry recorded from the running simulation.
+//!
+//! Each test runs one sample interval and reads the player's sample.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    nanobot::{
+        Charge, DefendHold, FieldResupply, GatherAssignment, OwnerSwarm, PlannedKind,
+        PlannedStructure, Structure, SwarmId, SwarmTelemetry, TELEMETRY_SAMPLE_TICKS,
+        TelemetryPlugin, TelemetrySample, world_to_cell,
+    },
+    resources::{ResourceKind, ResourceLedger, StockpileRole},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn telemetry_app() -> App {
+    let mut app = common::sim_app_with_gather();
+    app.add_plugins(TelemetryPlugin);
+    app
+}
+
+/// Run exactly one sample interval and return the player's sample.
+fn run_sample(app: &mut App) -> TelemetrySample {
+    for _ in 0..TELEMETRY_SAMPLE_TICKS {
+        app.update();
+    }
+    *app.world()
+        .resource::<SwarmTelemetry>()
+        .latest(SwarmId::PLAYER)
+        .expect("one sample per interval")
+}
+
+#[test]
+fn worker_extraction_is_recorded_for_its_swarm() {
+    let mut app = telemetry_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    let deposit = common::spawn_deposit(&mut app, Vec2::ZERO, 4);
+    let stockpile = common::spawn_stockpile(&mut app, Vec2::ZERO, 0, 10);
+    app.world_mut()
+        .entity_mut(stockpile)
+        .insert((StockpileRole::Source, OwnerSwarm(swarm)));
+    app.world_mut()
+        .entity_mut(worker)
+        .insert(GatherAssignment::new(IVec2::ZERO, deposit));
+
+    let sample = run_sample(&mut app);
+
+    assert_eq!(sample.flows.minerals_extracted, 4);
+    assert_eq!(sample.population, [1, 0, 0]);
+}
+
+#[test]
+fn despawned_nanobots_count_as_lost_by_type() {
+    let mut app = telemetry_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    let defender = common::spawn_defender_at(&mut app, Vec2::ZERO);
+    common::spawn_hauler_at(&mut app, Vec2::ZERO);
+    app.update();
+    app.world_mut().entity_mut(worker).despawn();
+    app.world_mut().entity_mut(defender).despawn();
+
+    let sample = run_sample(&mut app);
+
+    assert_eq!(sample.flows.lost, [1, 0, 1]);
+    assert_eq!(sample.population, [0, 1, 0]);
+}
+
+#[test]
+fn idle_ratio_counts_nanobots_without_behaviour_state() {
+    let mut app = telemetry_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    common::spawn_worker_at(&mut app, Vec2::ZERO);
+    common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    let sample = run_sample(&mut app);
+
+    assert_eq!(sample.idle_ratio, 1.0, "no work exists, so every bot idles");
+    assert_eq!(sample.population, [1, 1, 0]);
+}
+
+#[test]
+fn structure_plans_completions_and_collapses_are_counted() {
+    let mut app = telemetry_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let completed = app
+        .world_mut()
+        .spawn(PlannedStructure::new(
+            PlannedKind::SourceStockpile,
+            IVec2::ZERO,
+        ))
+        .id();
+    let cancelled = app
+        .world_mut()
+        .spawn(PlannedStructure::new(
+            PlannedKind::SourceStockpile,
+            IVec2::ONE,
+        ))
+        .id();
+    app.world_mut()
+        .entity_mut(completed)
+        .get_mut::<PlannedStructure>()
+        .unwrap()
+        .work_remaining = 0;
+    app.world_mut()
+        .entity_mut(completed)
+        .remove::<PlannedStructure>();
+    app.world_mut().entity_mut(cancelled).despawn();
+
+    let collapsed = common::spawn_structure_at(&mut app, Vec2::ZERO);
+    app.world_mut()
+        .entity_mut(collapsed)
+        .get_mut::<Structure>()
+        .unwrap()
+        .health = 0;
+    app.world_mut().entity_mut(collapsed).despawn();
+    let healthy = common::spawn_structure_at(&mut app, Vec2::ZERO);
+    app.world_mut().entity_mut(healthy).despawn();
+
+    let sample = run_sample(&mut app);
+
+    assert_eq!(sample.flows.structures_planned, 2);
+    assert_eq!(
+        sample.flows.structures_completed, 1,
+        "cancelled plans do not count"
+    );
+    assert_eq!(
+        sample.flows.structures_collapsed, 1,
+        "only zero-health removals count as collapse"
+    );
+}
+
+#[test]
+fn field_resupply_is_counted_apart_from_production_and_chargers() {
+    let mut app = common::sim_app_with_gather_haul();
+    app.add_plugins(TelemetryPlugin);
+    app.insert_resource(FieldResupply::default());
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let source = common::spawn_sink_stockpile(&mut app, Vec2::new(10.0, 0.0), 100, 100);
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        100,
+    );
+    let hold = Vec2::new(200.0, 0.0);
+    let defender = common::spawn_defender_at(&mut app, hold);
+    app.world_mut().entity_mut(defender).insert((
+        Charge {
+            current: 0.4,
+            max: 1.0,
+        },
+        DefendHold {
+            cell: world_to_cell(hold),
+        },
+    ));
+    common::spawn_hauler_at(&mut app, Vec2::ZERO);
+
+    for _ in 0..4 {
+        run_sample(&mut app);
+    }
+
+    let telemetry = app.world().resource::<SwarmTelemetry>();
+    let flows = telemetry
+        .history(SwarmId::PLAYER)
+        .map(|sample| sample.flows)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        flows
+            .iter()
+            .map(|flows| flows.minerals_to_resupply)
+            .sum::<u32>(),
+        12,
+        "the minerals turned into the defender's charge"
+    );
+    assert!(
+        flows
+            .iter()
+            .all(|flows| flows.minerals_to_production == 0 && flows.minerals_to_chargers == 0)
+    );
+}
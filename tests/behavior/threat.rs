@@ -0,0 +1,210 @@
+//! Threat-aware routing and fleeing: hostile Defenders raise per-swarm
+//! danger on the cells they reach, hauler routes bend around that
+//! danger, and Workers and Haulers caught in it drop their task, back
+//! away, and take work again once the cell stays calm.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::IntentGrid,
+    nanobot::{
+        Cargo, Commitment, DANGER_PER_DEFENDER, FLEE_CALM_TICKS, Fleeing, HaulerAssignment,
+        LogisticsReservation, OwnerSwarm, PlannedStructureClaim, RegionalLease, RegionalLeaseState,
+        SuspendedHaul, SwarmId, SwarmMember, ThreatPlugin, plan_hauler_route, world_to_cell,
+    },
+    resources::{ResourceKind, Stockpile},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+const HOSTILE: SwarmId = SwarmId(3);
+
+fn spawn_hostile_defender(app: &mut App, world_pos: Vec2) -> Entity {
+    let defender = common::spawn_defender_at(app, world_pos);
+    app.world_mut()
+        .entity_mut(defender)
+        .insert(SwarmMember::new(HOSTILE));
+    defender
+}
+
+fn position(app: &App, bot: Entity) -> Vec2 {
+    app.world()
+        .get::<Transform>(bot)
+        .unwrap()
+        .translation
+        .truncate()
+}
+
+#[test]
+fn hostile_defender_raises_danger_and_bends_hauler_routes() {
+    let mut app = common::sim_app();
+    app.add_plugins(ThreatPlugin);
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let start = common::cell_world_center(IVec2::new(0, 0));
+    let end = common::cell_world_center(IVec2::new(4, 0));
+    let calm = plan_hauler_route(
+        start,
+        end,
+        app.world().resource::<IntentGrid>(),
+        SwarmId::PLAYER,
+    )
+    .unwrap();
+    assert!(
+        calm.waypoints
+            .iter()
+            .any(|p| world_to_cell(*p) == IVec2::new(2, 0))
+    );
+
+    let cell = IVec2::new(2, 0);
+    let defender = spawn_hostile_defender(&mut app, common::cell_world_center(cell));
+    app.update();
+
+    let grid = app.world().resource::<IntentGrid>();
+    assert_eq!(grid.danger_for(cell, SwarmId::PLAYER), DANGER_PER_DEFENDER);
+    assert_eq!(grid.danger_for(cell, HOSTILE), 0, "own Defenders are safe");
+    let threatened = plan_hauler_route(start, end, grid, SwarmId::PLAYER).unwrap();
+    assert!(threatened.cost > calm.cost);
+    assert!(
+        threatened
+            .waypoints
+            .iter()
+            .all(|p| world_to_cell(*p) != cell),
+        "the route steps around the Defender's cell"
+    );
+
+    app.world_mut().despawn(defender);
+    app.update();
+    assert_eq!(
+        app.world()
+            .resource::<IntentGrid>()
+            .danger_for(cell, SwarmId::PLAYER),
+        0
+    );
+}
+
+#[test]
+fn worker_flees_a_hostile_defender_and_resumes_its_build() {
+    let mut app = common::sim_app_with_planned();
+    app.add_plugins(ThreatPlugin);
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let planned = common::spawn_planned_structure_at_cell(&mut app, IVec2::new(3, 0));
+    let worker = common::spawn_worker_at(&mut app, common::cell_world_center(IVec2::new(0, 0)));
+    for _ in 0..10 {
+        app.update();
+        if app.world().get::<PlannedStructureClaim>(worker).is_some() {
+            break;
+        }
+    }
+    assert!(app.world().get::<PlannedStructureClaim>(worker).is_some());
+
+    let ambush = world_to_cell(position(&app, worker));
+    let defender = spawn_hostile_defender(&mut app, common::cell_world_center(ambush));
+    app.update();
+    app.update();
+
+    assert!(app.world().get::<Fleeing>(worker).is_some());
+    assert_eq!(
+        app.world().get::<Commitment>(worker),
+        Some(&Commitment::Fleeing)
+    );
+    assert!(
+        app.world().get::<PlannedStructureClaim>(worker).is_none(),
+        "the build is abandoned"
+    );
+    assert_eq!(
+        app.world()
+            .get::<RegionalLease>(worker)
+            .map(|lease| lease.state),
+        Some(RegionalLeaseState::SuspendedForThreat)
+    );
+
+    let mut escaped = false;
+    for _ in 0..200 {
+        app.update();
+        if world_to_cell(position(&app, worker)) != ambush {
+            escaped = true;
+            break;
+        }
+    }
+    assert!(escaped, "the worker leaves the Defender's cell");
+
+    app.world_mut().despawn(defender);
+    for _ in 0..FLEE_CALM_TICKS + 10 {
+        app.update();
+    }
+
+    assert!(app.world().get::<Fleeing>(worker).is_none());
+    assert_ne!(
+        app.world().get::<Commitment>(worker),
+        Some(&Commitment::Fleeing)
+    );
+    let claim = app.world().get::<PlannedStructureClaim>(worker);
+    assert_eq!(
+        claim.map(|claim| claim.target),
+        Some(planned),
+        "the worker picks its build back up"
+    );
+}
+
+#[test]
+fn loaded_hauler_flees_and_delivers_once_calm() {
+    let mut app = common::sim_app_with_gather_haul();
+    app.add_plugins(ThreatPlugin);
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let start = common::cell_world_center(IVec2::new(0, 0));
+    let source = common::spawn_stockpile(&mut app, start, 0, 100);
+    let sink = common::spawn_sink_stockpile(
+        &mut app,
+        common::cell_world_center(IVec2::new(3, 0)),
+        0,
+        100,
+    );
+    app.world_mut().entity_mut(source).insert(OwnerSwarm(swarm));
+    app.world_mut().entity_mut(sink).insert(OwnerSwarm(swarm));
+    let hauler = common::spawn_hauler_at(&mut app, start);
+    let mut reservation = LogisticsReservation::new(source, sink, ResourceKind::Minerals, 10);
+    reservation.source_remaining = 0;
+    app.world_mut().entity_mut(hauler).insert((
+        Cargo {
+            kind: ResourceKind::Minerals,
+            amount: 10,
+        },
+        HaulerAssignment { source, sink },
+        reservation,
+    ));
+
+    let defender = spawn_hostile_defender(&mut app, start);
+    app.update();
+    app.update();
+
+    assert!(app.world().get::<Fleeing>(hauler).is_some());
+    assert!(app.world().get::<HaulerAssignment>(hauler).is_none());
+    let suspended = app
+        .world()
+        .get::<SuspendedHaul>(hauler)
+        .expect("the loaded hauler keeps its leg while it flees");
+    assert_eq!(suspended.assignment.sink, sink);
+    assert_eq!(
+        app.world().get::<Cargo>(hauler).map(|cargo| cargo.amount),
+        Some(10),
+        "the cargo stays aboard"
+    );
+
+    app.world_mut().despawn(defender);
+    let mut delivered = false;
+    for _ in 0..FLEE_CALM_TICKS + 600 {
+        app.update();
+        if app.world().get::<Stockpile>(sink).unwrap().amount == 10 {
+            delivered = true;
+            break;
+        }
+    }
+    assert!(delivered, "the hauler carries its load on after recovery");
+    assert!(app.world().get::<Fleeing>(hauler).is_none());
+    assert!(app.world().get::<SuspendedHaul>(hauler).is_none());
+    assert!(
+        app.world()
+            .get::<Cargo>(hauler)
+            .is_none_or(|cargo| cargo.amount == 0)
+    );
+}
@@ -27,7 +27,7 @@ use bevy::{math::Vec2, prelude::*};
 use top_down_2d_rts_prototype_nano_swarm::{
     ZONE_BLOCK_SIZE,
     intent::{IntentGrid, IntentKind},
-    nanobot::{GatherAssignment, SwarmId},
+    nanobot::{GatherAssignment, PlannedKind, PlannedStructure, PlannedStructureClaim, SwarmId},
 };
 
 #[path = "../common/mod.rs"]
@@ -157,40 +157,32 @@ fn build_bot_lands_at_structure_world_position() {
     // appeared at the bottom-left of the bot cluster.
     //
     // The positioned-swarm scenario path: the swarm is
-    // at the cell-center offset `(256, 256)`. The
-    // BuildSite is at the canonical build cell center
-    // `(256, 256)`. The worker walks to the site, builds
+    // at the cell-center offset `(256, 256)`. The planned
+    // structure is at the canonical build cell center
+    // `(256, 256)`. The worker walks to the plan, builds
     // it, and the test asserts the worker's
     // `GlobalTransform` is the structure's world
     // position, not the structure + (256, 256) offset.
     let mut app = build_app();
     let player_pos = Vec2::new(256.0, 256.0);
     let _swarm = common::spawn_swarm_at(&mut app, player_pos);
-    // Spawn a BuildSite at the swarm's world position so
-    // the test isolates the arrival behaviour. BuildSite
-    // is the legacy `BuildSite` type; the `BuildPlugin`
-    // is no longer registered, so the test inserts the
-    // site directly.
+    let worker = common::spawn_worker_at(&mut app, player_pos);
+    // Spawn the plan at the swarm's world position with the
+    // worker already claiming it so the test isolates the
+    // arrival behaviour.
     let site_pos = player_pos;
-    let _site = app
+    let mut plan = PlannedStructure::new(PlannedKind::SinkStockpile, IVec2::ZERO);
+    plan.active_worker = Some(worker);
+    let site = app
         .world_mut()
-        .spawn((
-            top_down_2d_rts_prototype_nano_swarm::nanobot::BuildSite::new(
-                IVec2::new(0, 0),
-                top_down_2d_rts_prototype_nano_swarm::nanobot::StructureKind::Basic,
-            ),
-            Transform::from_translation(site_pos.extend(0.0)),
-        ))
+        .spawn((plan, Transform::from_translation(site_pos.extend(0.0))))
         .id();
-    let worker = common::spawn_worker_at(&mut app, player_pos);
-    // Pre-seed the build assignment so the test isolates
-    // the arrival behaviour.
-    app.world_mut().entity_mut(worker).insert(
-        top_down_2d_rts_prototype_nano_swarm::nanobot::BuildAssignment {
-            cell: IVec2::new(0, 0),
-            target: _site,
-        },
-    );
+    app.world_mut()
+        .entity_mut(worker)
+        .insert(PlannedStructureClaim {
+            cell: IVec2::ZERO,
+            target: site,
+        });
 
     for _ in 0..50 {
         app.update();
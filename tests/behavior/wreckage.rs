@@ -0,0 +1,202 @@
+//! Wreckage: with a `Salvage` resource, a collapsed structure or a dead
+//! nanobot leaves an unowned deposit holding its buffered minerals or
+//! cargo plus a salvage share of its cost, which Workers gather inside
+//! Gather paint like any other deposit. Bots that fall close together
+//! share one wreck.
+
+use bevy::prelude::*;
+use top_down_2d_rts_prototype_nano_swarm::{
+    intent::{IntentGrid, IntentKind},
+    nanobot::{
+        Cargo, ConstructionCost, DEGRADATION_INTERVAL_TICKS, Health, MAINTENANCE_BUFFER_TICKS,
+        OwnerSwarm, PRODUCTION_COST_PER_BOT, PlannedKind, Salvage, Structure, StructureKind,
+        SwarmId, WRECKAGE_MERGE_RADIUS, Wreckage, WreckagePlugin, nanobot_death_cleanup_system,
+    },
+    resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile, StockpileRole},
+};
+
+#[path = "../common/mod.rs"]
+mod common;
+
+fn wrecks(app: &mut App) -> Vec<(Entity, ResourceDeposit, Vec2)> {
+    let world = app.world_mut();
+    let mut query =
+        world.query_filtered::<(Entity, &ResourceDeposit, &Transform), With<Wreckage>>();
+    query
+        .iter(world)
+        .map(|(entity, deposit, transform)| (entity, *deposit, transform.translation.truncate()))
+        .collect()
+}
+
+/// A stocked stockpile one degradation step from collapse.
+fn failing_stockpile(app: &mut App, pos: Vec2, amount: u32) -> Entity {
+    let stockpile = common::spawn_stockpile(app, pos, amount, 100);
+    app.world_mut().entity_mut(stockpile).insert(Structure {
+        health: 1,
+        ticks_since_maintained: MAINTENANCE_BUFFER_TICKS + DEGRADATION_INTERVAL_TICKS - 1,
+        ..Structure::new(StructureKind::Basic)
+    });
+    app.world_mut().resource_mut::<ResourceLedger>().add_for(
+        SwarmId::PLAYER,
+        ResourceKind::Minerals,
+        amount,
+    );
+    stockpile
+}
+
+fn salvage_app() -> App {
+    let mut app = common::sim_app_with_maintenance();
+    app.add_plugins(WreckagePlugin)
+        .add_systems(Update, nanobot_death_cleanup_system)
+        .insert_resource(Salvage::default());
+    app
+}
+
+#[test]
+fn collapsed_structure_leaves_its_buffer_as_wreckage() {
+    let mut app = salvage_app();
+    let pos = Vec2::new(40.0, 0.0);
+    let stockpile = failing_stockpile(&mut app, pos, 30);
+
+    app.update();
+
+    assert!(app.world().get_entity(stockpile).is_err());
+    let wrecks = wrecks(&mut app);
+    assert_eq!(wrecks.len(), 1);
+    let (_, deposit, wreck_pos) = wrecks[0];
+    assert_eq!(
+        deposit.amount, 30,
+        "without construction costs a tier-0 build cost nothing to salvage"
+    );
+    assert_eq!(wreck_pos, pos);
+    assert_eq!(
+        app.world()
+            .resource::<ResourceLedger>()
+            .total(ResourceKind::Minerals),
+        0,
+        "the buffer left the owner's custody"
+    );
+}
+
+#[test]
+fn collapsed_structure_salvages_the_material_it_was_built_from() {
+    let mut app = salvage_app();
+    app.insert_resource(ConstructionCost::default());
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let stockpile = failing_stockpile(&mut app, Vec2::new(40.0, 0.0), 30);
+    app.world_mut()
+        .entity_mut(stockpile)
+        .insert((StockpileRole::Source, OwnerSwarm(swarm)));
+
+    app.update();
+
+    let wrecks = wrecks(&mut app);
+    assert_eq!(wrecks.len(), 1);
+    assert_eq!(
+        wrecks[0].1.amount,
+        Salvage::default().wreckage(30, PlannedKind::SourceStockpile.mineral_cost()),
+        "the salvage share counts the construction material"
+    );
+}
+
+#[test]
+fn destruction_leaves_nothing_without_salvage() {
+    let mut app = common::sim_app_with_maintenance();
+    app.add_plugins(WreckagePlugin)
+        .add_systems(Update, nanobot_death_cleanup_system);
+    let stockpile = failing_stockpile(&mut app, Vec2::ZERO, 30);
+    let worker = common::spawn_worker_at(&mut app, Vec2::ZERO);
+    app.world_mut()
+        .entity_mut(worker)
+        .get_mut::<Health>()
+        .unwrap()
+        .current = 0;
+
+    app.update();
+
+    assert!(app.world().get_entity(stockpile).is_err());
+    assert!(app.world().get_entity(worker).is_err());
+    assert!(wrecks(&mut app).is_empty());
+}
+
+#[test]
+fn dead_nanobot_drops_cargo_and_salvage_for_workers_to_gather() {
+    let mut app = salvage_app();
+    let swarm = common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let cell = IVec2::ZERO;
+    let center = common::cell_world_center(cell);
+    let hauler = common::spawn_hauler_at(&mut app, center);
+    app.world_mut().entity_mut(hauler).insert(Cargo {
+        kind: ResourceKind::Minerals,
+        amount: 8,
+    });
+    app.world_mut()
+        .entity_mut(hauler)
+        .get_mut::<Health>()
+        .unwrap()
+        .current = 0;
+
+    app.update();
+
+    let wrecks_left = wrecks(&mut app);
+    assert_eq!(wrecks_left.len(), 1);
+    let (wreck, deposit, _) = wrecks_left[0];
+    let expected = 8 + Salvage::default().wreckage(0, PRODUCTION_COST_PER_BOT);
+    assert_eq!(deposit.amount, expected);
+
+    let stockpile = common::spawn_stockpile(&mut app, center, 0, 100);
+    app.world_mut()
+        .entity_mut(stockpile)
+        .insert((StockpileRole::Source, OwnerSwarm(swarm)));
+    common::spawn_worker_at(&mut app, center);
+    app.world_mut()
+        .resource_mut::<IntentGrid>()
+        .paint(cell, IntentKind::Gather);
+
+    for _ in 0..400 {
+        app.update();
+    }
+
+    assert!(
+        app.world().get_entity(wreck).is_err(),
+        "an emptied wreck despawns"
+    );
+    assert_eq!(
+        app.world().get::<Stockpile>(stockpile).unwrap().amount,
+        expected
+    );
+}
+
+#[test]
+fn dead_nanobots_close_together_share_one_wreck() {
+    let mut app = salvage_app();
+    common::spawn_swarm_at(&mut app, Vec2::ZERO);
+    let near = [
+        Vec2::ZERO,
+        Vec2::new(WRECKAGE_MERGE_RADIUS * 0.5, 0.0),
+        Vec2::new(0.0, WRECKAGE_MERGE_RADIUS * 0.5),
+    ];
+    let far = Vec2::new(WRECKAGE_MERGE_RADIUS * 4.0, 0.0);
+    for pos in near.into_iter().chain([far]) {
+        let hauler = common::spawn_hauler_at(&mut app, pos);
+        app.world_mut()
+            .entity_mut(hauler)
+            .get_mut::<Health>()
+            .unwrap()
+            .current = 0;
+    }
+
+    app.update();
+
+    let share = Salvage::default().wreckage(0, PRODUCTION_COST_PER_BOT);
+    let mut amounts: Vec<u32> = wrecks(&mut app)
+        .into_iter()
+        .map(|(_, deposit, _)| deposit.amount)
+        .collect();
+    amounts.sort_unstable();
+    assert_eq!(
+        amounts,
+        vec![share, share * near.len() as u32],
+        "the three bots that fell together leave one pile"
+    );
+}
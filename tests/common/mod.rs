@@ -47,10 +47,10 @@ use top_down_2d_rts_prototype_nano_swarm::{
         Charge, ChargePlugin, Charger, CollapsePlugin, Commitment, DefendPlugin, GatherPlugin,
         HaulPlugin, Health, MaintenancePlugin, Nanobot, NanobotBundle, NanobotSimulationSet,
         NanobotType, OwnerSwarm, PlannedStructure, PlannedStructurePlugin, ProductionFacility,
-        ProductionPlugin, RegionalAllocationPlugin, SoftWorkSlots, Structure, StructureKind, Swarm,
-        SwarmId, SwarmMember, VelocityComponent, bot_debug_circle_system, idle_spread_system,
-        initialize_nanobot_type_components, move_velocity_system, separation_system,
-        velocity_system,
+        ProductionPlugin, RegionalAllocationPlugin, RepairPlugin, SoftWorkSlots, Structure,
+        StructureKind, Swarm, SwarmId, SwarmMember, VelocityComponent, add_spatial_index,
+        bot_debug_circle_system, idle_spread_system, initialize_nanobot_type_components,
+        move_velocity_system, separation_system, velocity_system,
     },
     resources::{ResourceDeposit, ResourceKind, ResourceLedger, Stockpile, StockpileRole},
     structure_overlay::StructureOverlayPlugin,
@@ -81,6 +81,7 @@ pub const DEFAULT_GRID_HEIGHT: i32 = 8;
 /// Register deterministic fixed-tick movement and frame-driven debug drawing.
 /// Simulation plugins order their work after `move_velocity_system`.
 fn register_movement_systems(app: &mut App) {
+    add_spatial_index(app);
     app.add_observer(initialize_nanobot_type_components);
     app.configure_sets(
         FixedUpdate,
@@ -166,9 +167,9 @@ pub fn sim_app_with_gather_haul() -> App {
     app
 }
 
-/// `sim_app` + gather. After issue #29 the legacy `BuildPlugin`
-/// is no longer registered, so this helper is just an alias for
-/// [`sim_app_with_gather`]. It stays as a named entry point so
+/// `sim_app` + gather. Build paint has no chain of its own outside
+/// the planned-structure lifecycle, so this helper is just an alias
+/// for [`sim_app_with_gather`]. It stays as a named entry point so
 /// the maintenance test fixtures keep a stable "this app includes
 /// the gather chain" seam as the test list evolves.
 pub fn sim_app_with_build() -> App {
@@ -177,11 +178,9 @@ pub fn sim_app_with_build() -> App {
 
 /// `sim_app` + gather + planned structure. The issue #29
 /// regression tests use this builder to verify that Build paint
-/// routes through `PlannedStructurePlugin` without any legacy
-/// `BuildSite` auto-spawn path running. The helper intentionally
-/// does NOT include the legacy `BuildPlugin`; the regression
-/// suite asserts the planned-structure path is the only one
-/// that produces a structure.
+/// routes through `PlannedStructurePlugin`; the regression suite
+/// asserts the planned-structure path is the only one that
+/// produces a structure.
 pub fn sim_app_with_build_planned() -> App {
     let mut app = sim_app_with_gather();
     app.add_plugins(PlannedStructurePlugin);
@@ -224,6 +223,15 @@ pub fn sim_app_with_maintenance() -> App {
     app
 }
 
+/// `sim_app_with_maintenance` + repair. Repair shares the
+/// maintenance phase, so tests that damage structures see both
+/// activities the way the game wires them.
+pub fn sim_app_with_repair() -> App {
+    let mut app = sim_app_with_maintenance();
+    app.add_plugins(RepairPlugin);
+    app
+}
+
 /// `sim_app` + defend. The defend plugin brings its own assignment,
 /// hold, and home-cell systems; tests that exercise it can use
 /// this builder as-is.
@@ -322,12 +330,12 @@ pub fn sim_app_with_tactical() -> App {
 
 /// World position of the centre of `cell` in the project's
 /// coordinate system. Thin wrapper around the canonical
-/// `ai::get_world_from_zone` so tests share the same formula
-/// as the auto-creation systems; pinning "the BuildSite lives
-/// at the cell's world center" stays in lock-step with
+/// `nanobot::get_world_from_zone` so tests share the same formula
+/// as the auto-creation systems; pinning "the planned structure
+/// lives at the cell's world center" stays in lock-step with
 /// gameplay code.
 pub fn cell_world_center(cell: IVec2) -> Vec2 {
-    top_down_2d_rts_prototype_nano_swarm::ai::get_world_from_zone(cell)
+    top_down_2d_rts_prototype_nano_swarm::nanobot::get_world_from_zone(cell)
 }
 
 /// Spawn an empty [`Swarm`] at `world_pos`. The marker carries no
@@ -374,7 +382,6 @@ pub fn spawn_swarm_with_nanobots(
                         nanobot: Nanobot {},
                         nanobot_type: *kind,
                         velocity: VelocityComponent::default(),
-                        ai_state: Default::default(),
                         health: Health::default(),
                         swarm_member: SwarmMember::new(SwarmId::PLAYER),
                     },